@@ -10,6 +10,13 @@ use test::Bencher;
 const START: (isize, isize) = (678, 1000);
 const END: (isize, isize) = (0, 0);
 
+// A long line with an irrational-looking slope, so the steps along `y` don't fall into a pattern
+const START_LONG: (isize, isize) = (0, 0);
+const END_LONG: (isize, isize) = (100_000, 61_803);
+
+// A line straight along an axis, like the edges of most UI and tilemap shapes
+const END_AXIS: (isize, isize) = (0, 1000);
+
 const START_FLOAT: (f32, f32) = (START.0 as f32, START.1 as f32);
 const END_FLOAT: (f32, f32) = (END.0 as f32, END.1 as f32);
 
@@ -24,21 +31,80 @@ fn bench_bresenham(bencher: &mut Bencher) {
     bencher.iter(|| black_box(Bresenham::new(START, END)));
 }
 
+#[bench]
+fn bench_bresenham_long(bencher: &mut Bencher) {
+    bencher.iter(|| black_box(Bresenham::new(START_LONG, END_LONG)));
+}
+
+#[bench]
+fn bench_bresenham_axis(bencher: &mut Bencher) {
+    bencher.iter(|| black_box(Bresenham::new(END, END_AXIS)));
+}
+
+#[bench]
+fn bench_bresenham_walk(bencher: &mut Bencher) {
+    bencher.iter(|| {
+        Bresenham::walk(START, END, |point| {
+            test::black_box(point);
+            std::ops::ControlFlow::<()>::Continue(())
+        })
+    });
+}
+
 #[bench]
 fn bench_bresenham_crate(bencher: &mut Bencher) {
     bencher.iter(|| black_box(bresenham::Bresenham::new(START, END)));
 }
 
+#[bench]
+fn bench_rasterize_for_each(bencher: &mut Bencher) {
+    bencher.iter(|| {
+        rasterize(START, END, LineAlgorithm::WalkGrid).for_each(|point| {
+            test::black_box(point);
+        })
+    });
+}
+
 #[bench]
 fn bench_walk_grid(bencher: &mut Bencher) {
     bencher.iter(|| black_box(WalkGrid::new(START, END)));
 }
 
+#[bench]
+fn bench_walk_grid_walk(bencher: &mut Bencher) {
+    bencher.iter(|| {
+        WalkGrid::walk(START, END, |point| {
+            test::black_box(point);
+            std::ops::ControlFlow::<()>::Continue(())
+        })
+    });
+}
+
+#[bench]
+fn bench_walk_grid_axis(bencher: &mut Bencher) {
+    bencher.iter(|| black_box(WalkGrid::new(END, END_AXIS)));
+}
+
 #[bench]
 fn bench_supercover(bencher: &mut Bencher) {
     bencher.iter(|| black_box(Supercover::new(START, END)));
 }
 
+#[bench]
+fn bench_supercover_walk(bencher: &mut Bencher) {
+    bencher.iter(|| {
+        Supercover::walk(START, END, |point| {
+            test::black_box(point);
+            std::ops::ControlFlow::<()>::Continue(())
+        })
+    });
+}
+
+#[bench]
+fn bench_supercover_axis(bencher: &mut Bencher) {
+    bencher.iter(|| black_box(Supercover::new(END, END_AXIS)));
+}
+
 #[bench]
 fn bench_midpoint(bencher: &mut Bencher) {
     bencher.iter(|| black_box(Midpoint::<_, isize>::new(START_FLOAT, END_FLOAT)));
@@ -54,17 +120,42 @@ fn bench_bresenham_3d(bencher: &mut Bencher) {
     bencher.iter(|| black_box(Bresenham3d::new(START_VOXEL, END_VOXEL)));
 }
 
+#[bench]
+fn bench_bresenham_3d_walk(bencher: &mut Bencher) {
+    bencher.iter(|| {
+        Bresenham3d::walk(START_VOXEL, END_VOXEL, |voxel| {
+            test::black_box(voxel);
+            std::ops::ControlFlow::<()>::Continue(())
+        })
+    });
+}
+
 #[bench]
 fn bench_walk_voxels(bencher: &mut Bencher) {
     bencher.iter(|| {
         black_box(WalkVoxels::<_, isize>::new(
             START_VOXEL_FLOAT,
             END_VOXEL_FLOAT,
-            &VoxelOrigin::Center,
+            VoxelOrigin::Center,
         ))
     });
 }
 
+#[bench]
+fn bench_walk_voxels_walk(bencher: &mut Bencher) {
+    bencher.iter(|| {
+        WalkVoxels::<_, isize>::walk(
+            START_VOXEL_FLOAT,
+            END_VOXEL_FLOAT,
+            VoxelOrigin::Center,
+            |voxel| {
+                test::black_box(voxel);
+                std::ops::ControlFlow::<()>::Continue(())
+            },
+        )
+    });
+}
+
 #[bench]
 fn bench_steps_bresenham(bencher: &mut Bencher) {
     bencher.iter(|| black_box(Bresenham::new(START, END).steps()));
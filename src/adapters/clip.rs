@@ -0,0 +1,156 @@
+use {GridBounds, Point, SignedNum};
+
+/// An iterator that cuts a line off at the edges of some bounds.
+///
+/// Points are skipped until the line goes into the bounds, and it stops as soon as it comes back
+/// out. That's only right for straight lines, which can't go back in again, but it means the rest
+/// of a long line that goes off the edge is never walked through. Use [`Within`] for other shapes.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{Bresenham, Clipped, GridBounds};
+///
+/// fn main() {
+///     let bounds = GridBounds::new((0, 0), (3, 3));
+///
+///     for (x, y) in Clipped::new(Bresenham::new((-2, -1), (100, 50)), bounds) {
+///         print!("({}, {}), ", x, y);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// (0, 0), (1, 0), (2, 1), (3, 1),
+/// ```
+///
+/// [`Within`]: struct.Within.html
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Clipped<T, I> {
+    iterator: I,
+    bounds: GridBounds<T>,
+    entered: bool,
+    done: bool,
+}
+
+impl<T: SignedNum, I: Iterator<Item = Point<T>>> Clipped<T, I> {
+    #[inline]
+    pub fn new(iterator: I, bounds: GridBounds<T>) -> Self {
+        Self {
+            iterator,
+            bounds,
+            entered: false,
+            done: false,
+        }
+    }
+}
+
+impl<T: SignedNum, I: Iterator<Item = Point<T>>> Iterator for Clipped<T, I> {
+    type Item = Point<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            let point = self.iterator.next()?;
+
+            if self.bounds.contains(point) {
+                self.entered = true;
+                return Some(point);
+            }
+
+            self.done = self.entered;
+        }
+
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            (0, Some(0))
+        } else {
+            (0, self.iterator.size_hint().1)
+        }
+    }
+}
+
+/// An iterator that only returns the points of another one that are inside some bounds.
+///
+/// Unlike [`Clipped`], this works for any shape, but it goes through all of the points to find
+/// the ones that are inside.
+///
+/// [`Clipped`]: struct.Clipped.html
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Within<T, I> {
+    iterator: I,
+    bounds: GridBounds<T>,
+}
+
+impl<T: SignedNum, I: Iterator<Item = Point<T>>> Within<T, I> {
+    #[inline]
+    pub fn new(iterator: I, bounds: GridBounds<T>) -> Self {
+        Self { iterator, bounds }
+    }
+}
+
+impl<T: SignedNum, I: Iterator<Item = Point<T>>> Iterator for Within<T, I> {
+    type Item = Point<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let bounds = self.bounds;
+        self.iterator.find(|&point| bounds.contains(point))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iterator.size_hint().1)
+    }
+
+    #[inline]
+    fn fold<B, F>(self, init: B, mut function: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        let bounds = self.bounds;
+
+        self.iterator.fold(init, |accum, point| {
+            if bounds.contains(point) {
+                function(accum, point)
+            } else {
+                accum
+            }
+        })
+    }
+}
+
+#[test]
+fn tests() {
+    use {Bresenham, BresenhamCircle};
+
+    let bounds = GridBounds::new((-5, -5), (5, 5));
+
+    for &(start, end) in &[((-20, 3), (20, -4)), ((0, 0), (30, 9)), ((9, 9), (6, 6))] {
+        let line = Bresenham::new(start, end);
+        let clipped = Clipped::new(line.clone(), bounds).collect::<Vec<_>>();
+        let within = Within::new(line.clone(), bounds).collect::<Vec<_>>();
+        let filtered = line.filter(|&point| bounds.contains(point)).collect::<Vec<_>>();
+
+        assert_eq!(clipped, filtered);
+        assert_eq!(within, filtered);
+    }
+
+    // A circle goes in and out of the bounds, so only the first part is kept by clipping it
+    let circle = BresenhamCircle::contiguous(0, 0, 6);
+    let within = Within::new(circle.clone(), bounds).collect::<Vec<_>>();
+    assert_eq!(within.len(), circle.clone().filter(|&point| bounds.contains(point)).count());
+    assert!(Clipped::new(circle, bounds).count() < within.len());
+
+    let mut clipped = Clipped::new(Bresenham::new((0, 0), (10, 0)), bounds);
+    assert_eq!(clipped.size_hint(), (0, Some(11)));
+    assert_eq!(clipped.by_ref().count(), 6);
+    assert_eq!(clipped.size_hint(), (0, Some(0)));
+}
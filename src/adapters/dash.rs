@@ -0,0 +1,154 @@
+use {FloatNum, Point, SignedNum};
+
+/// An iterator that breaks a line up into dashes, measuring them by the distance along it.
+///
+/// The pattern is a list of lengths that take turns being drawn and skipped, starting with a drawn
+/// one, like a dash array in SVG. If it has an odd number of lengths it's repeated to make it
+/// even. Diagonal steps count for more distance than straight ones, so the dashes stay the same
+/// length around curves as on straight lines. The points need to be in order for that, so use it
+/// with [`BresenhamCircle::contiguous`] or [`MidpointEllipse`] for dashed circles and ellipses.
+///
+/// If the pattern is empty or all of its lengths are `0`, every point is drawn.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{BresenhamCircle, Dashed};
+///
+/// fn main() {
+///     for (x, y) in Dashed::new(BresenhamCircle::contiguous(0, 0, 3), &[3.0, 2.0]) {
+///         print!("({}, {}), ", x, y);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// (3, 0), (3, 1), (2, 2), (-1, 3), (-2, 2), (-3, -1), (-2, -2), (1, -3), (2, -2),
+/// ```
+///
+/// [`BresenhamCircle::contiguous`]: struct.BresenhamCircle.html#method.contiguous
+/// [`MidpointEllipse`]: struct.MidpointEllipse.html
+#[derive(Clone, Debug, PartialEq)]
+pub struct Dashed<'a, I, O, It> {
+    iterator: It,
+    pattern: &'a [I],
+    period: usize,
+    total: I,
+    index: usize,
+    distance: I,
+    last: Option<Point<O>>,
+}
+
+impl<'a, I: FloatNum, O: SignedNum, It: Iterator<Item = Point<O>>> Dashed<'a, I, O, It> {
+    #[inline]
+    pub fn new(iterator: It, pattern: &'a [I]) -> Self {
+        let mut period = pattern.len();
+        let mut total = pattern
+            .iter()
+            .fold(I::zero(), |total, &length| total + length);
+
+        if period % 2 == 1 {
+            period *= 2;
+            total = total + total;
+        }
+
+        Self {
+            iterator,
+            pattern,
+            period,
+            total,
+            index: 0,
+            distance: I::zero(),
+            last: None,
+        }
+    }
+}
+
+impl<'a, I: FloatNum, O: SignedNum, It: Iterator<Item = Point<O>>> Iterator
+    for Dashed<'a, I, O, It>
+{
+    type Item = Point<O>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let point = self.iterator.next()?;
+
+            if self.total <= I::zero() {
+                return Some(point);
+            }
+
+            if let Some(last) = self.last {
+                let x = I::cast(point.0 - last.0);
+                let y = I::cast(point.1 - last.1);
+                self.distance += (x * x + y * y).sqrt();
+            }
+
+            self.last = Some(point);
+
+            // Skip whole repeats of the pattern, then find the length that the point is in
+            if self.distance >= self.total {
+                self.distance %= self.total;
+            }
+
+            while self.distance >= self.pattern[self.index % self.pattern.len()] {
+                self.distance -= self.pattern[self.index % self.pattern.len()];
+                self.index = (self.index + 1) % self.period;
+            }
+
+            // The even lengths are the drawn ones
+            if self.index & 1 == 0 {
+                return Some(point);
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.total <= I::zero() {
+            self.iterator.size_hint()
+        } else {
+            (0, self.iterator.size_hint().1)
+        }
+    }
+}
+
+#[test]
+fn tests() {
+    use {Bresenham, BresenhamCircle, MidpointEllipse};
+
+    let dashed = |pattern: &[f64]| {
+        Dashed::new(Bresenham::new((0, 0), (9, 0)), pattern)
+            .map(|(x, _)| x)
+            .collect::<Vec<i32>>()
+    };
+
+    assert_eq!(dashed(&[3.0, 2.0]), [0, 1, 2, 5, 6, 7]);
+    assert_eq!(dashed(&[1.0, 0.0, 0.0, 1.0]), [0, 2, 4, 6, 8]);
+    assert_eq!(dashed(&[2.0]), [0, 1, 4, 5, 8, 9]);
+    assert_eq!(dashed(&[]).len(), 10);
+    assert_eq!(dashed(&[0.0, 0.0]).len(), 10);
+    assert_eq!(dashed(&[1.0, 100.0]), [0]);
+
+    // Diagonal steps are longer
+    assert_eq!(
+        Dashed::new(Bresenham::new((0, 0), (5, 5)), &[3.0, 3.0]).collect::<Vec<_>>(),
+        [(0, 0), (1, 1), (2, 2), (5, 5)]
+    );
+
+    // The dashes are spread evenly around a circle, rather than bunched up in one quadrant
+    let circle = BresenhamCircle::contiguous(0, 0, 20).collect::<Vec<_>>();
+    let dashed = Dashed::new(circle.iter().cloned(), &[5.0, 5.0]).collect::<Vec<_>>();
+    let gaps = circle
+        .windows(2)
+        .filter(|pair| dashed.contains(&pair[0]) != dashed.contains(&pair[1]))
+        .count();
+
+    assert!((22..=26).contains(&gaps));
+    assert!(dashed.len() * 2 >= circle.len() - 4 && dashed.len() * 2 <= circle.len() + 4);
+
+    let ellipse = MidpointEllipse::new(0, 0, 10, 4).collect::<Vec<_>>();
+    let dashed = Dashed::new(ellipse.iter().cloned(), &[4.0, 4.0]).collect::<Vec<_>>();
+    assert!(dashed.len() * 2 >= ellipse.len() - 4 && dashed.len() * 2 <= ellipse.len() + 4);
+}
@@ -0,0 +1,142 @@
+/// An iterator that only returns every `n`th point of a line, and always the last one.
+///
+/// Unlike `step_by`, the end of the line is never dropped, which makes it handy for placing
+/// markers along a path or cheaply sampling a long line. It works with any kind of point, so it
+/// can be used with the 3D algorithms too.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{Bresenham, Decimate};
+///
+/// fn main() {
+///     for (x, y) in Decimate::new(Bresenham::new((0, 0), (10, 0)), 4) {
+///         print!("({}, {}), ", x, y);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// (0, 0), (4, 0), (8, 0), (10, 0),
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "I: ::serde::Serialize, I::Item: ::serde::Serialize",
+        deserialize = "I: ::serde::Deserialize<'de>, I::Item: ::serde::Deserialize<'de>"
+    ))
+)]
+pub struct Decimate<I: Iterator> {
+    iterator: I,
+    step: usize,
+    index: usize,
+    last: Option<I::Item>,
+}
+
+impl<I: Iterator> Decimate<I> {
+    /// Return every `step`th point. A step of `0` is treated as `1`.
+    #[inline]
+    pub fn new(iterator: I, step: usize) -> Self {
+        Self {
+            iterator,
+            step: step.max(1),
+            index: 0,
+            last: None,
+        }
+    }
+
+    /// Count how many points would be returned if there were `count` more to come.
+    #[inline]
+    fn kept(&self, count: usize) -> usize {
+        if count == 0 {
+            return self.last.is_some() as usize;
+        }
+
+        // The points at the start of each step, and the end if it isn't one of them
+        let first = (self.step - self.index) % self.step;
+        let every = if first < count {
+            (count - first - 1) / self.step + 1
+        } else {
+            0
+        };
+        let end = every == 0 || first + (every - 1) * self.step != count - 1;
+
+        every + end as usize
+    }
+}
+
+impl<I: Iterator> Iterator for Decimate<I> {
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.iterator.next() {
+                Some(point) => {
+                    let keep = self.index == 0;
+                    self.index += 1;
+
+                    if self.index == self.step {
+                        self.index = 0;
+                    }
+
+                    if keep {
+                        self.last = None;
+                        return Some(point);
+                    }
+
+                    self.last = Some(point);
+                }
+                // The end of the line is returned if it was skipped
+                None => return self.last.take(),
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (low, high) = self.iterator.size_hint();
+        (self.kept(low), high.map(|high| self.kept(high)))
+    }
+}
+
+#[test]
+fn tests() {
+    use {Bresenham, Bresenham3d};
+
+    let decimate = |end, step| Decimate::new(Bresenham::new((0, 0), end), step).collect::<Vec<_>>();
+
+    assert_eq!(decimate((6, 0), 3), [(0, 0), (3, 0), (6, 0)]);
+    assert_eq!(decimate((7, 0), 3), [(0, 0), (3, 0), (6, 0), (7, 0)]);
+    assert_eq!(decimate((0, 0), 5), [(0, 0)]);
+    assert_eq!(decimate((2, 0), 0), [(0, 0), (1, 0), (2, 0)]);
+    assert_eq!(decimate((2, 0), 1), [(0, 0), (1, 0), (2, 0)]);
+    assert_eq!(decimate((3, 0), 100), [(0, 0), (3, 0)]);
+
+    assert_eq!(
+        Decimate::new(Bresenham3d::new((0, 0, 0), (5, 5, 5)), 2).collect::<Vec<_>>(),
+        [(0, 0, 0), (2, 2, 2), (4, 4, 4), (5, 5, 5)]
+    );
+
+    assert_eq!(Decimate::new(core::iter::empty::<u8>(), 2).count(), 0);
+
+    // The size hint is exact when the line's length is known
+    for length in 0..12 {
+        for step in 1..5 {
+            let mut decimate = Decimate::new(0..length, step);
+
+            loop {
+                let (low, high) = decimate.size_hint();
+                let count = decimate.clone().count();
+                assert_eq!((low, high), (count, Some(count)));
+
+                if decimate.next().is_none() {
+                    break;
+                }
+            }
+        }
+    }
+}
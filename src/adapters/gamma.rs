@@ -0,0 +1,265 @@
+use FloatNum;
+
+/// A transfer function for turning the linear coverage of anti-aliased algorithms into alpha
+/// values that blend correctly in a gamma-encoded color space like sRGB.
+///
+/// Coverage is how much of a pixel the line covers, so it's linear, but blending it straight into
+/// sRGB pixels makes the edges of lines look too thin and dark. Encoding it first fixes that.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::Transfer;
+///
+/// fn main() {
+///     for &transfer in &[Transfer::Linear, Transfer::Gamma(2.2), Transfer::Srgb] {
+///         println!("{:.3}", transfer.apply(0.25f32));
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// 0.250
+/// 0.533
+/// 0.537
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Transfer<I> {
+    /// Leave the coverage as it is.
+    Linear,
+    /// Raise the coverage to the power of `1 / gamma`, for a display with the given gamma, which
+    /// is usually `2.2`.
+    Gamma(I),
+    /// The exact sRGB encoding curve.
+    Srgb,
+}
+
+impl<I: FloatNum> Transfer<I> {
+    /// Encode a coverage value from `0` to `1`.
+    #[inline]
+    pub fn apply(self, coverage: I) -> I {
+        match self {
+            Transfer::Linear => coverage,
+            Transfer::Gamma(gamma) => coverage.powf(gamma.recip()),
+            Transfer::Srgb => {
+                if coverage <= I::cast(0.003_130_8) {
+                    coverage * I::cast(12.92)
+                } else {
+                    I::cast(1.055) * coverage.powf(I::cast(1.0 / 2.4)) - I::cast(0.055)
+                }
+            }
+        }
+    }
+
+    /// Make a lookup table that encodes `u8` alpha values, for [`GammaTable`].
+    ///
+    /// [`GammaTable`]: struct.GammaTable.html
+    #[inline]
+    pub fn table(self) -> [u8; 256] {
+        let mut table = [0; 256];
+        let max = I::cast(255);
+
+        for (alpha, entry) in table.iter_mut().enumerate() {
+            let encoded = self.apply(I::cast(alpha) / max) * max;
+            *entry = encoded.round().max(I::zero()).min(max).to_u8().unwrap();
+        }
+
+        table
+    }
+}
+
+/// An iterator that applies a [`Transfer`] function to the coverage of an anti-aliased one, like
+/// [`XiaolinWu`].
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{GammaCorrected, Transfer, XiaolinWu};
+///
+/// fn main() {
+///     let line = XiaolinWu::<f32, i32>::new((0.0, 0.0), (4.0, 1.0));
+///
+///     for ((x, y), alpha) in GammaCorrected::new(line, Transfer::Srgb) {
+///         print!("(({}, {}), {:.2}), ", x, y, alpha);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// ((0, 0), 1.00), ((1, 0), 0.88), ((1, 1), 0.54), ((2, 0), 0.74), ((2, 1), 0.74), ((3, 0), 0.54), ((3, 1), 0.88), ((4, 1), 1.00),
+/// ```
+///
+/// [`Transfer`]: enum.Transfer.html
+/// [`XiaolinWu`]: struct.XiaolinWu.html
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GammaCorrected<I, It> {
+    iterator: It,
+    transfer: Transfer<I>,
+}
+
+impl<P, I: FloatNum, It: Iterator<Item = (P, I)>> GammaCorrected<I, It> {
+    #[inline]
+    pub fn new(iterator: It, transfer: Transfer<I>) -> Self {
+        Self { iterator, transfer }
+    }
+}
+
+impl<P, I: FloatNum, It: Iterator<Item = (P, I)>> Iterator for GammaCorrected<I, It> {
+    type Item = (P, I);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let transfer = self.transfer;
+
+        self.iterator
+            .next()
+            .map(|(point, coverage)| (point, transfer.apply(coverage)))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iterator.size_hint()
+    }
+
+    #[inline]
+    fn fold<B, F>(self, init: B, mut function: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        let transfer = self.transfer;
+
+        self.iterator.fold(init, |accum, (point, coverage)| {
+            function(accum, (point, transfer.apply(coverage)))
+        })
+    }
+}
+
+impl<P, I: FloatNum, It: ExactSizeIterator<Item = (P, I)>> ExactSizeIterator
+    for GammaCorrected<I, It>
+{
+}
+
+/// An iterator that looks the `u8` alpha values of another one up in a table, like one made with
+/// [`Transfer::table`].
+///
+/// This is for [`XiaolinWuAlpha`], so that the alpha values can be encoded without any
+/// floating-point math.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{GammaTable, Transfer, XiaolinWu};
+///
+/// fn main() {
+///     let table = Transfer::Gamma(2.2f32).table();
+///     let line = XiaolinWu::<f32, i32>::new((0.0, 0.0), (4.0, 1.0)).alpha::<u8>();
+///
+///     for ((x, y), alpha) in GammaTable::new(line, &table) {
+///         print!("(({}, {}), {}), ", x, y, alpha);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// ((0, 0), 255), ((1, 0), 224), ((1, 1), 136), ((2, 0), 186), ((2, 1), 186), ((3, 0), 135), ((3, 1), 224), ((4, 1), 255),
+/// ```
+///
+/// [`Transfer::table`]: enum.Transfer.html#method.table
+/// [`XiaolinWuAlpha`]: struct.XiaolinWuAlpha.html
+#[derive(Clone, Debug, PartialEq)]
+pub struct GammaTable<'a, It> {
+    iterator: It,
+    table: &'a [u8; 256],
+}
+
+impl<'a, P, It: Iterator<Item = (P, u8)>> GammaTable<'a, It> {
+    #[inline]
+    pub fn new(iterator: It, table: &'a [u8; 256]) -> Self {
+        Self { iterator, table }
+    }
+}
+
+impl<'a, P, It: Iterator<Item = (P, u8)>> Iterator for GammaTable<'a, It> {
+    type Item = (P, u8);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let table = self.table;
+
+        self.iterator
+            .next()
+            .map(|(point, alpha)| (point, table[alpha as usize]))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iterator.size_hint()
+    }
+
+    #[inline]
+    fn fold<B, F>(self, init: B, mut function: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        let table = self.table;
+
+        self.iterator.fold(init, |accum, (point, alpha)| {
+            function(accum, (point, table[alpha as usize]))
+        })
+    }
+}
+
+impl<'a, P, It: ExactSizeIterator<Item = (P, u8)>> ExactSizeIterator for GammaTable<'a, It> {}
+
+#[test]
+fn tests() {
+    use XiaolinWu;
+
+    // The ends are left where they are, and everything in between is brightened
+    for &transfer in &[Transfer::Gamma(2.2), Transfer::Srgb] {
+        assert_eq!(transfer.apply(0.0f64), 0.0);
+        assert!((transfer.apply(1.0f64) - 1.0).abs() < 1e-9);
+
+        for step in 1..10 {
+            let coverage = f64::from(step) / 10.0;
+            assert!(transfer.apply(coverage) > coverage);
+        }
+    }
+
+    assert_eq!(Transfer::Linear.apply(0.3f32), 0.3);
+    assert!((Transfer::Srgb.apply(0.002f64) - 0.02584).abs() < 1e-9);
+    assert!((Transfer::Gamma(2.0f64).apply(0.25) - 0.5).abs() < 1e-9);
+
+    // The tables match applying the function
+    let linear = Transfer::<f32>::Linear.table();
+    assert!(linear.iter().enumerate().all(|(alpha, &entry)| alpha == entry as usize));
+
+    let srgb = Transfer::<f64>::Srgb.table();
+    assert_eq!((srgb[0], srgb[255]), (0, 255));
+    assert!(srgb.windows(2).all(|pair| pair[0] <= pair[1]));
+    assert_eq!(srgb[64], (Transfer::Srgb.apply(64.0f64 / 255.0) * 255.0).round() as u8);
+
+    let line = XiaolinWu::<f32, i32>::new((0.0, 0.0), (7.0, 3.0));
+    let corrected = GammaCorrected::new(line.clone(), Transfer::Gamma(2.2));
+    assert_eq!(corrected.size_hint(), line.size_hint());
+    assert_eq!(
+        corrected.collect::<Vec<_>>(),
+        line.clone()
+            .map(|(point, coverage)| (point, coverage.powf(1.0 / 2.2)))
+            .collect::<Vec<_>>()
+    );
+
+    let table = Transfer::Gamma(2.2f32).table();
+    let alpha = line.alpha::<u8>();
+    assert_eq!(
+        GammaTable::new(alpha.clone(), &table).collect::<Vec<_>>(),
+        alpha
+            .map(|(point, alpha)| (point, table[alpha as usize]))
+            .collect::<Vec<_>>()
+    );
+}
@@ -0,0 +1,141 @@
+use {Point, SignedNum};
+
+/// An iterator that turns points into indices into a flat buffer, like the pixels of an image
+/// stored a row at a time.
+///
+/// Each point becomes `y * stride + x`. With [`checked`], points outside of the buffer are left
+/// out; otherwise they need to be inside it, and a point with a negative coordinate panics.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{Bresenham, BufferIndices};
+///
+/// fn main() {
+///     let (width, height) = (8, 4);
+///     let mut pixels = vec![0u8; width * height];
+///
+///     for index in BufferIndices::checked(Bresenham::new((-2, -1), (9, 3)), width, (8, 4)) {
+///         pixels[index] = 1;
+///     }
+///
+///     for row in pixels.chunks(width) {
+///         println!("{:?}", row);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// [0, 1, 1, 1, 0, 0, 0, 0]
+/// [0, 0, 0, 0, 1, 1, 1, 0]
+/// [0, 0, 0, 0, 0, 0, 0, 1]
+/// [0, 0, 0, 0, 0, 0, 0, 0]
+/// ```
+///
+/// [`checked`]: #method.checked
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BufferIndices<T, I> {
+    iterator: I,
+    stride: usize,
+    size: Option<Point<T>>,
+}
+
+impl<T: SignedNum, I: Iterator<Item = Point<T>>> BufferIndices<T, I> {
+    /// Turn the points into indices without checking that they're inside the buffer.
+    #[inline]
+    pub fn new(iterator: I, stride: usize) -> Self {
+        Self {
+            iterator,
+            stride,
+            size: None,
+        }
+    }
+
+    /// Turn the points into indices, leaving out the ones that aren't inside a buffer with
+    /// `size.0` columns and `size.1` rows.
+    ///
+    /// The stride can be more than the width, for buffers that have padding at the end of each
+    /// row.
+    #[inline]
+    pub fn checked(iterator: I, stride: usize, size: Point<T>) -> Self {
+        Self {
+            iterator,
+            stride,
+            size: Some(size),
+        }
+    }
+
+    #[inline]
+    fn index(&self, (x, y): Point<T>) -> usize {
+        y.to_usize().unwrap() * self.stride + x.to_usize().unwrap()
+    }
+}
+
+impl<T: SignedNum, I: Iterator<Item = Point<T>>> Iterator for BufferIndices<T, I> {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.size {
+            None => self.iterator.next().map(|point| self.index(point)),
+            Some(size) => {
+                let inside = |&(x, y): &Point<T>| {
+                    x >= T::zero() && x < size.0 && y >= T::zero() && y < size.1
+                };
+                let point = self.iterator.find(inside)?;
+
+                Some(self.index(point))
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (low, high) = self.iterator.size_hint();
+
+        if self.size.is_some() {
+            (0, high)
+        } else {
+            (low, high)
+        }
+    }
+}
+
+#[test]
+fn tests() {
+    use {Bresenham, BresenhamCircle};
+
+    let line = Bresenham::new((0, 0), (5, 2));
+    let indices = BufferIndices::new(line.clone(), 10);
+    assert_eq!(indices.size_hint(), (6, Some(6)));
+    assert_eq!(
+        indices.collect::<Vec<_>>(),
+        line.map(|(x, y)| (y * 10 + x) as usize).collect::<Vec<_>>()
+    );
+
+    // Points off the edges are left out, including ones that would wrap onto the next row
+    let circle = BresenhamCircle::new(2, 2, 3);
+    let size = (4, 5);
+    let checked = BufferIndices::checked(circle.clone(), 6, size).collect::<Vec<_>>();
+    let inside = circle
+        .filter(|&(x, y)| x >= 0 && x < size.0 && y >= 0 && y < size.1)
+        .map(|(x, y)| (y * 6 + x) as usize)
+        .collect::<Vec<_>>();
+    assert!(!inside.is_empty());
+    assert_eq!(checked, inside);
+
+    assert_eq!(
+        BufferIndices::checked(Bresenham::new((-3, -3), (-1, -1)), 4, (4, 4)).count(),
+        0
+    );
+}
+
+#[test]
+#[should_panic]
+fn negative() {
+    use Bresenham;
+
+    BufferIndices::new(Bresenham::new((0, 0), (-2, 0)), 4).for_each(drop);
+}
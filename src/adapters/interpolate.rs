@@ -0,0 +1,270 @@
+use {FloatNum, Point, Progress};
+use num_traits::Float;
+
+/// A value that can be blended linearly between two others, like a color, height or texture
+/// coordinate.
+///
+/// It's implemented for `f32`, `f64`, `u8` and `u16`, which are rounded to the nearest value, and
+/// for tuples and arrays of them.
+pub trait Lerp: Copy {
+    /// Blend from `self` at `t = 0` to `end` at `t = 1`.
+    fn lerp(self, end: Self, t: f32) -> Self;
+}
+
+impl Lerp for f32 {
+    #[inline]
+    fn lerp(self, end: Self, t: f32) -> Self {
+        self * (1.0 - t) + end * t
+    }
+}
+
+impl Lerp for f64 {
+    #[inline]
+    fn lerp(self, end: Self, t: f32) -> Self {
+        let t = f64::from(t);
+
+        self * (1.0 - t) + end * t
+    }
+}
+
+impl Lerp for u8 {
+    #[inline]
+    fn lerp(self, end: Self, t: f32) -> Self {
+        Float::round(f32::from(self).lerp(f32::from(end), t)) as u8
+    }
+}
+
+impl Lerp for u16 {
+    #[inline]
+    fn lerp(self, end: Self, t: f32) -> Self {
+        Float::round(f32::from(self).lerp(f32::from(end), t)) as u16
+    }
+}
+
+impl<A: Lerp, B: Lerp> Lerp for (A, B) {
+    #[inline]
+    fn lerp(self, end: Self, t: f32) -> Self {
+        (self.0.lerp(end.0, t), self.1.lerp(end.1, t))
+    }
+}
+
+impl<A: Lerp, B: Lerp, C: Lerp> Lerp for (A, B, C) {
+    #[inline]
+    fn lerp(self, end: Self, t: f32) -> Self {
+        (self.0.lerp(end.0, t), self.1.lerp(end.1, t), self.2.lerp(end.2, t))
+    }
+}
+
+impl<A: Lerp, B: Lerp, C: Lerp, D: Lerp> Lerp for (A, B, C, D) {
+    #[inline]
+    fn lerp(self, end: Self, t: f32) -> Self {
+        (
+            self.0.lerp(end.0, t),
+            self.1.lerp(end.1, t),
+            self.2.lerp(end.2, t),
+            self.3.lerp(end.3, t),
+        )
+    }
+}
+
+impl<V: Lerp, const N: usize> Lerp for [V; N] {
+    #[inline]
+    fn lerp(mut self, end: Self, t: f32) -> Self {
+        for (value, &end) in self.iter_mut().zip(end.iter()) {
+            *value = value.lerp(end, t);
+        }
+
+        self
+    }
+}
+
+/// Turn a fraction of the way along a line on the screen into the fraction of the way along it
+/// before it was projected, given `1 / w` at each end.
+#[inline]
+pub(crate) fn perspective_correct<I: FloatNum>(t: I, inverse_w: Point<I>) -> I {
+    let end = t * inverse_w.1;
+
+    end / ((I::one() - t) * inverse_w.0 + end)
+}
+
+/// An iterator that blends a value from `start` at the first point to `end` at the last one, and
+/// returns it along with each point, for things like Gouraud shading.
+///
+/// How far along the line each point is comes from [`Progress`], so the value is spread evenly
+/// over the points that are actually drawn, and the first and last points get exactly `start` and
+/// `end`. For lines that have been projected onto the screen, [`perspective`] blends the value the
+/// way it would be blended in 3D instead.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{Bresenham, Interpolate};
+///
+/// fn main() {
+///     let red = [255u8, 0, 0];
+///     let blue = [0u8, 0, 255];
+///
+///     for ((x, y), color) in Interpolate::new(Bresenham::new((0, 0), (4, 1)), red, blue) {
+///         print!("(({}, {}), {:?}), ", x, y, color);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// ((0, 0), [255, 0, 0]), ((1, 0), [191, 0, 64]), ((2, 0), [128, 0, 128]), ((3, 0), [64, 0, 191]), ((4, 1), [0, 0, 255]),
+/// ```
+///
+/// [`Progress`]: struct.Progress.html
+/// [`perspective`]: #method.perspective
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Interpolate<V, I> {
+    progress: Progress<I>,
+    start: V,
+    end: V,
+    inverse_w: Option<Point<f32>>,
+}
+
+impl<V: Lerp, I: ExactSizeIterator> Interpolate<V, I> {
+    #[inline]
+    pub fn new(iterator: I, start: V, end: V) -> Self {
+        Self {
+            progress: Progress::new(iterator),
+            start,
+            end,
+            inverse_w: None,
+        }
+    }
+
+    /// Blend the value with perspective correction, given the `w` that each end had before it was
+    /// divided by to project it onto the screen.
+    ///
+    /// Blending evenly across the screen warps the value on long lines that go into the distance,
+    /// because the far half of the line is squashed into fewer points. This blends `value / w` and
+    /// `1 / w` instead, which are even across the screen. The `w`s need to be more than `0`, which
+    /// they are for points in front of the camera.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// extern crate line_drawing;
+    /// use line_drawing::{Bresenham, Interpolate};
+    ///
+    /// fn main() {
+    ///     let line = Bresenham::new((0, 0), (4, 0));
+    ///
+    ///     for ((x, y), u) in Interpolate::perspective(line, 0.0, 1.0, 1.0, 3.0) {
+    ///         print!("(({}, {}), {:.3}), ", x, y, u);
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// ```text
+    /// ((0, 0), 0.000), ((1, 0), 0.100), ((2, 0), 0.250), ((3, 0), 0.500), ((4, 0), 1.000),
+    /// ```
+    #[inline]
+    pub fn perspective(iterator: I, start: V, end: V, start_w: f32, end_w: f32) -> Self {
+        Self {
+            inverse_w: Some((start_w.recip(), end_w.recip())),
+            ..Self::new(iterator, start, end)
+        }
+    }
+}
+
+impl<V: Lerp, I: ExactSizeIterator> Iterator for Interpolate<V, I> {
+    type Item = (I::Item, V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (item, mut t) = self.progress.next()?;
+
+        if let Some(inverse_w) = self.inverse_w {
+            t = perspective_correct(t, inverse_w);
+        }
+
+        Some((item, self.start.lerp(self.end, t)))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.progress.size_hint()
+    }
+}
+
+impl<V: Lerp, I: ExactSizeIterator> ExactSizeIterator for Interpolate<V, I> {}
+
+#[test]
+fn tests() {
+    use {Bresenham, Bresenham3d};
+
+    let values = |start, end| {
+        Interpolate::new(Bresenham::new((0, 0), (-4, 2)), start, end)
+            .map(|(_, value)| value)
+            .collect::<Vec<f32>>()
+    };
+
+    assert_eq!(values(1.0, 3.0), [1.0, 1.5, 2.0, 2.5, 3.0]);
+    assert_eq!(values(3.0, 1.0), [3.0, 2.5, 2.0, 1.5, 1.0]);
+
+    // The ends are exact, even when the steps aren't
+    let heights = Interpolate::new(Bresenham::new((0, 0), (6, 0)), 0.1f64, 0.7)
+        .map(|(_, height)| height)
+        .collect::<Vec<_>>();
+    assert_eq!((heights[0], heights[6]), (0.1, 0.7));
+
+    let colors = Interpolate::new(Bresenham::new((0, 0), (0, 2)), [0u8, 100, 255], [255, 0, 0]);
+    assert_eq!(colors.len(), 3);
+    assert_eq!(
+        colors.collect::<Vec<_>>(),
+        [
+            ((0, 0), [0, 100, 255]),
+            ((0, 1), [128, 50, 128]),
+            ((0, 2), [255, 0, 0])
+        ]
+    );
+
+    // Tuples can hold different kinds of values
+    let line = Bresenham3d::new((0, 0, 0), (2, 0, 0));
+    let attributes = Interpolate::new(line, ((0.0f32, 1.0f32), 0u16), ((1.0, 0.0), 1000));
+    assert_eq!(
+        attributes.map(|(_, value)| value).collect::<Vec<_>>(),
+        [((0.0, 1.0), 0), ((0.5, 0.5), 500), ((1.0, 0.0), 1000)]
+    );
+
+    assert_eq!(
+        Interpolate::new(Bresenham::new((5, 5), (5, 5)), 2.0f32, 9.0).collect::<Vec<_>>(),
+        [((5, 5), 2.0)]
+    );
+}
+
+#[test]
+fn perspective() {
+    use Bresenham;
+
+    let line = Bresenham::new((0, 0), (8, 0));
+    let values = |line: Interpolate<f32, Bresenham<i32>>| {
+        line.map(|(_, value)| value).collect::<Vec<_>>()
+    };
+
+    // The same w at both ends is the same as blending evenly
+    assert_eq!(
+        values(Interpolate::perspective(line.clone(), 2.0, 6.0, 5.0, 5.0)),
+        values(Interpolate::new(line.clone(), 2.0, 6.0))
+    );
+
+    // The far end is squashed into fewer points
+    let corrected = values(Interpolate::perspective(line.clone(), 0.0, 1.0, 1.0, 3.0));
+    assert_eq!((corrected[0], corrected[8]), (0.0, 1.0));
+    assert_eq!(corrected[4], 0.25);
+    assert!(corrected.windows(2).all(|pair| pair[0] < pair[1]));
+    assert!(corrected.windows(3).all(|values| values[1] - values[0] < values[2] - values[1]));
+
+    // Blending value / w and 1 / w gives the same values
+    for (index, &value) in corrected.iter().enumerate() {
+        let t = index as f32 / 8.0;
+        let over_w = (1.0 - t) * (0.0 / 1.0) + t * (1.0 / 3.0);
+        let inverse_w = (1.0 - t) * (1.0 / 1.0) + t * (1.0 / 3.0);
+        assert!((value - over_w / inverse_w).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,383 @@
+//! Wrappers that change the points of another iterator, which work with any of the algorithms.
+//!
+//! They're all re-exported from the root of the crate, and can be chained onto an iterator with
+//! [`LineExt`].
+//!
+//! [`LineExt`]: trait.LineExt.html
+
+mod clip;
+mod dash;
+mod decimate;
+mod gamma;
+mod index;
+mod interpolate;
+mod morton;
+mod symmetry;
+mod transform;
+mod vertices;
+
+pub use self::clip::*;
+pub use self::dash::*;
+pub use self::decimate::*;
+pub use self::gamma::*;
+pub use self::index::*;
+pub use self::interpolate::*;
+pub use self::morton::*;
+pub use self::symmetry::*;
+pub use self::transform::*;
+pub use self::vertices::*;
+
+use {FloatNum, GridBounds, PixelPerfect, Point, PointSink, SignedNum, Stipple, Wrapped};
+
+/// Methods for chaining the adapters onto any iterator of points.
+///
+/// Each one is the same as calling the adapter's `new` function with the iterator.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{Bresenham, GridBounds, LineExt};
+///
+/// fn main() {
+///     let bounds = GridBounds::new((0, 0), (9, 9));
+///     let line = Bresenham::new((-5, 0), (20, 0)).clipped(bounds).dashed(&[4.0, 2.0]);
+///
+///     for (x, y) in line {
+///         print!("({}, {}), ", x, y);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// (0, 0), (1, 0), (2, 0), (3, 0), (6, 0), (7, 0), (8, 0), (9, 0),
+/// ```
+pub trait LineExt: Iterator + Sized {
+    /// Cut a line off at the edges of some bounds. See [`Clipped`].
+    ///
+    /// [`Clipped`]: struct.Clipped.html
+    #[inline]
+    fn clipped<T: SignedNum>(self, bounds: GridBounds<T>) -> Clipped<T, Self>
+    where
+        Self: Iterator<Item = Point<T>>,
+    {
+        Clipped::new(self, bounds)
+    }
+
+    /// Leave out the points outside some bounds. See [`Within`].
+    ///
+    /// [`Within`]: struct.Within.html
+    #[inline]
+    fn within<T: SignedNum>(self, bounds: GridBounds<T>) -> Within<T, Self>
+    where
+        Self: Iterator<Item = Point<T>>,
+    {
+        Within::new(self, bounds)
+    }
+
+    /// Break a line up into dashes. See [`Dashed`].
+    ///
+    /// [`Dashed`]: struct.Dashed.html
+    #[inline]
+    fn dashed<I: FloatNum, O: SignedNum>(self, pattern: &[I]) -> Dashed<'_, I, O, Self>
+    where
+        Self: Iterator<Item = Point<O>>,
+    {
+        Dashed::new(self, pattern)
+    }
+
+    /// Only return every `step`th point, and the last one. See [`Decimate`].
+    ///
+    /// [`Decimate`]: struct.Decimate.html
+    #[inline]
+    fn decimated(self, step: usize) -> Decimate<Self> {
+        Decimate::new(self, step)
+    }
+
+    /// Apply an affine transform to the points. See [`Transformed`].
+    ///
+    /// [`Transformed`]: struct.Transformed.html
+    #[inline]
+    fn transformed<I: FloatNum, O: SignedNum>(
+        self,
+        affine: Affine<I>,
+        rounding: Rounding,
+    ) -> Transformed<I, Self>
+    where
+        Self: Iterator<Item = Point<O>>,
+    {
+        Transformed::new(self, affine, rounding)
+    }
+
+    /// Return each point along with its reflection. See [`Mirror`].
+    ///
+    /// [`Mirror`]: struct.Mirror.html
+    #[inline]
+    fn mirrored<T: SignedNum>(self, reflection: Reflection<T>) -> Mirror<T, Self>
+    where
+        Self: Iterator<Item = Point<T>>,
+    {
+        Mirror::new(self, reflection)
+    }
+
+    /// Return each point turned around a center a number of times. See [`Rotational`].
+    ///
+    /// [`Rotational`]: struct.Rotational.html
+    #[inline]
+    fn rotational<I: FloatNum, O: SignedNum>(
+        self,
+        center: Point<I>,
+        folds: usize,
+    ) -> Rotational<I, O, Self>
+    where
+        Self: Iterator<Item = Point<O>>,
+    {
+        Rotational::new(self, center, folds)
+    }
+
+    /// Only return the points where a repeating bit pattern is set. See [`Stipple`].
+    ///
+    /// [`Stipple`]: struct.Stipple.html
+    #[inline]
+    fn stippled(self, pattern: u16, factor: usize) -> Stipple<Self> {
+        Stipple::new(self, pattern, factor)
+    }
+
+    /// Remove the corners of L-shaped steps. See [`PixelPerfect`].
+    ///
+    /// [`PixelPerfect`]: struct.PixelPerfect.html
+    #[inline]
+    fn pixel_perfect<T: SignedNum>(self) -> PixelPerfect<T, Self>
+    where
+        Self: Iterator<Item = Point<T>>,
+    {
+        PixelPerfect::new(self)
+    }
+
+    /// Wrap the points around the edges of a grid. See [`Wrapped`].
+    ///
+    /// [`Wrapped`]: struct.Wrapped.html
+    #[inline]
+    fn wrapped<T: SignedNum>(self, size: Point<T>) -> Wrapped<T, Self>
+    where
+        Self: Iterator<Item = Point<T>>,
+    {
+        Wrapped::new(self, size)
+    }
+
+    /// Turn the points into indices into a flat buffer. See [`BufferIndices`].
+    ///
+    /// [`BufferIndices`]: struct.BufferIndices.html
+    #[inline]
+    fn indices<T: SignedNum>(self, stride: usize) -> BufferIndices<T, Self>
+    where
+        Self: Iterator<Item = Point<T>>,
+    {
+        BufferIndices::new(self, stride)
+    }
+
+    /// Turn the points into indices into a flat buffer, leaving out the ones outside of it. See
+    /// [`BufferIndices::checked`].
+    ///
+    /// [`BufferIndices::checked`]: struct.BufferIndices.html#method.checked
+    #[inline]
+    fn checked_indices<T: SignedNum>(self, stride: usize, size: Point<T>) -> BufferIndices<T, Self>
+    where
+        Self: Iterator<Item = Point<T>>,
+    {
+        BufferIndices::checked(self, stride, size)
+    }
+
+    /// Return the Morton codes of the points instead. See [`MortonCodes`].
+    ///
+    /// [`MortonCodes`]: struct.MortonCodes.html
+    #[inline]
+    fn morton_codes(self) -> MortonCodes<Self>
+    where
+        Self::Item: MortonCode,
+    {
+        MortonCodes::new(self)
+    }
+
+    /// Return the points along with their Morton codes. See [`WithMorton`].
+    ///
+    /// [`WithMorton`]: struct.WithMorton.html
+    #[inline]
+    fn with_morton(self) -> WithMorton<Self>
+    where
+        Self::Item: MortonCode + Copy,
+    {
+        WithMorton::new(self)
+    }
+
+    /// Apply a transfer function to the coverage of an anti-aliased line. See [`GammaCorrected`].
+    ///
+    /// [`GammaCorrected`]: struct.GammaCorrected.html
+    #[inline]
+    fn gamma_corrected<P, I: FloatNum>(self, transfer: Transfer<I>) -> GammaCorrected<I, Self>
+    where
+        Self: Iterator<Item = (P, I)>,
+    {
+        GammaCorrected::new(self, transfer)
+    }
+
+    /// Look the `u8` alpha values of an anti-aliased line up in a table. See [`GammaTable`].
+    ///
+    /// [`GammaTable`]: struct.GammaTable.html
+    #[inline]
+    fn gamma_table<P>(self, table: &[u8; 256]) -> GammaTable<'_, Self>
+    where
+        Self: Iterator<Item = (P, u8)>,
+    {
+        GammaTable::new(self, table)
+    }
+
+    /// Blend a value from `start` to `end` along the line. See [`Interpolate`].
+    ///
+    /// [`Interpolate`]: struct.Interpolate.html
+    #[inline]
+    fn interpolate<V: Lerp>(self, start: V, end: V) -> Interpolate<V, Self>
+    where
+        Self: ExactSizeIterator,
+    {
+        Interpolate::new(self, start, end)
+    }
+
+    /// Only return the points where the line changes direction, and the ends. See [`Vertices`].
+    ///
+    /// [`Vertices`]: struct.Vertices.html
+    #[inline]
+    fn vertices<T: SignedNum>(self) -> Vertices<T, Self>
+    where
+        Self: Iterator<Item = Point<T>>,
+    {
+        Vertices::new(self)
+    }
+
+    /// Fill in the points between the corners made by [`Vertices`]. See [`ExpandVertices`].
+    ///
+    /// [`Vertices`]: struct.Vertices.html
+    /// [`ExpandVertices`]: struct.ExpandVertices.html
+    #[inline]
+    fn expand_vertices<T: SignedNum>(self) -> ExpandVertices<T, Self>
+    where
+        Self: Iterator<Item = Point<T>>,
+    {
+        ExpandVertices::new(self)
+    }
+
+    /// Pour the points into a [`PointSink`] until either of them runs out, and return how many
+    /// were written.
+    ///
+    /// [`PointSink`]: trait.PointSink.html
+    #[inline]
+    fn rasterize_into<S: PointSink<Self::Item> + ?Sized>(self, sink: &mut S) -> usize {
+        let mut written = 0;
+
+        for point in self {
+            if !sink.push_point(point) {
+                break;
+            }
+
+            written += 1;
+        }
+
+        written
+    }
+}
+
+impl<I: Iterator> LineExt for I {}
+
+#[test]
+fn tests() {
+    use {Bresenham, BresenhamCircle, Supercover, XiaolinWu};
+
+    let line = || Bresenham::new((-3, 1), (12, 6));
+    let bounds = GridBounds::new((0, 0), (8, 8));
+
+    assert_eq!(
+        line().clipped(bounds).collect::<Vec<_>>(),
+        Clipped::new(line(), bounds).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        BresenhamCircle::new(0, 0, 9).within(bounds).collect::<Vec<_>>(),
+        Within::new(BresenhamCircle::new(0, 0, 9), bounds).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        line().dashed(&[3.0, 1.5]).collect::<Vec<_>>(),
+        Dashed::new(line(), &[3.0, 1.5]).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        line().decimated(4).collect::<Vec<_>>(),
+        Decimate::new(line(), 4).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        line().transformed(Affine::scale(2.0, -1.0), Rounding::Floor).collect::<Vec<_>>(),
+        Transformed::new(line(), Affine::scale(2.0, -1.0), Rounding::Floor).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        line().mirrored(Reflection::FlipY(4)).collect::<Vec<_>>(),
+        Mirror::new(line(), Reflection::FlipY(4)).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        line().rotational((0.5, 0.5), 3).collect::<Vec<_>>(),
+        Rotational::new(line(), (0.5, 0.5), 3).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        line().stippled(0b1010, 2).collect::<Vec<_>>(),
+        Stipple::new(line(), 0b1010, 2).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        Supercover::new((0, 0), (5, 3)).pixel_perfect().collect::<Vec<_>>(),
+        PixelPerfect::new(Supercover::new((0, 0), (5, 3))).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        line().wrapped((5, 5)).collect::<Vec<_>>(),
+        Wrapped::new(line(), (5, 5)).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        line().wrapped((5, 5)).indices(5).collect::<Vec<_>>(),
+        BufferIndices::new(Wrapped::new(line(), (5, 5)), 5).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        line().checked_indices(9, (8, 4)).collect::<Vec<_>>(),
+        BufferIndices::checked(line(), 9, (8, 4)).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        line().clipped(bounds).morton_codes().collect::<Vec<_>>(),
+        MortonCodes::new(Clipped::new(line(), bounds)).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        line().clipped(bounds).with_morton().collect::<Vec<_>>(),
+        WithMorton::new(Clipped::new(line(), bounds)).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        line().interpolate(0.0f32, 1.0).collect::<Vec<_>>(),
+        Interpolate::new(line(), 0.0f32, 1.0).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        line().vertices().collect::<Vec<_>>(),
+        Vertices::new(line()).collect::<Vec<_>>()
+    );
+    assert_eq!(line().vertices().expand_vertices().collect::<Vec<_>>(), line().collect::<Vec<_>>());
+
+    let aa = || XiaolinWu::<f32, i32>::new((-3.0, 1.0), (12.0, 6.0));
+    assert_eq!(
+        aa().gamma_corrected(Transfer::Srgb).collect::<Vec<_>>(),
+        GammaCorrected::new(aa(), Transfer::Srgb).collect::<Vec<_>>()
+    );
+    let table = Transfer::Gamma(2.2f32).table();
+    assert_eq!(
+        aa().alpha::<u8>().gamma_table(&table).collect::<Vec<_>>(),
+        GammaTable::new(aa().alpha::<u8>(), &table).collect::<Vec<_>>()
+    );
+
+    // They can be chained in any order
+    let chained = line()
+        .wrapped((8, 8))
+        .mirrored(Reflection::FlipX(7))
+        .within(GridBounds::new((0, 0), (3, 7)))
+        .decimated(2)
+        .collect::<Vec<_>>();
+    assert!(chained.iter().all(|point| point.0 <= 3));
+}
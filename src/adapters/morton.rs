@@ -0,0 +1,289 @@
+use {Point, SignedNum, Voxel};
+
+/// Points that can be turned into Morton codes, also known as Z-order codes, and back.
+///
+/// The bits of the coordinates are interleaved, starting with the lowest bit of `x`, so points
+/// that are close together usually have codes that are close together. The coordinates can't be
+/// negative, and need to fit in 32 bits for 2D points and 21 bits for 3D ones.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::MortonCode;
+///
+/// fn main() {
+///     assert_eq!((3, 5).to_morton(), 0b100111);
+///     assert_eq!((1, 0, 1).to_morton(), 0b101);
+///     assert_eq!(<(i32, i32)>::from_morton(0b100111), (3, 5));
+/// }
+/// ```
+pub trait MortonCode: Sized {
+    /// Get the Morton code of the point.
+    ///
+    /// # Panics
+    ///
+    /// If a coordinate is negative or too large.
+    fn to_morton(self) -> u64;
+
+    /// Get the point back out of a Morton code.
+    fn from_morton(code: u64) -> Self;
+}
+
+/// Spread the lower 32 bits out to every other bit.
+#[inline]
+fn spread_2d(value: u64) -> u64 {
+    let mut value = value & 0xFFFF_FFFF;
+    value = (value | (value << 16)) & 0x0000_FFFF_0000_FFFF;
+    value = (value | (value << 8)) & 0x00FF_00FF_00FF_00FF;
+    value = (value | (value << 4)) & 0x0F0F_0F0F_0F0F_0F0F;
+    value = (value | (value << 2)) & 0x3333_3333_3333_3333;
+    (value | (value << 1)) & 0x5555_5555_5555_5555
+}
+
+#[inline]
+fn compact_2d(code: u64) -> u64 {
+    let mut value = code & 0x5555_5555_5555_5555;
+    value = (value | (value >> 1)) & 0x3333_3333_3333_3333;
+    value = (value | (value >> 2)) & 0x0F0F_0F0F_0F0F_0F0F;
+    value = (value | (value >> 4)) & 0x00FF_00FF_00FF_00FF;
+    value = (value | (value >> 8)) & 0x0000_FFFF_0000_FFFF;
+    (value | (value >> 16)) & 0xFFFF_FFFF
+}
+
+/// Spread the lower 21 bits out to every third bit.
+#[inline]
+fn spread_3d(value: u64) -> u64 {
+    let mut value = value & 0x1F_FFFF;
+    value = (value | (value << 32)) & 0x001F_0000_0000_FFFF;
+    value = (value | (value << 16)) & 0x001F_0000_FF00_00FF;
+    value = (value | (value << 8)) & 0x100F_00F0_0F00_F00F;
+    value = (value | (value << 4)) & 0x10C3_0C30_C30C_30C3;
+    (value | (value << 2)) & 0x1249_2492_4924_9249
+}
+
+#[inline]
+fn compact_3d(code: u64) -> u64 {
+    let mut value = code & 0x1249_2492_4924_9249;
+    value = (value | (value >> 2)) & 0x10C3_0C30_C30C_30C3;
+    value = (value | (value >> 4)) & 0x100F_00F0_0F00_F00F;
+    value = (value | (value >> 8)) & 0x001F_0000_FF00_00FF;
+    value = (value | (value >> 16)) & 0x001F_0000_0000_FFFF;
+    (value | (value >> 32)) & 0x1F_FFFF
+}
+
+/// Get a coordinate as an unsigned number with the given number of bits.
+#[inline]
+fn coordinate<T: SignedNum>(value: T, bits: u32) -> u64 {
+    match value.to_u64() {
+        Some(value) if value >> bits == 0 => value,
+        _ => panic!("coordinate doesn't fit in a {}-bit morton code", bits),
+    }
+}
+
+impl<T: SignedNum> MortonCode for Point<T> {
+    #[inline]
+    fn to_morton(self) -> u64 {
+        spread_2d(coordinate(self.0, 32)) | (spread_2d(coordinate(self.1, 32)) << 1)
+    }
+
+    #[inline]
+    fn from_morton(code: u64) -> Self {
+        (T::cast(compact_2d(code)), T::cast(compact_2d(code >> 1)))
+    }
+}
+
+impl<T: SignedNum> MortonCode for Voxel<T> {
+    #[inline]
+    fn to_morton(self) -> u64 {
+        spread_3d(coordinate(self.0, 21))
+            | (spread_3d(coordinate(self.1, 21)) << 1)
+            | (spread_3d(coordinate(self.2, 21)) << 2)
+    }
+
+    #[inline]
+    fn from_morton(code: u64) -> Self {
+        (
+            T::cast(compact_3d(code)),
+            T::cast(compact_3d(code >> 1)),
+            T::cast(compact_3d(code >> 2)),
+        )
+    }
+}
+
+/// An iterator that returns the Morton codes of the points of another one, instead of the points.
+///
+/// This is for storage that's keyed by Morton code, like sparse voxel sets and quadtrees. See
+/// [`MortonCode`] for how the codes are made, and [`WithMorton`] to get the points as well.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{Bresenham, MortonCodes};
+///
+/// fn main() {
+///     println!("{:?}", MortonCodes::new(Bresenham::new((0, 0), (3, 1))).collect::<Vec<_>>());
+/// }
+/// ```
+///
+/// ```text
+/// [0, 1, 4, 7]
+/// ```
+///
+/// [`MortonCode`]: trait.MortonCode.html
+/// [`WithMorton`]: struct.WithMorton.html
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MortonCodes<I> {
+    iterator: I,
+}
+
+impl<I: Iterator> MortonCodes<I>
+where
+    I::Item: MortonCode,
+{
+    #[inline]
+    pub fn new(iterator: I) -> Self {
+        Self { iterator }
+    }
+}
+
+impl<I: Iterator> Iterator for MortonCodes<I>
+where
+    I::Item: MortonCode,
+{
+    type Item = u64;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iterator.next().map(MortonCode::to_morton)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iterator.size_hint()
+    }
+
+    #[inline]
+    fn fold<B, F>(self, init: B, mut function: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.iterator
+            .fold(init, |accum, point| function(accum, point.to_morton()))
+    }
+}
+
+impl<I: ExactSizeIterator> ExactSizeIterator for MortonCodes<I> where I::Item: MortonCode {}
+
+/// An iterator that returns the points of another one along with their Morton codes.
+///
+/// See [`MortonCodes`] for details.
+///
+/// [`MortonCodes`]: struct.MortonCodes.html
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WithMorton<I> {
+    iterator: I,
+}
+
+impl<I: Iterator> WithMorton<I>
+where
+    I::Item: MortonCode + Copy,
+{
+    #[inline]
+    pub fn new(iterator: I) -> Self {
+        Self { iterator }
+    }
+}
+
+impl<I: Iterator> Iterator for WithMorton<I>
+where
+    I::Item: MortonCode + Copy,
+{
+    type Item = (I::Item, u64);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iterator.next().map(|point| (point, point.to_morton()))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iterator.size_hint()
+    }
+
+    #[inline]
+    fn fold<B, F>(self, init: B, mut function: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.iterator
+            .fold(init, |accum, point| function(accum, (point, point.to_morton())))
+    }
+}
+
+impl<I: ExactSizeIterator> ExactSizeIterator for WithMorton<I> where I::Item: MortonCode + Copy {}
+
+#[test]
+fn tests() {
+    use {Bresenham, Bresenham3d};
+
+    // The bits are interleaved one at a time
+    let slow_2d = |(x, y): Point<u64>| {
+        (0..32).fold(0, |code, bit| {
+            code | (((x >> bit) & 1) << (bit * 2)) | (((y >> bit) & 1) << (bit * 2 + 1))
+        })
+    };
+    let slow_3d = |(x, y, z): Voxel<u64>| {
+        (0..21).fold(0, |code, bit| {
+            code | (((x >> bit) & 1) << (bit * 3))
+                | (((y >> bit) & 1) << (bit * 3 + 1))
+                | (((z >> bit) & 1) << (bit * 3 + 2))
+        })
+    };
+
+    let values = [0, 1, 2, 3, 5, 100, 12_345, 0x1F_FFFF];
+
+    for &x in &values {
+        for &y in &values {
+            let code = (x, y).to_morton();
+            assert_eq!(code, slow_2d((x as u64, y as u64)));
+            assert_eq!(<(i64, i64)>::from_morton(code), (x, y));
+
+            for &z in &values {
+                let code = (x, y, z).to_morton();
+                assert_eq!(code, slow_3d((x as u64, y as u64, z as u64)));
+                assert_eq!(<(i64, i64, i64)>::from_morton(code), (x, y, z));
+            }
+        }
+    }
+
+    assert_eq!((0xFFFF_FFFFi64, 0xFFFF_FFFFi64).to_morton(), u64::MAX);
+    assert_eq!((0x1F_FFFF, 0x1F_FFFF, 0x1F_FFFF).to_morton(), u64::MAX >> 1);
+
+    let line = Bresenham::new((2, 7), (9, 3));
+    let codes = MortonCodes::new(line.clone());
+    assert_eq!(codes.len(), 8);
+    assert_eq!(
+        codes.collect::<Vec<_>>(),
+        line.clone().map(MortonCode::to_morton).collect::<Vec<_>>()
+    );
+    assert!(WithMorton::new(line).all(|(point, code)| <(i32, i32)>::from_morton(code) == point));
+
+    let mut voxels = WithMorton::new(Bresenham3d::new((0, 0, 0), (4, 9, 2)));
+    assert!(voxels.all(|(voxel, code)| <(i32, i32, i32)>::from_morton(code) == voxel));
+}
+
+#[test]
+#[should_panic]
+fn negative() {
+    (-1, 0).to_morton();
+}
+
+#[test]
+#[should_panic]
+fn too_large() {
+    (0, 0, 0x20_0000).to_morton();
+}
@@ -0,0 +1,295 @@
+use {FloatNum, Point, SignedNum};
+
+/// A way of reflecting points for a [`Mirror`].
+///
+/// Each axis is given as the sum of a coordinate and its reflection, so that it can be in the
+/// middle of a cell or between two cells. To mirror an image that's `width` pixels wide, use
+/// `FlipX(width - 1)`.
+///
+/// [`Mirror`]: struct.Mirror.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Reflection<T> {
+    /// Reflect across a vertical line, so `(x, y)` becomes `(axis - x, y)`.
+    FlipX(T),
+    /// Reflect across a horizontal line, so `(x, y)` becomes `(x, axis - y)`.
+    FlipY(T),
+    /// Reflect through a point, so `(x, y)` becomes `(center.0 - x, center.1 - y)`.
+    FlipBoth(Point<T>),
+}
+
+impl<T: SignedNum> Reflection<T> {
+    /// Reflect a point.
+    #[inline]
+    pub fn reflect(&self, point: Point<T>) -> Point<T> {
+        match *self {
+            Reflection::FlipX(axis) => (axis - point.0, point.1),
+            Reflection::FlipY(axis) => (point.0, axis - point.1),
+            Reflection::FlipBoth(center) => (center.0 - point.0, center.1 - point.1),
+        }
+    }
+}
+
+/// An iterator that returns each point along with its reflection, for drawing with mirror
+/// symmetry.
+///
+/// Points that lie on the axis are their own reflection, so they're only returned once.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{Bresenham, Mirror, Reflection};
+///
+/// fn main() {
+///     let line = Bresenham::new((0, 0), (2, 1));
+///
+///     for (x, y) in Mirror::new(line, Reflection::FlipX(4)) {
+///         print!("({}, {}), ", x, y);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// (0, 0), (4, 0), (1, 0), (3, 0), (2, 1),
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Mirror<T, I> {
+    iterator: I,
+    reflection: Reflection<T>,
+    pending: Option<Point<T>>,
+}
+
+impl<T: SignedNum, I: Iterator<Item = Point<T>>> Mirror<T, I> {
+    #[inline]
+    pub fn new(iterator: I, reflection: Reflection<T>) -> Self {
+        Self {
+            iterator,
+            reflection,
+            pending: None,
+        }
+    }
+}
+
+impl<T: SignedNum, I: Iterator<Item = Point<T>>> Iterator for Mirror<T, I> {
+    type Item = Point<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(point) = self.pending.take() {
+            return Some(point);
+        }
+
+        let point = self.iterator.next()?;
+        let reflected = self.reflection.reflect(point);
+
+        if reflected != point {
+            self.pending = Some(reflected);
+        }
+
+        Some(point)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Each point comes with up to one reflection
+        let (low, high) = self.iterator.size_hint();
+        let pending = self.pending.is_some() as usize;
+
+        (
+            low.saturating_add(pending),
+            high.and_then(|high| high.checked_mul(2)?.checked_add(pending)),
+        )
+    }
+}
+
+/// An iterator that returns each point along with copies of it rotated around a center, for
+/// drawing with rotational symmetry.
+///
+/// Each point is followed by its rotations by `360 / folds` degrees, `2 * 360 / folds` degrees and
+/// so on, which are rounded to the nearest cell, with ties going away from zero. Rotations by a
+/// multiple of 90 degrees are done exactly, so 2-fold and 4-fold symmetry never suffer from
+/// rounding errors. The center can be in the middle of a cell or on a corner between cells, e.g.
+/// `(3.5, 3.5)` for an 8x8 grid.
+///
+/// Points that lie on the center, or rotations that round to the same cell, are returned more than
+/// once.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{Bresenham, Rotational};
+///
+/// fn main() {
+///     let line = Bresenham::new((3, 0), (4, 0));
+///
+///     for (x, y) in Rotational::new(line, (0.0, 0.0), 4) {
+///         print!("({}, {}), ", x, y);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// (3, 0), (0, 3), (-3, 0), (0, -3), (4, 0), (0, 4), (-4, 0), (0, -4),
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Rotational<I, O, It> {
+    iterator: It,
+    center: Point<I>,
+    folds: usize,
+    point: Point<O>,
+    index: usize,
+}
+
+impl<I: FloatNum, O: SignedNum, It: Iterator<Item = Point<O>>> Rotational<I, O, It> {
+    #[inline]
+    pub fn new(iterator: It, center: Point<I>, folds: usize) -> Self {
+        let folds = folds.max(1);
+
+        Self {
+            iterator,
+            center,
+            folds,
+            point: (O::zero(), O::zero()),
+            index: folds,
+        }
+    }
+
+    /// Rotate a point by `index / folds` of a full turn around the center.
+    #[inline]
+    fn rotate(&self, point: Point<O>, index: usize) -> Point<O> {
+        if index == 0 {
+            return point;
+        }
+
+        let quarters = index * 4 / self.folds;
+
+        let (cos, sin) = if quarters * self.folds == index * 4 {
+            match quarters % 4 {
+                0 => (I::one(), I::zero()),
+                1 => (I::zero(), I::one()),
+                2 => (-I::one(), I::zero()),
+                _ => (I::zero(), -I::one()),
+            }
+        } else {
+            let turn = I::cast(2.0 * core::f64::consts::PI);
+            let angle = turn * I::cast(index) / I::cast(self.folds);
+
+            (angle.cos(), angle.sin())
+        };
+
+        let x = I::cast(point.0) - self.center.0;
+        let y = I::cast(point.1) - self.center.1;
+
+        // Snap away tiny errors from the trigonometry, so that rotations landing exactly halfway
+        // between cells are rounded consistently
+        let precision = I::cast(1 << 20);
+        let snap = |value: I| (value * precision).round() / precision;
+
+        (
+            O::cast((self.center.0 + snap(x * cos - y * sin)).round()),
+            O::cast((self.center.1 + snap(x * sin + y * cos)).round()),
+        )
+    }
+}
+
+impl<I: FloatNum, O: SignedNum, It: Iterator<Item = Point<O>>> Iterator for Rotational<I, O, It> {
+    type Item = Point<O>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.folds {
+            self.point = self.iterator.next()?;
+            self.index = 0;
+        }
+
+        let point = self.rotate(self.point, self.index);
+        self.index += 1;
+
+        Some(point)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (low, high) = self.iterator.size_hint();
+        let rotations = |count: usize| {
+            count
+                .checked_mul(self.folds)?
+                .checked_add(self.folds - self.index)
+        };
+
+        (
+            rotations(low).unwrap_or(usize::MAX),
+            high.and_then(rotations),
+        )
+    }
+}
+
+#[test]
+fn tests() {
+    use Bresenham;
+
+    let mirror =
+        |reflection| Mirror::new(Bresenham::new((1, 1), (3, 2)), reflection).collect::<Vec<_>>();
+
+    assert_eq!(
+        mirror(Reflection::FlipX(7)),
+        [(1, 1), (6, 1), (2, 1), (5, 1), (3, 2), (4, 2)]
+    );
+    assert_eq!(
+        mirror(Reflection::FlipY(2)),
+        [(1, 1), (2, 1), (3, 2), (3, 0)]
+    );
+    assert_eq!(
+        mirror(Reflection::FlipBoth((4, 3))),
+        [(1, 1), (3, 2), (2, 1), (2, 2), (3, 2), (1, 1)]
+    );
+
+    // Reflecting twice gets back to the start
+    for &reflection in &[
+        Reflection::FlipX(-3),
+        Reflection::FlipY(10),
+        Reflection::FlipBoth((5, -2)),
+    ] {
+        assert_eq!(reflection.reflect(reflection.reflect((7, 4))), (7, 4));
+    }
+
+    let rotate = |line: Bresenham<i32>, center: Point<f64>, folds| {
+        Rotational::new(line, center, folds).collect::<Vec<_>>()
+    };
+
+    // Quarter turns around the corner between cells
+    assert_eq!(
+        rotate(Bresenham::new((4, 4), (4, 4)), (3.5, 3.5), 4),
+        [(4, 4), (3, 4), (3, 3), (4, 3)]
+    );
+    assert_eq!(
+        rotate(Bresenham::new((7, 0), (7, 0)), (3.5, 3.5), 2),
+        [(7, 0), (0, 7)]
+    );
+    assert_eq!(
+        rotate(Bresenham::new((5, 0), (5, 0)), (0.0, 0.0), 6),
+        [(5, 0), (3, 4), (-3, 4), (-5, 0), (-3, -4), (3, -4)]
+    );
+    assert_eq!(
+        rotate(Bresenham::new((1, 2), (3, 2)), (0.0, 0.0), 1).len(),
+        3
+    );
+    assert_eq!(
+        rotate(Bresenham::new((1, 2), (3, 2)), (0.0, 0.0), 0).len(),
+        3
+    );
+
+    // Quarter turns are exact even far from the center
+    assert_eq!(
+        rotate(
+            Bresenham::new((1_000_001, 3), (1_000_001, 3)),
+            (0.5, 0.5),
+            8
+        )[2],
+        (-2, 1_000_001)
+    );
+}
@@ -0,0 +1,356 @@
+use {FloatNum, Point, SignedNum};
+
+/// A 2D affine transform, made out of rotations, scales and translations.
+///
+/// A point `(x, y)` becomes `matrix * (x, y) + translation`, with each row of the matrix giving
+/// one coordinate of the result. Transforms can be built up with [`then`], which applies one after
+/// another.
+///
+/// [`then`]: #method.then
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Affine<I> {
+    pub matrix: [[I; 2]; 2],
+    pub translation: Point<I>,
+}
+
+impl<I: FloatNum> Affine<I> {
+    /// A transform that leaves points where they are.
+    #[inline]
+    pub fn identity() -> Self {
+        Self::scale(I::one(), I::one())
+    }
+
+    /// Move points by an offset.
+    #[inline]
+    pub fn translation(x: I, y: I) -> Self {
+        Self {
+            translation: (x, y),
+            ..Self::identity()
+        }
+    }
+
+    /// Scale points away from the origin by a factor along each axis.
+    #[inline]
+    pub fn scale(x: I, y: I) -> Self {
+        Self {
+            matrix: [[x, I::zero()], [I::zero(), y]],
+            translation: (I::zero(), I::zero()),
+        }
+    }
+
+    /// Rotate points around the origin by an angle in radians, from the positive `x` axis
+    /// towards the positive `y` axis.
+    #[inline]
+    pub fn rotation(radians: I) -> Self {
+        let (sin, cos) = radians.sin_cos();
+
+        Self {
+            matrix: [[cos, -sin], [sin, cos]],
+            translation: (I::zero(), I::zero()),
+        }
+    }
+
+    /// Apply this transform and then `next`.
+    #[inline]
+    pub fn then(self, next: Self) -> Self {
+        let [[a, b], [c, d]] = next.matrix;
+        let [[e, f], [g, h]] = self.matrix;
+
+        Self {
+            matrix: [[a * e + b * g, a * f + b * h], [c * e + d * g, c * f + d * h]],
+            translation: next.apply(self.translation),
+        }
+    }
+
+    /// Transform a point.
+    #[inline]
+    pub fn apply(&self, point: Point<I>) -> Point<I> {
+        let [[a, b], [c, d]] = self.matrix;
+
+        (
+            a * point.0 + b * point.1 + self.translation.0,
+            c * point.0 + d * point.1 + self.translation.1,
+        )
+    }
+}
+
+/// How a [`Transformed`] iterator turns transformed points back into cells.
+///
+/// [`Transformed`]: struct.Transformed.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Rounding {
+    /// Round to the nearest cell, with ties going away from zero.
+    Nearest,
+    /// Round towards negative infinity.
+    Floor,
+    /// Round towards positive infinity.
+    Ceil,
+    /// Round towards zero.
+    Truncate,
+}
+
+impl Rounding {
+    /// Round a value.
+    #[inline]
+    pub fn round<I: FloatNum>(self, value: I) -> I {
+        match self {
+            Rounding::Nearest => value.round(),
+            Rounding::Floor => value.floor(),
+            Rounding::Ceil => value.ceil(),
+            Rounding::Truncate => value.trunc(),
+        }
+    }
+}
+
+/// An iterator that applies an [`Affine`] transform to the points of another one.
+///
+/// Transforming the points that come out of a line, rather than the end points that go into it,
+/// keeps the shape of the line the same, so a line drawn at an angle and then rotated to be
+/// straight is still the same staircase. The points are rounded back to cells with the given
+/// [`Rounding`], after tiny errors from the trigonometry are snapped away so that quarter turns
+/// land exactly on whole numbers. Anything other than quarter turns and whole number scales and
+/// translations can put two points in the same cell or leave gaps between them.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{Affine, Bresenham, Rounding, Transformed};
+///
+/// fn main() {
+///     let quarter_turn = Affine::rotation(std::f64::consts::FRAC_PI_2)
+///         .then(Affine::translation(10.0, 0.0));
+///     let line = Bresenham::new((0, 0), (3, 1));
+///
+///     for (x, y) in Transformed::new(line, quarter_turn, Rounding::Nearest) {
+///         print!("({}, {}), ", x, y);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// (10, 0), (10, 1), (10, 2), (9, 3),
+/// ```
+///
+/// [`Affine`]: struct.Affine.html
+/// [`Rounding`]: enum.Rounding.html
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Transformed<I, It> {
+    iterator: It,
+    affine: Affine<I>,
+    rounding: Rounding,
+}
+
+impl<I: FloatNum, O: SignedNum, It: Iterator<Item = Point<O>>> Transformed<I, It> {
+    #[inline]
+    pub fn new(iterator: It, affine: Affine<I>, rounding: Rounding) -> Self {
+        Self {
+            iterator,
+            affine,
+            rounding,
+        }
+    }
+}
+
+impl<I: FloatNum, O: SignedNum, It: Iterator<Item = Point<O>>> Iterator for Transformed<I, It> {
+    type Item = Point<O>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let point = self.iterator.next()?;
+        let (x, y) = self.affine.apply((I::cast(point.0), I::cast(point.1)));
+
+        let precision = I::cast(1 << 20);
+        let snap = |value: I| self.rounding.round((value * precision).round() / precision);
+
+        Some((O::cast(snap(x)), O::cast(snap(y))))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iterator.size_hint()
+    }
+}
+
+impl<I, O, It> ExactSizeIterator for Transformed<I, It>
+where
+    I: FloatNum,
+    O: SignedNum,
+    It: ExactSizeIterator<Item = Point<O>>,
+{
+}
+
+/// An iterator that turns the cells of another one into positions in world space, on a grid with
+/// cells of the given size.
+///
+/// This is the other way around from constructors like [`WalkGrid::with_cells`], and uses the same
+/// grid, where cell `(0, 0)` has its top left corner at `origin`. Each cell becomes the position of
+/// its top left corner, or its center if it's created with [`centers`].
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{Bresenham, ToWorld};
+///
+/// fn main() {
+///     let line = Bresenham::new((0, 0), (2, 1));
+///
+///     for (x, y) in ToWorld::centers(line, (16.0, 16.0), (100.0, 0.0)) {
+///         print!("({}, {}), ", x, y);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// (108, 8), (124, 8), (140, 24),
+/// ```
+///
+/// [`WalkGrid::with_cells`]: struct.WalkGrid.html#method.with_cells
+/// [`centers`]: #method.centers
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ToWorld<I, It> {
+    iterator: It,
+    cell_size: Point<I>,
+    origin: Point<I>,
+    // Where in each cell the position is, as a fraction of the cell size
+    anchor: I,
+}
+
+impl<I: FloatNum, O: SignedNum, It: Iterator<Item = Point<O>>> ToWorld<I, It> {
+    #[inline]
+    pub fn new(iterator: It, cell_size: Point<I>, origin: Point<I>) -> Self {
+        Self {
+            iterator,
+            cell_size,
+            origin,
+            anchor: I::zero(),
+        }
+    }
+
+    /// Return the center of each cell instead of its top left corner.
+    #[inline]
+    pub fn centers(iterator: It, cell_size: Point<I>, origin: Point<I>) -> Self {
+        Self {
+            anchor: I::cast(0.5),
+            ..Self::new(iterator, cell_size, origin)
+        }
+    }
+}
+
+impl<I: FloatNum, O: SignedNum, It: Iterator<Item = Point<O>>> Iterator for ToWorld<I, It> {
+    type Item = Point<I>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (x, y) = self.iterator.next()?;
+
+        Some((
+            self.origin.0 + (I::cast(x) + self.anchor) * self.cell_size.0,
+            self.origin.1 + (I::cast(y) + self.anchor) * self.cell_size.1,
+        ))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iterator.size_hint()
+    }
+}
+
+impl<I, O, It> ExactSizeIterator for ToWorld<I, It>
+where
+    I: FloatNum,
+    O: SignedNum,
+    It: ExactSizeIterator<Item = Point<O>>,
+{
+}
+
+#[test]
+fn tests() {
+    use core::f64::consts::PI;
+    use Bresenham;
+
+    let transformed = |affine, rounding| {
+        Transformed::new(Bresenham::new((0, 0), (4, 2)), affine, rounding).collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        transformed(Affine::identity(), Rounding::Floor),
+        Bresenham::new((0, 0), (4, 2)).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        transformed(Affine::translation(-3.0, 5.0), Rounding::Nearest),
+        [(-3, 5), (-2, 5), (-1, 6), (0, 6), (1, 7)]
+    );
+    assert_eq!(
+        transformed(Affine::scale(2.0, -1.0), Rounding::Nearest),
+        [(0, 0), (2, 0), (4, -1), (6, -1), (8, -2)]
+    );
+
+    // Half turns land exactly on cells, whichever way they're rounded
+    for &rounding in &[
+        Rounding::Nearest,
+        Rounding::Floor,
+        Rounding::Ceil,
+        Rounding::Truncate,
+    ] {
+        assert_eq!(
+            transformed(Affine::rotation(PI), rounding),
+            [(0, 0), (-1, 0), (-2, -1), (-3, -1), (-4, -2)]
+        );
+    }
+
+    assert_eq!(
+        transformed(Affine::scale(0.5, 0.5), Rounding::Floor),
+        [(0, 0), (0, 0), (1, 0), (1, 0), (2, 1)]
+    );
+    assert_eq!(
+        transformed(Affine::scale(0.5, 0.5), Rounding::Ceil),
+        [(0, 0), (1, 0), (1, 1), (2, 1), (2, 1)]
+    );
+
+    // Transforms are applied in order
+    let affine = Affine::translation(1.0, 0.0).then(Affine::rotation(PI / 2.0));
+    let (x, y) = affine.apply((2.0, 0.0));
+    assert!(x.abs() < 1e-9 && (y - 3.0).abs() < 1e-9);
+
+    let affine = Affine::rotation(PI / 2.0).then(Affine::translation(1.0, 0.0));
+    let (x, y) = affine.apply((2.0, 0.0));
+    assert!((x - 1.0).abs() < 1e-9 && (y - 2.0).abs() < 1e-9);
+
+    let line = Bresenham::new((0, 0), (9, 3));
+    assert_eq!(
+        Transformed::new(line, Affine::<f32>::identity(), Rounding::Nearest).len(),
+        10
+    );
+}
+
+#[test]
+fn to_world() {
+    use WalkGrid;
+
+    let (start, end) = ((5.0, 5.0), (70.0, -20.0));
+    let (cell_size, origin) = ((16.0, 8.0), (-2.0, 3.0));
+    let walk = WalkGrid::<i32>::with_cells(start, end, cell_size, origin);
+
+    // Each cell covers the area from its corner to the corner of the next one
+    let corners = ToWorld::new(walk.clone(), cell_size, origin);
+    let centers = ToWorld::centers(walk.clone(), cell_size, origin);
+    assert_eq!(corners.len(), walk.len());
+
+    for ((corner, center), cell) in corners.zip(centers).zip(walk) {
+        assert_eq!(center, (corner.0 + 8.0, corner.1 + 4.0));
+        assert_eq!(
+            WalkGrid::<i32>::with_cells(center, center, cell_size, origin).next(),
+            Some(cell)
+        );
+    }
+
+    let mut corners = ToWorld::new(WalkGrid::new((0, 0), (1, 1)), (2.0, 3.0), (0.5, 0.0));
+    assert_eq!(corners.next(), Some((0.5, 0.0)));
+    assert_eq!(corners.last(), Some((2.5, 3.0)));
+}
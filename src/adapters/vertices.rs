@@ -0,0 +1,232 @@
+use {Point, SignedNum};
+
+/// An iterator that only returns the points of another one where it changes direction, along with
+/// the first and last points, which are the corners of the staircase that a line makes.
+///
+/// This is a much smaller way to store a path, like the route a unit walked. As long as each step
+/// is to one of the 8 neighbouring cells, which it is for all of the algorithms in this crate,
+/// [`ExpandVertices`] turns the corners back into every point.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{Vertices, WalkGrid};
+///
+/// fn main() {
+///     for (x, y) in Vertices::new(WalkGrid::new((0, 0), (6, 2))) {
+///         print!("({}, {}), ", x, y);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// (0, 0), (1, 0), (1, 1), (4, 1), (4, 2), (6, 2),
+/// ```
+///
+/// [`ExpandVertices`]: struct.ExpandVertices.html
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Vertices<T, I> {
+    iterator: I,
+    last: Option<Point<T>>,
+    direction: Option<Point<T>>,
+    returned: bool,
+}
+
+impl<T: SignedNum, I: Iterator<Item = Point<T>>> Vertices<T, I> {
+    #[inline]
+    pub fn new(iterator: I) -> Self {
+        Self {
+            iterator,
+            last: None,
+            direction: None,
+            returned: false,
+        }
+    }
+}
+
+impl<T: SignedNum, I: Iterator<Item = Point<T>>> Iterator for Vertices<T, I> {
+    type Item = Point<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut last = match self.last {
+            Some(last) => last,
+            None => {
+                let first = self.iterator.next()?;
+                self.last = Some(first);
+                self.returned = true;
+                return Some(first);
+            }
+        };
+
+        loop {
+            let point = match self.iterator.next() {
+                Some(point) => point,
+                None if self.returned => return None,
+                None => {
+                    // Always finish with the last point
+                    self.returned = true;
+                    return Some(last);
+                }
+            };
+
+            let direction = (point.0 - last.0, point.1 - last.1);
+            let turned = self.direction.is_some_and(|previous| previous != direction);
+
+            self.direction = Some(direction);
+            self.last = Some(point);
+            self.returned = false;
+
+            if turned {
+                return Some(last);
+            }
+
+            last = point;
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (low, high) = self.iterator.size_hint();
+        let pending = self.last.is_some() && !self.returned;
+
+        (
+            (low > 0 || pending) as usize,
+            high.and_then(|high| high.checked_add(pending as usize)),
+        )
+    }
+}
+
+/// An iterator that fills in the points between the corners made by [`Vertices`], stepping
+/// straight from one to the next.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{ExpandVertices, Vertices, WalkGrid};
+///
+/// fn main() {
+///     let corners = Vertices::new(WalkGrid::new((0, 0), (6, 2))).collect::<Vec<_>>();
+///     let points = ExpandVertices::new(corners.into_iter()).collect::<Vec<_>>();
+///
+///     assert_eq!(points, WalkGrid::new((0, 0), (6, 2)).collect::<Vec<_>>());
+/// }
+/// ```
+///
+/// [`Vertices`]: struct.Vertices.html
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ExpandVertices<T, I> {
+    iterator: I,
+    point: Option<Point<T>>,
+    target: Option<Point<T>>,
+}
+
+impl<T: SignedNum, I: Iterator<Item = Point<T>>> ExpandVertices<T, I> {
+    #[inline]
+    pub fn new(iterator: I) -> Self {
+        Self {
+            iterator,
+            point: None,
+            target: None,
+        }
+    }
+}
+
+impl<T: SignedNum, I: Iterator<Item = Point<T>>> Iterator for ExpandVertices<T, I> {
+    type Item = Point<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut point = match self.point {
+            Some(point) => point,
+            None => {
+                let first = self.iterator.next()?;
+                self.point = Some(first);
+                self.target = Some(first);
+                return Some(first);
+            }
+        };
+
+        while self.target == Some(point) {
+            self.target = Some(self.iterator.next()?);
+        }
+
+        let target = self.target?;
+        point.0 += (target.0 - point.0).signum();
+        point.1 += (target.1 - point.1).signum();
+        self.point = Some(point);
+
+        Some(point)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // The corners after the current one might all be repeats of it
+        let steps = match (self.point, self.target) {
+            (Some(point), Some(target)) => {
+                (target.0 - point.0).abs().max((target.1 - point.1).abs())
+            }
+            _ => T::zero(),
+        };
+
+        (steps.to_usize().unwrap_or(0), None)
+    }
+}
+
+#[test]
+fn tests() {
+    use {Bresenham, BresenhamCircle, Supercover, WalkGrid};
+
+    let vertices = |line: &mut dyn Iterator<Item = Point<i32>>| {
+        Vertices::new(line).collect::<Vec<_>>()
+    };
+
+    assert_eq!(vertices(&mut WalkGrid::new((0, 0), (3, 0))), [(0, 0), (3, 0)]);
+    assert_eq!(
+        vertices(&mut Bresenham::new((0, 0), (6, 2))),
+        [(0, 0), (2, 0), (3, 1), (5, 1), (6, 2)]
+    );
+    assert_eq!(vertices(&mut Bresenham::new((2, 2), (2, 2))), [(2, 2)]);
+    assert_eq!(vertices(&mut Bresenham::new((2, 2), (3, 3))), [(2, 2), (3, 3)]);
+    assert!(vertices(&mut Bresenham::new((0, 0), (1, 1)).skip(5)).is_empty());
+
+    // Expanding them gives back every point
+    fn check<I: Iterator<Item = Point<i32>> + Clone>(line: I) {
+        let points = line.clone().collect::<Vec<_>>();
+        let corners = Vertices::new(line).collect::<Vec<_>>();
+        assert!(corners.len() < points.len());
+        assert_eq!(corners[0], points[0]);
+        assert_eq!(corners[corners.len() - 1], points[points.len() - 1]);
+
+        let expanded = ExpandVertices::new(corners.iter().cloned());
+        let (low, _) = expanded.size_hint();
+        assert!(low <= points.len());
+        assert_eq!(expanded.collect::<Vec<_>>(), points);
+    }
+
+    check(Bresenham::new((-3, 9), (14, -2)));
+    check(WalkGrid::new((5, 1), (-4, 3)));
+    check(Supercover::new((0, 0), (7, 7)));
+    check(BresenhamCircle::contiguous(0, 0, 6));
+
+    // Repeated points are kept
+    assert_eq!(
+        Vertices::new([(0, 0), (1, 0), (1, 0), (2, 0)].iter().cloned()).collect::<Vec<_>>(),
+        [(0, 0), (1, 0), (1, 0), (2, 0)]
+    );
+
+    let mut corners = Vertices::new(Bresenham::new((0, 0), (6, 2)));
+    loop {
+        let count = corners.clone().count();
+        let (low, high) = corners.size_hint();
+        assert!(low <= count && count <= high.unwrap());
+
+        if corners.next().is_none() {
+            break;
+        }
+    }
+}
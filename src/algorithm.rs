@@ -0,0 +1,206 @@
+use {Bresenham, FloatNum, Midpoint, Point, SignedNum, Supercover, ThickSupercover, WalkGrid};
+use steps::Steps;
+
+/// The line algorithms that go between two points on the integer grid, for picking one at runtime.
+///
+/// Use [`rasterize`] to draw a line with one of them.
+///
+/// [`rasterize`]: fn.rasterize.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum LineAlgorithm {
+    /// [`Bresenham`](struct.Bresenham.html)
+    Bresenham,
+    /// [`Midpoint`](struct.Midpoint.html), working in `f64`.
+    Midpoint,
+    /// [`WalkGrid`](struct.WalkGrid.html)
+    WalkGrid,
+    /// [`Supercover`](struct.Supercover.html)
+    Supercover,
+    /// [`ThickSupercover`](struct.ThickSupercover.html)
+    ThickSupercover,
+}
+
+impl LineAlgorithm {
+    /// All of the algorithms, for listing them in a menu.
+    pub const ALL: [LineAlgorithm; 5] = [
+        LineAlgorithm::Bresenham,
+        LineAlgorithm::Midpoint,
+        LineAlgorithm::WalkGrid,
+        LineAlgorithm::Supercover,
+        LineAlgorithm::ThickSupercover,
+    ];
+}
+
+/// Draw a line with an algorithm that's picked at runtime.
+///
+/// The line is returned as a [`Line`], which works the same whichever algorithm it's drawn with,
+/// so there's no need to box the iterators or match on the algorithm again.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{rasterize, LineAlgorithm};
+///
+/// fn main() {
+///     for &algorithm in &[LineAlgorithm::Bresenham, LineAlgorithm::WalkGrid] {
+///         println!("{:?}", rasterize((0, 0), (3, 2), algorithm).collect::<Vec<_>>());
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// [(0, 0), (1, 0), (2, 1), (3, 2)]
+/// [(0, 0), (1, 0), (1, 1), (2, 1), (2, 2), (3, 2)]
+/// ```
+///
+/// [`Line`]: enum.Line.html
+#[inline]
+pub fn rasterize<T: SignedNum>(
+    start: Point<T>,
+    end: Point<T>,
+    algorithm: LineAlgorithm,
+) -> Line<T> {
+    let float = |point: Point<T>| (f64::cast(point.0), f64::cast(point.1));
+
+    match algorithm {
+        LineAlgorithm::Bresenham => Line::Bresenham(Bresenham::new(start, end)),
+        LineAlgorithm::Midpoint => Line::Midpoint(Midpoint::new(float(start), float(end))),
+        LineAlgorithm::WalkGrid => Line::WalkGrid(WalkGrid::new(start, end)),
+        LineAlgorithm::Supercover => Line::Supercover(Supercover::new(start, end)),
+        LineAlgorithm::ThickSupercover => Line::ThickSupercover(ThickSupercover::new(start, end)),
+    }
+}
+
+/// A line drawn by [`rasterize`], with any of the [`LineAlgorithm`]s.
+///
+/// [`rasterize`]: fn.rasterize.html
+/// [`LineAlgorithm`]: enum.LineAlgorithm.html
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "T: ::serde::Serialize, T::Wide: ::serde::Serialize",
+        deserialize = "T: ::serde::Deserialize<'de>, T::Wide: ::serde::Deserialize<'de>"
+    ))
+)]
+pub enum Line<T: SignedNum> {
+    Bresenham(Bresenham<T>),
+    Midpoint(Midpoint<f64, T>),
+    WalkGrid(WalkGrid<T>),
+    Supercover(Supercover<T>),
+    ThickSupercover(ThickSupercover<T>),
+}
+
+impl<T: SignedNum> Line<T> {
+    /// The algorithm the line is being drawn with.
+    #[inline]
+    pub fn algorithm(&self) -> LineAlgorithm {
+        match *self {
+            Line::Bresenham(_) => LineAlgorithm::Bresenham,
+            Line::Midpoint(_) => LineAlgorithm::Midpoint,
+            Line::WalkGrid(_) => LineAlgorithm::WalkGrid,
+            Line::Supercover(_) => LineAlgorithm::Supercover,
+            Line::ThickSupercover(_) => LineAlgorithm::ThickSupercover,
+        }
+    }
+
+    #[inline]
+    pub fn steps(self) -> Steps<Point<T>, Self> {
+        Steps::new(self)
+    }
+}
+
+impl<T: SignedNum> Iterator for Line<T> {
+    type Item = Point<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match *self {
+            Line::Bresenham(ref mut line) => line.next(),
+            Line::Midpoint(ref mut line) => line.next(),
+            Line::WalkGrid(ref mut line) => line.next(),
+            Line::Supercover(ref mut line) => line.next(),
+            Line::ThickSupercover(ref mut line) => line.next(),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match *self {
+            Line::Bresenham(ref line) => line.size_hint(),
+            Line::Midpoint(ref line) => line.size_hint(),
+            Line::WalkGrid(ref line) => line.size_hint(),
+            Line::Supercover(ref line) => line.size_hint(),
+            Line::ThickSupercover(ref line) => line.size_hint(),
+        }
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        match self {
+            Line::Bresenham(line) => line.count(),
+            Line::Midpoint(line) => line.count(),
+            Line::WalkGrid(line) => line.count(),
+            Line::Supercover(line) => line.count(),
+            Line::ThickSupercover(line) => line.count(),
+        }
+    }
+
+    #[inline]
+    fn last(self) -> Option<Self::Item> {
+        match self {
+            Line::Bresenham(line) => line.last(),
+            Line::Midpoint(line) => line.last(),
+            Line::WalkGrid(line) => line.last(),
+            Line::Supercover(line) => line.last(),
+            Line::ThickSupercover(line) => line.last(),
+        }
+    }
+
+    /// Going through the points with `for_each` or `fold` picks the algorithm once, instead of
+    /// for every point.
+    #[inline]
+    fn fold<B, F>(self, init: B, function: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        match self {
+            Line::Bresenham(line) => line.fold(init, function),
+            Line::Midpoint(line) => line.fold(init, function),
+            Line::WalkGrid(line) => line.fold(init, function),
+            Line::Supercover(line) => line.fold(init, function),
+            Line::ThickSupercover(line) => line.fold(init, function),
+        }
+    }
+}
+
+#[test]
+fn tests() {
+    let (start, end) = ((-3, 7), (12, -2));
+
+    for &algorithm in &LineAlgorithm::ALL {
+        let line = rasterize(start, end, algorithm);
+        assert_eq!(line.algorithm(), algorithm);
+
+        let points = line.collect::<Vec<_>>();
+        let expected = match algorithm {
+            LineAlgorithm::Bresenham => Bresenham::new(start, end).collect::<Vec<_>>(),
+            LineAlgorithm::Midpoint => {
+                Midpoint::<f64, _>::new((-3.0, 7.0), (12.0, -2.0)).collect::<Vec<_>>()
+            }
+            LineAlgorithm::WalkGrid => WalkGrid::new(start, end).collect::<Vec<_>>(),
+            LineAlgorithm::Supercover => Supercover::new(start, end).collect::<Vec<_>>(),
+            LineAlgorithm::ThickSupercover => ThickSupercover::new(start, end).collect::<Vec<_>>(),
+        };
+
+        assert_eq!(points, expected);
+        assert_eq!(rasterize(start, end, algorithm).count(), points.len());
+    }
+
+    // Counting a long supercover line doesn't step through it
+    let line = rasterize((0, 0), (3i64 << 40, -(1 << 40)), LineAlgorithm::Supercover);
+    assert_eq!(line.count(), (3 << 40) + 1);
+}
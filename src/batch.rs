@@ -0,0 +1,420 @@
+//! Draw several lines at once with SIMD, one in each lane (needs the `simd` feature and a nightly
+//! compiler).
+//!
+//! Each batch runs one of the algorithms over `N` lines side by side with `core::simd`, so the
+//! setup and every step are done for all of them together. The points are exactly the same as
+//! the scalar iterators give, and can be taken a step at a time from every lane at once, or poured
+//! into a separate [`PointSink`] for each lane with `rasterize_into`.
+//!
+//! The math is done in `i32` lanes, which wrap around instead of panicking, so the end points
+//! need to stay within `2^29` (about 500 million) of the origin.
+//!
+//! Example:
+//!
+//! ```
+//! extern crate line_drawing;
+//! use line_drawing::batch::BresenhamBatch;
+//!
+//! fn main() {
+//!     let trails = [((0, 0), (3, 1)), ((5, 5), (5, 3)), ((-2, 0), (0, 2))];
+//!
+//!     for batch in trails.chunks(4).map(BresenhamBatch::<4>::from_slice) {
+//!         for points in batch {
+//!             println!("{:?}", points.to_array());
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! ```text
+//! [Some((0, 0)), Some((5, 5)), Some((-2, 0)), None]
+//! [Some((1, 0)), Some((5, 4)), Some((-1, 1)), None]
+//! [Some((2, 0)), Some((5, 3)), Some((0, 2)), None]
+//! [Some((3, 1)), None, None, None]
+//! ```
+//!
+//! [`PointSink`]: ../trait.PointSink.html
+
+use {Point, PointSink};
+use core::simd::cmp::SimdPartialOrd;
+use core::simd::num::SimdInt;
+use core::simd::{Mask, Select, Simd};
+
+/// The points from one step of a batch, one for each lane.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BatchPoints<const N: usize> {
+    /// The `x` coordinates of the points.
+    pub x: Simd<i32, N>,
+    /// The `y` coordinates of the points.
+    pub y: Simd<i32, N>,
+    /// Which lanes have a point, since the shorter lines run out before the longer ones.
+    pub active: Mask<i32, N>,
+}
+
+impl<const N: usize> BatchPoints<N> {
+    /// The point in a lane, if it has one.
+    #[inline]
+    pub fn get(&self, lane: usize) -> Option<Point<i32>> {
+        if lane < N && self.active.test(lane) {
+            Some((self.x[lane], self.y[lane]))
+        } else {
+            None
+        }
+    }
+
+    /// The points in every lane.
+    #[inline]
+    pub fn to_array(&self) -> [Option<Point<i32>>; N] {
+        core::array::from_fn(|lane| self.get(lane))
+    }
+}
+
+/// The start and end coordinates of up to `N` lines, each in its own lane, and which lanes have
+/// a line.
+struct Lanes<const N: usize> {
+    start: Point<Simd<i32, N>>,
+    end: Point<Simd<i32, N>>,
+    used: Mask<i32, N>,
+}
+
+impl<const N: usize> Lanes<N> {
+    #[inline]
+    fn new(lines: &[(Point<i32>, Point<i32>)]) -> Self {
+        assert!(lines.len() <= N, "a batch can't hold more than {} lines", N);
+
+        let lane = |coord: fn(&(Point<i32>, Point<i32>)) -> i32| {
+            Simd::from_array(core::array::from_fn(|lane| lines.get(lane).map_or(0, coord)))
+        };
+
+        Self {
+            start: (lane(|line| (line.0).0), lane(|line| (line.0).1)),
+            end: (lane(|line| (line.1).0), lane(|line| (line.1).1)),
+            used: Mask::from_array(core::array::from_fn(|lane| lane < lines.len())),
+        }
+    }
+}
+
+/// Pour the points in each lane of a batch into the sink for that lane, until either of them runs
+/// out, and return how many were written to each.
+#[inline]
+fn rasterize_lanes<I, S, const N: usize>(batch: I, sinks: &mut [S; N]) -> [usize; N]
+where
+    I: Iterator<Item = BatchPoints<N>>,
+    S: PointSink<Point<i32>>,
+{
+    let mut written = [0; N];
+    let mut full = [false; N];
+
+    for points in batch {
+        for (lane, sink) in sinks.iter_mut().enumerate() {
+            if let Some(point) = points.get(lane).filter(|_| !full[lane]) {
+                if sink.push_point(point) {
+                    written[lane] += 1;
+                } else {
+                    full[lane] = true;
+                }
+            }
+        }
+
+        if full.iter().all(|&full| full) {
+            break;
+        }
+    }
+
+    written
+}
+
+/// [`Bresenham`] lines in each lane.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::batch::BresenhamBatch;
+///
+/// fn main() {
+///     let batch = BresenhamBatch::new([((0, 0), (4, 2)), ((3, 3), (0, 0))]);
+///     let (mut first, mut second) = ([(0, 0); 8], [(0, 0); 8]);
+///
+///     let written = batch.rasterize_into(&mut [&mut first[..], &mut second[..]]);
+///
+///     println!("{:?}", &first[..written[0]]);
+///     println!("{:?}", &second[..written[1]]);
+/// }
+/// ```
+///
+/// ```text
+/// [(0, 0), (1, 0), (2, 1), (3, 1), (4, 2)]
+/// [(3, 3), (2, 2), (1, 1), (0, 0)]
+/// ```
+///
+/// [`Bresenham`]: ../struct.Bresenham.html
+#[derive(Clone, Debug, PartialEq)]
+pub struct BresenhamBatch<const N: usize> {
+    point: Point<Simd<i32, N>>,
+    end_x: Simd<i32, N>,
+    delta_x: Simd<i32, N>,
+    delta_y: Simd<i32, N>,
+    error: Simd<i32, N>,
+    swap: Mask<i32, N>,
+    negate_x: Mask<i32, N>,
+    negate_y: Mask<i32, N>,
+}
+
+impl<const N: usize> BresenhamBatch<N> {
+    /// Create a batch with a line in each lane.
+    #[inline]
+    pub fn new(lines: [(Point<i32>, Point<i32>); N]) -> Self {
+        Self::from_slice(&lines)
+    }
+
+    /// Create a batch with a line in each of the first lanes, leaving the rest empty.
+    ///
+    /// Panics if there are more than `N` lines.
+    #[inline]
+    pub fn from_slice(lines: &[(Point<i32>, Point<i32>)]) -> Self {
+        let Lanes { start, end, used } = Lanes::new(lines);
+        let zero = Simd::splat(0);
+
+        // Find the octant of each line the same way as `Octant::new`, as the flips that take it
+        // there and back
+        let (delta_x, delta_y) = (end.0 - start.0, end.1 - start.1);
+        let down = delta_y.simd_lt(zero);
+        let (delta_x, delta_y) = (down.select(-delta_x, delta_x), down.select(-delta_y, delta_y));
+        let left = delta_x.simd_lt(zero);
+        let (delta_x, delta_y) = (left.select(delta_y, delta_x), left.select(-delta_x, delta_y));
+        let steep = delta_x.simd_lt(delta_y);
+
+        let mut batch = Self {
+            point: (zero, zero),
+            end_x: zero,
+            delta_x: zero,
+            delta_y: zero,
+            error: zero,
+            swap: left ^ steep,
+            negate_x: down ^ left,
+            negate_y: down,
+        };
+
+        let start = batch.in_octant(start);
+        let end = batch.in_octant(end);
+
+        batch.point = start;
+        batch.delta_x = end.0 - start.0;
+        batch.delta_y = end.1 - start.1;
+        batch.error = batch.delta_y - batch.delta_x;
+        // Empty lanes end before they start
+        batch.end_x = used.select(end.0, start.0 - Simd::splat(1));
+
+        batch
+    }
+
+    /// Pour the points of each line into the sink for its lane, until either of them runs out,
+    /// and return how many were written to each.
+    #[inline]
+    pub fn rasterize_into<S: PointSink<Point<i32>>>(self, sinks: &mut [S; N]) -> [usize; N] {
+        rasterize_lanes(self, sinks)
+    }
+
+    /// Convert points to their positions in each lane's octant, like `Octant::to`.
+    #[inline]
+    fn in_octant(&self, (x, y): Point<Simd<i32, N>>) -> Point<Simd<i32, N>> {
+        let (x, y) = (self.negate_x.select(-x, x), self.negate_y.select(-y, y));
+
+        (self.swap.select(y, x), self.swap.select(x, y))
+    }
+
+    /// Convert points from their positions in each lane's octant, like `Octant::from`.
+    #[inline]
+    fn out_of_octant(&self, (x, y): Point<Simd<i32, N>>) -> Point<Simd<i32, N>> {
+        let (x, y) = (self.swap.select(y, x), self.swap.select(x, y));
+
+        (self.negate_x.select(-x, x), self.negate_y.select(-y, y))
+    }
+}
+
+impl<const N: usize> Iterator for BresenhamBatch<N> {
+    type Item = BatchPoints<N>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let active = self.point.0.simd_le(self.end_x);
+
+        if !active.any() {
+            return None;
+        }
+
+        let (x, y) = self.out_of_octant(self.point);
+        let (zero, one) = (Simd::splat(0), Simd::splat(1));
+        let step_y = active & self.error.simd_ge(zero);
+
+        self.point.1 += step_y.select(one, zero);
+        self.error -= step_y.select(self.delta_x, zero);
+        self.point.0 += active.select(one, zero);
+        self.error += active.select(self.delta_y, zero);
+
+        Some(BatchPoints { x, y, active })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.end_x - self.point.0 + Simd::splat(1)).reduce_max().max(0) as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<const N: usize> ExactSizeIterator for BresenhamBatch<N> {}
+
+/// [`WalkGrid`] lines in each lane.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::batch::WalkGridBatch;
+///
+/// fn main() {
+///     for points in WalkGridBatch::new([((0, 0), (2, 1)), ((0, 0), (-1, -1))]) {
+///         println!("{:?}", points.to_array());
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// [Some((0, 0)), Some((0, 0))]
+/// [Some((1, 0)), Some((0, -1))]
+/// [Some((1, 1)), Some((-1, -1))]
+/// [Some((2, 1)), None]
+/// ```
+///
+/// [`WalkGrid`]: ../struct.WalkGrid.html
+#[derive(Clone, Debug, PartialEq)]
+pub struct WalkGridBatch<const N: usize> {
+    point: Point<Simd<i32, N>>,
+    sign: Point<Simd<i32, N>>,
+    delta: Point<Simd<i32, N>>,
+    error: Simd<i32, N>,
+    remaining: Simd<i32, N>,
+}
+
+impl<const N: usize> WalkGridBatch<N> {
+    /// Create a batch with a line in each lane.
+    #[inline]
+    pub fn new(lines: [(Point<i32>, Point<i32>); N]) -> Self {
+        Self::from_slice(&lines)
+    }
+
+    /// Create a batch with a line in each of the first lanes, leaving the rest empty.
+    ///
+    /// Panics if there are more than `N` lines.
+    #[inline]
+    pub fn from_slice(lines: &[(Point<i32>, Point<i32>)]) -> Self {
+        let Lanes { start, end, used } = Lanes::new(lines);
+        let (delta_x, delta_y) = (end.0 - start.0, end.1 - start.1);
+        let (nx, ny) = (delta_x.abs(), delta_y.abs());
+
+        Self {
+            point: start,
+            sign: (delta_x.signum(), delta_y.signum()),
+            delta: (nx, ny),
+            // Which of the next grid lines is crossed first, like in `WalkGrid`
+            error: ny - nx,
+            remaining: used.select(nx + ny + Simd::splat(1), Simd::splat(0)),
+        }
+    }
+
+    /// Pour the points of each line into the sink for its lane, until either of them runs out,
+    /// and return how many were written to each.
+    #[inline]
+    pub fn rasterize_into<S: PointSink<Point<i32>>>(self, sinks: &mut [S; N]) -> [usize; N] {
+        rasterize_lanes(self, sinks)
+    }
+}
+
+impl<const N: usize> Iterator for WalkGridBatch<N> {
+    type Item = BatchPoints<N>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let zero = Simd::splat(0);
+        let active = self.remaining.simd_gt(zero);
+
+        if !active.any() {
+            return None;
+        }
+
+        let (x, y) = self.point;
+        let step_x = active & self.error.simd_lt(zero);
+        let step_y = active & !step_x;
+
+        self.point.0 += step_x.select(self.sign.0, zero);
+        self.error += step_x.select(self.delta.1 + self.delta.1, zero);
+        self.point.1 += step_y.select(self.sign.1, zero);
+        self.error -= step_y.select(self.delta.0 + self.delta.0, zero);
+        self.remaining -= active.select(Simd::splat(1), zero);
+
+        Some(BatchPoints { x, y, active })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.remaining.reduce_max().max(0) as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<const N: usize> ExactSizeIterator for WalkGridBatch<N> {}
+
+#[test]
+fn tests() {
+    use {Bresenham, WalkGrid};
+
+    // Every line out from a point, in batches of 8 with some left over
+    let lines = (-6..=6)
+        .flat_map(|x| (-6..=6).map(move |y| ((1, -2), (x, y))))
+        .collect::<Vec<_>>();
+
+    for chunk in lines.chunks(8) {
+        let mut buffers = [[(0, 0); 32]; 8];
+        let mut lanes = buffers.each_mut().map(|buffer| &mut buffer[..]);
+        let written = BresenhamBatch::<8>::from_slice(chunk).rasterize_into(&mut lanes);
+
+        for (lane, &(start, end)) in chunk.iter().enumerate() {
+            let points = Bresenham::new(start, end).collect::<Vec<_>>();
+            assert_eq!(buffers[lane][..written[lane]], points[..]);
+        }
+
+        let mut lanes = buffers.each_mut().map(|buffer| &mut buffer[..]);
+        let written = WalkGridBatch::<8>::from_slice(chunk).rasterize_into(&mut lanes);
+
+        for (lane, &(start, end)) in chunk.iter().enumerate() {
+            let points = WalkGrid::new(start, end).collect::<Vec<_>>();
+            assert_eq!(buffers[lane][..written[lane]], points[..]);
+        }
+
+        // The empty lanes never have a point
+        assert!(written[chunk.len()..].iter().all(|&written| written == 0));
+    }
+
+    // The length is the longest line's
+    let mut batch = BresenhamBatch::new([((0, 0), (5, 2)), ((0, 0), (0, -9)), ((4, 4), (4, 4))]);
+    assert_eq!(batch.len(), 10);
+    assert_eq!(
+        batch.next().map(|points| points.to_array()),
+        Some([Some((0, 0)), Some((0, 0)), Some((4, 4))])
+    );
+    assert_eq!(batch.len(), 9);
+    assert_eq!(batch.count(), 9);
+
+    let batch = WalkGridBatch::new([((0, 0), (5, 2)), ((0, 0), (0, -9)), ((4, 4), (4, 4))]);
+    assert_eq!(batch.len(), 10);
+    assert_eq!(batch.last().map(|points| points.get(1)), Some(Some((0, -9))));
+
+    // A full sink stops its own lane without holding up the others
+    let mut buffer = [(0, 0); 2];
+    let mut lanes = [&mut buffer[..], &mut [(0, 0); 8][..]];
+    let batch = BresenhamBatch::new([((0, 0), (5, 0)), ((0, 0), (0, 5))]);
+    let written = batch.rasterize_into(&mut lanes);
+    assert_eq!(written, [2, 6]);
+    assert_eq!(buffer, [(0, 0), (1, 0)]);
+}
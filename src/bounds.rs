@@ -0,0 +1,172 @@
+use {IntoPoint, IntoVoxel, Point, SignedNum, Voxel};
+use core::ops::Neg;
+use num_traits::Num;
+
+/// An axis-aligned box of voxels, including both the `min` and `max` corners.
+///
+/// Used by the `clipped` constructors to skip the parts of a line outside of the world.
+///
+/// ```
+/// use line_drawing::{Bresenham3d, VoxelBounds};
+///
+/// let bounds = VoxelBounds::new((0, 0, 0), (15, 15, 15));
+/// let line = Bresenham3d::clipped((-10_000, 5, 5), (10, 5, 5), bounds);
+///
+/// assert_eq!(line.count(), 11);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VoxelBounds<T> {
+    pub min: Voxel<T>,
+    pub max: Voxel<T>,
+}
+
+impl<T: SignedNum> VoxelBounds<T> {
+    #[inline]
+    pub fn new(min: impl IntoVoxel<T>, max: impl IntoVoxel<T>) -> Self {
+        let (min, max) = (min.into_voxel(), max.into_voxel());
+
+        Self { min, max }
+    }
+
+    /// Check if a voxel is inside the bounds.
+    #[inline]
+    pub fn contains(&self, voxel: Voxel<T>) -> bool {
+        voxel.0 >= self.min.0
+            && voxel.0 <= self.max.0
+            && voxel.1 >= self.min.1
+            && voxel.1 <= self.max.1
+            && voxel.2 >= self.min.2
+            && voxel.2 <= self.max.2
+    }
+
+    /// Clamp a voxel to be inside the bounds.
+    #[inline]
+    pub fn clamp(&self, voxel: Voxel<T>) -> Voxel<T> {
+        (
+            voxel.0.max(self.min.0).min(self.max.0),
+            voxel.1.max(self.min.1).min(self.max.1),
+            voxel.2.max(self.min.2).min(self.max.2),
+        )
+    }
+}
+
+/// An axis-aligned rectangle of cells, including both the `min` and `max` corners.
+///
+/// The 2D version of [`VoxelBounds`], used by the `clipped` constructors of the 2D algorithms.
+///
+/// ```
+/// use line_drawing::{Bresenham, GridBounds};
+///
+/// let bounds = GridBounds::new((0, 0), (639, 479));
+/// let line = Bresenham::clipped((-10_000, -10_000), (100, 100), bounds);
+///
+/// assert_eq!(line.count(), 101);
+/// ```
+///
+/// [`VoxelBounds`]: struct.VoxelBounds.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GridBounds<T> {
+    pub min: Point<T>,
+    pub max: Point<T>,
+}
+
+impl<T: SignedNum> GridBounds<T> {
+    #[inline]
+    pub fn new(min: impl IntoPoint<T>, max: impl IntoPoint<T>) -> Self {
+        let (min, max) = (min.into_point(), max.into_point());
+
+        Self { min, max }
+    }
+
+    /// Check if a point is inside the bounds.
+    #[inline]
+    pub fn contains(&self, point: Point<T>) -> bool {
+        point.0 >= self.min.0
+            && point.0 <= self.max.0
+            && point.1 >= self.min.1
+            && point.1 <= self.max.1
+    }
+
+    /// Clamp a point to be inside the bounds.
+    #[inline]
+    pub fn clamp(&self, point: Point<T>) -> Point<T> {
+        (
+            point.0.max(self.min.0).min(self.max.0),
+            point.1.max(self.min.1).min(self.max.1),
+        )
+    }
+}
+
+/// Narrow the `(enter, exit)` range of a parametric segment to the part between `min` and `max`
+/// along one axis.
+#[inline]
+pub(crate) fn clip_axis<I>(start: I, delta: I, min: I, max: I, range: &mut (I, I))
+where
+    I: Copy + Num + PartialOrd + Neg<Output = I>,
+{
+    if delta.is_zero() {
+        if start < min || start > max {
+            range.1 = -I::one();
+        }
+    } else {
+        let a = (min - start) / delta;
+        let b = (max - start) / delta;
+        let (near, far) = if a < b { (a, b) } else { (b, a) };
+
+        if near > range.0 {
+            range.0 = near;
+        }
+        if far < range.1 {
+            range.1 = far;
+        }
+    }
+}
+
+/// Find the first value in `low..=high` that `predicate` returns `false` for, assuming that it
+/// returns `true` for some prefix of the range. Returns `high + 1` if there isn't one.
+#[inline]
+pub(crate) fn partition_point<T, F>(mut low: T, mut high: T, predicate: F) -> T
+where
+    T: SignedNum,
+    F: Fn(T) -> bool,
+{
+    high += T::one();
+
+    while low < high {
+        let middle = low + (high - low) / T::cast(2);
+
+        if predicate(middle) {
+            low = middle + T::one();
+        } else {
+            high = middle;
+        }
+    }
+
+    low
+}
+
+/// Find the range of steps where a coordinate moving in the direction of `sign` is between `min`
+/// and `max`.
+#[inline]
+pub(crate) fn steps_inside<T, F>(sign: T, min: T, max: T, longest: T, coord: F) -> (T, T)
+where
+    T: SignedNum,
+    F: Fn(T) -> T,
+{
+    let (before, after) = if sign < T::zero() {
+        (max, min)
+    } else {
+        (min, max)
+    };
+
+    let first = partition_point(T::zero(), longest, |step| {
+        (coord(step) - before) * sign < T::zero()
+    });
+    let last = partition_point(T::zero(), longest, |step| {
+        (coord(step) - after) * sign <= T::zero()
+    });
+
+    (first, last - T::one())
+}
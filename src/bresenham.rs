@@ -1,10 +1,18 @@
-use {Point, SignedNum};
+use {Coord, GridBounds, IntoPoint, LineError, LineRasterizer, Point, SignedNum};
+use bounds::steps_inside;
+use error::{check_len, check_span};
+use exact::mul_div;
 use octant::Octant;
 use steps::Steps;
+use toroidal::Wrapped;
+use core::mem;
+use core::ops::ControlFlow;
+use num_traits::{NumCast, One, Signed, ToPrimitive, Zero};
 
 /// An implementation of [Bresenham's line algorithm].
 ///
-/// Includes both the start and end point and is asymmetrical.
+/// Includes both the start and end point and is asymmetrical, unless it's created with
+/// [`symmetric`].
 ///
 /// Example:
 ///
@@ -23,19 +31,55 @@ use steps::Steps;
 /// (0, 0), (0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (5, 6),
 /// ```
 ///
+/// The math is done in [`SignedNum::Wide`], so lines can go between any two points that fit in
+/// `T`, even from one end of it to the other. Horizontal, vertical and exactly diagonal lines are
+/// spotted when they're created, and just take the same step every time.
+///
 /// [Bresenham's line algorithm]: https://en.wikipedia.org/wiki/Bresenham's_line_algorithm
-pub struct Bresenham<T> {
-    point: Point<T>,
-    end_x: T,
-    delta_x: T,
-    delta_y: T,
-    error: T,
+/// [`symmetric`]: #method.symmetric
+/// [`SignedNum::Wide`]: trait.SignedNum.html#associatedtype.Wide
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Bresenham<T: SignedNum> {
+    point: Point<T::Wide>,
+    end_x: T::Wide,
+    delta_x: T::Wide,
+    delta_y: T::Wide,
+    error: T::Wide,
     octant: Octant,
+    straight: Option<Straight<T::Wide>>,
+}
+
+/// A line along the `x` or `y` axis or at exactly 45°, which always steps sideways or never does,
+/// so its points can be stepped along in world space instead of being converted out of the octant
+/// one at a time.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct Straight<W> {
+    point: Point<W>,
+    step: Point<W>,
+    // How far each step moves along `y` in octant space, which is `1` for a diagonal and `0` for
+    // an axis
+    rise: W,
+}
+
+/// Convert a point to the wide type.
+#[inline]
+fn widen<T: SignedNum>(point: Point<T>) -> Point<T::Wide> {
+    (point.0.widen(), point.1.widen())
+}
+
+/// Convert a point on the line back from the wide type, which it always fits in.
+#[inline]
+fn narrow<T: SignedNum>(point: Point<T::Wide>) -> Point<T> {
+    (T::cast(point.0), T::cast(point.1))
 }
 
 impl<T: SignedNum> Bresenham<T> {
     #[inline]
-    pub fn new(start: Point<T>, end: Point<T>) -> Self {
+    pub fn new(start: impl IntoPoint<T>, end: impl IntoPoint<T>) -> Self {
+        let (start, end) = (widen(start.into_point()), widen(end.into_point()));
+
         let octant = Octant::new(start, end);
         let start = octant.to(start);
         let end = octant.to(end);
@@ -43,19 +87,302 @@ impl<T: SignedNum> Bresenham<T> {
         let delta_x = end.0 - start.0;
         let delta_y = end.1 - start.1;
 
+        // The error stays the same on these, since it changes by `delta_y - delta_x` with every
+        // step when it steps sideways, and by `delta_y` when it doesn't
+        let straight = if delta_y == T::Wide::zero() || delta_y == delta_x {
+            let rise = delta_y.signum();
+
+            Some(Straight {
+                point: octant.from(start),
+                step: octant.from((T::Wide::one(), rise)),
+                rise,
+            })
+        } else {
+            None
+        };
+
         Self {
             delta_x,
             delta_y,
             octant,
+            straight,
             point: start,
             end_x: end.0,
             error: delta_y - delta_x,
         }
     }
 
+    /// Create a line like [`new`], but return an error instead of overflowing when the points are
+    /// too far apart, or too close to the limits of `T::Wide`. That can only happen with `i128`,
+    /// which has nothing wider to do the math in. Lines with too many points to count in a `usize`
+    /// are an error as well.
+    ///
+    /// [`new`]: #method.new
+    #[inline]
+    pub fn try_new(start: impl IntoPoint<T>, end: impl IntoPoint<T>) -> Result<Self, LineError> {
+        let (start, end) = (start.into_point(), end.into_point());
+        let (wide_start, wide_end) = (widen(start), widen(end));
+
+        check_span(wide_start.0, wide_end.0)?;
+        check_span(wide_start.1, wide_end.1)?;
+
+        let line = Self::new(start, end);
+        check_len(line.end_x - line.point.0)?;
+
+        Ok(line)
+    }
+
+    /// Create a line between end points of any number type, rounding floats to the nearest cell.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// extern crate line_drawing;
+    /// use line_drawing::Bresenham;
+    ///
+    /// fn main() {
+    ///     let line = Bresenham::<i32>::from_coords((0.2, -0.5), (4.7, 1.4));
+    ///     assert_eq!(line, Bresenham::new((0, -1), (5, 1)));
+    /// }
+    /// ```
+    #[inline]
+    pub fn from_coords<C: Coord>(start: impl IntoPoint<C>, end: impl IntoPoint<C>) -> Self {
+        let (start, end) = (start.into_point(), end.into_point());
+
+        Self::new(
+            (start.0.to_cell(), start.1.to_cell()),
+            (end.0.to_cell(), end.1.to_cell()),
+        )
+    }
+
+    /// Create a line that goes through the same points as the one from `end` to `start`.
+    ///
+    /// The points are rounded to the nearest cell instead of always towards the start, and the
+    /// ones exactly halfway between two cells are broken the same way in both directions, by
+    /// ordering the end points. This is for anything that draws the same line from either end, like
+    /// the shared edges of a wireframe, or that has to agree across machines.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// extern crate line_drawing;
+    /// use line_drawing::Bresenham;
+    ///
+    /// fn main() {
+    ///     let forwards = Bresenham::symmetric((0, 0), (5, 2)).collect::<Vec<_>>();
+    ///     let mut backwards = Bresenham::symmetric((5, 2), (0, 0)).collect::<Vec<_>>();
+    ///     backwards.reverse();
+    ///
+    ///     assert_eq!(forwards, backwards);
+    ///     assert_eq!(forwards, [(0, 0), (1, 0), (2, 1), (3, 1), (4, 2), (5, 2)]);
+    /// }
+    /// ```
+    #[inline]
+    pub fn symmetric(start: impl IntoPoint<T>, end: impl IntoPoint<T>) -> Self {
+        let (start, end) = (start.into_point(), end.into_point());
+
+        let mut line = Self::new(start, end);
+        let two = T::Wide::one() + T::Wide::one();
+
+        // Compare against the halfway point of each cell, rounding ties up in octant space in one
+        // direction and down in the other, so that they land on the same cells in both
+        line.error = two * line.delta_y - line.delta_x;
+        if start > end {
+            line.error -= T::Wide::one();
+        }
+
+        line.delta_x *= two;
+        line.delta_y *= two;
+
+        line
+    }
+
+    /// Create a new `Bresenham` iterator that only returns the points inside `bounds`.
+    ///
+    /// This produces the same points as filtering the full line, but skips straight to the first
+    /// point inside the bounds without stepping through the ones before it.
+    #[inline]
+    pub fn clipped(
+        start: impl IntoPoint<T>,
+        end: impl IntoPoint<T>,
+        bounds: GridBounds<T>,
+    ) -> Self {
+        let (start, end) = (start.into_point(), end.into_point());
+
+        let mut line = Self::new(start, end);
+        let length = line.end_x - line.point.0;
+        let (start, end) = (widen(start), widen(end));
+        let (min, max) = (widen(bounds.min), widen(bounds.max));
+
+        // The points are visited in order along each axis, so the ones inside the bounds are
+        // always a single range of steps
+        let x = steps_inside(
+            (end.0 - start.0).signum(),
+            min.0,
+            max.0,
+            length,
+            |step| line.octant.from(line.position(step).0).0,
+        );
+        let y = steps_inside(
+            (end.1 - start.1).signum(),
+            min.1,
+            max.1,
+            length,
+            |step| line.octant.from(line.position(step).0).1,
+        );
+
+        let first = x.0.max(y.0);
+        let last = x.1.min(y.1);
+
+        if first <= last && bounds.contains(narrow(line.octant.from(line.position(first).0))) {
+            let (point, error) = line.position(first);
+
+            line.move_to(point, error);
+            line.end_x = point.0 + last - first;
+        } else {
+            line.end_x = line.point.0 - T::Wide::one();
+        }
+
+        line
+    }
+
+    /// Jump to a point and error in octant space, keeping the world space point of a straight line
+    /// up to date.
+    #[inline]
+    fn move_to(&mut self, point: Point<T::Wide>, error: T::Wide) {
+        self.point = point;
+        self.error = error;
+
+        if let Some(ref mut straight) = self.straight {
+            straight.point = self.octant.from(point);
+        }
+    }
+
+    /// Find the point and error after taking a number of steps from the current point, in octant
+    /// space.
+    #[inline]
+    fn position(&self, step: T::Wide) -> (Point<T::Wide>, T::Wide) {
+        // The products can need more bits than even `T::Wide` has, so they're done in `i128`, or
+        // split into whole multiples of `delta_x` and what's left over when that isn't enough
+        let wide = |value: T::Wide| value.to_i128().unwrap();
+        let (error, delta_x, delta_y) = (wide(self.error), wide(self.delta_x), wide(self.delta_y));
+        let steps = wide(step);
+
+        // The error before the last step decides how many of them also moved along y. It's never
+        // less than `-delta_x`, so only the whole multiples count unless the rest is positive.
+        let moved = if steps > 0 {
+            let (whole, rest) = mul_div((steps - 1) as u128, delta_y as u128, delta_x as u128);
+            let rest = rest as i128 + error;
+
+            if rest >= 0 {
+                whole as i128 + rest / delta_x + 1
+            } else {
+                whole as i128
+            }
+        } else {
+            0
+        };
+
+        // The error always fits, so wrapping around in between still gives the right answer
+        let error = error
+            .wrapping_add(steps.wrapping_mul(delta_y))
+            .wrapping_sub(moved.wrapping_mul(delta_x));
+
+        (
+            (self.point.0 + step, self.point.1 + T::Wide::cast(moved)),
+            T::Wide::cast(error),
+        )
+    }
+
+    /// Get a point further along the line without going through the ones before it.
+    ///
+    /// An `index` of `0` is the point that would be returned next, so this gives the same point as
+    /// `nth` on a copy of the iterator, in constant time. Returns `None` if the line ends before
+    /// then.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// extern crate line_drawing;
+    /// use line_drawing::Bresenham;
+    ///
+    /// fn main() {
+    ///     let line = Bresenham::new((0, 0), (100, 37));
+    ///
+    ///     for &index in &[0, 50, 100, 101] {
+    ///         print!("{:?}, ", line.point_at(index));
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// ```text
+    /// Some((0, 0)), Some((50, 18)), Some((100, 37)), None,
+    /// ```
+    #[inline]
+    pub fn point_at(&self, index: usize) -> Option<Point<T>> {
+        let step = <T::Wide as NumCast>::from(index)?;
+
+        if step > self.end_x - self.point.0 {
+            return None;
+        }
+
+        Some(narrow(self.octant.from(self.position(step).0)))
+    }
+
+    /// Like [`LineRasterizer::steps`], without needing the trait imported.
+    ///
+    /// [`LineRasterizer::steps`]: trait.LineRasterizer.html#method.steps
     #[inline]
     pub fn steps(self) -> Steps<Point<T>, Self> {
-        Steps::new(self)
+        LineRasterizer::steps(self)
+    }
+
+    /// Wrap the points around the edges of a grid of the given size.
+    #[inline]
+    pub fn wrapped(self, size: Point<T>) -> Wrapped<T, Self> {
+        Wrapped::new(self, size)
+    }
+}
+
+impl<T: SignedNum> LineRasterizer<T, T> for Bresenham<T> {
+    #[inline]
+    fn new(start: Point<T>, end: Point<T>) -> Self {
+        Bresenham::new(start, end)
+    }
+
+    #[inline]
+    fn walk<B, F>(start: Point<T>, end: Point<T>, mut function: F) -> ControlFlow<B>
+    where
+        F: FnMut(Point<T>) -> ControlFlow<B>,
+    {
+        let line = Bresenham::new(start, end);
+        let (one, zero) = (T::Wide::one(), T::Wide::zero());
+
+        // Work out the steps in world space once, instead of converting every point
+        let step = line.octant.from((one, zero));
+        let side_step = line.octant.from((zero, one));
+
+        let mut point = widen(start);
+        let mut error = line.error;
+        let mut remaining = line.end_x - line.point.0;
+
+        loop {
+            function(narrow(point))?;
+
+            if remaining == zero {
+                return ControlFlow::Continue(());
+            }
+
+            remaining -= one;
+
+            if error >= zero {
+                point = (point.0 + side_step.0, point.1 + side_step.1);
+                error -= line.delta_x;
+            }
+
+            point = (point.0 + step.0, point.1 + step.1);
+            error += line.delta_y;
+        }
     }
 }
 
@@ -64,15 +391,31 @@ impl<T: SignedNum> Iterator for Bresenham<T> {
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
+        if let Some(ref mut straight) = self.straight {
+            if self.point.0 > self.end_x {
+                return None;
+            }
+
+            let point = straight.point;
+            straight.point = (point.0 + straight.step.0, point.1 + straight.step.1);
+            self.point = (self.point.0 + T::Wide::one(), self.point.1 + straight.rise);
+
+            return Some(narrow(point));
+        }
+
         if self.point.0 <= self.end_x {
-            let point = self.octant.from(self.point);
+            let point = narrow(self.octant.from(self.point));
 
-            if self.error >= T::zero() {
-                self.point.1 += T::one();
-                self.error -= self.delta_x;
-            }
+            // Step along `y` without branching on the error, which is hard to predict on long
+            // lines. Shifting the sign bit all the way across gives all ones when the error is
+            // negative, so `step` is `-1` when the line steps along `y` and `0` when it doesn't.
+            let sign = self.error >> (mem::size_of::<T::Wide>() * 8 - 1);
+            let step = !sign;
+
+            self.point.1 -= step;
+            self.error -= self.delta_x & step;
 
-            self.point.0 += T::one();
+            self.point.0 += T::Wide::one();
             self.error += self.delta_y;
 
             Some(point)
@@ -80,12 +423,237 @@ impl<T: SignedNum> Iterator for Bresenham<T> {
             None
         }
     }
+
+    /// Lines with more points left than a `usize` can count, which [`try_new`] rules out, say
+    /// they have `usize::MAX`, so that [`len`] gives that instead of panicking.
+    ///
+    /// [`try_new`]: struct.Bresenham.html#method.try_new
+    /// [`len`]: https://doc.rust-lang.org/core/iter/trait.ExactSizeIterator.html#method.len
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.end_x - self.point.0 + T::Wide::one())
+            .to_usize()
+            .unwrap_or(usize::MAX);
+
+        (remaining, Some(remaining))
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        match <T::Wide as NumCast>::from(n) {
+            Some(step) if step <= self.end_x - self.point.0 => {
+                let (point, error) = Bresenham::position(self, step);
+                self.move_to(point, error);
+                self.next()
+            }
+            _ => {
+                self.end_x = self.point.0 - T::Wide::one();
+                None
+            }
+        }
+    }
+
+    #[inline]
+    fn last(self) -> Option<Self::Item> {
+        let steps = self.end_x - self.point.0;
+
+        if steps < T::Wide::zero() {
+            return None;
+        }
+
+        Some(narrow(self.octant.from(self.position(steps).0)))
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.len()
+    }
 }
 
+impl<T: SignedNum> ExactSizeIterator for Bresenham<T> {}
+
 #[test]
 fn test() {
     assert_eq!(
         Bresenham::new((0, 0), (5, 5)).collect::<Vec<_>>(),
         [(0, 0), (1, 1), (2, 2), (3, 3), (4, 4), (5, 5)]
-    )
+    );
+    assert_eq!(Bresenham::new((3, 9), (-4, 2)).len(), 8);
+
+    // Lines across most of the range of `i64` still jump straight to their points, even though
+    // working them out takes more bits than `i128` has
+    let (start, end) = ((i64::MIN, 0), (i64::MAX, 3));
+    let line = Bresenham::new(start, end);
+
+    for &index in &[1usize << 62, 1 << 63, usize::MAX - 1] {
+        let y = index as u128 * 3 / u64::MAX as u128;
+        assert_eq!(line.point_at(index), Some((index as i64 ^ 1 << 63, y as i64)));
+    }
+
+    assert_eq!(line.clone().nth(usize::MAX), Some(end));
+    assert_eq!(line.clone().last(), Some(end));
+    assert_eq!(line.size_hint(), (usize::MAX, Some(usize::MAX)));
+    assert_eq!(line.len(), usize::MAX);
+    assert_eq!(Bresenham::new((i64::MIN, 0), (i64::MAX, 0)).last(), Some((i64::MAX, 0)));
+    assert_eq!(Bresenham::try_new(start, end), Err(LineError::Overflow));
+    assert!(Bresenham::try_new((i64::MIN + 1, 0), (i64::MAX, 0)).is_ok());
+
+    // Walking goes through the same points, and stops where it's told to
+    for &(start, end) in &[
+        ((0, 0), (5, 5)),
+        ((3, 9), (-4, 2)),
+        ((2, -7), (-6, -4)),
+        ((1, 1), (1, 1)),
+    ] {
+        let mut points = Vec::new();
+        let result = Bresenham::walk(start, end, |point| {
+            points.push(point);
+            ControlFlow::<()>::Continue(())
+        });
+
+        assert_eq!(result, ControlFlow::Continue(()));
+        assert_eq!(points, Bresenham::new(start, end).collect::<Vec<_>>());
+
+        let mut count = 0;
+        let result = Bresenham::walk(start, end, |point| {
+            count += 1;
+            if count == 3 {
+                ControlFlow::Break(point)
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+
+        assert_eq!(
+            result,
+            match Bresenham::new(start, end).nth(2) {
+                Some(point) => ControlFlow::Break(point),
+                None => ControlFlow::Continue(()),
+            }
+        );
+    }
+
+    // Lines with more points than a `usize` can count still get walked
+    assert_eq!(
+        Bresenham::walk((i64::MIN, 0), (i64::MAX, 3), ControlFlow::Break),
+        ControlFlow::Break((i64::MIN, 0))
+    );
+
+    // Jumping ahead gives the same points as stepping through them
+    for &(start, end) in &[
+        ((0, 0), (17, 6)),
+        ((3, 9), (-4, 2)),
+        ((2, -7), (-6, -23)),
+        ((1, 1), (1, 1)),
+    ] {
+        let points = Bresenham::new(start, end).collect::<Vec<_>>();
+
+        for skip in 0..points.len() {
+            let mut line = Bresenham::new(start, end);
+            assert_eq!(line.nth(skip), Some(points[skip]));
+
+            for index in 0..points.len() + 2 {
+                assert_eq!(line.point_at(index), points.get(skip + 1 + index).cloned());
+            }
+
+            assert_eq!(line.len(), points.len() - skip - 1);
+            assert_eq!(line.collect::<Vec<_>>(), &points[skip + 1..]);
+        }
+
+        let mut line = Bresenham::new(start, end);
+        assert_eq!(line.nth(points.len()), None);
+        assert_eq!(line.next(), None);
+    }
+
+    // A copy carries on from the same place
+    let mut line = Bresenham::new((3, 9), (-4, 2));
+    line.next();
+    let copy = line.clone();
+    assert_eq!(copy, line);
+    assert_eq!(copy.collect::<Vec<_>>(), line.collect::<Vec<_>>());
+
+    // Symmetric lines round to the nearest cell, and break ties the same way in both directions
+    assert_eq!(
+        Bresenham::symmetric((0, 0), (5, 6)).collect::<Vec<_>>(),
+        [(0, 0), (1, 1), (2, 2), (3, 3), (3, 4), (4, 5), (5, 6)]
+    );
+    assert_eq!(
+        Bresenham::symmetric((0, 0), (4, 1)).collect::<Vec<_>>(),
+        [(0, 0), (1, 0), (2, 1), (3, 1), (4, 1)]
+    );
+    assert_eq!(
+        Bresenham::symmetric((4, 1), (0, 0)).collect::<Vec<_>>(),
+        [(4, 1), (3, 1), (2, 1), (1, 0), (0, 0)]
+    );
+
+    let mut line = Bresenham::symmetric((-3, 8), (14, 1));
+    assert_eq!(line.point_at(5), line.clone().nth(5));
+    line.next();
+    assert_eq!(line.len(), line.clone().count());
+    assert_eq!(line.last(), Some((14, 1)));
+
+    let bounds = GridBounds::new((-3, -2), (4, 6));
+
+    for &(start, end) in &[
+        ((-10, -10), (10, 10)),
+        ((-20, 3), (20, 1)),
+        ((7, -9), (-6, 12)),
+        ((0, 0), (2, 3)),
+        ((-8, 30), (2, -25)),
+        ((10, 10), (20, 5)),
+        ((-5, 5), (-5, 5)),
+        ((1, 1), (1, 1)),
+        ((5, -4), (-9, -1)),
+    ] {
+        let mut line = Bresenham::clipped(start, end, bounds);
+        assert_eq!(line.len(), line.by_ref().count());
+        assert_eq!(line.len(), 0);
+
+        assert_eq!(
+            Bresenham::clipped(start, end, bounds).collect::<Vec<_>>(),
+            Bresenham::new(start, end)
+                .filter(|&point| bounds.contains(point))
+                .collect::<Vec<_>>()
+        );
+    }
+}
+
+#[test]
+fn full_range() {
+    let wide = |point: Point<i8>| (point.0 as i32, point.1 as i32);
+
+    // Small types work from one end of their range to the other, the same as wider ones
+    for &(start, end) in &[
+        ((i8::MIN, i8::MIN), (i8::MAX, i8::MAX)),
+        ((i8::MAX, -3), (i8::MIN, 100)),
+        ((0, i8::MIN), (1, i8::MAX)),
+        ((i8::MAX, i8::MAX), (i8::MAX, i8::MAX)),
+    ] {
+        let points = Bresenham::new(wide(start), wide(end)).collect::<Vec<_>>();
+        let line = Bresenham::<i8>::new(start, end);
+
+        assert_eq!(line.len(), points.len());
+        assert_eq!(line.clone().map(wide).collect::<Vec<_>>(), points);
+        assert_eq!(line.point_at(points.len() / 2).map(wide), Some(points[points.len() / 2]));
+        assert_eq!(line.last(), Some(end));
+        assert!(Bresenham::try_new(start, end).is_ok());
+
+        assert_eq!(
+            Bresenham::<i8>::symmetric(start, end).map(wide).collect::<Vec<_>>(),
+            Bresenham::symmetric(wide(start), wide(end)).collect::<Vec<_>>()
+        );
+
+        let mut walked = Vec::new();
+        let _ = Bresenham::<i8>::walk(start, end, |point| {
+            walked.push(wide(point));
+            ControlFlow::<()>::Continue(())
+        });
+        assert_eq!(walked, points);
+    }
+
+    let bounds = GridBounds::new((-128i16, -5), (32_767, 5));
+    assert_eq!(
+        Bresenham::clipped((i16::MIN, i16::MIN), (i16::MAX, i16::MAX), bounds).len(),
+        11
+    );
 }
@@ -1,6 +1,12 @@
-use {SignedNum, Voxel};
+use {Coord, IntoVoxel, LineError, SignedNum, Voxel, VoxelBounds};
 use steps::Steps;
+use chunked::Chunked;
+use bounds::steps_inside;
+use error::{check_len, check_span};
+use exact::mul_div;
 use core::cmp::max;
+use core::ops::ControlFlow;
+use num_traits::{One, Signed, ToPrimitive, Zero};
 
 /// An 3-D implementation of bresenham, sourced from [this site].
 ///
@@ -23,25 +29,46 @@ use core::cmp::max;
 /// (0, 0, 0), (1, 1, 1), (1, 2, 2), (2, 3, 3), (3, 3, 4), (4, 4, 5), (4, 5, 6), (5, 6, 7),
 /// ```
 ///
+/// Like [`Bresenham`], the math is done in [`SignedNum::Wide`], so the voxels can be anywhere in
+/// `T`.
+///
 /// [this site]: http://members.chello.at/~easyfilter/bresenham.html
-pub struct Bresenham3d<T> {
-    sign_x: T,
-    sign_y: T,
-    sign_z: T,
-    err_x: T,
-    err_y: T,
-    err_z: T,
-    len_x: T,
-    len_y: T,
-    len_z: T,
-    longest: T,
-    count: T,
-    voxel: Voxel<T>,
+/// [`Bresenham`]: struct.Bresenham.html
+/// [`SignedNum::Wide`]: trait.SignedNum.html#associatedtype.Wide
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Bresenham3d<T: SignedNum> {
+    sign_x: T::Wide,
+    sign_y: T::Wide,
+    sign_z: T::Wide,
+    err_x: T::Wide,
+    err_y: T::Wide,
+    err_z: T::Wide,
+    len_x: T::Wide,
+    len_y: T::Wide,
+    len_z: T::Wide,
+    longest: T::Wide,
+    count: T::Wide,
+    voxel: Voxel<T::Wide>,
+}
+
+/// Convert a voxel to the wide type.
+#[inline]
+fn widen<T: SignedNum>(voxel: Voxel<T>) -> Voxel<T::Wide> {
+    (voxel.0.widen(), voxel.1.widen(), voxel.2.widen())
+}
+
+/// Convert a voxel on the line back from the wide type, which it always fits in.
+#[inline]
+fn narrow<T: SignedNum>(voxel: Voxel<T::Wide>) -> Voxel<T> {
+    (T::cast(voxel.0), T::cast(voxel.1), T::cast(voxel.2))
 }
 
 impl<T: SignedNum> Bresenham3d<T> {
     #[inline]
-    pub fn new(start: Voxel<T>, end: Voxel<T>) -> Self {
+    pub fn new(start: impl IntoVoxel<T>, end: impl IntoVoxel<T>) -> Self {
+        let (start, end) = (widen(start.into_voxel()), widen(end.into_voxel()));
+
         let delta_x = end.0 - start.0;
         let delta_y = end.1 - start.1;
         let delta_z = end.2 - start.2;
@@ -58,9 +85,9 @@ impl<T: SignedNum> Bresenham3d<T> {
             len_z,
             longest,
             count: longest,
-            err_x: longest / T::cast(2),
-            err_y: longest / T::cast(2),
-            err_z: longest / T::cast(2),
+            err_x: longest / T::Wide::cast(2),
+            err_y: longest / T::Wide::cast(2),
+            err_z: longest / T::Wide::cast(2),
             sign_x: delta_x.signum(),
             sign_y: delta_y.signum(),
             sign_z: delta_z.signum(),
@@ -68,10 +95,198 @@ impl<T: SignedNum> Bresenham3d<T> {
         }
     }
 
+    /// Create a line like [`new`], but return an error instead of overflowing when the voxels are
+    /// too far apart, or too close to the limits of `T::Wide`, which can only happen with `i128`.
+    /// Lines with too many voxels to count in a `usize` are an error as well.
+    ///
+    /// [`new`]: #method.new
+    #[inline]
+    pub fn try_new(start: impl IntoVoxel<T>, end: impl IntoVoxel<T>) -> Result<Self, LineError> {
+        let (start, end) = (start.into_voxel(), end.into_voxel());
+        let (wide_start, wide_end) = (widen(start), widen(end));
+
+        check_span(wide_start.0, wide_end.0)?;
+        check_span(wide_start.1, wide_end.1)?;
+        check_span(wide_start.2, wide_end.2)?;
+
+        let line = Self::new(start, end);
+        check_len(line.longest)?;
+
+        Ok(line)
+    }
+
+    /// Create a line between end points of any number type, rounding floats to the nearest voxel.
+    #[inline]
+    pub fn from_coords<C: Coord>(start: impl IntoVoxel<C>, end: impl IntoVoxel<C>) -> Self {
+        let (start, end) = (start.into_voxel(), end.into_voxel());
+
+        Self::new(
+            (start.0.to_cell(), start.1.to_cell(), start.2.to_cell()),
+            (end.0.to_cell(), end.1.to_cell(), end.2.to_cell()),
+        )
+    }
+
+    /// Create a new `Bresenham3d` iterator that only returns the voxels inside `bounds`.
+    ///
+    /// This produces the same voxels as filtering the full line, but skips straight to the first
+    /// voxel inside the bounds without stepping through the ones before it.
+    #[inline]
+    pub fn clipped(
+        start: impl IntoVoxel<T>,
+        end: impl IntoVoxel<T>,
+        bounds: VoxelBounds<T>,
+    ) -> Self {
+        let (start, end) = (start.into_voxel(), end.into_voxel());
+
+        let mut line = Self::new(start, end);
+        let (min, max) = (widen(bounds.min), widen(bounds.max));
+
+        // The voxels are visited in order along each axis, so the ones inside the bounds are
+        // always a single range of steps
+        let x = steps_inside(
+            line.sign_x,
+            min.0,
+            max.0,
+            line.longest,
+            |step| (line.position(step).0).0,
+        );
+        let y = steps_inside(
+            line.sign_y,
+            min.1,
+            max.1,
+            line.longest,
+            |step| (line.position(step).0).1,
+        );
+        let z = steps_inside(
+            line.sign_z,
+            min.2,
+            max.2,
+            line.longest,
+            |step| (line.position(step).0).2,
+        );
+
+        let first = x.0.max(y.0).max(z.0);
+        let last = x.1.min(y.1).min(z.1);
+
+        if first <= last && bounds.contains(narrow(line.position(first).0)) {
+            let (voxel, (err_x, err_y, err_z)) = line.position(first);
+
+            line.voxel = voxel;
+            line.err_x = err_x;
+            line.err_y = err_y;
+            line.err_z = err_z;
+            line.count = last - first;
+        } else {
+            line.count = -T::Wide::one();
+        }
+
+        line
+    }
+
+    /// Calculate the voxel and error values after taking a number of steps from the current voxel.
+    #[inline]
+    fn position(&self, step: T::Wide) -> (Voxel<T::Wide>, Voxel<T::Wide>) {
+        // The products can need more bits than even `T::Wide` has, so they're split into whole
+        // multiples of the longest axis and what's left over
+        let wide = |value: T::Wide| value.to_i128().unwrap();
+        let (steps, longest) = (wide(step), wide(self.longest));
+
+        let axis = |len: T::Wide, err: T::Wide| {
+            let (len, err) = (wide(len), wide(err));
+
+            // The error is never negative or more than the longest axis, so what's left over only
+            // adds one more step if it's still positive once the error is taken off
+            let moved = if steps > 0 && len > 0 {
+                let (whole, rest) = mul_div(steps as u128, len as u128, longest as u128);
+                whole as i128 + if rest as i128 > err { 1 } else { 0 }
+            } else {
+                0
+            };
+
+            // The error always fits, so wrapping around in between still gives the right answer
+            let err = err
+                .wrapping_sub(steps.wrapping_mul(len))
+                .wrapping_add(moved.wrapping_mul(longest));
+
+            (T::Wide::cast(moved), T::Wide::cast(err))
+        };
+
+        let (x, err_x) = axis(self.len_x, self.err_x);
+        let (y, err_y) = axis(self.len_y, self.err_y);
+        let (z, err_z) = axis(self.len_z, self.err_z);
+
+        (
+            (
+                self.voxel.0 + x * self.sign_x,
+                self.voxel.1 + y * self.sign_y,
+                self.voxel.2 + z * self.sign_z,
+            ),
+            (err_x, err_y, err_z),
+        )
+    }
+
+    /// Call a function with each voxel of a line from `start` to `end`, stopping early if it
+    /// returns [`ControlFlow::Break`], like [`LineRasterizer::walk`] does for the 2D lines.
+    ///
+    /// [`ControlFlow::Break`]: https://doc.rust-lang.org/core/ops/enum.ControlFlow.html
+    /// [`LineRasterizer::walk`]: trait.LineRasterizer.html#method.walk
+    #[inline]
+    pub fn walk<B, F>(
+        start: impl IntoVoxel<T>,
+        end: impl IntoVoxel<T>,
+        mut function: F,
+    ) -> ControlFlow<B>
+    where
+        F: FnMut(Voxel<T>) -> ControlFlow<B>,
+    {
+        let mut line = Self::new(start, end);
+
+        loop {
+            function(narrow(line.voxel))?;
+
+            if line.count == T::Wide::zero() {
+                return ControlFlow::Continue(());
+            }
+
+            line.count -= T::Wide::one();
+            line.step();
+        }
+    }
+
+    /// Move the errors along one step, and the voxel with them.
+    #[inline]
+    fn step(&mut self) {
+        self.err_x -= self.len_x;
+        self.err_y -= self.len_y;
+        self.err_z -= self.len_z;
+
+        if self.err_x < T::Wide::zero() {
+            self.err_x += self.longest;
+            self.voxel.0 += self.sign_x;
+        }
+
+        if self.err_y < T::Wide::zero() {
+            self.err_y += self.longest;
+            self.voxel.1 += self.sign_y;
+        }
+
+        if self.err_z < T::Wide::zero() {
+            self.err_z += self.longest;
+            self.voxel.2 += self.sign_z;
+        }
+    }
+
     #[inline]
     pub fn steps(self) -> Steps<Voxel<T>, Self> {
         Steps::new(self)
     }
+
+    /// Split the voxels into `(chunk, local)` coordinates for chunks of the given size, which has
+    /// to be positive on every axis.
+    #[inline]
+    pub fn chunked(self, size: Voxel<T>) -> Chunked<T, Self> {
+        Chunked::new(self, size)
+    }
 }
 
 impl<T: SignedNum> Iterator for Bresenham3d<T> {
@@ -79,36 +294,42 @@ impl<T: SignedNum> Iterator for Bresenham3d<T> {
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        if self.count >= T::zero() {
-            self.count -= T::one();
-            self.err_x -= self.len_x;
-            self.err_y -= self.len_y;
-            self.err_z -= self.len_z;
-
-            let voxel = self.voxel;
+        if self.count >= T::Wide::zero() {
+            self.count -= T::Wide::one();
 
-            if self.err_x < T::zero() {
-                self.err_x += self.longest;
-                self.voxel.0 += self.sign_x;
-            }
+            let voxel = narrow(self.voxel);
+            self.step();
 
-            if self.err_y < T::zero() {
-                self.err_y += self.longest;
-                self.voxel.1 += self.sign_y;
-            }
+            Some(voxel)
+        } else {
+            None
+        }
+    }
 
-            if self.err_z < T::zero() {
-                self.err_z += self.longest;
-                self.voxel.2 += self.sign_z;
-            }
+    /// Capped at `usize::MAX`, like [`Bresenham`](struct.Bresenham.html#method.size_hint).
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.count + T::Wide::one()).to_usize().unwrap_or(usize::MAX);
+        (remaining, Some(remaining))
+    }
 
-            Some(voxel)
+    #[inline]
+    fn last(self) -> Option<Self::Item> {
+        if self.count >= T::Wide::zero() {
+            Some(narrow(self.position(self.count).0))
         } else {
             None
         }
     }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.len()
+    }
 }
 
+impl<T: SignedNum> ExactSizeIterator for Bresenham3d<T> {}
+
 #[test]
 fn tests() {
     assert_eq!(
@@ -125,5 +346,50 @@ fn tests() {
 
     assert_eq!(Bresenham3d::new((0, 0, 0), (500, 678, 1000)).count(), 1001);
 
+    // Lines across most of the range of `i64` still jump straight to their ends
+    let (start, end) = ((i64::MIN, 0, i64::MAX), (i64::MAX, 3, i64::MIN + 1));
+    assert_eq!(Bresenham3d::new(start, end).last(), Some(end));
+    assert_eq!(Bresenham3d::new(start, end).len(), usize::MAX);
+    assert_eq!(Bresenham3d::try_new(start, end), Err(LineError::Overflow));
+    assert_eq!(Bresenham3d::new((0, 0, 0), (500, 678, 1000)).len(), 1001);
+
     assert_eq!(Bresenham3d::new((500, 678, 1000), (0, 0, 0)).count(), 1001);
+
+    // Clipping should give the same voxels as filtering the whole line
+    let bounds = VoxelBounds::new((-20, 3, -7), (31, 40, 12));
+
+    for &(start, end) in &[
+        ((-100, -50, -20), (100, 70, 30)),
+        ((100, 70, 30), (-100, -50, -20)),
+        ((25, 300, 0), (-3, -200, 5)),
+        ((0, 5, 0), (0, 5, 500)),
+        ((-50, 0, 0), (50, 0, 0)),
+        ((5, 5, 5), (5, 5, 5)),
+    ] {
+        let mut line = Bresenham3d::clipped(start, end, bounds);
+        assert_eq!(line.len(), line.by_ref().count());
+        assert_eq!(line.len(), 0);
+
+        assert_eq!(
+            Bresenham3d::clipped(start, end, bounds).collect::<Vec<_>>(),
+            Bresenham3d::new(start, end)
+                .filter(|&voxel| bounds.contains(voxel))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    // Small types work from one end of their range to the other, the same as wider ones
+    let wide = |voxel: Voxel<i8>| (i32::from(voxel.0), i32::from(voxel.1), i32::from(voxel.2));
+
+    for &(start, end) in &[
+        ((i8::MIN, i8::MIN, i8::MIN), (i8::MAX, i8::MAX, i8::MAX)),
+        ((i8::MAX, 0, -7), (i8::MIN, 90, i8::MAX)),
+    ] {
+        let voxels = Bresenham3d::new(wide(start), wide(end)).collect::<Vec<_>>();
+        let line = Bresenham3d::<i8>::new(start, end);
+
+        assert_eq!(line.len(), voxels.len());
+        assert_eq!(line.clone().last(), Some(end));
+        assert_eq!(line.map(wide).collect::<Vec<_>>(), voxels);
+    }
 }
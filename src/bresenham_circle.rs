@@ -1,4 +1,8 @@
-use {Point, SignedNum};
+use {LineError, Point, SignedNum};
+use steps::Steps;
+use error::check_radius;
+use bounds::partition_point;
+use num_traits::{CheckedAdd, CheckedMul, CheckedSub, ToPrimitive};
 
 /// An implementation of [Bresenham's circle algorithm].
 ///
@@ -22,7 +26,14 @@ use {Point, SignedNum};
 /// (1, 0), (0, 1), (-1, 0), (0, -1),
 /// ```
 ///
+/// Use [`contiguous`] to get the points in order around the circle instead, which is needed for
+/// anything that follows the outline, like [`Dashed`].
+///
 /// [Bresenham's circle algorithm]: http://members.chello.at/~easyfilter/bresenham.html
+/// [`contiguous`]: #method.contiguous
+/// [`Dashed`]: struct.Dashed.html
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct BresenhamCircle<T> {
     x: T,
     y: T,
@@ -31,6 +42,11 @@ pub struct BresenhamCircle<T> {
     radius: T,
     error: T,
     quadrant: u8,
+    contiguous: bool,
+    // Set if the first point is returned again at the end, to close the outline
+    closed: bool,
+    // The number of points returned so far, not counting the one that closes the outline
+    taken: usize,
 }
 
 impl<T: SignedNum> BresenhamCircle<T> {
@@ -44,6 +60,126 @@ impl<T: SignedNum> BresenhamCircle<T> {
             y: T::zero(),
             error: T::cast(2) - T::cast(2) * radius,
             quadrant: 1,
+            contiguous: false,
+            closed: false,
+            taken: 0,
+        }
+    }
+
+    /// Create a circle like [`new`], but return an error if the radius is negative, or if the
+    /// circle is too large for `T`.
+    ///
+    /// [`new`]: #method.new
+    #[inline]
+    pub fn try_new(center_x: T, center_y: T, radius: T) -> Result<Self, LineError>
+    where
+        T: CheckedAdd + CheckedMul + CheckedSub,
+    {
+        check_radius(center_x, radius)?;
+        check_radius(center_y, radius)?;
+
+        Ok(Self::new(center_x, center_y, radius))
+    }
+
+    /// Return the points in order around the circle, so that each one is next to the last.
+    ///
+    /// This goes through each quadrant in turn rather than switching between them, starting from
+    /// the point to the right of the center and ending next to it.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// extern crate line_drawing;
+    /// use line_drawing::BresenhamCircle;
+    ///
+    /// fn main() {
+    ///     for (x, y) in BresenhamCircle::contiguous(0, 0, 2) {
+    ///         print!("({}, {}), ", x, y);
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// ```text
+    /// (2, 0), (2, 1), (1, 2), (0, 2), (-1, 2), (-2, 1), (-2, 0), (-2, -1), (-1, -2), (0, -2), (1, -2), (2, -1),
+    /// ```
+    #[inline]
+    pub fn contiguous(center_x: T, center_y: T, radius: T) -> Self {
+        Self {
+            contiguous: true,
+            ..Self::new(center_x, center_y, radius)
+        }
+    }
+
+    /// Return `(start, end)` tuples for each step around a circle, in order like [`contiguous`].
+    ///
+    /// The last step goes back to the first point, so the steps make a closed outline, like for
+    /// the edges of a collision shape. This takes the circle's parameters rather than a circle,
+    /// since one that has already been partly walked can't be put in order.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// extern crate line_drawing;
+    /// use line_drawing::BresenhamCircle;
+    ///
+    /// fn main() {
+    ///     for (start, end) in BresenhamCircle::steps(0, 0, 1) {
+    ///         print!("{:?} -> {:?}, ", start, end);
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// ```text
+    /// (1, 0) -> (0, 1), (0, 1) -> (-1, 0), (-1, 0) -> (0, -1), (0, -1) -> (1, 0),
+    /// ```
+    ///
+    /// [`contiguous`]: #method.contiguous
+    #[inline]
+    pub fn steps(center_x: T, center_y: T, radius: T) -> Steps<Point<T>, Self> {
+        Steps::new(Self {
+            closed: radius > T::zero(),
+            ..Self::contiguous(center_x, center_y, radius)
+        })
+    }
+
+    /// Count the points in each quadrant without walking through them, or return `None` if there
+    /// are too many to count in a `usize`.
+    ///
+    /// Going up from the x axis, the points below the diagonal are one to a row, at the nearest
+    /// whole `x` to `sqrt(r^2 - y^2)`. That's further out than `y` while `y * (2 * y + 1) < r^2`,
+    /// which gives the number of rows below the diagonal. The points above it mirror the ones
+    /// below, apart from the one on the x axis. There's one more on the diagonal if the last row
+    /// below it is two columns out, which is when `rows * (2 * rows - 1) + 1 < r^2`.
+    #[inline]
+    fn quadrant_length(&self) -> Option<usize> {
+        let radius = match self.radius.to_i128() {
+            Some(radius) if radius > 0 => radius,
+            _ => return Some(0),
+        };
+
+        // A radius this big has more points than a `usize` can count anyway
+        let square = radius.checked_mul(radius)?;
+
+        let rows = partition_point(0, radius, |y: i128| {
+            y.checked_mul(2 * y + 1).is_some_and(|area| area < square)
+        });
+        let diagonal = rows * (2 * rows - 1) + 1 < square;
+
+        (2 * rows - 1 + diagonal as i128).to_usize()
+    }
+
+    #[inline]
+    fn advance(&mut self) {
+        let radius = self.error;
+
+        if radius <= self.y {
+            self.y += T::one();
+            self.error += self.y * T::cast(2) + T::one();
+        }
+
+        if radius > self.x || self.error > self.y {
+            self.x += T::one();
+            self.error += self.x * T::cast(2) + T::one();
         }
     }
 }
@@ -53,6 +189,14 @@ impl<T: SignedNum> Iterator for BresenhamCircle<T> {
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
+        // Start the next quadrant from the beginning once the last one is done
+        if self.contiguous && self.x >= T::zero() && self.quadrant < 4 {
+            self.x = -self.radius;
+            self.y = T::zero();
+            self.error = T::cast(2) - T::cast(2) * self.radius;
+            self.quadrant += 1;
+        }
+
         if self.x < T::zero() {
             let point = match self.quadrant {
                 1 => (self.center_x - self.x, self.center_y + self.y),
@@ -62,26 +206,99 @@ impl<T: SignedNum> Iterator for BresenhamCircle<T> {
                 _ => unreachable!(),
             };
 
-            // Update the variables after each set of quadrants
-            if self.quadrant == 4 {
-                self.radius = self.error;
-
-                if self.radius <= self.y {
-                    self.y += T::one();
-                    self.error += self.y * T::cast(2) + T::one();
+            if self.contiguous {
+                self.advance();
+            } else {
+                // Update the variables after each set of quadrants
+                if self.quadrant == 4 {
+                    self.advance();
                 }
 
-                if self.radius > self.x || self.error > self.y {
-                    self.x += T::one();
-                    self.error += self.x * T::cast(2) + T::one();
-                }
+                self.quadrant = self.quadrant % 4 + 1;
             }
 
-            self.quadrant = self.quadrant % 4 + 1;
+            self.taken += 1;
 
             Some(point)
+        } else if self.closed {
+            self.closed = false;
+
+            Some((self.center_x + self.radius, self.center_y))
         } else {
             None
         }
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self
+            .quadrant_length()
+            .and_then(|length| length.checked_mul(4))
+            .map_or(usize::MAX, |points| points - self.taken + self.closed as usize);
+
+        (remaining, Some(remaining))
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.len()
+    }
+}
+
+impl<T: SignedNum> ExactSizeIterator for BresenhamCircle<T> {}
+
+#[test]
+fn tests() {
+    for radius in 0..20 {
+        for &contiguous in &[false, true] {
+            let mut circle = if contiguous {
+                BresenhamCircle::contiguous(3, -2, radius)
+            } else {
+                BresenhamCircle::new(3, -2, radius)
+            };
+
+            while circle.len() > 0 {
+                let length = circle.len();
+                assert!(circle.next().is_some());
+                assert_eq!(circle.len(), length - 1);
+            }
+
+            assert_eq!(circle.next(), None);
+        }
+    }
+}
+
+#[test]
+fn lengths() {
+    // Counting the points gives the same as walking through them, for every radius
+    for radius in -2..2000 {
+        let circle = BresenhamCircle::new(0, 0, radius);
+        assert_eq!(circle.len(), circle.fold(0, |count, _| count + 1));
+    }
+
+    assert_eq!(BresenhamCircle::new(0i64, 0, 1 << 30).len(), 6_074_001_000);
+    assert_eq!(BresenhamCircle::new(0i128, 0, 1 << 62).len(), usize::MAX);
+    assert_eq!(BresenhamCircle::new(0i128, 0, 1 << 100).len(), usize::MAX);
+}
+
+#[test]
+fn steps() {
+    // Every step is to a neighbouring point, with one for each point on the circle
+    for radius in 1..20i32 {
+        let steps = BresenhamCircle::steps(-4, 7, radius);
+        assert_eq!(steps.len(), BresenhamCircle::new(-4, 7, radius).len());
+
+        let steps = steps.collect::<Vec<_>>();
+        let points = BresenhamCircle::contiguous(-4, 7, radius).collect::<Vec<_>>();
+
+        for (&(start, end), &point) in steps.iter().zip(&points) {
+            assert_eq!(start, point);
+            assert!((start.0 - end.0).abs() <= 1 && (start.1 - end.1).abs() <= 1);
+        }
+
+        // The outline is closed, going back to where it started
+        assert_eq!(steps[steps.len() - 1], (points[points.len() - 1], points[0]));
+    }
+
+    assert_eq!(BresenhamCircle::steps(0, 0, 0).count(), 0);
 }
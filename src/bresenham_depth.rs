@@ -0,0 +1,200 @@
+use {Bresenham, FloatNum, IntoPoint, Point, SignedNum};
+use adapters::perspective_correct;
+
+/// A [`Bresenham`] line with a depth at each end, which returns the depth of each point along with
+/// it, for drawing lines into a z-buffer.
+///
+/// The depth is worked out from how many points have been returned out of the total, so it stays
+/// in step with the line, and the first and last points get exactly the depths they were given.
+/// Depths that have already been divided by `w`, like the `z` of normalized device coordinates,
+/// can be blended evenly across the screen, but others need [`perspective`].
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::BresenhamDepth;
+///
+/// fn main() {
+///     for ((x, y), z) in BresenhamDepth::new((0, 0), (4, 2), 1.0, 0.0) {
+///         print!("(({}, {}), {}), ", x, y, z);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// ((0, 0), 1), ((1, 0), 0.75), ((2, 1), 0.5), ((3, 1), 0.25), ((4, 2), 0),
+/// ```
+///
+/// [`Bresenham`]: struct.Bresenham.html
+/// [`perspective`]: #method.perspective
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "T: ::serde::Serialize, T::Wide: ::serde::Serialize, I: ::serde::Serialize",
+        deserialize = "T: ::serde::Deserialize<'de>, \
+            T::Wide: ::serde::Deserialize<'de>, I: ::serde::Deserialize<'de>"
+    ))
+)]
+pub struct BresenhamDepth<T: SignedNum, I> {
+    line: Bresenham<T>,
+    start_depth: I,
+    end_depth: I,
+    index: usize,
+    last: usize,
+    inverse_w: Option<Point<I>>,
+}
+
+impl<T: SignedNum, I: FloatNum> BresenhamDepth<T, I> {
+    #[inline]
+    pub fn new(
+        start: impl IntoPoint<T>,
+        end: impl IntoPoint<T>,
+        start_depth: I,
+        end_depth: I,
+    ) -> Self {
+        let line = Bresenham::new(start, end);
+
+        Self {
+            last: line.len() - 1,
+            line,
+            start_depth,
+            end_depth,
+            index: 0,
+            inverse_w: None,
+        }
+    }
+
+    /// Create a line that blends the depth with perspective correction, given the `w` that each
+    /// end had before it was divided by to project it onto the screen.
+    ///
+    /// This is for depths that are still linear in 3D, like the distance from the camera, which
+    /// would otherwise be warped on long lines that go into the distance. The `w`s need to be more
+    /// than `0`. See [`Interpolate::perspective`] for details.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// extern crate line_drawing;
+    /// use line_drawing::BresenhamDepth;
+    ///
+    /// fn main() {
+    ///     // With a standard projection, w is the distance from the camera
+    ///     for ((x, y), z) in BresenhamDepth::perspective((0, 0), (4, 2), 1.0, 3.0, 1.0, 3.0) {
+    ///         print!("(({}, {}), {:.3}), ", x, y, z);
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// ```text
+    /// ((0, 0), 1.000), ((1, 0), 1.200), ((2, 1), 1.500), ((3, 1), 2.000), ((4, 2), 3.000),
+    /// ```
+    ///
+    /// [`Interpolate::perspective`]: struct.Interpolate.html#method.perspective
+    #[inline]
+    pub fn perspective(
+        start: impl IntoPoint<T>,
+        end: impl IntoPoint<T>,
+        start_depth: I,
+        end_depth: I,
+        start_w: I,
+        end_w: I,
+    ) -> Self {
+        Self {
+            inverse_w: Some((start_w.recip(), end_w.recip())),
+            ..Self::new(start, end, start_depth, end_depth)
+        }
+    }
+
+    /// Get the depth at a fraction of the way along the line.
+    #[inline]
+    fn depth(&self, t: I) -> I {
+        self.start_depth * (I::one() - t) + self.end_depth * t
+    }
+}
+
+impl<T: SignedNum, I: FloatNum> Iterator for BresenhamDepth<T, I> {
+    type Item = (Point<T>, I);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let point = self.line.next()?;
+
+        let mut t = if self.last == 0 {
+            I::zero()
+        } else {
+            I::cast(self.index) / I::cast(self.last)
+        };
+
+        if let Some(inverse_w) = self.inverse_w {
+            t = perspective_correct(t, inverse_w);
+        }
+
+        self.index += 1;
+
+        Some((point, self.depth(t)))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.line.size_hint()
+    }
+}
+
+impl<T: SignedNum, I: FloatNum> ExactSizeIterator for BresenhamDepth<T, I> {}
+
+#[test]
+fn tests() {
+    let depths = |start, end, start_depth, end_depth| {
+        BresenhamDepth::<i32, f64>::new(start, end, start_depth, end_depth).collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        depths((0, 0), (4, 1), 0.0, 1.0),
+        [
+            ((0, 0), 0.0),
+            ((1, 0), 0.25),
+            ((2, 0), 0.5),
+            ((3, 0), 0.75),
+            ((4, 1), 1.0)
+        ]
+    );
+
+    // The points are the same as Bresenham's, in every direction
+    for &(start, end) in &[((0, 0), (-3, 7)), ((5, 2), (-6, -1)), ((1, 1), (1, -4))] {
+        let line = depths(start, end, 2.0, -2.0);
+
+        assert_eq!(
+            line.iter().map(|&(point, _)| point).collect::<Vec<_>>(),
+            Bresenham::new(start, end).collect::<Vec<_>>()
+        );
+        assert_eq!((line[0].1, line[line.len() - 1].1), (2.0, -2.0));
+        assert!(line.windows(2).all(|pair| pair[0].1 > pair[1].1));
+    }
+
+    // The ends are exact, even when the steps aren't
+    let line = depths((0, 0), (0, 6), 0.1, 0.7);
+    assert_eq!((line[0].1, line[6].1), (0.1, 0.7));
+
+    assert_eq!(depths((3, 3), (3, 3), 0.5, 9.0), [((3, 3), 0.5)]);
+    assert_eq!(BresenhamDepth::new((0, 0), (9, 4), 0.0f32, 1.0).len(), 10);
+
+    // With perspective, the depth is the reciprocal of the evenly blended 1 / w
+    let line = BresenhamDepth::<i32, f64>::perspective((0, 0), (-8, 3), 2.0, 10.0, 2.0, 10.0);
+    assert_eq!(line.len(), 9);
+
+    for (index, ((x, _), depth)) in line.enumerate() {
+        assert_eq!(x, -(index as i32));
+
+        let t = index as f64 / 8.0;
+        let inverse_w = (1.0 - t) / 2.0 + t / 10.0;
+        assert!((depth - inverse_w.recip()).abs() < 1e-9);
+    }
+
+    assert_eq!(
+        BresenhamDepth::perspective((0, 0), (6, 1), 0.5, 1.5, 4.0, 4.0).collect::<Vec<_>>(),
+        depths((0, 0), (6, 1), 0.5, 1.5)
+    );
+}
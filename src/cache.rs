@@ -0,0 +1,287 @@
+use {BresenhamCircle, Point, SignedNum};
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// A cache of line shapes, for drawing the same lines over and over again.
+///
+/// The integer line algorithms only depend on the difference between the start and end points, so
+/// the offsets of a line from its start can be stored and reused for every line with the same
+/// delta. This turns drawing a line that has been seen before into going through a slice, which
+/// helps when the same rays are cast every turn for a field of view, or a brush draws the same
+/// strokes repeatedly.
+///
+/// The cache is filled using the function it's created with, which takes the start and end points
+/// of a line. It's only correct for algorithms that don't depend on where the line is, which is
+/// true of all the integer ones in this crate.
+///
+/// This needs the `alloc` feature.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{Bresenham, LineCache};
+///
+/// fn main() {
+///     let mut cache = LineCache::new(Bresenham::new);
+///
+///     for (x, y) in cache.line((10, 10), (13, 11)) {
+///         print!("({}, {}), ", x, y);
+///     }
+///
+///     // Uses the offsets that were stored for the first line
+///     assert_eq!(cache.line((-5, 0), (-2, 1)).count(), 4);
+/// }
+/// ```
+///
+/// ```text
+/// (10, 10), (11, 10), (12, 10), (13, 11),
+/// ```
+#[derive(Clone)]
+pub struct LineCache<T, F> {
+    lines: BTreeMap<Point<T>, Vec<Point<T>>>,
+    draw: F,
+}
+
+// The line function is usually a closure, which can't be printed
+impl<T: fmt::Debug, F> fmt::Debug for LineCache<T, F> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("LineCache")
+            .field("lines", &self.lines)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, F, L> LineCache<T, F>
+where
+    T: SignedNum,
+    F: FnMut(Point<T>, Point<T>) -> L,
+    L: IntoIterator<Item = Point<T>>,
+{
+    #[inline]
+    pub fn new(draw: F) -> Self {
+        Self {
+            lines: BTreeMap::new(),
+            draw,
+        }
+    }
+
+    /// Get the offsets of a line from `(0, 0)` to `delta`, drawing it if it isn't cached yet.
+    #[inline]
+    pub fn offsets(&mut self, delta: Point<T>) -> &[Point<T>] {
+        let draw = &mut self.draw;
+
+        self.lines
+            .entry(delta)
+            .or_insert_with(|| draw((T::zero(), T::zero()), delta).into_iter().collect())
+    }
+
+    /// Get a line between two points from the cache.
+    #[inline]
+    pub fn line(&mut self, start: Point<T>, end: Point<T>) -> CachedLine<'_, T> {
+        let offsets = self.offsets((end.0 - start.0, end.1 - start.1));
+
+        CachedLine { start, offsets }
+    }
+
+    /// Remove all of the cached lines.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.lines.clear();
+    }
+}
+
+/// A line from a [`LineCache`].
+///
+/// [`LineCache`]: struct.LineCache.html
+#[derive(Clone, Debug, PartialEq)]
+pub struct CachedLine<'a, T: 'a> {
+    start: Point<T>,
+    offsets: &'a [Point<T>],
+}
+
+impl<'a, T: SignedNum> Iterator for CachedLine<'a, T> {
+    type Item = Point<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (&(x, y), offsets) = self.offsets.split_first()?;
+        self.offsets = offsets;
+
+        Some((self.start.0 + x, self.start.1 + y))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.offsets.len(), Some(self.offsets.len()))
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.len()
+    }
+}
+
+impl<'a, T: SignedNum> ExactSizeIterator for CachedLine<'a, T> {}
+
+/// A precomputed table of rays from the origin out to the edge of a circle.
+///
+/// This is the table used for table-driven field of view: the rays are drawn once at startup, and
+/// checking what's visible from a point is just a matter of going along each ray until something
+/// blocks it. The rays go to every cell of a [`BresenhamCircle`], drawn with whichever line
+/// function the table is created with, and are stored one after another in a single `Vec`.
+///
+/// This needs the `alloc` feature.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{RayTable, Supercover};
+///
+/// fn main() {
+///     let table = RayTable::new(2, Supercover::new);
+///
+///     for ray in table.rays().take(2) {
+///         println!("{:?}", ray);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// [(0, 0), (1, 0), (2, 0)]
+/// [(0, 0), (0, 1), (0, 2)]
+/// ```
+///
+/// [`BresenhamCircle`]: struct.BresenhamCircle.html
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RayTable<T> {
+    points: Vec<Point<T>>,
+    ends: Vec<usize>,
+}
+
+impl<T: SignedNum> RayTable<T> {
+    #[inline]
+    pub fn new<F, L>(radius: T, mut draw: F) -> Self
+    where
+        F: FnMut(Point<T>, Point<T>) -> L,
+        L: IntoIterator<Item = Point<T>>,
+    {
+        let mut points = Vec::new();
+        let mut ends = Vec::new();
+
+        for end in BresenhamCircle::new(T::zero(), T::zero(), radius) {
+            points.extend(draw((T::zero(), T::zero()), end));
+            ends.push(points.len());
+        }
+
+        Self { points, ends }
+    }
+
+    /// The number of rays in the table.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.ends.len()
+    }
+
+    /// Check if there are no rays, which is the case for a radius of zero.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.ends.is_empty()
+    }
+
+    /// Get the points of a ray, as offsets from the origin.
+    #[inline]
+    pub fn ray(&self, index: usize) -> &[Point<T>] {
+        let start = if index == 0 { 0 } else { self.ends[index - 1] };
+
+        &self.points[start..self.ends[index]]
+    }
+
+    /// Go through all of the rays in order.
+    #[inline]
+    pub fn rays(&self) -> impl Iterator<Item = &[Point<T>]> {
+        (0..self.len()).map(move |index| self.ray(index))
+    }
+
+    /// Find the cells that can be seen from `center` by following each ray until it's blocked.
+    ///
+    /// `visible` is called for the center and then for every cell along each ray up to and
+    /// including the first `opaque` one. Cells that are shared by several rays are passed to
+    /// `visible` once for each of them.
+    #[inline]
+    pub fn cast<O, V>(&self, center: Point<T>, mut opaque: O, mut visible: V)
+    where
+        O: FnMut(Point<T>) -> bool,
+        V: FnMut(Point<T>),
+    {
+        visible(center);
+
+        for ray in self.rays() {
+            for &(x, y) in ray.iter().skip(1) {
+                let point = (center.0 + x, center.1 + y);
+                visible(point);
+
+                if opaque(point) {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn tests() {
+    use {Bresenham, Supercover};
+
+    let mut calls = 0;
+    let mut cache = LineCache::new(|start, end| {
+        calls += 1;
+        Supercover::new(start, end)
+    });
+
+    for &(start, end) in &[
+        ((0, 0), (5, 3)),
+        ((10, -4), (15, -1)),
+        ((3, 3), (-4, 7)),
+        ((-7, 1), (-7, 1)),
+        ((100, 100), (95, 104)),
+    ] {
+        assert_eq!(
+            cache.line(start, end).collect::<Vec<_>>(),
+            Supercover::new(start, end).collect::<Vec<_>>()
+        );
+    }
+
+    assert_eq!(
+        cache.offsets((5, 3)).len(),
+        Supercover::new((0, 0), (5, 3)).count()
+    );
+    cache.clear();
+    drop(cache);
+    assert_eq!(calls, 4);
+
+    let mut cache = LineCache::new(Bresenham::new);
+    assert_eq!(cache.line((2, 2), (4, 3)).len(), 3);
+
+    let table = RayTable::new(6, Supercover::new);
+    let circle = BresenhamCircle::new(0, 0, 6).collect::<Vec<_>>();
+
+    assert_eq!(table.len(), circle.len());
+    for (ray, &end) in table.rays().zip(&circle) {
+        assert_eq!(ray, &Supercover::new((0, 0), end).collect::<Vec<_>>()[..]);
+    }
+
+    assert!(RayTable::new(0, Bresenham::new).is_empty());
+
+    // Walls stop the rays
+    let mut seen = Vec::new();
+    RayTable::new(3, Bresenham::new).cast((10, 10), |(x, _)| x == 11, |point| seen.push(point));
+    assert!(seen.contains(&(10, 10)));
+    assert!(seen.contains(&(11, 10)));
+    assert!(!seen.contains(&(12, 10)));
+    assert!(seen.contains(&(10, 13)));
+}
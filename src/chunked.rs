@@ -0,0 +1,170 @@
+use {div_floor, SignedNum, Voxel};
+
+/// Keep a local coordinate within `0..size`, moving the chunk coordinate along with it.
+#[inline]
+fn wrap<T: SignedNum>(chunk: &mut T, local: &mut T, size: T) {
+    if *local < T::zero() || *local >= size {
+        let (chunks, remainder) = div_floor(*local, size);
+        *chunk += chunks;
+        *local = remainder;
+    }
+}
+
+/// An iterator that splits voxels into `(chunk, local)` coordinate pairs.
+///
+/// The chunk coordinates are tracked incrementally as the walk progresses, so the usual division
+/// and modulo is only done for the first voxel and the ones that move into another chunk. Negative
+/// coordinates are rounded towards negative infinity, so that the local coordinates are always
+/// within `0..size`.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::Bresenham3d;
+///
+/// fn main() {
+///     for (chunk, local) in Bresenham3d::new((-2, 0, 0), (2, 0, 0)).chunked((16, 16, 16)) {
+///         print!("({:?}, {:?}), ", chunk, local);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// ((-1, 0, 0), (14, 0, 0)), ((-1, 0, 0), (15, 0, 0)), ((0, 0, 0), (0, 0, 0)), ((0, 0, 0), (1, 0, 0)), ((0, 0, 0), (2, 0, 0)),
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Chunked<T, I> {
+    iterator: I,
+    size: Voxel<T>,
+    prev: Option<(Voxel<T>, Voxel<T>, Voxel<T>)>,
+}
+
+impl<T: SignedNum, I: Iterator<Item = Voxel<T>>> Chunked<T, I> {
+    /// Split the voxels of `iterator` into chunks of the given size.
+    ///
+    /// # Panics
+    ///
+    /// If any of the sizes isn't positive.
+    #[inline]
+    pub fn new(iterator: I, size: Voxel<T>) -> Self {
+        assert!(
+            size.0 > T::zero() && size.1 > T::zero() && size.2 > T::zero(),
+            "chunk sizes must be positive"
+        );
+
+        Self {
+            iterator,
+            size,
+            prev: None,
+        }
+    }
+}
+
+impl<T: SignedNum, I: Iterator<Item = Voxel<T>>> Iterator for Chunked<T, I> {
+    type Item = (Voxel<T>, Voxel<T>);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let voxel = self.iterator.next()?;
+
+        let (chunk, local) = match self.prev {
+            Some((prev, mut chunk, mut local)) => {
+                local.0 += voxel.0 - prev.0;
+                local.1 += voxel.1 - prev.1;
+                local.2 += voxel.2 - prev.2;
+
+                wrap(&mut chunk.0, &mut local.0, self.size.0);
+                wrap(&mut chunk.1, &mut local.1, self.size.1);
+                wrap(&mut chunk.2, &mut local.2, self.size.2);
+
+                (chunk, local)
+            }
+            None => {
+                let x = div_floor(voxel.0, self.size.0);
+                let y = div_floor(voxel.1, self.size.1);
+                let z = div_floor(voxel.2, self.size.2);
+
+                ((x.0, y.0, z.0), (x.1, y.1, z.1))
+            }
+        };
+
+        self.prev = Some((voxel, chunk, local));
+
+        Some((chunk, local))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iterator.size_hint()
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.iterator.count()
+    }
+}
+
+impl<T: SignedNum, I: ExactSizeIterator<Item = Voxel<T>>> ExactSizeIterator for Chunked<T, I> {}
+
+#[test]
+fn tests() {
+    use {Bresenham3d, VoxelOrigin, WalkVoxels};
+
+    let chunked = |a, b| {
+        Bresenham3d::new(a, b)
+            .chunked((4, 4, 4))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        chunked((2, -1, 7), (5, -1, 7)),
+        [
+            ((0, -1, 1), (2, 3, 3)),
+            ((0, -1, 1), (3, 3, 3)),
+            ((1, -1, 1), (0, 3, 3)),
+            ((1, -1, 1), (1, 3, 3))
+        ]
+    );
+
+    // The chunks should always match up with a division for every voxel
+    let a = (-13.2, 5.7, 22.1);
+    let b = (17.9, -30.4, 3.3);
+
+    for (voxel, (chunk, local)) in WalkVoxels::<f32, i32>::new(a, b, VoxelOrigin::Center)
+        .zip(WalkVoxels::new(a, b, VoxelOrigin::Center).chunked((16, 8, 4)))
+    {
+        assert_eq!(
+            (
+                voxel.0.div_euclid(16),
+                voxel.1.div_euclid(8),
+                voxel.2.div_euclid(4)
+            ),
+            chunk
+        );
+        assert_eq!(
+            (
+                voxel.0.rem_euclid(16),
+                voxel.1.rem_euclid(8),
+                voxel.2.rem_euclid(4)
+            ),
+            local
+        );
+    }
+
+    // Voxels far apart land in the right chunks without stepping through the ones between
+    let voxels = vec![(0, 0, 0), (1_000_000_007, -1_000_000_007, 3)];
+    assert_eq!(
+        Chunked::new(voxels.into_iter(), (16, 8, 4)).last(),
+        Some(((62_500_000, -125_000_001, 0), (7, 1, 3)))
+    );
+}
+
+#[test]
+#[should_panic(expected = "chunk sizes must be positive")]
+fn empty_chunks() {
+    use Bresenham3d;
+
+    Bresenham3d::new((0, 0, 0), (5, 2, 1)).chunked((16, 0, 16)).next();
+}
@@ -0,0 +1,244 @@
+use {Point, Voxel};
+use core::marker::PhantomData;
+
+/// Something that can be used as a 2D point, like the end points of a line.
+///
+/// The constructors take anything that implements this, so points can be passed in as tuples,
+/// arrays, or any other type that it's implemented for. To use a vector type from another crate,
+/// implement it for that type:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{Bresenham, FromPoint, IntoPoint, Point};
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Vec2 {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// impl IntoPoint<i32> for Vec2 {
+///     fn into_point(self) -> Point<i32> {
+///         (self.x, self.y)
+///     }
+/// }
+///
+/// impl FromPoint<i32> for Vec2 {
+///     fn from_point((x, y): Point<i32>) -> Self {
+///         Vec2 { x, y }
+///     }
+/// }
+///
+/// fn main() {
+///     let line = Bresenham::new(Vec2 { x: 0, y: 0 }, Vec2 { x: 2, y: 1 });
+///     let last = line.map(Vec2::from_point).last();
+///
+///     assert_eq!(last, Some(Vec2 { x: 2, y: 1 }));
+/// }
+/// ```
+pub trait IntoPoint<T> {
+    /// Get the coordinates of the point.
+    fn into_point(self) -> Point<T>;
+}
+
+/// Something that can be made out of a 2D point, for turning the points of a line into another type.
+///
+/// See [`IntoPoint`] for an example, and [`Converted`] for converting all of the points of a line.
+///
+/// [`IntoPoint`]: trait.IntoPoint.html
+/// [`Converted`]: struct.Converted.html
+pub trait FromPoint<T> {
+    /// Make a value out of the coordinates of a point.
+    fn from_point(point: Point<T>) -> Self;
+}
+
+/// Something that can be used as a 3D point, like the end points of a line through voxels.
+///
+/// This is the 3D version of [`IntoPoint`].
+///
+/// [`IntoPoint`]: trait.IntoPoint.html
+pub trait IntoVoxel<T> {
+    /// Get the coordinates of the voxel.
+    fn into_voxel(self) -> Voxel<T>;
+}
+
+/// Something that can be made out of a 3D point.
+///
+/// This is the 3D version of [`FromPoint`].
+///
+/// [`FromPoint`]: trait.FromPoint.html
+pub trait FromVoxel<T> {
+    /// Make a value out of the coordinates of a voxel.
+    fn from_voxel(voxel: Voxel<T>) -> Self;
+}
+
+impl<T> IntoPoint<T> for Point<T> {
+    #[inline]
+    fn into_point(self) -> Point<T> {
+        self
+    }
+}
+
+impl<T> IntoPoint<T> for [T; 2] {
+    #[inline]
+    fn into_point(self) -> Point<T> {
+        let [x, y] = self;
+        (x, y)
+    }
+}
+
+impl<T> FromPoint<T> for Point<T> {
+    #[inline]
+    fn from_point(point: Point<T>) -> Self {
+        point
+    }
+}
+
+impl<T> FromPoint<T> for [T; 2] {
+    #[inline]
+    fn from_point((x, y): Point<T>) -> Self {
+        [x, y]
+    }
+}
+
+impl<T> IntoVoxel<T> for Voxel<T> {
+    #[inline]
+    fn into_voxel(self) -> Voxel<T> {
+        self
+    }
+}
+
+impl<T> IntoVoxel<T> for [T; 3] {
+    #[inline]
+    fn into_voxel(self) -> Voxel<T> {
+        let [x, y, z] = self;
+        (x, y, z)
+    }
+}
+
+impl<T> FromVoxel<T> for Voxel<T> {
+    #[inline]
+    fn from_voxel(voxel: Voxel<T>) -> Self {
+        voxel
+    }
+}
+
+impl<T> FromVoxel<T> for [T; 3] {
+    #[inline]
+    fn from_voxel((x, y, z): Voxel<T>) -> Self {
+        [x, y, z]
+    }
+}
+
+/// The points and voxels that a [`Converted`] iterator can turn into `P`.
+///
+/// This is implemented for 2D points when `P` implements [`FromPoint`], and for 3D ones when it
+/// implements [`FromVoxel`].
+///
+/// [`Converted`]: struct.Converted.html
+/// [`FromPoint`]: trait.FromPoint.html
+/// [`FromVoxel`]: trait.FromVoxel.html
+pub trait ConvertInto<P> {
+    /// Turn the point into `P`.
+    fn convert_into(self) -> P;
+}
+
+impl<T, P: FromPoint<T>> ConvertInto<P> for Point<T> {
+    #[inline]
+    fn convert_into(self) -> P {
+        P::from_point(self)
+    }
+}
+
+impl<T, P: FromVoxel<T>> ConvertInto<P> for Voxel<T> {
+    #[inline]
+    fn convert_into(self) -> P {
+        P::from_voxel(self)
+    }
+}
+
+/// An iterator that turns the points of another one into another type, with [`FromPoint`] or
+/// [`FromVoxel`].
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{Bresenham3d, Converted};
+///
+/// fn main() {
+///     let line = Bresenham3d::new([0, 0, 0], [2, 1, 0]);
+///
+///     for [x, y, z] in Converted::<[i32; 3], _>::new(line) {
+///         print!("[{}, {}, {}], ", x, y, z);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// [0, 0, 0], [1, 0, 0], [2, 1, 0],
+/// ```
+///
+/// [`FromPoint`]: trait.FromPoint.html
+/// [`FromVoxel`]: trait.FromVoxel.html
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Converted<P, I> {
+    iterator: I,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    point: PhantomData<P>,
+}
+
+impl<P, I: Iterator> Converted<P, I>
+where
+    I::Item: ConvertInto<P>,
+{
+    #[inline]
+    pub fn new(iterator: I) -> Self {
+        Self {
+            iterator,
+            point: PhantomData,
+        }
+    }
+}
+
+impl<P, I: Iterator> Iterator for Converted<P, I>
+where
+    I::Item: ConvertInto<P>,
+{
+    type Item = P;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iterator.next().map(ConvertInto::convert_into)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iterator.size_hint()
+    }
+}
+
+impl<P, I: ExactSizeIterator> ExactSizeIterator for Converted<P, I> where I::Item: ConvertInto<P> {}
+
+#[test]
+fn tests() {
+    use {Bresenham, Bresenham3d, GridBounds, WalkGrid};
+
+    assert_eq!(
+        Bresenham::new([0, 0], [3, 1]).collect::<Vec<_>>(),
+        Bresenham::new((0, 0), (3, 1)).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        WalkGrid::<i32>::from_float([0.5, 0.5], [2.5, 1.5]).collect::<Vec<_>>(),
+        WalkGrid::<i32>::from_float((0.5, 0.5), (2.5, 1.5)).collect::<Vec<_>>()
+    );
+    assert_eq!(GridBounds::new([0, 1], [4, 5]), GridBounds::new((0, 1), (4, 5)));
+
+    let points = Converted::<[i32; 2], _>::new(Bresenham::new((0, 0), (2, 1)));
+    assert_eq!(points.len(), 3);
+    assert_eq!(points.collect::<Vec<_>>(), [[0, 0], [1, 0], [2, 1]]);
+
+    let voxels = Converted::<[i32; 3], _>::new(Bresenham3d::new([0, 0, 0], [2, 1, 0]));
+    assert_eq!(voxels.collect::<Vec<_>>(), [[0, 0, 0], [1, 0, 0], [2, 1, 0]]);
+}
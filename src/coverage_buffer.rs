@@ -0,0 +1,418 @@
+use {IntoPoint, Point, RealNum};
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// How the overlapping parts of a shape are filled in by a [`CoverageBuffer`], the same as
+/// SVG's `fill-rule`.
+///
+/// [`CoverageBuffer`]: struct.CoverageBuffer.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum FillRule {
+    /// Fill anywhere that the edges go around at least once, whichever way they go.
+    NonZero,
+    /// Fill anywhere that the edges go around an odd number of times, leaving holes where they
+    /// overlap.
+    EvenOdd,
+}
+
+impl Default for FillRule {
+    #[inline]
+    fn default() -> Self {
+        FillRule::NonZero
+    }
+}
+
+impl FillRule {
+    /// Turn the number of times that a cell is gone around, which is fractional along the edges,
+    /// into how much of it is covered.
+    #[inline]
+    fn apply<I: RealNum>(self, winding: I) -> I {
+        let winding = winding.abs();
+
+        match self {
+            FillRule::NonZero => winding.min(I::one()),
+            FillRule::EvenOdd => {
+                let two = I::one() + I::one();
+                let winding = winding - (winding / two).floor() * two;
+
+                if winding > I::one() {
+                    two - winding
+                } else {
+                    winding
+                }
+            }
+        }
+    }
+}
+
+/// Fill polygons and paths with the exact area of each cell that they cover, for rendering text
+/// and vector shapes.
+///
+/// This works the same way as the smooth rasterizers in FreeType and stb_truetype. Each edge adds
+/// the signed area on either side of it to the cells that it goes through, and then the cells are
+/// added up from left to right along each row to get the coverage. The edges can be added in any
+/// order, and any number of shapes can be drawn into the same buffer before it's read.
+///
+/// Like the rest of the crate, the cell `(x, y)` is centered on the point `(x, y)`, so it covers
+/// from `x - 0.5` to `x + 0.5`. The cells go from `(0, 0)` up to but not including
+/// `(width, height)`, and anything outside of that is cut off. The shapes have to be closed, which
+/// [`polygon`] and [`Path::accumulate`] do on their own.
+///
+/// This needs the `alloc` feature.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{CoverageBuffer, FillRule};
+///
+/// fn main() {
+///     let mut buffer = CoverageBuffer::<f32>::new(4, 3);
+///     buffer.polygon(vec![(-0.5, -0.5), (3.5, -0.5), (-0.5, 2.5)]);
+///
+///     let mut rows = [[0.0; 4]; 3];
+///
+///     for ((x, y), coverage) in buffer.coverage(FillRule::NonZero) {
+///         rows[y][x] = coverage;
+///     }
+///
+///     for row in &rows {
+///         println!("{:.3?}", row);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// [1.000, 1.000, 0.958, 0.375]
+/// [1.000, 0.833, 0.167, 0.000]
+/// [0.625, 0.042, 0.000, 0.000]
+/// ```
+///
+/// [`polygon`]: #method.polygon
+/// [`Path::accumulate`]: struct.Path.html#method.accumulate
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CoverageBuffer<I> {
+    width: usize,
+    height: usize,
+    cells: Vec<I>,
+}
+
+impl<I: RealNum> CoverageBuffer<I> {
+    /// Create an empty buffer with `width * height` cells.
+    #[inline]
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![I::zero(); width * height],
+        }
+    }
+
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Take everything out of the buffer, to draw something else into it.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.cells.iter_mut().for_each(|cell| *cell = I::zero());
+    }
+
+    /// Add one edge of a shape, going from `start` to `end`.
+    ///
+    /// The edges that go up cancel out the ones that go down, so this is only useful for drawing
+    /// shapes a piece at a time. It's up to the caller to make sure that they end up closed.
+    pub fn line(&mut self, start: impl IntoPoint<I>, end: impl IntoPoint<I>) {
+        let half = I::one() / (I::one() + I::one());
+        let corner = |(x, y): Point<I>| (x + half, y + half);
+        let (start, end) = (corner(start.into_point()), corner(end.into_point()));
+
+        let (direction, top, bottom) = match start.1.partial_cmp(&end.1) {
+            Some(core::cmp::Ordering::Less) => (I::one(), start, end),
+            Some(core::cmp::Ordering::Greater) => (-I::one(), end, start),
+            _ => return,
+        };
+
+        let slope = (bottom.0 - top.0) / (bottom.1 - top.1);
+        let x_at = |y: I| top.0 + (y - top.1) * slope;
+
+        // Only the rows that are in the buffer are gone through, however long the edge is
+        let height = I::cast(self.height);
+        let first = top.1.max(I::zero()).floor().to_int::<isize>();
+        let last = bottom.1.min(height).ceil().to_int::<isize>();
+
+        for row in first..last {
+            let y = I::cast(row);
+            let (upper, lower) = (y.max(top.1), (y + I::one()).min(bottom.1));
+
+            self.clip(row as usize, (x_at(upper), upper), (x_at(lower), lower), direction);
+        }
+    }
+
+    /// Add a closed polygon, with an edge from the last point back to the first.
+    pub fn polygon<P: IntoPoint<I>>(&mut self, points: impl IntoIterator<Item = P>) {
+        let mut points = points.into_iter().map(IntoPoint::into_point);
+
+        let first = match points.next() {
+            Some(point) => point,
+            None => return,
+        };
+
+        let last = points.fold(first, |previous, point| {
+            self.line(previous, point);
+            point
+        });
+
+        self.line(last, first);
+    }
+
+    /// Add up the edges to get how much of each cell is covered, from `0` to `1`.
+    ///
+    /// This goes a row at a time from the top, leaving out the cells that aren't covered at all.
+    #[inline]
+    pub fn coverage(&self, rule: FillRule) -> BufferCoverage<'_, I> {
+        BufferCoverage {
+            buffer: self,
+            rule,
+            index: 0,
+            winding: I::zero(),
+        }
+    }
+
+    /// Split the part of an edge that's in one row where it goes off either side of the buffer.
+    ///
+    /// Everything to the left of the buffer covers the whole row from there on, so it's piled up
+    /// in the first cell, and everything to the right doesn't cover anything.
+    fn clip(&mut self, row: usize, upper: Point<I>, lower: Point<I>, direction: I) {
+        let sides = [I::zero(), I::cast(self.width)];
+
+        for &side in &sides {
+            if (upper.0 < side) != (lower.0 < side) && upper.0 != side && lower.0 != side {
+                let y = upper.1 + (side - upper.0) * (lower.1 - upper.1) / (lower.0 - upper.0);
+
+                self.clip(row, upper, (side, y), direction);
+                self.clip(row, (side, y), lower, direction);
+                return;
+            }
+        }
+
+        let area = (lower.1 - upper.1) * direction;
+
+        if upper.0.max(lower.0) <= sides[0] {
+            self.add(0, row, area);
+        } else if upper.0.min(lower.0) < sides[1] {
+            self.accumulate(row, upper.0, lower.0, area);
+        }
+    }
+
+    /// Spread the area of an edge that's in one row over the cells that it goes through.
+    fn accumulate(&mut self, row: usize, upper: I, lower: I, area: I) {
+        let half = I::one() / (I::one() + I::one());
+        let (left, right) = if upper < lower {
+            (upper, lower)
+        } else {
+            (lower, upper)
+        };
+
+        let (left_cell, right_cell) = (left.floor(), right.ceil());
+        let first = left_cell.to_int::<isize>() as usize;
+        let last = right_cell.to_int::<isize>() as usize;
+
+        if last <= first + 1 {
+            // The edge is all in one cell, so the part of it to the right of the middle of the
+            // edge is covered
+            let middle = (upper + lower) * half - left_cell;
+
+            self.add(first, row, area * (I::one() - middle));
+            self.add(first + 1, row, area * middle);
+            return;
+        }
+
+        // How much of the row's area each cell that the edge goes all the way across gets
+        let step = I::one() / (right - left);
+        let start = left - left_cell;
+        let first_area = half * step * (I::one() - start) * (I::one() - start);
+        let end = right - right_cell + I::one();
+        let last_area = half * step * end * end;
+
+        self.add(first, row, area * first_area);
+
+        if last == first + 2 {
+            self.add(first + 1, row, area * (I::one() - first_area - last_area));
+        } else {
+            let second_area = step * (I::one() + half - start);
+            self.add(first + 1, row, area * (second_area - first_area));
+
+            for x in first + 2..last - 1 {
+                self.add(x, row, area * step);
+            }
+
+            let before_last = second_area + I::cast(last - first - 3) * step;
+            self.add(last - 1, row, area * (I::one() - before_last - last_area));
+        }
+
+        self.add(last, row, area * last_area);
+    }
+
+    #[inline]
+    fn add(&mut self, x: usize, y: usize, area: I) {
+        if x < self.width {
+            self.cells[y * self.width + x] += area;
+        }
+    }
+}
+
+/// The coverage of the cells in a [`CoverageBuffer`], made with [`CoverageBuffer::coverage`].
+///
+/// [`CoverageBuffer`]: struct.CoverageBuffer.html
+/// [`CoverageBuffer::coverage`]: struct.CoverageBuffer.html#method.coverage
+#[derive(Clone, Debug)]
+pub struct BufferCoverage<'a, I: 'a> {
+    buffer: &'a CoverageBuffer<I>,
+    rule: FillRule,
+    index: usize,
+    winding: I,
+}
+
+impl<'a, I: RealNum> Iterator for BufferCoverage<'a, I> {
+    type Item = (Point<usize>, I);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let width = self.buffer.width;
+
+        while let Some(&area) = self.buffer.cells.get(self.index) {
+            let (x, y) = (self.index % width, self.index / width);
+            self.index += 1;
+
+            // Each row is added up on its own, so rounding errors don't carry over to the next
+            if x == 0 {
+                self.winding = I::zero();
+            }
+
+            self.winding += area;
+            let coverage = self.rule.apply(self.winding);
+
+            if !coverage.is_zero() {
+                return Some(((x, y), coverage));
+            }
+        }
+
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.buffer.cells.len() - self.index))
+    }
+}
+
+#[test]
+fn tests() {
+    use Path;
+
+    let covered = |buffer: &CoverageBuffer<f64>, rule| {
+        let mut cells = vec![0.0; buffer.width() * buffer.height()];
+
+        for ((x, y), coverage) in buffer.coverage(rule) {
+            cells[y * buffer.width() + x] = coverage;
+        }
+
+        cells
+    };
+    let close = |a: &[f64], b: &[f64]| a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-9);
+
+    // A square that lines up with the cells covers them fully, whichever way around it goes
+    let mut buffer = CoverageBuffer::new(4, 4);
+    buffer.polygon(vec![(0.5, 0.5), (2.5, 0.5), (2.5, 2.5), (0.5, 2.5)]);
+    let square = [
+        0.0, 0.0, 0.0, 0.0, //
+        0.0, 1.0, 1.0, 0.0, //
+        0.0, 1.0, 1.0, 0.0, //
+        0.0, 0.0, 0.0, 0.0, //
+    ];
+    assert!(close(&covered(&buffer, FillRule::NonZero), &square));
+    assert_eq!(buffer.coverage(FillRule::NonZero).count(), 4);
+
+    buffer.clear();
+    buffer.polygon(vec![(0.5, 2.5), (2.5, 2.5), (2.5, 0.5), (0.5, 0.5)]);
+    assert!(close(&covered(&buffer, FillRule::NonZero), &square));
+
+    // Moving it a quarter of a cell over covers a quarter of the cells on one side
+    buffer.clear();
+    buffer.polygon(vec![(0.75, 0.5), (2.75, 0.5), (2.75, 2.5), (0.75, 2.5)]);
+    assert!(close(
+        &covered(&buffer, FillRule::NonZero),
+        &[
+            0.0, 0.0, 0.0, 0.0, //
+            0.0, 0.75, 1.0, 0.25, //
+            0.0, 0.75, 1.0, 0.25, //
+            0.0, 0.0, 0.0, 0.0, //
+        ]
+    ));
+
+    // The coverage of a shape adds up to its area, even when the edges are at an angle
+    buffer.clear();
+    buffer.polygon(vec![(0.1, 0.2), (3.3, 1.1), (1.7, 2.9)]);
+    let area = covered(&buffer, FillRule::NonZero).iter().sum::<f64>();
+    assert!((area - (3.2 * 2.7 - 1.6 * 0.9) / 2.0).abs() < 1e-9, "{}", area);
+
+    // Shapes that are drawn over each other only count once with non-zero, and cut holes with
+    // even-odd
+    buffer.clear();
+    buffer.polygon(vec![(-0.5, -0.5), (3.5, -0.5), (3.5, 3.5), (-0.5, 3.5)]);
+    buffer.polygon(vec![(0.5, 0.5), (2.5, 0.5), (2.5, 2.5), (0.5, 2.5)]);
+    assert!(close(&covered(&buffer, FillRule::NonZero), &[1.0; 16]));
+    assert!(close(
+        &covered(&buffer, FillRule::EvenOdd),
+        &square.iter().map(|cell| 1.0 - cell).collect::<Vec<_>>()
+    ));
+
+    // Paths are closed if they aren't already, and filled the same as polygons
+    let mut path = CoverageBuffer::new(4, 4);
+    Path::new()
+        .move_to((0.1, 0.2))
+        .line_to((3.3, 1.1))
+        .line_to((1.7, 2.9))
+        .move_to((0.5, 2.5))
+        .line_to((0.5, 3.5))
+        .line_to((-0.5, 3.5))
+        .accumulate(&mut path);
+    buffer.clear();
+    buffer.polygon(vec![(0.1, 0.2), (3.3, 1.1), (1.7, 2.9)]);
+    buffer.polygon(vec![(0.5, 2.5), (0.5, 3.5), (-0.5, 3.5)]);
+    assert!(close(&covered(&path, FillRule::NonZero), &covered(&buffer, FillRule::NonZero)));
+
+    // Curves are split up closely enough for a circle to come out at about the right size, if a
+    // little smaller where the straight pieces cut across it
+    let mut circle = CoverageBuffer::new(16, 16);
+    Path::new()
+        .move_to((1.5, 7.5))
+        .arc_to((6.0, 6.0), 0.0, false, true, (13.5, 7.5))
+        .arc_to((6.0, 6.0), 0.0, false, true, (1.5, 7.5))
+        .accumulate(&mut circle);
+    let area = circle.coverage(FillRule::NonZero).map(|(_, coverage)| coverage).sum::<f64>();
+    assert!((105.0..36.0 * core::f64::consts::PI).contains(&area), "{}", area);
+
+    // Anything outside of the buffer is cut off, but still covers the rest of the row
+    buffer.clear();
+    buffer.polygon(vec![(-1e9, 0.5), (1.5, 0.5), (1.5, 1.5), (-1e9, 1.5)]);
+    buffer.polygon(vec![(2.5, -100.0), (1e9, -100.0), (1e9, 0.0), (2.5, 0.0)]);
+    assert!(close(
+        &covered(&buffer, FillRule::NonZero),
+        &[
+            0.0, 0.0, 0.0, 0.5, //
+            1.0, 1.0, 0.0, 0.0, //
+            0.0, 0.0, 0.0, 0.0, //
+            0.0, 0.0, 0.0, 0.0, //
+        ]
+    ));
+}
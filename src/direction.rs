@@ -0,0 +1,54 @@
+use {Point, SignedNum};
+
+/// One of the four orthogonal directions on a grid.
+///
+/// `x` increases to the east and `y` increases to the south, as in screen coordinates.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// The change in position from taking a step in this direction.
+    #[inline]
+    pub fn offset<T: SignedNum>(self) -> Point<T> {
+        match self {
+            Direction::North => (T::zero(), -T::one()),
+            Direction::East => (T::one(), T::zero()),
+            Direction::South => (T::zero(), T::one()),
+            Direction::West => (-T::one(), T::zero()),
+        }
+    }
+
+    /// The direction pointing the other way.
+    #[inline]
+    pub fn opposite(self) -> Self {
+        match self {
+            Direction::North => Direction::South,
+            Direction::East => Direction::West,
+            Direction::South => Direction::North,
+            Direction::West => Direction::East,
+        }
+    }
+}
+
+#[test]
+fn tests() {
+    for &direction in &[
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ] {
+        let (x, y): Point<i32> = direction.offset();
+        assert_eq!(direction.opposite().offset(), (-x, -y));
+        assert_eq!(direction.opposite().opposite(), direction);
+    }
+
+    assert_eq!(Direction::North.offset::<i32>(), (0, -1));
+    assert_eq!(Direction::East.offset::<i32>(), (1, 0));
+}
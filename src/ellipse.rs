@@ -0,0 +1,548 @@
+use {FloatNum, LineError, Point, SignedNum};
+use error::check_radius;
+use num_traits::{CheckedAdd, CheckedMul, CheckedSub};
+
+/// An implementation of the mid-point ellipse algorithm.
+///
+/// The points are returned in order around the ellipse, so that each one is next to the last,
+/// starting from the point to the right of the center. Where the outline is flat it takes one point
+/// per column, and where it's steep one point per row, each one being the closest to the real
+/// ellipse.
+///
+/// The tips of very thin ellipses can be a run of points along an axis, which is walked there and
+/// back again, so those points are returned twice. An ellipse with a radius of `0` on one axis is
+/// a line, which is walked the same way.
+///
+/// The working values grow as `4 * radius_x² * radius_y²`, so larger ellipses need a wider
+/// integer type than `i32`.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::MidpointEllipse;
+///
+/// fn main() {
+///     for (x, y) in MidpointEllipse::new(0, 0, 3, 1) {
+///         print!("({}, {}), ", x, y);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// (3, 0), (2, 1), (1, 1), (0, 1), (-1, 1), (-2, 1), (-3, 0), (-2, -1), (-1, -1), (0, -1), (1, -1), (2, -1),
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MidpointEllipse<T> {
+    center: Point<T>,
+    radius: Point<T>,
+    split: Point<T>,
+    point: Point<T>,
+    rows: bool,
+    quadrant: u8,
+    remaining: usize,
+}
+
+impl<T: SignedNum> MidpointEllipse<T> {
+    #[inline]
+    pub fn new(center_x: T, center_y: T, radius_x: T, radius_y: T) -> Self {
+        let mut ellipse = Self {
+            center: (center_x, center_y),
+            radius: (radius_x.abs(), radius_y.abs()),
+            split: (T::zero(), T::zero()),
+            point: (T::zero(), T::zero()),
+            rows: false,
+            quadrant: 5,
+            remaining: 0,
+        };
+
+        let (a, b) = ellipse.radius;
+
+        if a == T::zero() && b == T::zero() {
+            return ellipse;
+        }
+
+        // Take one point per column until the outline gets too steep for that to stay connected
+        let (mut x, mut y) = (T::zero(), b);
+
+        while x < a {
+            let mut next = y;
+
+            while next > T::zero() && !ellipse.fits_column(x + T::one(), next) {
+                next -= T::one();
+            }
+
+            if y - next > T::one() {
+                break;
+            }
+
+            x += T::one();
+            y = next;
+        }
+
+        ellipse.split = (x, y);
+
+        // Walk through one quadrant ahead of time to find out how many points there are
+        ellipse.start(1);
+        let mut length = 1;
+
+        while ellipse.forward() {
+            length += 1;
+        }
+
+        ellipse.start(1);
+        ellipse.remaining = length * 4;
+        ellipse
+    }
+
+    /// Create an ellipse like [`new`], but return an error if either radius is negative, or if
+    /// the ellipse is too large for `T`.
+    ///
+    /// [`new`]: #method.new
+    #[inline]
+    pub fn try_new(center_x: T, center_y: T, radius_x: T, radius_y: T) -> Result<Self, LineError>
+    where
+        T: CheckedAdd + CheckedMul + CheckedSub,
+    {
+        check_radius(center_x, radius_x)?;
+        check_radius(center_y, radius_y)?;
+
+        // Finding the outline multiplies the squares of both radii together
+        let square = |radius: T| radius.checked_mul(&radius).ok_or(LineError::Overflow);
+        let double = |radius: T| radius.checked_add(&radius).ok_or(LineError::Overflow);
+        square(radius_x)?
+            .checked_mul(&square(double(radius_y)?)?)
+            .ok_or(LineError::Overflow)?;
+        square(radius_y)?
+            .checked_mul(&square(double(radius_x)?)?)
+            .ok_or(LineError::Overflow)?;
+
+        Ok(Self::new(center_x, center_y, radius_x, radius_y))
+    }
+
+    /// Check if a point is at or inside the outline, going by the middle of its lower edge.
+    #[inline]
+    fn fits_column(&self, x: T, y: T) -> bool {
+        let (a, b) = self.radius;
+        let two = T::cast(2);
+
+        y == T::zero()
+            || a * a * (two * y - T::one()) * (two * y - T::one())
+                <= two * two * b * b * (a * a - x * x)
+    }
+
+    /// Check if a point is at or inside the outline, going by the middle of its left edge.
+    #[inline]
+    fn fits_row(&self, x: T, y: T) -> bool {
+        let (a, b) = self.radius;
+        let two = T::cast(2);
+
+        x == T::zero()
+            || b * b * (two * x - T::one()) * (two * x - T::one())
+                <= two * two * a * a * (b * b - y * y)
+    }
+
+    /// Go to the start of a quadrant.
+    ///
+    /// The odd quadrants go from the x axis to the y axis and the even ones go back again, each
+    /// leaving out the point where the next one starts.
+    #[inline]
+    fn start(&mut self, quadrant: u8) {
+        self.quadrant = quadrant;
+
+        if quadrant == 1 || quadrant == 3 {
+            self.rows = self.split.1 > T::zero();
+            self.point = if self.rows {
+                (self.radius.0, T::zero())
+            } else {
+                self.split
+            };
+        } else {
+            self.rows = false;
+            self.point = (T::zero(), self.radius.1);
+        }
+    }
+
+    /// Move towards the y axis, returning false at the end of the quadrant.
+    #[inline]
+    fn forward(&mut self) -> bool {
+        let (xs, r) = self.split;
+        let (mut x, mut y) = self.point;
+
+        if self.rows {
+            y += T::one();
+
+            if y < r {
+                while x > T::zero() && !self.fits_row(x, y) {
+                    x -= T::one();
+                }
+            } else {
+                self.rows = false;
+                x = xs;
+                y = r;
+            }
+        } else {
+            x -= T::one();
+
+            while y < self.radius.1 && self.fits_column(x, y + T::one()) {
+                y += T::one();
+            }
+        }
+
+        self.point = (x, y);
+
+        self.rows || x > T::zero()
+    }
+
+    /// Move towards the x axis, returning false at the end of the quadrant.
+    #[inline]
+    fn backward(&mut self) -> bool {
+        let (xs, r) = self.split;
+        let (mut x, mut y) = self.point;
+
+        if self.rows {
+            y -= T::one();
+        } else {
+            x += T::one();
+
+            if x < xs || (x == xs && r > T::zero()) {
+                while y > T::zero() && !self.fits_column(x, y) {
+                    y -= T::one();
+                }
+
+                self.point = (x, y);
+                return true;
+            }
+
+            self.rows = true;
+            x = xs;
+            y = r - T::one();
+        }
+
+        while x < self.radius.0 && self.fits_row(x + T::one(), y) {
+            x += T::one();
+        }
+
+        self.point = (x, y);
+
+        y > T::zero()
+    }
+}
+
+impl<T: SignedNum> Iterator for MidpointEllipse<T> {
+    type Item = Point<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (x, y) = self.point;
+        let (center_x, center_y) = self.center;
+
+        let point = match self.quadrant {
+            1 => (center_x + x, center_y + y),
+            2 => (center_x - x, center_y + y),
+            3 => (center_x - x, center_y - y),
+            4 => (center_x + x, center_y - y),
+            _ => return None,
+        };
+
+        let more = if self.quadrant == 1 || self.quadrant == 3 {
+            self.forward()
+        } else {
+            self.backward()
+        };
+
+        if !more {
+            self.start(self.quadrant + 1);
+        }
+
+        self.remaining -= 1;
+
+        Some(point)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.len()
+    }
+}
+
+impl<T: SignedNum> ExactSizeIterator for MidpointEllipse<T> {}
+
+/// An anti-aliased ellipse, drawn with [Xiaolin Wu's algorithm] for ellipses.
+///
+/// Each point comes with how much of it the outline covers, from `0.0` to `1.0`, like with
+/// [`XiaolinWu`]. The flat parts of the outline are drawn a column at a time, with the two rows
+/// that it passes between, and the steep parts a row at a time. The points aren't in order around
+/// the ellipse, and a few of them around where the flat and steep parts meet are returned twice,
+/// once from each side.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::XiaolinWuEllipse;
+///
+/// fn main() {
+///     for ((x, y), coverage) in XiaolinWuEllipse::<f32, i32>::new(0, 0, 2.0, 1.0).take(8) {
+///         print!("(({}, {}), {}), ", x, y, coverage);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// ((0, 1), 1), ((0, -1), 1), ((1, 0), 0.13397461), ((-1, 0), 0.13397461), ((1, 1), 0.8660254),
+/// ((-1, 1), 0.8660254), ((-1, -1), 0.8660254), ((1, -1), 0.8660254),
+/// ```
+///
+/// [Xiaolin Wu's algorithm]: https://en.wikipedia.org/wiki/Xiaolin_Wu%27s_line_algorithm
+/// [`XiaolinWu`]: struct.XiaolinWu.html
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct XiaolinWuEllipse<I, O> {
+    center: Point<O>,
+    radius: Point<I>,
+    // Whether the steps go along the rows instead of the columns
+    rows: bool,
+    step: O,
+    last: O,
+    points: [(Point<O>, I); 8],
+    index: u8,
+    len: u8,
+}
+
+impl<I: FloatNum, O: SignedNum> XiaolinWuEllipse<I, O> {
+    #[inline]
+    pub fn new(center_x: O, center_y: O, radius_x: I, radius_y: I) -> Self {
+        let radius = (radius_x.abs(), radius_y.abs());
+
+        let mut ellipse = Self {
+            center: (center_x, center_y),
+            radius,
+            rows: false,
+            step: O::zero(),
+            last: O::zero(),
+            points: [((O::zero(), O::zero()), I::zero()); 8],
+            index: 0,
+            len: 0,
+        };
+
+        // A flat ellipse is a line, which only needs to be stepped along one way
+        if radius.0.is_zero() && radius.1.is_zero() {
+            ellipse.rows = true;
+            ellipse.step = O::one();
+        } else if radius.0.is_zero() {
+            ellipse.rows = true;
+            ellipse.last = ellipse.last_step(true);
+        } else {
+            ellipse.last = ellipse.last_step(false);
+        }
+
+        ellipse
+    }
+
+    /// The last column or row that's stepped along, where the outline is at 45 degrees.
+    #[inline]
+    fn last_step(&self, rows: bool) -> O {
+        let (a, b) = self.radius;
+        let along = if rows { b } else { a };
+
+        O::cast((along * along / (a * a + b * b).sqrt()).floor())
+    }
+
+    /// Find the points for the next column or row, in each of the quadrants.
+    #[inline]
+    fn advance(&mut self) -> bool {
+        if self.step > self.last {
+            if self.rows || self.radius.1.is_zero() {
+                return false;
+            }
+
+            self.rows = true;
+            self.step = O::zero();
+            self.last = self.last_step(true);
+        }
+
+        let (a, b) = if self.rows {
+            (self.radius.1, self.radius.0)
+        } else {
+            self.radius
+        };
+
+        let along = I::cast(self.step);
+        let across = if along.is_zero() {
+            b
+        } else {
+            b * (I::one() - (along / a) * (along / a)).max(I::zero()).sqrt()
+        };
+
+        let inner = O::cast(across.floor());
+        let fraction = across - across.floor();
+
+        self.index = 0;
+        self.len = 0;
+
+        for &(offset, coverage) in &[(inner, I::one() - fraction), (inner + O::one(), fraction)] {
+            if coverage.is_zero() {
+                continue;
+            }
+
+            let flips = [(false, false), (true, false), (true, true), (false, true)];
+
+            for &(flip_along, flip_across) in &flips {
+                // Don't return the points on the axes twice
+                if (flip_along && self.step.is_zero()) || (flip_across && offset.is_zero()) {
+                    continue;
+                }
+
+                let along = if flip_along { -self.step } else { self.step };
+                let across = if flip_across { -offset } else { offset };
+                let (x, y) = if self.rows { (across, along) } else { (along, across) };
+
+                self.points[self.len as usize] = ((self.center.0 + x, self.center.1 + y), coverage);
+                self.len += 1;
+            }
+        }
+
+        self.step += O::one();
+        true
+    }
+}
+
+impl<I: FloatNum, O: SignedNum> Iterator for XiaolinWuEllipse<I, O> {
+    type Item = (Point<O>, I);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.index == self.len {
+            if !self.advance() {
+                return None;
+            }
+        }
+
+        let point = self.points[self.index as usize];
+        self.index += 1;
+
+        Some(point)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let buffered = (self.len - self.index) as usize;
+        let mut steps = (self.last - self.step + O::one()).max(O::zero()).to_usize().unwrap();
+
+        if !self.rows {
+            steps += self.last_step(true).to_usize().unwrap() + 1;
+        }
+
+        (buffered, Some(buffered + steps * 8))
+    }
+}
+
+#[test]
+fn tests() {
+    let ellipse = |a, b| MidpointEllipse::new(0, 0, a, b).collect::<Vec<_>>();
+
+    assert_eq!(ellipse(0, 0), []);
+    assert_eq!(ellipse(1, 0), [(1, 0), (0, 0), (-1, 0), (0, 0)]);
+    assert_eq!(ellipse(0, 1), [(0, 0), (0, 1), (0, 0), (0, -1)]);
+    assert_eq!(ellipse(1, 1), [(1, 0), (0, 1), (-1, 0), (0, -1)]);
+    assert_eq!(ellipse(8, 1)[..4], [(8, 0), (7, 0), (6, 1), (5, 1)]);
+    assert_eq!(
+        MidpointEllipse::new(5, 5, 2, 1).collect::<Vec<_>>(),
+        [
+            (7, 5),
+            (6, 6),
+            (5, 6),
+            (4, 6),
+            (3, 5),
+            (4, 4),
+            (5, 4),
+            (6, 4)
+        ]
+    );
+
+    for a in 1i32..40 {
+        for b in 1..40 {
+            let points = ellipse(a, b);
+            assert_eq!(MidpointEllipse::new(0, 0, a, b).len(), points.len());
+
+            for (i, &point) in points.iter().enumerate() {
+                // Each point is next to the one after it, all the way around
+                let next = points[(i + 1) % points.len()];
+                assert!((next.0 - point.0).abs() <= 1 && (next.1 - point.1).abs() <= 1);
+
+                // Only points along the axes at the tips are returned twice
+                assert!(point.0 == 0 || point.1 == 0 || !points[i + 1..].contains(&point));
+
+                // Every point is within a cell of the real ellipse
+                let (x, y) = (
+                    f64::from(point.0) / f64::from(a),
+                    f64::from(point.1) / f64::from(b),
+                );
+                let distance = (x * x + y * y).sqrt();
+                assert!((distance - 1.0).abs() * f64::from(a.min(b)) <= 1.0);
+            }
+
+            // A circle is the same whichever way it's turned
+            if a == b {
+                let mut turned = points.iter().map(|&(x, y)| (y, x)).collect::<Vec<_>>();
+                let mut points = points.clone();
+                turned.sort();
+                points.sort();
+                assert_eq!(turned, points);
+            }
+        }
+    }
+}
+
+#[test]
+fn xiaolin_wu() {
+    use std::collections::HashMap;
+
+    let ellipse = |a, b| XiaolinWuEllipse::<f64, i32>::new(3, -2, a, b).collect::<Vec<_>>();
+
+    assert_eq!(ellipse(0.0, 0.0), []);
+    assert_eq!(ellipse(1.0, 1.0).len(), 4);
+    assert_eq!(
+        ellipse(1.0, 0.0),
+        [((3, -2), 1.0), ((4, -2), 1.0), ((2, -2), 1.0)]
+    );
+    assert_eq!(
+        ellipse(0.0, 1.0),
+        [((3, -2), 1.0), ((3, -1), 1.0), ((3, -3), 1.0)]
+    );
+
+    for &(a, b) in &[(1.0, 1.0), (2.5, 1.0), (7.0, 3.25), (4.0, 12.0), (20.0, 20.0)] {
+        let points = ellipse(a, b);
+        let iterator = XiaolinWuEllipse::<f64, i32>::new(3, -2, a, b);
+        let (low, high) = iterator.size_hint();
+        assert!(low <= points.len() && points.len() <= high.unwrap());
+
+        let mut coverage = HashMap::new();
+
+        for &((x, y), amount) in &points {
+            assert!(amount > 0.0 && amount <= 1.0);
+            *coverage.entry((x, y)).or_insert(0.0) += amount;
+
+            // Every point is within a cell of the real ellipse
+            let (x, y) = (f64::from(x - 3) / a, f64::from(y + 2) / b);
+            let distance = (x * x + y * y).sqrt();
+            assert!((distance - 1.0).abs() * a.min(b) <= 1.5);
+        }
+
+        // The same point can come up on both sides of the 45 degree line, but no more than that
+        assert!(coverage.values().all(|&amount| amount <= 2.0));
+
+        // It's the same shape flipped either way
+        for &((x, y), _) in &points {
+            assert!(coverage.contains_key(&(6 - x, y)));
+            assert!(coverage.contains_key(&(x, -4 - y)));
+        }
+    }
+}
@@ -0,0 +1,165 @@
+/// An iterator that leaves out the first or last point of a line, or both.
+///
+/// Leaving out the start is useful for casting a line of sight from a cell without checking the
+/// cell itself, and leaving out the end for joining lines together without drawing the points
+/// where they meet twice. It works the same way with every algorithm, and with any kind of point.
+/// The 2D lines can use [`LineRasterizer::exclude_start`] and [`LineRasterizer::exclude_end`]
+/// instead of calling `new`.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{Bresenham, LineRasterizer};
+///
+/// fn main() {
+///     let path = [(0, 0), (3, 1), (3, 4)];
+///
+///     for segment in path.windows(2) {
+///         for (x, y) in Bresenham::new(segment[0], segment[1]).exclude_end() {
+///             print!("({}, {}), ", x, y);
+///         }
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// (0, 0), (1, 0), (2, 0), (3, 1), (3, 2), (3, 3),
+/// ```
+///
+/// [`LineRasterizer::exclude_start`]: trait.LineRasterizer.html#method.exclude_start
+/// [`LineRasterizer::exclude_end`]: trait.LineRasterizer.html#method.exclude_end
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "I: ::serde::Serialize, I::Item: ::serde::Serialize",
+        deserialize = "I: ::serde::Deserialize<'de>, I::Item: ::serde::Deserialize<'de>"
+    ))
+)]
+pub struct Endpoints<I: Iterator> {
+    iterator: I,
+    exclude_start: bool,
+    exclude_end: bool,
+    next: Option<I::Item>,
+}
+
+impl<I: Iterator> Endpoints<I> {
+    /// Choose whether the first and last points of the iterator are returned.
+    #[inline]
+    pub fn new(iterator: I, include_start: bool, include_end: bool) -> Self {
+        Self {
+            iterator,
+            exclude_start: !include_start,
+            exclude_end: !include_end,
+            next: None,
+        }
+    }
+
+    /// Leave out the first point as well.
+    #[inline]
+    pub fn exclude_start(self) -> Self {
+        Self {
+            exclude_start: true,
+            ..self
+        }
+    }
+
+    /// Leave out the last point as well.
+    #[inline]
+    pub fn exclude_end(self) -> Self {
+        Self {
+            exclude_end: true,
+            ..self
+        }
+    }
+}
+
+impl<I: Iterator> Iterator for Endpoints<I> {
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.exclude_start {
+            self.exclude_start = false;
+            self.iterator.next();
+        }
+
+        if !self.exclude_end {
+            return self.iterator.next();
+        }
+
+        // Stay one point ahead, so the last one is known when it comes up
+        let point = match self.next.take() {
+            Some(point) => point,
+            None => self.iterator.next()?,
+        };
+
+        self.next = Some(self.iterator.next()?);
+
+        Some(point)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (low, high) = self.iterator.size_hint();
+        let ahead = self.next.is_some() as usize;
+        let left_out = self.exclude_start as usize + self.exclude_end as usize;
+
+        (
+            (low + ahead).saturating_sub(left_out),
+            high.map(|high| (high + ahead).saturating_sub(left_out)),
+        )
+    }
+}
+
+impl<I: ExactSizeIterator> ExactSizeIterator for Endpoints<I> {}
+
+#[test]
+fn tests() {
+    use {Bresenham, Bresenham3d, LineRasterizer, Supercover};
+
+    let line = || Bresenham::new((0, 0), (3, 0));
+    let xs = |endpoints: Endpoints<Bresenham<i32>>| endpoints.map(|(x, _)| x).collect::<Vec<_>>();
+
+    assert_eq!(xs(Endpoints::new(line(), true, true)), [0, 1, 2, 3]);
+    assert_eq!(xs(line().exclude_start()), [1, 2, 3]);
+    assert_eq!(xs(line().exclude_end()), [0, 1, 2]);
+    assert_eq!(xs(line().exclude_start().exclude_end()), [1, 2]);
+    assert_eq!(xs(line().exclude_end().exclude_start()), [1, 2]);
+
+    // A line with a single point has nothing left
+    let point = || Bresenham::new((5, 5), (5, 5));
+    assert_eq!(xs(point().exclude_start()), []);
+    assert_eq!(xs(point().exclude_end()), []);
+    assert_eq!(xs(point().exclude_start().exclude_end()), []);
+
+    // The sizes are kept up to date
+    for &(start, end) in &[(true, true), (false, true), (true, false), (false, false)] {
+        let mut endpoints = Endpoints::new(Bresenham::new((2, 1), (-4, 6)), start, end);
+
+        loop {
+            let count = endpoints.clone().fold(0, |count, _| count + 1);
+            assert_eq!(endpoints.len(), count);
+
+            if endpoints.next().is_none() {
+                break;
+            }
+        }
+    }
+
+    assert_eq!(
+        Supercover::new((0, 0), (2, 2))
+            .exclude_start()
+            .collect::<Vec<_>>(),
+        [(1, 1), (2, 2)]
+    );
+    assert_eq!(
+        Endpoints::new(Bresenham3d::new((0, 0, 0), (2, 1, 3)), false, false).collect::<Vec<_>>(),
+        Bresenham3d::new((0, 0, 0), (2, 1, 3))
+            .skip(1)
+            .take(2)
+            .collect::<Vec<_>>()
+    );
+}
@@ -0,0 +1,233 @@
+use {RealNum, SignedNum};
+use core::fmt;
+use num_traits::{CheckedAdd, CheckedMul, CheckedNeg, CheckedSub, NumCast, ToPrimitive};
+
+/// Why a `try_new` constructor couldn't make a line or shape.
+///
+/// The `new` constructors don't check for these, and either panic or return the wrong points when
+/// they happen, so `try_new` is the one to use with input that can't be trusted.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{Bresenham, BresenhamCircle, LineError, Midpoint, XiaolinWu};
+///
+/// fn main() {
+///     assert!(Bresenham::<i32>::try_new((0, 0), (5, 2)).is_ok());
+///     assert_eq!(
+///         Midpoint::<f32, i8>::try_new((0.0, 0.0), (200.0, 0.0)),
+///         Err(LineError::Overflow)
+///     );
+///     assert_eq!(BresenhamCircle::try_new(0, 0, -3), Err(LineError::NegativeRadius));
+///     assert_eq!(
+///         XiaolinWu::<f32, i32>::try_new((0.0, 0.0), (1.0, std::f32::NAN)),
+///         Err(LineError::NotFinite)
+///     );
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum LineError {
+    /// A coordinate was NaN or infinite.
+    NotFinite,
+    /// A radius was negative.
+    NegativeRadius,
+    /// The points are too far apart, or too close to the limits of the coordinate type, for the
+    /// calculations to fit in it, or the line has too many points to count in a `usize`.
+    Overflow,
+}
+
+impl fmt::Display for LineError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(match *self {
+            LineError::NotFinite => "a coordinate is NaN or infinite",
+            LineError::NegativeRadius => "a radius is negative",
+            LineError::Overflow => "the coordinates are too large for the coordinate type",
+        })
+    }
+}
+
+impl core::error::Error for LineError {}
+
+/// Check that a line from `start` to `end` fits in `T`.
+///
+/// The coordinates are flipped into the first octant and the distance between them is doubled
+/// by some of the algorithms, so both of those need to fit.
+#[inline]
+pub(crate) fn check_span<T>(start: T, end: T) -> Result<(), LineError>
+where
+    T: SignedNum + CheckedAdd + CheckedNeg + CheckedSub,
+{
+    start.checked_neg().ok_or(LineError::Overflow)?;
+    end.checked_neg().ok_or(LineError::Overflow)?;
+
+    let delta = end.checked_sub(&start).ok_or(LineError::Overflow)?;
+    delta.checked_add(&delta).ok_or(LineError::Overflow)?;
+
+    Ok(())
+}
+
+/// Check that a line between two cells fits in `T`, like [`check_span`], for the algorithms that
+/// step through the cells in `T` itself instead of in its wide type.
+///
+/// [`check_span`]: fn.check_span.html
+#[inline]
+pub(crate) fn check_cells<T: SignedNum>(start: T, end: T) -> Result<(), LineError> {
+    let (start, end) = (start.widen(), end.widen());
+    check_span(start, end)?;
+
+    let delta = end - start;
+
+    for &value in &[-start, -end, delta + delta] {
+        <T as NumCast>::from(value).ok_or(LineError::Overflow)?;
+    }
+
+    Ok(())
+}
+
+/// Check that a line that takes `steps` steps can count its points, one more than that, in a
+/// `usize`.
+#[inline]
+pub(crate) fn check_len<T: ToPrimitive>(steps: T) -> Result<(), LineError> {
+    match steps.to_usize().and_then(|steps| steps.checked_add(1)) {
+        Some(_) => Ok(()),
+        None => Err(LineError::Overflow),
+    }
+}
+
+/// Check that a circle or ellipse fits in `T`, with the radius doubled and squared as well.
+#[inline]
+pub(crate) fn check_radius<T>(center: T, radius: T) -> Result<(), LineError>
+where
+    T: SignedNum + CheckedAdd + CheckedMul + CheckedSub,
+{
+    if radius < T::zero() {
+        return Err(LineError::NegativeRadius);
+    }
+
+    center.checked_add(&radius).ok_or(LineError::Overflow)?;
+    center.checked_sub(&radius).ok_or(LineError::Overflow)?;
+
+    let double = radius.checked_add(&radius).ok_or(LineError::Overflow)?;
+    double.checked_mul(&double).ok_or(LineError::Overflow)?;
+
+    Ok(())
+}
+
+/// Check that a floating-point coordinate is finite, and that the cells around it fit in `O`.
+#[inline]
+pub(crate) fn check_float<I: RealNum, O: SignedNum>(value: I) -> Result<(), LineError> {
+    if !value.is_finite() {
+        return Err(LineError::NotFinite);
+    }
+
+    for &cell in &[value.floor() - I::one(), value.ceil() + I::one()] {
+        cell.to_i128()
+            .and_then(<O as NumCast>::from)
+            .ok_or(LineError::Overflow)?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn tests() {
+    assert_eq!(check_span(-30i8, 33), Ok(()));
+    assert_eq!(check_span(-30i8, 34), Err(LineError::Overflow));
+    assert_eq!(check_span(-128i8, 0), Err(LineError::Overflow));
+    assert_eq!(check_span(i64::MAX / 2, 0), Ok(()));
+
+    assert_eq!(check_cells(-30i8, 33), Ok(()));
+    assert_eq!(check_cells(-30i8, 34), Err(LineError::Overflow));
+    assert_eq!(check_cells(0i8, -128), Err(LineError::Overflow));
+    assert_eq!(check_cells(i128::MAX / 2, 0), Ok(()));
+    assert_eq!(check_cells(i128::MIN / 2, i128::MAX / 2), Err(LineError::Overflow));
+
+    assert_eq!(check_len(usize::MAX - 1), Ok(()));
+    assert_eq!(check_len(usize::MAX), Err(LineError::Overflow));
+    assert_eq!(check_len(-1i32), Err(LineError::Overflow));
+
+    assert_eq!(check_radius(0i16, 90), Ok(()));
+    assert_eq!(check_radius(0i16, 91), Err(LineError::Overflow));
+    assert_eq!(check_radius(32_760i16, 10), Err(LineError::Overflow));
+    assert_eq!(check_radius(0, -1), Err(LineError::NegativeRadius));
+
+    assert_eq!(check_float::<f32, i8>(126.0), Ok(()));
+    assert_eq!(check_float::<f32, i8>(-127.0), Ok(()));
+    assert_eq!(check_float::<f32, i8>(126.5), Err(LineError::Overflow));
+    assert_eq!(check_float::<f64, i32>(f64::NAN), Err(LineError::NotFinite));
+    assert_eq!(check_float::<f64, i32>(f64::NEG_INFINITY), Err(LineError::NotFinite));
+
+    assert_eq!(LineError::NegativeRadius.to_string(), "a radius is negative");
+
+    use {Bresenham, Bresenham3d, BresenhamCircle, Midpoint, MidpointEllipse, Supercover,
+         VoxelOrigin, WalkGrid, WalkVoxels, XiaolinWu};
+
+    // The lines that can be made are the same as with `new`
+    assert_eq!(
+        Bresenham::try_new((3, -1), (-4, 6)),
+        Ok(Bresenham::new((3, -1), (-4, 6)))
+    );
+    assert_eq!(
+        WalkVoxels::<f64, i16>::try_new((0.5, 1.0, 2.0), (9.0, 8.0, 7.5), VoxelOrigin::Center),
+        Ok(WalkVoxels::new((0.5, 1.0, 2.0), (9.0, 8.0, 7.5), VoxelOrigin::Center))
+    );
+    assert!(MidpointEllipse::try_new(0, 0, 100, 50).is_ok());
+
+    // The cells at the ends can fit when the distance between them doesn't
+    assert_eq!(
+        Midpoint::<f32, i8>::try_new((-100.0, 0.0), (100.0, 0.0)),
+        Err(LineError::Overflow)
+    );
+    assert_eq!(
+        XiaolinWu::<f32, i8>::try_new((0.0, -100.0), (1.0, 100.0)),
+        Err(LineError::Overflow)
+    );
+    assert_eq!(
+        WalkVoxels::<f32, i8>::try_new((-50.0, 0.0, 0.0), (50.0, 0.0, 0.0), VoxelOrigin::Center),
+        Err(LineError::Overflow)
+    );
+    assert_eq!(
+        WalkVoxels::<f32, i8>::try_new((0.0, 0.0, 0.0), (50.0, 50.0, 50.0), VoxelOrigin::Corner),
+        Err(LineError::Overflow)
+    );
+    assert!(
+        WalkVoxels::<f32, i8>::try_new((0.0, 0.0, 0.0), (40.0, 40.0, 40.0), VoxelOrigin::Corner)
+            .is_ok()
+    );
+
+    assert!(WalkGrid::<i8>::try_new((i8::MIN, 0), (i8::MAX, 0)).is_ok());
+    assert_eq!(
+        WalkGrid::<i128>::try_new((0, 0), (i128::MAX / 2 + 1, 0)),
+        Err(LineError::Overflow)
+    );
+    assert_eq!(
+        Supercover::<i128>::try_new((0, i128::MIN), (0, 0)),
+        Err(LineError::Overflow)
+    );
+    assert!(Supercover::<i128>::try_new((0, i128::MIN / 4), (5, i128::MIN / 4 + 3)).is_ok());
+    assert_eq!(
+        Supercover::<i128>::try_new((0, i128::MIN / 4), (0, 0)),
+        Err(LineError::Overflow)
+    );
+    assert!(Bresenham3d::<i16>::try_new((0, 0, i16::MIN), (0, 0, i16::MAX)).is_ok());
+    assert_eq!(
+        Bresenham3d::<i128>::try_new((0, 0, i128::MIN), (0, 0, 0)),
+        Err(LineError::Overflow)
+    );
+    assert_eq!(BresenhamCircle::<i8>::try_new(120, 0, 10), Err(LineError::Overflow));
+    assert_eq!(
+        MidpointEllipse::<i32>::try_new(0, 0, 5, -1),
+        Err(LineError::NegativeRadius)
+    );
+    assert_eq!(MidpointEllipse::<i32>::try_new(0, 0, 300, 300), Err(LineError::Overflow));
+    assert_eq!(
+        Midpoint::<f64, i8>::try_new((0.0, 0.0), (200.0, 0.0)),
+        Err(LineError::Overflow)
+    );
+    assert_eq!(
+        XiaolinWu::<f32, i64>::try_new((f32::INFINITY, 0.0), (0.0, 0.0)),
+        Err(LineError::NotFinite)
+    );
+}
@@ -0,0 +1,79 @@
+use euclid::{Point2D, Point3D, Vector2D, Vector3D};
+use {FromPoint, FromVoxel, IntoPoint, IntoVoxel, Point, Voxel};
+
+impl<T, U> IntoPoint<T> for Point2D<T, U> {
+    #[inline]
+    fn into_point(self) -> Point<T> {
+        (self.x, self.y)
+    }
+}
+
+impl<T, U> FromPoint<T> for Point2D<T, U> {
+    #[inline]
+    fn from_point((x, y): Point<T>) -> Self {
+        Point2D::new(x, y)
+    }
+}
+
+impl<T, U> IntoPoint<T> for Vector2D<T, U> {
+    #[inline]
+    fn into_point(self) -> Point<T> {
+        (self.x, self.y)
+    }
+}
+
+impl<T, U> FromPoint<T> for Vector2D<T, U> {
+    #[inline]
+    fn from_point((x, y): Point<T>) -> Self {
+        Vector2D::new(x, y)
+    }
+}
+
+impl<T, U> IntoVoxel<T> for Point3D<T, U> {
+    #[inline]
+    fn into_voxel(self) -> Voxel<T> {
+        (self.x, self.y, self.z)
+    }
+}
+
+impl<T, U> FromVoxel<T> for Point3D<T, U> {
+    #[inline]
+    fn from_voxel((x, y, z): Voxel<T>) -> Self {
+        Point3D::new(x, y, z)
+    }
+}
+
+impl<T, U> IntoVoxel<T> for Vector3D<T, U> {
+    #[inline]
+    fn into_voxel(self) -> Voxel<T> {
+        (self.x, self.y, self.z)
+    }
+}
+
+impl<T, U> FromVoxel<T> for Vector3D<T, U> {
+    #[inline]
+    fn from_voxel((x, y, z): Voxel<T>) -> Self {
+        Vector3D::new(x, y, z)
+    }
+}
+
+#[test]
+fn tests() {
+    use {Bresenham, Bresenham3d, Converted, SupercoverRay};
+
+    struct Screen;
+    struct World;
+    type ScreenPoint = Point2D<i32, Screen>;
+    type WorldVoxel = Point3D<i32, World>;
+
+    let line = Bresenham::new(ScreenPoint::new(0, 0), ScreenPoint::new(2, 1));
+    let points: Vec<ScreenPoint> = Converted::new(line).collect();
+    assert_eq!(points, [ScreenPoint::new(0, 0), ScreenPoint::new(1, 0), ScreenPoint::new(2, 1)]);
+
+    let line = Bresenham3d::new(WorldVoxel::new(0, 0, 0), WorldVoxel::new(2, 1, 0));
+    let last: Option<WorldVoxel> = Converted::new(line).last();
+    assert_eq!(last, Some(WorldVoxel::new(2, 1, 0)));
+
+    let ray = SupercoverRay::new(ScreenPoint::new(1, 1), Vector2D::<i32, Screen>::new(0, -1));
+    assert_eq!(ray.take(3).collect::<Vec<_>>(), [(1, 1), (1, 0), (1, -1)]);
+}
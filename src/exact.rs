@@ -0,0 +1,103 @@
+use core::cmp::Ordering;
+
+/// Multiply `a` by `b` and divide by `divisor`, returning the quotient and remainder.
+///
+/// The product can need more bits than `u128` has, so it's split into 64-bit halves and divided
+/// one bit at a time when it does. The quotient has to fit in a `u128`, and the divisor has to be
+/// below `2^127`, which the lengths of lines in `i128` always are.
+#[inline]
+pub(crate) fn mul_div(a: u128, b: u128, divisor: u128) -> (u128, u128) {
+    if let Some(product) = a.checked_mul(b) {
+        return (product / divisor, product % divisor);
+    }
+
+    let half = |value: u128| (value >> 64, value & u128::from(u64::MAX));
+    let ((a_high, a_low), (b_high, b_low)) = (half(a), half(b));
+
+    let (middle, carry) = (a_high * b_low).overflowing_add(a_low * b_high);
+    let (low, low_carry) = (a_low * b_low).overflowing_add(middle << 64);
+    let high = a_high * b_high
+        + (middle >> 64)
+        + (u128::from(carry) << 64)
+        + u128::from(low_carry);
+
+    let (mut quotient, mut remainder) = (0u128, 0u128);
+
+    for bit in (0..256).rev() {
+        let word = if bit >= 128 { high } else { low };
+
+        remainder = (remainder << 1) | ((word >> (bit % 128)) & 1);
+        quotient <<= 1;
+
+        if remainder >= divisor {
+            remainder -= divisor;
+            quotient |= 1;
+        }
+    }
+
+    (quotient, remainder)
+}
+
+/// Compare `a / b` with `c / d` without multiplying them out, so that it can't overflow. Neither
+/// `b` nor `d` can be zero.
+///
+/// The whole parts are compared first. If they're the same, comparing what's left over is the
+/// same as comparing its reciprocals the other way around, which are another pair of fractions with
+/// smaller numbers, the same as in Euclid's algorithm.
+#[inline]
+pub(crate) fn compare_fractions(mut a: u128, mut b: u128, mut c: u128, mut d: u128) -> Ordering {
+    loop {
+        let (left, right) = (a / b, c / d);
+
+        if left != right {
+            return left.cmp(&right);
+        }
+
+        let (left, right) = (a % b, c % d);
+
+        match (left == 0, right == 0) {
+            (true, true) => return Ordering::Equal,
+            (true, false) => return Ordering::Less,
+            (false, true) => return Ordering::Greater,
+            (false, false) => {
+                let (next_a, next_c) = (d, b);
+                a = next_a;
+                c = next_c;
+                b = right;
+                d = left;
+            }
+        }
+    }
+}
+
+#[test]
+fn tests() {
+    // Small numbers give the same answers as multiplying directly
+    for a in 0..20u128 {
+        for b in 0..20 {
+            for divisor in 1..20 {
+                assert_eq!(mul_div(a, b, divisor), (a * b / divisor, a * b % divisor));
+            }
+
+            for c in 0..20 {
+                for d in 1..20 {
+                    if b > 0 {
+                        assert_eq!(compare_fractions(a, b, c, d), (a * d).cmp(&(c * b)));
+                    }
+                }
+            }
+        }
+    }
+
+    // Products bigger than a `u128`
+    let big = 1u128 << 126;
+    assert_eq!(mul_div(big, 12, 3 << 64), (1 << 64, 0));
+    assert_eq!(mul_div(big + 1, big + 1, big), (big + 2, 1));
+    assert_eq!(mul_div(u128::MAX, 3, big + 1), (11, big - 14));
+    assert_eq!(mul_div(u128::MAX, 2, u128::MAX >> 1), (4, 2));
+
+    assert_eq!(compare_fractions(big + 1, big, big + 2, big + 1), Ordering::Greater);
+    assert_eq!(compare_fractions(3, big, 6, 2 * big), Ordering::Equal);
+    assert_eq!(compare_fractions(u128::MAX - 1, u128::MAX, big - 1, big), Ordering::Greater);
+    assert_eq!(compare_fractions(1, 3, big, u128::MAX), Ordering::Greater);
+}
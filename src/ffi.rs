@@ -0,0 +1,331 @@
+//! A C API for some of the algorithms, for using them from C and C++ (needs the `ffi` feature).
+//!
+//! Each algorithm has a function to create a handle to one of its iterators, one to get the next
+//! point out of it, and one to free it. A header can be made with [cbindgen].
+//!
+//! ```c
+//! LineDrawingBresenham *line = line_drawing_bresenham_new(0, 0, 5, 2);
+//! int32_t x, y;
+//!
+//! while (line_drawing_bresenham_next(line, &x, &y)) {
+//!     plot(x, y);
+//! }
+//!
+//! line_drawing_bresenham_free(line);
+//! ```
+//!
+//! The integer algorithms use `int32_t` coordinates and the floating-point ones use `float`.
+//! Creating a voxel walk or anti-aliased line returns null if it can't be drawn, like when an end
+//! point is NaN or the line is too long for `int32_t`. Passing a null handle to any of the
+//! functions is allowed, and does nothing.
+//!
+//! [cbindgen]: https://github.com/mozilla/cbindgen
+
+use {Bresenham, LineError, VoxelOrigin, WalkGrid, WalkVoxels, XiaolinWu};
+use alloc::boxed::Box;
+use core::ptr;
+
+/// A [`Bresenham`](../struct.Bresenham.html) line.
+pub struct LineDrawingBresenham(Bresenham<i32>);
+
+/// A [`WalkGrid`](../struct.WalkGrid.html) line.
+pub struct LineDrawingWalkGrid(WalkGrid<i32>);
+
+/// A [`WalkVoxels`](../struct.WalkVoxels.html) line.
+pub struct LineDrawingWalkVoxels(WalkVoxels<f32, i32>);
+
+/// A [`XiaolinWu`](../struct.XiaolinWu.html) line.
+pub struct LineDrawingXiaolinWu(XiaolinWu<f32, i32>);
+
+/// Write a value through a pointer, if it isn't null.
+#[inline]
+unsafe fn write<T>(pointer: *mut T, value: T) {
+    if !pointer.is_null() {
+        *pointer = value;
+    }
+}
+
+/// Make a handle for a line, or return null if it couldn't be made.
+#[inline]
+fn new<T, U>(line: Result<T, LineError>, handle: fn(T) -> U) -> *mut U {
+    match line {
+        Ok(line) => Box::into_raw(Box::new(handle(line))),
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+/// Free a handle made with `Box::into_raw`, if it isn't null.
+#[inline]
+unsafe fn free<T>(handle: *mut T) {
+    if !handle.is_null() {
+        drop(Box::from_raw(handle));
+    }
+}
+
+/// Create a Bresenham line, which needs to be freed with `line_drawing_bresenham_free`.
+#[no_mangle]
+pub extern "C" fn line_drawing_bresenham_new(
+    x0: i32,
+    y0: i32,
+    x1: i32,
+    y1: i32,
+) -> *mut LineDrawingBresenham {
+    Box::into_raw(Box::new(LineDrawingBresenham(Bresenham::new((x0, y0), (x1, y1)))))
+}
+
+/// Get the next point of a Bresenham line, returning `false` at the end of it.
+///
+/// # Safety
+///
+/// `line` has to be null or a handle from `line_drawing_bresenham_new` that hasn't been freed,
+/// and `x` and `y` have to be null or valid to write to.
+#[no_mangle]
+pub unsafe extern "C" fn line_drawing_bresenham_next(
+    line: *mut LineDrawingBresenham,
+    x: *mut i32,
+    y: *mut i32,
+) -> bool {
+    match line.as_mut().and_then(|line| line.0.next()) {
+        Some(point) => {
+            write(x, point.0);
+            write(y, point.1);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Free a Bresenham line.
+///
+/// # Safety
+///
+/// `line` has to be null or a handle from `line_drawing_bresenham_new` that hasn't been freed.
+#[no_mangle]
+pub unsafe extern "C" fn line_drawing_bresenham_free(line: *mut LineDrawingBresenham) {
+    free(line);
+}
+
+/// Create a grid walk, which needs to be freed with `line_drawing_walk_grid_free`.
+#[no_mangle]
+pub extern "C" fn line_drawing_walk_grid_new(
+    x0: i32,
+    y0: i32,
+    x1: i32,
+    y1: i32,
+) -> *mut LineDrawingWalkGrid {
+    Box::into_raw(Box::new(LineDrawingWalkGrid(WalkGrid::new((x0, y0), (x1, y1)))))
+}
+
+/// Get the next point of a grid walk, returning `false` at the end of it.
+///
+/// # Safety
+///
+/// `line` has to be null or a handle from `line_drawing_walk_grid_new` that hasn't been freed,
+/// and `x` and `y` have to be null or valid to write to.
+#[no_mangle]
+pub unsafe extern "C" fn line_drawing_walk_grid_next(
+    line: *mut LineDrawingWalkGrid,
+    x: *mut i32,
+    y: *mut i32,
+) -> bool {
+    match line.as_mut().and_then(|line| line.0.next()) {
+        Some(point) => {
+            write(x, point.0);
+            write(y, point.1);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Free a grid walk.
+///
+/// # Safety
+///
+/// `line` has to be null or a handle from `line_drawing_walk_grid_new` that hasn't been freed.
+#[no_mangle]
+pub unsafe extern "C" fn line_drawing_walk_grid_free(line: *mut LineDrawingWalkGrid) {
+    free(line);
+}
+
+/// Create a voxel walk, which needs to be freed with `line_drawing_walk_voxels_free`.
+///
+/// The voxels are centered on whole numbers if `center` is true, and start at them otherwise.
+/// Returns null if any of the coordinates are NaN or infinite, or if the voxels or the number of
+/// steps between them don't fit in an `int32_t`.
+#[no_mangle]
+pub extern "C" fn line_drawing_walk_voxels_new(
+    x0: f32,
+    y0: f32,
+    z0: f32,
+    x1: f32,
+    y1: f32,
+    z1: f32,
+    center: bool,
+) -> *mut LineDrawingWalkVoxels {
+    let origin = if center {
+        VoxelOrigin::Center
+    } else {
+        VoxelOrigin::Corner
+    };
+    let walk = WalkVoxels::try_new((x0, y0, z0), (x1, y1, z1), origin);
+
+    new(walk, LineDrawingWalkVoxels)
+}
+
+/// Get the next voxel of a voxel walk, returning `false` at the end of it.
+///
+/// # Safety
+///
+/// `line` has to be null or a handle from `line_drawing_walk_voxels_new` that hasn't been freed,
+/// and `x`, `y` and `z` have to be null or valid to write to.
+#[no_mangle]
+pub unsafe extern "C" fn line_drawing_walk_voxels_next(
+    line: *mut LineDrawingWalkVoxels,
+    x: *mut i32,
+    y: *mut i32,
+    z: *mut i32,
+) -> bool {
+    match line.as_mut().and_then(|line| line.0.next()) {
+        Some(voxel) => {
+            write(x, voxel.0);
+            write(y, voxel.1);
+            write(z, voxel.2);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Free a voxel walk.
+///
+/// # Safety
+///
+/// `line` has to be null or a handle from `line_drawing_walk_voxels_new` that hasn't been freed.
+#[no_mangle]
+pub unsafe extern "C" fn line_drawing_walk_voxels_free(line: *mut LineDrawingWalkVoxels) {
+    free(line);
+}
+
+/// Create an anti-aliased line, which needs to be freed with `line_drawing_xiaolin_wu_free`.
+///
+/// Returns null if any of the coordinates are NaN or infinite, or if the pixels or the distance
+/// between them don't fit in an `int32_t`.
+#[no_mangle]
+pub extern "C" fn line_drawing_xiaolin_wu_new(
+    x0: f32,
+    y0: f32,
+    x1: f32,
+    y1: f32,
+) -> *mut LineDrawingXiaolinWu {
+    new(XiaolinWu::try_new((x0, y0), (x1, y1)), LineDrawingXiaolinWu)
+}
+
+/// Get the next point of an anti-aliased line and how much of it is covered, from `0` to `1`,
+/// returning `false` at the end of it.
+///
+/// # Safety
+///
+/// `line` has to be null or a handle from `line_drawing_xiaolin_wu_new` that hasn't been freed,
+/// and `x`, `y` and `coverage` have to be null or valid to write to.
+#[no_mangle]
+pub unsafe extern "C" fn line_drawing_xiaolin_wu_next(
+    line: *mut LineDrawingXiaolinWu,
+    x: *mut i32,
+    y: *mut i32,
+    coverage: *mut f32,
+) -> bool {
+    match line.as_mut().and_then(|line| line.0.next()) {
+        Some((point, amount)) => {
+            write(x, point.0);
+            write(y, point.1);
+            write(coverage, amount);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Free an anti-aliased line.
+///
+/// # Safety
+///
+/// `line` has to be null or a handle from `line_drawing_xiaolin_wu_new` that hasn't been freed.
+#[no_mangle]
+pub unsafe extern "C" fn line_drawing_xiaolin_wu_free(line: *mut LineDrawingXiaolinWu) {
+    free(line);
+}
+
+#[test]
+fn tests() {
+    unsafe {
+        let line = line_drawing_bresenham_new(0, 0, 5, 2);
+        let (mut x, mut y) = (0, 0);
+        let mut points = Vec::new();
+
+        while line_drawing_bresenham_next(line, &mut x, &mut y) {
+            points.push((x, y));
+        }
+
+        assert!(!line_drawing_bresenham_next(line, &mut x, &mut y));
+        line_drawing_bresenham_free(line);
+        assert_eq!(points, Bresenham::new((0, 0), (5, 2)).collect::<Vec<_>>());
+
+        let line = line_drawing_walk_grid_new(3, 1, -2, 4);
+        let mut points = Vec::new();
+
+        while line_drawing_walk_grid_next(line, &mut x, &mut y) {
+            points.push((x, y));
+        }
+
+        line_drawing_walk_grid_free(line);
+        assert_eq!(points, WalkGrid::new((3, 1), (-2, 4)).collect::<Vec<_>>());
+
+        let line = line_drawing_walk_voxels_new(0.5, 0.0, 1.0, 4.0, -2.5, 3.0, true);
+        let mut z = 0;
+        let mut voxels = Vec::new();
+
+        while line_drawing_walk_voxels_next(line, &mut x, &mut y, &mut z) {
+            voxels.push((x, y, z));
+        }
+
+        line_drawing_walk_voxels_free(line);
+        assert_eq!(
+            voxels,
+            WalkVoxels::new((0.5, 0.0, 1.0), (4.0, -2.5, 3.0), VoxelOrigin::Center)
+                .collect::<Vec<_>>()
+        );
+
+        let line = line_drawing_xiaolin_wu_new(0.0, 0.0, 4.5, 1.5);
+        let mut coverage = 0.0;
+        let mut points = Vec::new();
+
+        while line_drawing_xiaolin_wu_next(line, &mut x, &mut y, &mut coverage) {
+            points.push(((x, y), coverage));
+        }
+
+        line_drawing_xiaolin_wu_free(line);
+        assert_eq!(points, XiaolinWu::new((0.0, 0.0), (4.5, 1.5)).collect::<Vec<_>>());
+
+        // Null pointers are ignored
+        let line = line_drawing_bresenham_new(0, 0, 1, 0);
+        assert!(line_drawing_bresenham_next(line, ptr::null_mut(), &mut y));
+        assert!(line_drawing_bresenham_next(line, &mut x, ptr::null_mut()));
+        assert_eq!(x, 1);
+        assert!(!line_drawing_bresenham_next(line, ptr::null_mut(), ptr::null_mut()));
+        line_drawing_bresenham_free(line);
+
+        assert!(!line_drawing_walk_grid_next(ptr::null_mut(), &mut x, &mut y));
+        line_drawing_xiaolin_wu_free(ptr::null_mut());
+
+        // Lines that can't be drawn give null handles instead of panicking
+        assert!(line_drawing_walk_voxels_new(0.0, 0.0, 0.0, f32::NAN, 1.0, 1.0, true).is_null());
+        assert!(line_drawing_walk_voxels_new(0.0, 0.0, 0.0, 3e9, 0.0, 0.0, false).is_null());
+        assert!(line_drawing_xiaolin_wu_new(0.0, 0.0, f32::INFINITY, 1.0).is_null());
+        assert!(line_drawing_xiaolin_wu_new(-2e9, 0.0, 2e9, 1.0).is_null());
+
+        let line = line_drawing_walk_voxels_new(f32::NAN, 0.0, 0.0, 1.0, 1.0, 1.0, false);
+        assert!(!line_drawing_walk_voxels_next(line, &mut x, &mut y, &mut z));
+        line_drawing_walk_voxels_free(line);
+    }
+}
@@ -0,0 +1,391 @@
+use {RealNum, SignedNum};
+use core::num::ParseIntError;
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub,
+                SubAssign};
+use num_traits::{self, Float, Num, One, ToPrimitive, Zero};
+
+/// The number of fractional bits in a [`Q16`].
+///
+/// [`Q16`]: struct.Q16.html
+const FRACTION_BITS: u32 = 16;
+const FRACTION_MASK: i32 = (1 << FRACTION_BITS) - 1;
+
+/// A Q16.16 fixed-point number, with 16 bits for the whole part and 16 for the fraction.
+///
+/// It's a [`RealNum`], so [`Midpoint`], [`XiaolinWu`] and [`WalkVoxels`] can take subpixel end
+/// points as fixed-point on chips without a floating-point unit, where all of the stepping is
+/// done with integers. It covers from `-32768` to just under `32768`, with a step of `1 / 65536`,
+/// and it overflows like an `i32` past that.
+///
+/// Whole numbers can be made with `From<i16>` and [`from_bits`] without any floating-point math.
+/// Casting from a float with [`RealNum::cast`] rounds to the nearest step.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{Midpoint, Q16};
+///
+/// fn main() {
+///     let start = (Q16::from_bits(0x3333), Q16::from_bits(0x0800));
+///     let end = (Q16::from(3) - Q16::from_bits(0x3333), Q16::from(7) + Q16::from_bits(0xb333));
+///
+///     for (x, y) in Midpoint::<Q16, i8>::new(start, end) {
+///         print!("({}, {}), ", x, y);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// (0, 0), (1, 1), (1, 2), (1, 3), (2, 4), (2, 5), (2, 6), (3, 7), (3, 8),
+/// ```
+///
+/// [`RealNum`]: trait.RealNum.html
+/// [`RealNum::cast`]: trait.RealNum.html#method.cast
+/// [`Midpoint`]: struct.Midpoint.html
+/// [`XiaolinWu`]: struct.XiaolinWu.html
+/// [`WalkVoxels`]: struct.WalkVoxels.html
+/// [`from_bits`]: #method.from_bits
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Q16(i32);
+
+impl Q16 {
+    /// Make a number from its bits, which are the number times `65536`.
+    #[inline]
+    pub fn from_bits(bits: i32) -> Self {
+        Q16(bits)
+    }
+
+    /// Get the bits of the number, which are the number times `65536`.
+    #[inline]
+    pub fn to_bits(self) -> i32 {
+        self.0
+    }
+}
+
+impl From<i16> for Q16 {
+    #[inline]
+    fn from(value: i16) -> Self {
+        Q16(i32::from(value) << FRACTION_BITS)
+    }
+}
+
+impl Add for Q16 {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, other: Self) -> Self {
+        Q16(self.0 + other.0)
+    }
+}
+
+impl Sub for Q16 {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, other: Self) -> Self {
+        Q16(self.0 - other.0)
+    }
+}
+
+impl Mul for Q16 {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, other: Self) -> Self {
+        Q16(((i64::from(self.0) * i64::from(other.0)) >> FRACTION_BITS) as i32)
+    }
+}
+
+impl Div for Q16 {
+    type Output = Self;
+
+    #[inline]
+    fn div(self, other: Self) -> Self {
+        Q16(((i64::from(self.0) << FRACTION_BITS) / i64::from(other.0)) as i32)
+    }
+}
+
+impl Rem for Q16 {
+    type Output = Self;
+
+    #[inline]
+    fn rem(self, other: Self) -> Self {
+        Q16(self.0 % other.0)
+    }
+}
+
+impl Neg for Q16 {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        Q16(-self.0)
+    }
+}
+
+macro_rules! assign_ops {
+    ($($trait:ident, $method:ident, $op:tt);*) => {
+        $(
+            impl $trait for Q16 {
+                #[inline]
+                fn $method(&mut self, other: Self) {
+                    *self = *self $op other;
+                }
+            }
+        )*
+    };
+}
+
+assign_ops!(
+    AddAssign, add_assign, +;
+    SubAssign, sub_assign, -;
+    MulAssign, mul_assign, *;
+    DivAssign, div_assign, /;
+    RemAssign, rem_assign, %
+);
+
+impl Zero for Q16 {
+    #[inline]
+    fn zero() -> Self {
+        Q16(0)
+    }
+
+    #[inline]
+    fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl One for Q16 {
+    #[inline]
+    fn one() -> Self {
+        Q16(1 << FRACTION_BITS)
+    }
+}
+
+impl Num for Q16 {
+    type FromStrRadixErr = ParseIntError;
+
+    /// Parse a number like `-12.375`, with the digits after the point rounded down to the
+    /// nearest step.
+    fn from_str_radix(text: &str, radix: u32) -> Result<Self, ParseIntError> {
+        let (whole, fraction) = match text.find('.') {
+            Some(index) => (&text[..index], &text[index + 1..]),
+            None => (text, ""),
+        };
+
+        let negative = whole.starts_with('-');
+        let whole = match whole {
+            "" | "-" | "+" if !fraction.is_empty() => 0,
+            _ => i16::from_str_radix(whole, radix)?,
+        };
+
+        // Go through the digits backwards, dividing by the radix after each one
+        let mut bits = 0u64;
+        for digit in fraction.chars().rev() {
+            match digit.to_digit(radix) {
+                Some(digit) => {
+                    bits = (bits + (u64::from(digit) << FRACTION_BITS)) / u64::from(radix)
+                }
+                // Let the integer parser make the error
+                None => return i16::from_str_radix(fraction, radix).map(Q16::from),
+            }
+        }
+
+        let bits = bits as i32;
+        let whole = Q16::from(whole);
+        Ok(if negative { whole - Q16(bits) } else { whole + Q16(bits) })
+    }
+}
+
+impl ToPrimitive for Q16 {
+    /// Round towards zero, the same as casting a float.
+    #[inline]
+    fn to_i64(&self) -> Option<i64> {
+        let whole = self.0 >> FRACTION_BITS;
+
+        if self.0 < 0 && self.0 & FRACTION_MASK != 0 {
+            Some(i64::from(whole + 1))
+        } else {
+            Some(i64::from(whole))
+        }
+    }
+
+    #[inline]
+    fn to_u64(&self) -> Option<u64> {
+        self.to_i64().and_then(|value| value.to_u64())
+    }
+
+    #[inline]
+    fn to_f64(&self) -> Option<f64> {
+        Some(f64::from(self.0) / f64::from(1 << FRACTION_BITS))
+    }
+}
+
+impl num_traits::NumCast for Q16 {
+    /// Convert a number, rounding to the nearest step. Returns `None` if it's out of range or
+    /// NaN.
+    #[inline]
+    fn from<T: ToPrimitive>(value: T) -> Option<Self> {
+        let bits = Float::round(value.to_f64()? * (1 << FRACTION_BITS) as f64);
+
+        if bits >= i32::MIN as f64 && bits <= i32::MAX as f64 {
+            Some(Q16(bits as i32))
+        } else {
+            None
+        }
+    }
+}
+
+impl RealNum for Q16 {
+    #[inline]
+    fn cast<T: num_traits::NumCast>(value: T) -> Self {
+        <Q16 as num_traits::NumCast>::from(value).unwrap()
+    }
+
+    #[inline]
+    fn to_int<O: SignedNum>(self) -> O {
+        O::cast(self)
+    }
+
+    #[inline]
+    fn floor(self) -> Self {
+        Q16(self.0 & !FRACTION_MASK)
+    }
+
+    #[inline]
+    fn ceil(self) -> Self {
+        Q16((self.0 + FRACTION_MASK) & !FRACTION_MASK)
+    }
+
+    #[inline]
+    fn round(self) -> Self {
+        let half = 1 << (FRACTION_BITS - 1);
+
+        if self.0 < 0 {
+            -Q16(-self.0 + half).floor()
+        } else {
+            Q16(self.0 + half).floor()
+        }
+    }
+
+    #[inline]
+    fn abs(self) -> Self {
+        Q16(self.0.abs())
+    }
+
+    #[inline]
+    fn is_finite(self) -> bool {
+        true
+    }
+}
+
+#[test]
+fn tests() {
+    use {Midpoint, VoxelOrigin, WalkVoxels, XiaolinWu};
+
+    let q16 = |value: f64| <Q16 as RealNum>::cast(value);
+
+    assert_eq!(Q16::from(3).to_bits(), 3 << 16);
+    assert_eq!(q16(-1.5), Q16::from_bits(-0x18000));
+    assert_eq!(q16(1.0 / 65536.0), Q16::from_bits(1));
+    assert_eq!(<Q16 as num_traits::NumCast>::from(40000.0), None);
+    assert_eq!(<Q16 as num_traits::NumCast>::from(f32::NAN), None);
+
+    assert_eq!(q16(1.5) + q16(2.25), q16(3.75));
+    assert_eq!(q16(1.5) - q16(2.25), q16(-0.75));
+    assert_eq!(q16(1.5) * q16(-2.25), q16(-3.375));
+    assert_eq!(q16(-3.375) / q16(1.5), q16(-2.25));
+    assert_eq!(q16(7.5) % q16(2.0), q16(1.5));
+
+    // Rounding is the same as with floats
+    for &value in &[-2.5, -2.25, -2.0, -0.75, -0.5, 0.0, 0.25, 0.5, 1.5, 2.75, 3.0] {
+        assert_eq!(q16(value).floor(), q16(value.floor()));
+        assert_eq!(q16(value).ceil(), q16(value.ceil()));
+        assert_eq!(RealNum::round(q16(value)), q16(value.round()));
+        assert_eq!(RealNum::abs(q16(value)), q16(value.abs()));
+        assert_eq!(q16(value).to_i32(), Some(value as i32));
+        assert_eq!(q16(value).to_f64(), Some(value));
+    }
+
+    assert_eq!(Q16::from_str_radix("-12.375", 10), Ok(q16(-12.375)));
+    assert_eq!(Q16::from_str_radix("0.5", 10), Ok(q16(0.5)));
+    assert_eq!(Q16::from_str_radix("-.25", 10), Ok(q16(-0.25)));
+    assert_eq!(Q16::from_str_radix("7", 10), Ok(q16(7.0)));
+    assert_eq!(Q16::from_str_radix("1.8", 16), Ok(q16(1.5)));
+    assert!(Q16::from_str_radix("1.2x", 10).is_err());
+    assert!(Q16::from_str_radix("", 10).is_err());
+
+    // The algorithms give the same points as with floats, for end points that fit exactly
+    let ends = [
+        ((0.25, 0.5), (2.75, 7.75)),
+        ((-4.5, 3.125), (9.0, -1.0)),
+        ((6.0, 3.0), (0.0, 0.0)),
+        ((1.5, -2.5), (1.5, 8.5)),
+    ];
+
+    for &(start, end) in &ends {
+        let fixed = |(x, y)| (q16(x), q16(y));
+
+        assert_eq!(
+            Midpoint::<Q16, i32>::new(fixed(start), fixed(end)).collect::<Vec<_>>(),
+            Midpoint::<f64, i32>::new(start, end).collect::<Vec<_>>()
+        );
+
+        let xiaolin_wu = XiaolinWu::<Q16, i32>::new(fixed(start), fixed(end));
+        let float = XiaolinWu::<f64, i32>::new(start, end);
+        assert_eq!(xiaolin_wu.clone().count(), float.clone().count());
+        for ((point, coverage), (float_point, float_coverage)) in xiaolin_wu.zip(float) {
+            assert_eq!(point, float_point);
+            assert!((coverage.to_f64().unwrap() - float_coverage).abs() < 0.001);
+        }
+
+        let voxel = |(x, y): (f64, f64)| (x, y, x - y);
+        let fixed_voxel = |point| {
+            let (x, y, z) = voxel(point);
+            (q16(x), q16(y), q16(z))
+        };
+
+        for &origin in &[VoxelOrigin::Center, VoxelOrigin::Corner] {
+            assert_eq!(
+                WalkVoxels::<Q16, i32>::new(fixed_voxel(start), fixed_voxel(end), origin)
+                    .collect::<Vec<_>>(),
+                WalkVoxels::<f64, i32>::new(voxel(start), voxel(end), origin).collect::<Vec<_>>()
+            );
+        }
+    }
+
+    // Longer walks as well, where the distances to the planes get multiplied by the lengths
+    for &(start, end) in &[
+        ((0.0, 0.0, 0.0), (60.0, 60.0, 20.0)),
+        ((0.0, 0.0, 0.0), (100.0, 60.0, 5.0)),
+        ((-90.25, 17.375, 3.125), (80.625, -40.75, -60.125)),
+        ((0.25, 0.25, 0.25), (150.75, 150.75, 150.75)),
+    ] {
+        let fixed = |(x, y, z)| (q16(x), q16(y), q16(z));
+        let single = |(x, y, z): (f64, f64, f64)| (x as f32, y as f32, z as f32);
+
+        for &origin in &[VoxelOrigin::Center, VoxelOrigin::Corner] {
+            let walk = WalkVoxels::<Q16, i32>::new(fixed(start), fixed(end), origin)
+                .collect::<Vec<_>>();
+
+            assert!(walk.len() > 100);
+            assert_eq!(
+                walk,
+                WalkVoxels::<f32, i32>::new(single(start), single(end), origin).collect::<Vec<_>>()
+            );
+        }
+    }
+
+    for &end in &[(60, 60, 20), (100, 60, 5)] {
+        let walk = WalkVoxels::<Q16, i32>::from_coords((0, 0, 0), end, VoxelOrigin::Center);
+        assert_eq!(walk.last(), Some(end));
+    }
+
+    assert_eq!(
+        Midpoint::<Q16, i16>::from_coords((0, 0), (5, 2)),
+        Midpoint::new((Q16::from(0), Q16::from(0)), (Q16::from(5), Q16::from(2)))
+    );
+}
@@ -0,0 +1,491 @@
+use {div_floor, BresenhamCircle, FloatNum, IntoPoint, Point, SignedNum, Supercover};
+
+/// Walk lines from a center point out to every cell on the edge of a circle around it.
+///
+/// This combines [`BresenhamCircle`] and [`Supercover`] to give the rays for a simple ray-casting
+/// field of view. Returns `(ray, point)` pairs, where `ray` is the index of the ray the point
+/// belongs to. Every ray starts at the center and goes out in order, so a ray can be cut short by
+/// skipping the rest of its points once something blocks it.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::RayFan;
+///
+/// fn main() {
+///     for (ray, (x, y)) in RayFan::new((0, 0), 1) {
+///         print!("{}: ({}, {}), ", ray, x, y);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// 0: (0, 0), 0: (1, 0), 1: (0, 0), 1: (0, 1), 2: (0, 0), 2: (-1, 0), 3: (0, 0), 3: (0, -1),
+/// ```
+///
+/// [`BresenhamCircle`]: struct.BresenhamCircle.html
+/// [`Supercover`]: struct.Supercover.html
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "T: ::serde::Serialize, T::Wide: ::serde::Serialize",
+        deserialize = "T: ::serde::Deserialize<'de>, T::Wide: ::serde::Deserialize<'de>"
+    ))
+)]
+pub struct RayFan<T: SignedNum> {
+    center: Point<T>,
+    radius: usize,
+    circle: BresenhamCircle<T>,
+    ray: usize,
+    line: Option<Supercover<T>>,
+}
+
+impl<T: SignedNum> RayFan<T> {
+    #[inline]
+    pub fn new(center: impl IntoPoint<T>, radius: T) -> Self {
+        let center = center.into_point();
+
+        let mut circle = BresenhamCircle::new(center.0, center.1, radius);
+        let line = circle.next().map(|end| Supercover::new(center, end));
+
+        Self {
+            center,
+            radius: radius.to_usize().unwrap_or(0),
+            circle,
+            ray: 0,
+            line,
+        }
+    }
+}
+
+impl<T: SignedNum> Iterator for RayFan<T> {
+    type Item = (usize, Point<T>);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(point) = self.line.as_mut()?.next() {
+                return Some((self.ray, point));
+            }
+
+            let center = self.center;
+            self.line = self.circle.next().map(|end| Supercover::new(center, end));
+            self.ray += 1;
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (low, high) = match self.line {
+            Some(ref line) => line.size_hint(),
+            None => (0, Some(0)),
+        };
+
+        // Every point on the circle is at least half the radius away on one axis and at most the
+        // radius away on both, which limits how long the rest of the rays can be
+        let rays = self.circle.len();
+
+        (
+            low.saturating_add(rays.saturating_mul(self.radius / 2 + 1)),
+            high.and_then(|high| high.checked_add(rays.checked_mul(self.radius * 2 + 1)?)),
+        )
+    }
+}
+
+/// Check if there's a clear line of sight between two points.
+///
+/// The cells between `start` and `end` along a [`Supercover`] line are passed to `blocked` in
+/// order, stopping at the first one that blocks the view. The end points themselves aren't
+/// checked, so walls and other things standing on them can still be seen. Since [`Supercover`] is
+/// symmetrical, swapping `start` and `end` always gives the same answer.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::line_of_sight;
+///
+/// fn main() {
+///     let wall = |(x, y)| x == 2 && y < 3;
+///
+///     assert!(!line_of_sight((0, 0), (4, 2), wall));
+///     assert!(line_of_sight((0, 4), (4, 3), wall));
+/// }
+/// ```
+///
+/// [`Supercover`]: struct.Supercover.html
+#[inline]
+pub fn line_of_sight<T, F>(start: Point<T>, end: Point<T>, blocked: F) -> bool
+where
+    T: SignedNum,
+    F: FnMut(Point<T>) -> bool,
+{
+    first_blocked(start, end, blocked).is_none()
+}
+
+/// Like [`line_of_sight`] but returns the first cell that blocks the view, if there is one.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::first_blocked;
+///
+/// fn main() {
+///     let wall = |(x, y)| x == 2 && y < 3;
+///
+///     assert_eq!(first_blocked((0, 0), (4, 2), wall), Some((2, 1)));
+///     assert_eq!(first_blocked((0, 4), (4, 3), wall), None);
+/// }
+/// ```
+///
+/// [`line_of_sight`]: fn.line_of_sight.html
+#[inline]
+pub fn first_blocked<T, F>(start: Point<T>, end: Point<T>, mut blocked: F) -> Option<Point<T>>
+where
+    T: SignedNum,
+    F: FnMut(Point<T>) -> bool,
+{
+    Supercover::new(start, end)
+        .skip(1)
+        .take_while(|&point| point != end)
+        .find(|&point| blocked(point))
+}
+
+/// Check if there's a clear line of sight over a heightmap.
+///
+/// This is [`line_of_sight`] with elevation. `start_height` and `end_height` are the heights of the
+/// observer's eye and the target, which are joined by a straight line. Each cell between them is
+/// blocked if its `height` is above that line where it passes the center of the cell.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::terrain_line_of_sight;
+///
+/// fn main() {
+///     let hill = |(x, _): (i32, i32)| if x == 5 { 3.0 } else { 0.0 };
+///
+///     assert!(!terrain_line_of_sight((0, 0), 2.0, (10, 0), 2.0, hill));
+///     assert!(terrain_line_of_sight((0, 0), 2.0, (10, 0), 5.0, hill));
+/// }
+/// ```
+///
+/// [`line_of_sight`]: fn.line_of_sight.html
+#[inline]
+pub fn terrain_line_of_sight<T, I, F>(
+    start: Point<T>,
+    start_height: I,
+    end: Point<T>,
+    end_height: I,
+    height: F,
+) -> bool
+where
+    T: SignedNum,
+    I: FloatNum,
+    F: FnMut(Point<T>) -> I,
+{
+    first_terrain_blocked(start, start_height, end, end_height, height).is_none()
+}
+
+/// Like [`terrain_line_of_sight`] but returns the first cell that blocks the view, if there is
+/// one.
+///
+/// [`terrain_line_of_sight`]: fn.terrain_line_of_sight.html
+#[inline]
+pub fn first_terrain_blocked<T, I, F>(
+    start: Point<T>,
+    start_height: I,
+    end: Point<T>,
+    end_height: I,
+    mut height: F,
+) -> Option<Point<T>>
+where
+    T: SignedNum,
+    I: FloatNum,
+    F: FnMut(Point<T>) -> I,
+{
+    let (dx, dy) = (I::cast(end.0 - start.0), I::cast(end.1 - start.1));
+    let length = dx * dx + dy * dy;
+
+    first_blocked(start, end, |point| {
+        // How far along the line the center of the cell is
+        let (x, y) = (I::cast(point.0 - start.0), I::cast(point.1 - start.1));
+        let t = ((x * dx + y * dy) / length).max(I::zero()).min(I::one());
+
+        height(point) > start_height + (end_height - start_height) * t
+    })
+}
+
+/// A row of cells at the same depth in one quadrant, between two slopes stored as
+/// `(numerator, denominator)` pairs with positive denominators.
+struct Row<T> {
+    depth: T,
+    start: Point<T>,
+    end: Point<T>,
+}
+
+/// Recursively scan one quadrant, starting at the given row.
+fn scan<T, P, O, V>(row: Row<T>, radius: T, transform: &P, opaque: &O, visible: &mut V)
+where
+    T: SignedNum,
+    P: Fn(T, T) -> Point<T>,
+    O: Fn(Point<T>) -> bool,
+    V: FnMut(Point<T>),
+{
+    if row.depth > radius {
+        return;
+    }
+
+    let two = T::cast(2);
+    let Row {
+        depth,
+        mut start,
+        end,
+    } = row;
+
+    // Round the start slope with ties going up and the end slope with ties going down
+    let min_column = div_floor(two * depth * start.0 + start.1, two * start.1).0;
+    let max_column = -div_floor(end.1 - two * depth * end.0, two * end.1).0;
+
+    let mut previous_opaque = None;
+    let mut column = min_column;
+
+    while column <= max_column {
+        let point = transform(depth, column);
+        let is_opaque = opaque(point);
+
+        // Floors are only seen if their center is inside the visible area, which keeps it symmetric
+        let symmetric = column * start.1 >= depth * start.0 && column * end.1 <= depth * end.0;
+
+        if (is_opaque || symmetric) && column * column + depth * depth <= radius * radius {
+            visible(point);
+        }
+
+        let slope = (two * column - T::one(), two * depth);
+
+        match (previous_opaque, is_opaque) {
+            (Some(true), false) => start = slope,
+            (Some(false), true) => {
+                let next = Row {
+                    depth: depth + T::one(),
+                    start,
+                    end: slope,
+                };
+                scan(next, radius, transform, opaque, visible);
+            }
+            _ => {}
+        }
+
+        previous_opaque = Some(is_opaque);
+        column += T::one();
+    }
+
+    if previous_opaque == Some(false) {
+        let next = Row {
+            depth: depth + T::one(),
+            start,
+            end,
+        };
+        scan(next, radius, transform, opaque, visible);
+    }
+}
+
+/// Find the cells that can be seen from a point using [symmetric shadowcasting].
+///
+/// The `opaque` callback says which cells block sight, and `visible` is called for every cell
+/// that can be seen within `radius` of `center`, including the center itself. Opaque cells are
+/// visible when any part of them can be seen, but other cells only when their center can, so if
+/// one floor cell can see another, the second can always see the first.
+///
+/// The four quadrants are scanned separately, so cells on the diagonals through the center may
+/// be passed to `visible` twice.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::shadowcast;
+///
+/// fn main() {
+///     let map = ["#####", "#...#", "#.#.#", "#...#", "#####"];
+///     let mut seen = [[false; 5]; 5];
+///
+///     shadowcast(
+///         (1, 1),
+///         4,
+///         |(x, y)| map[y as usize].as_bytes()[x as usize] == b'#',
+///         |(x, y)| seen[y as usize][x as usize] = true,
+///     );
+///
+///     for row in &seen {
+///         let row = row.iter().map(|&seen| if seen { '*' } else { '.' });
+///         println!("{}", row.collect::<String>());
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// *****
+/// *****
+/// *****
+/// ***..
+/// ***..
+/// ```
+///
+/// [symmetric shadowcasting]: https://www.albertford.com/shadowcasting/
+pub fn shadowcast<T, O, V>(center: Point<T>, radius: T, opaque: O, mut visible: V)
+where
+    T: SignedNum,
+    O: Fn(Point<T>) -> bool,
+    V: FnMut(Point<T>),
+{
+    if radius < T::zero() {
+        return;
+    }
+
+    visible(center);
+
+    let (x, y) = center;
+    let first = || Row {
+        depth: T::one(),
+        start: (-T::one(), T::one()),
+        end: (T::one(), T::one()),
+    };
+
+    let north = |depth, column| (x + column, y - depth);
+    let south = |depth, column| (x + column, y + depth);
+    let east = |depth, column| (x + depth, y + column);
+    let west = |depth, column| (x - depth, y + column);
+
+    scan(first(), radius, &north, &opaque, &mut visible);
+    scan(first(), radius, &south, &opaque, &mut visible);
+    scan(first(), radius, &east, &opaque, &mut visible);
+    scan(first(), radius, &west, &opaque, &mut visible);
+}
+
+#[test]
+fn tests() {
+    let fan = RayFan::new((5, -3), 7).collect::<Vec<_>>();
+    let rays = BresenhamCircle::new(5, -3, 7).collect::<Vec<_>>();
+
+    // Each ray goes from the center to its point on the circle
+    for (i, &end) in rays.iter().enumerate() {
+        let ray = fan
+            .iter()
+            .filter(|&&(ray, _)| ray == i)
+            .map(|&(_, point)| point)
+            .collect::<Vec<_>>();
+
+        assert_eq!(ray, Supercover::new((5, -3), end).collect::<Vec<_>>());
+    }
+
+    assert_eq!(fan.last().unwrap().0, rays.len() - 1);
+    assert_eq!(RayFan::new((0, 0), 0).count(), 0);
+
+    // Every cell in an empty area within the radius is visible
+    let mut seen = Vec::new();
+    shadowcast((3, -2), 5, |_| false, |point| seen.push(point));
+    seen.sort();
+    seen.dedup();
+
+    let mut expected = Vec::new();
+    for x in -2..=8 {
+        for y in -7..=3 {
+            if (x - 3) * (x - 3) + (y + 2) * (y + 2) <= 25 {
+                expected.push((x, y));
+            }
+        }
+    }
+    assert_eq!(seen, expected);
+
+    // Floor cells can see each other both ways
+    let map = [
+        "..#.....#...",
+        "....##......",
+        ".#.......#..",
+        "......#.....",
+        "..##.....#..",
+        ".........#..",
+        "....#.......",
+        ".#.....##...",
+        "........#...",
+        "...#.....#..",
+        "......#.....",
+        "..#.........",
+    ];
+    let opaque = |(x, y): Point<i32>| {
+        x < 0 || y < 0 || x >= 12 || y >= 12 || map[y as usize].as_bytes()[x as usize] == b'#'
+    };
+    let visible_from = |center| {
+        let mut seen = [[false; 12]; 12];
+        shadowcast(center, 20, opaque, |(x, y)| {
+            if !opaque((x, y)) {
+                seen[y as usize][x as usize] = true;
+            }
+        });
+        seen
+    };
+
+    for y in 0..12 {
+        for x in 0..12 {
+            if opaque((x, y)) {
+                continue;
+            }
+
+            let seen = visible_from((x, y));
+
+            for (other_y, row) in seen.iter().enumerate() {
+                for (other_x, &seen) in row.iter().enumerate() {
+                    if seen {
+                        let back = visible_from((other_x as i32, other_y as i32));
+                        assert!(back[y as usize][x as usize]);
+                    }
+                }
+            }
+        }
+    }
+
+    // The end points aren't checked
+    assert!(line_of_sight((0, 0), (3, 0), |point| point == (0, 0)));
+    assert!(line_of_sight((0, 0), (3, 0), |point| point == (3, 0)));
+    assert!(line_of_sight((1, 1), (1, 1), |_| true));
+    assert!(!line_of_sight((0, 0), (2, 0), |point| point == (1, 0)));
+
+    // Corners are crossed diagonally
+    assert!(line_of_sight((0, 0), (2, 2), |point| point == (1, 0) || point == (0, 1)));
+
+    // Stops at the first blocking cell
+    let mut checked = Vec::new();
+    let hit = first_blocked((0, 0), (6, 0), |point| {
+        checked.push(point);
+        point.0 >= 3
+    });
+    assert_eq!(hit, Some((3, 0)));
+    assert_eq!(checked, [(1, 0), (2, 0), (3, 0)]);
+
+    // Line of sight is the same both ways
+    let wall = |(x, y): Point<i32>| (x * 7 + y * 13) % 5 == 0;
+    for &(a, b) in &[((0, 0), (9, 4)), ((-3, 8), (6, -2)), ((2, 2), (-7, -7))] {
+        assert_eq!(line_of_sight(a, b, wall), line_of_sight(b, a, wall));
+    }
+
+    // Looking over terrain
+    let hill = |(x, y): Point<i32>| if (x, y) == (3, 1) { 2.0 } else { 0.0 };
+    assert_eq!(
+        first_terrain_blocked((0, 0), 1.0, (6, 2), 1.0, hill),
+        Some((3, 1))
+    );
+    assert_eq!(first_terrain_blocked((0, 0), 1.0, (6, 2), 3.0, hill), None);
+    assert_eq!(first_terrain_blocked((0, 0), 3.0, (6, 2), 1.5, hill), None);
+
+    // The line between the heights is followed from one end to the other
+    let slope = |(x, _): Point<i32>| f64::from(x);
+    assert!(terrain_line_of_sight((0, 0), 0.5, (8, 0), 8.5, slope));
+    assert!(!terrain_line_of_sight((0, 0), 0.0, (8, 0), 7.5, slope));
+    assert!(terrain_line_of_sight((5, 5), 0.0, (5, 5), 0.0, |_| 10.0));
+}
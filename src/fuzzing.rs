@@ -6,7 +6,8 @@ use self::rand::Rng;
 use self::rand::distributions::uniform::SampleUniform;
 use ::*;
 
-use std::ops::Neg;
+use std::ops::{ControlFlow, Neg};
+use std::fmt::Debug;
 
 const NUM_TESTS: u16 = 10000;
 const RANGE: isize = 500;
@@ -51,6 +52,19 @@ fn supercover_symmetrical() {
     }
 }
 
+#[test]
+fn bresenham_symmetric_symmetrical() {
+    let bresenham = |a, b| Bresenham::symmetric(a, b).collect::<Vec<_>>();
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..NUM_TESTS {
+        let start = random_point(&mut rng, RANGE);
+        let end = random_point(&mut rng, RANGE);
+
+        assert_eq!(bresenham(start, end), reverse_slice(&bresenham(end, start)));
+    }
+}
+
 #[test]
 #[should_panic]
 fn bresenham_not_symmetrical() {
@@ -87,7 +101,7 @@ fn bresenham_3d_not_symmetrical() {
 fn walk_voxels_symmetrical() {
     use VoxelOrigin::Center;
 
-    let walk_voxels = |a, b| WalkVoxels::<_, i16>::new(a, b, &Center).collect::<Vec<_>>();
+    let walk_voxels = |a, b| WalkVoxels::<_, i16>::new(a, b, Center).collect::<Vec<_>>();
     let mut rng = rand::thread_rng();
 
     for _ in 0..NUM_TESTS {
@@ -100,3 +114,794 @@ fn walk_voxels_symmetrical() {
         );
     }
 }
+
+#[test]
+fn bresenham_point_at() {
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..NUM_TESTS {
+        let start = random_point(&mut rng, RANGE);
+        let end = random_point(&mut rng, RANGE);
+        let points = Bresenham::new(start, end).collect::<Vec<_>>();
+
+        let mut line = Bresenham::new(start, end);
+        let skip = rng.gen_range(0..points.len());
+        assert_eq!(line.nth(skip), Some(points[skip]));
+
+        let index = rng.gen_range(0..points.len() + 1);
+        assert_eq!(line.point_at(index), points.get(skip + 1 + index).cloned());
+    }
+}
+
+/// Check that counting the items gives the same as going through them.
+fn assert_count<I: Iterator + Clone>(iterator: I) {
+    assert_eq!(
+        iterator.clone().count(),
+        iterator.fold(0, |count, _| count + 1)
+    );
+}
+
+#[test]
+fn counts() {
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..NUM_TESTS / 10 {
+        let start = random_point(&mut rng, RANGE);
+        let end = random_point(&mut rng, RANGE);
+        let start_float = random_point(&mut rng, RANGE_FLOAT);
+        let end_float = random_point(&mut rng, RANGE_FLOAT);
+        let radius = rng.gen_range(0..100);
+
+        assert_count(Bresenham::new(start, end));
+        assert_count(Bresenham::new(start, end).steps());
+        assert_count(Bresenham::new(start, end).wrapped((16, 16)));
+        assert_count(Bresenham3d::new(
+            random_voxel(&mut rng, RANGE),
+            random_voxel(&mut rng, RANGE),
+        ));
+        assert_count(Midpoint::<f32, isize>::new(start_float, end_float));
+        assert_count(BresenhamCircle::new(start.0, start.1, radius));
+        assert_count(BresenhamCircle::contiguous(start.0, start.1, radius));
+        assert_count(MidpointEllipse::new(
+            0,
+            0,
+            radius as i64,
+            end.0.abs() as i64 / 5,
+        ));
+        assert_count(WalkGrid::new(start, end));
+        assert_count(WalkGrid::new(start, end).directions());
+        assert_count(Supercover::new(start, end));
+        assert_count(Supercover::<isize>::from_float(start_float, end_float));
+        assert_count(WalkEdges::new(start, end));
+        assert_count(HexLine::new(start, end));
+        assert_count(WalkVoxels::<f32, isize>::new(
+            random_voxel(&mut rng, RANGE_FLOAT),
+            random_voxel(&mut rng, RANGE_FLOAT),
+            VoxelOrigin::Center,
+        ));
+        assert_count(WalkIso::<f32, isize>::new(
+            start_float,
+            end_float,
+            (2.0, 1.0),
+        ));
+    }
+}
+
+/// Check that folding goes through the same items as calling `next`, also after some of them have
+/// been taken.
+fn assert_fold<I: Iterator + Clone>(mut iterator: I)
+where
+    I::Item: PartialEq + Debug,
+{
+    for _ in 0..3 {
+        // A `for` loop calls `next`
+        let mut expected = Vec::new();
+        for item in iterator.clone() {
+            expected.push(item);
+        }
+
+        let folded = iterator.clone().fold(Vec::new(), |mut items, item| {
+            items.push(item);
+            items
+        });
+
+        assert_eq!(folded, expected);
+        iterator.next();
+    }
+}
+
+#[test]
+fn folds() {
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..NUM_TESTS / 10 {
+        let start = random_point(&mut rng, RANGE);
+        let end = random_point(&mut rng, RANGE);
+        let start_float = random_point(&mut rng, RANGE_FLOAT);
+        let end_float = random_point(&mut rng, RANGE_FLOAT);
+        let bounds = GridBounds::new(random_point(&mut rng, RANGE), random_point(&mut rng, RANGE));
+
+        assert_fold(Bresenham::new(start, end));
+        assert_fold(Bresenham::clipped(start, end, bounds));
+        assert_fold(Bresenham::symmetric(start, end));
+        assert_fold(Midpoint::<f32, isize>::new(start_float, end_float));
+        assert_fold(WalkGrid::new(start, end));
+        assert_fold(WalkGrid::clipped(start, end, bounds));
+        assert_fold(WalkGrid::<isize>::from_float(start_float, end_float));
+
+        for &algorithm in &LineAlgorithm::ALL {
+            assert_fold(rasterize(start, end, algorithm));
+            assert_fold(rasterize(start, end, algorithm).within(bounds));
+        }
+
+        let table = Transfer::<f32>::Srgb.table();
+        let line = XiaolinWu::<f32, isize>::new(start_float, end_float);
+        assert_fold(line.clone().gamma_corrected(Transfer::Gamma(2.2)));
+        assert_fold(line.alpha::<u8>().gamma_table(&table));
+
+        // Morton codes need points that aren't negative
+        let positive = GridBounds::new((0, 0), (RANGE, RANGE));
+        assert_fold(Bresenham::new(start, end).within(positive).morton_codes());
+        assert_fold(Bresenham::new(start, end).within(positive).with_morton());
+    }
+}
+
+/// Check that the last item is the same as the one found by going through them all, also after
+/// some of them have been taken.
+fn assert_last<I: Iterator + Clone>(mut iterator: I)
+where
+    I::Item: PartialEq + Debug,
+{
+    for _ in 0..3 {
+        assert_eq!(
+            iterator.clone().last(),
+            iterator.clone().fold(None, |_, item| Some(item))
+        );
+        iterator.next();
+    }
+}
+
+#[test]
+fn lasts() {
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..NUM_TESTS / 10 {
+        let start = random_point(&mut rng, RANGE);
+        let end = random_point(&mut rng, RANGE);
+        let start_float = random_point(&mut rng, RANGE_FLOAT);
+        let end_float = random_point(&mut rng, RANGE_FLOAT);
+        let bounds = GridBounds::new(random_point(&mut rng, RANGE), random_point(&mut rng, RANGE));
+
+        assert_last(Bresenham::new(start, end));
+        assert_last(Bresenham::clipped(start, end, bounds));
+        assert_last(Bresenham::new(start, end).wrapped((16, 16)));
+        assert_last(Bresenham3d::new(
+            random_voxel(&mut rng, RANGE),
+            random_voxel(&mut rng, RANGE),
+        ));
+        assert_last(WalkGrid::new(start, end));
+        assert_last(WalkGrid::clipped(start, end, bounds));
+        assert_last(WalkGrid::<isize>::from_float(start_float, end_float));
+        assert_last(Supercover::new(start, end));
+        assert_last(Supercover::clipped(start, end, bounds));
+        assert_last(Supercover::<isize>::from_float(start_float, end_float));
+        assert_last(HexLine::new(start, end));
+    }
+}
+
+/// Check that skipping items with `nth` lands on the same ones as going through them all, for a
+/// few different numbers of items skipped each time.
+fn assert_nth<I: Iterator + Clone>(iterator: I)
+where
+    I::Item: Clone + PartialEq + Debug,
+{
+    let expected = iterator.clone().collect::<Vec<_>>();
+
+    for &skip in &[0, 1, 2, 7, expected.len() / 3] {
+        let mut iterator = iterator.clone();
+        let mut taken = Vec::new();
+
+        while let Some(item) = iterator.nth(skip) {
+            taken.push(item);
+        }
+
+        assert_eq!(
+            taken,
+            expected
+                .iter()
+                .skip(skip)
+                .step_by(skip + 1)
+                .cloned()
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(iterator.next(), None);
+    }
+}
+
+#[test]
+fn nths() {
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..NUM_TESTS / 100 {
+        let start = random_point(&mut rng, RANGE);
+        let end = random_point(&mut rng, RANGE);
+        let start_float = random_point(&mut rng, RANGE_FLOAT);
+        let end_float = random_point(&mut rng, RANGE_FLOAT);
+        let bounds = GridBounds::new(random_point(&mut rng, RANGE), random_point(&mut rng, RANGE));
+
+        assert_nth(Bresenham::new(start, end));
+        assert_nth(WalkGrid::new(start, end));
+        assert_nth(WalkGrid::clipped(start, end, bounds));
+        assert_nth(WalkGrid::<isize>::from_float(start_float, end_float));
+        assert_nth(Supercover::new(start, end));
+        assert_nth(Supercover::clipped(start, end, bounds));
+        assert_nth(Supercover::<isize>::from_float(start_float, end_float));
+
+        // Lines that pass through lots of corners
+        let scale = rng.gen_range(-RANGE / 7..RANGE / 7);
+        assert_nth(Supercover::new(start, (start.0 + 7 * scale, start.1 - 3 * scale)));
+        assert_nth(Supercover::new(start, (start.0 + scale, start.1 + scale)));
+    }
+}
+
+#[test]
+fn axis_lines() {
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..NUM_TESTS / 10 {
+        let start = random_point(&mut rng, RANGE);
+        let length = rng.gen_range(-RANGE..RANGE);
+        let bounds = GridBounds::new(random_point(&mut rng, RANGE), random_point(&mut rng, RANGE));
+
+        for &step in &[(length.signum(), 0), (0, length.signum())] {
+            let end = (start.0 + step.0 * length.abs(), start.1 + step.1 * length.abs());
+            let points = (0..=length.abs())
+                .map(|i| (start.0 + step.0 * i, start.1 + step.1 * i))
+                .collect::<Vec<_>>();
+            let inside = points
+                .iter()
+                .cloned()
+                .filter(|&point| bounds.contains(point))
+                .collect::<Vec<_>>();
+
+            assert_eq!(Bresenham::new(start, end).collect::<Vec<_>>(), points);
+            assert_eq!(Bresenham::symmetric(start, end).collect::<Vec<_>>(), points);
+            assert_eq!(WalkGrid::new(start, end).collect::<Vec<_>>(), points);
+            assert_eq!(Supercover::new(start, end).collect::<Vec<_>>(), points);
+
+            assert_eq!(Bresenham::clipped(start, end, bounds).collect::<Vec<_>>(), inside);
+            assert_eq!(WalkGrid::clipped(start, end, bounds).collect::<Vec<_>>(), inside);
+            assert_eq!(Supercover::clipped(start, end, bounds).collect::<Vec<_>>(), inside);
+
+            assert_nth(Bresenham::new(start, end));
+            assert_nth(WalkGrid::new(start, end));
+            assert_nth(Supercover::new(start, end));
+            assert_last(Bresenham::clipped(start, end, bounds));
+            assert_last(WalkGrid::clipped(start, end, bounds));
+            assert_last(Supercover::clipped(start, end, bounds));
+        }
+    }
+}
+
+#[test]
+fn diagonal_lines() {
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..NUM_TESTS / 10 {
+        let start = random_point(&mut rng, RANGE);
+        let length = rng.gen_range(0..RANGE);
+        let bounds = GridBounds::new(random_point(&mut rng, RANGE), random_point(&mut rng, RANGE));
+
+        for &step in &[(1, 1), (1, -1), (-1, 1), (-1, -1)] {
+            let end = (start.0 + step.0 * length, start.1 + step.1 * length);
+            let points = (0..=length)
+                .map(|i| (start.0 + step.0 * i, start.1 + step.1 * i))
+                .collect::<Vec<_>>();
+            let inside = points
+                .iter()
+                .cloned()
+                .filter(|&point| bounds.contains(point))
+                .collect::<Vec<_>>();
+
+            assert_eq!(Bresenham::new(start, end).collect::<Vec<_>>(), points);
+            assert_eq!(Bresenham::symmetric(start, end).collect::<Vec<_>>(), points);
+            assert_eq!(Supercover::new(start, end).collect::<Vec<_>>(), points);
+
+            assert_eq!(Bresenham::clipped(start, end, bounds).collect::<Vec<_>>(), inside);
+            assert_eq!(Supercover::clipped(start, end, bounds).collect::<Vec<_>>(), inside);
+
+            assert_nth(Bresenham::new(start, end));
+            assert_nth(WalkGrid::new(start, end));
+            assert_nth(Supercover::new(start, end));
+            assert_last(Bresenham::clipped(start, end, bounds));
+            assert_last(WalkGrid::clipped(start, end, bounds));
+            assert_last(Supercover::clipped(start, end, bounds));
+        }
+    }
+}
+
+#[test]
+fn xiaolin_wu_lut() {
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..NUM_TESTS {
+        let start = random_point(&mut rng, RANGE);
+        let end = random_point(&mut rng, RANGE);
+
+        // Work out each column with a division, where the line is `rise * column / delta` along
+        let steep = (end.1 - start.1).abs() > (end.0 - start.0).abs();
+        let flip = |(x, y): Point<isize>| if steep { (y, x) } else { (x, y) };
+        let (mut a, mut b) = (flip(start), flip(end));
+        if a.0 > b.0 {
+            std::mem::swap(&mut a, &mut b);
+        }
+        let (delta, rise) = (b.0 - a.0, b.1 - a.1);
+
+        let mut expected = Vec::new();
+        for column in 0..=delta {
+            let (whole, part) = if delta == 0 {
+                (0, 0)
+            } else {
+                ((rise * column).div_euclid(delta), (rise * column).rem_euclid(delta))
+            };
+            let index = (((part as u64) << 32) / delta.max(1) as u64) >> 28;
+            let (x, y) = (a.0 + column, a.1 + whole);
+
+            expected.push((flip((x, y)), LINEAR_COVERAGE[15 - index as usize]));
+            if part > 0 {
+                expected.push((flip((x, y + 1)), LINEAR_COVERAGE[index as usize]));
+            }
+        }
+
+        let line = XiaolinWuLut::new(start, end, &LINEAR_COVERAGE);
+        assert_eq!(line.collect::<Vec<_>>(), expected);
+
+        let mut directed = XiaolinWuLut::directed(start, end, &LINEAR_COVERAGE)
+            .map(|(point, _)| point)
+            .collect::<Vec<_>>();
+        assert_eq!(directed[0], start);
+        directed.sort();
+        let mut points = expected.iter().map(|&(point, _)| point).collect::<Vec<_>>();
+        points.sort();
+        assert_eq!(directed, points);
+    }
+}
+
+/// Check that the size hint holds the number of items left at every step.
+fn assert_size_hints<I: Iterator>(mut iterator: I) {
+    let mut hints = Vec::new();
+
+    loop {
+        hints.push(iterator.size_hint());
+
+        if iterator.next().is_none() {
+            break;
+        }
+    }
+
+    let count = hints.len() - 1;
+
+    for (index, &(low, high)) in hints.iter().enumerate() {
+        let remaining = count - index;
+        assert!(low <= remaining, "{} <= {}", low, remaining);
+        assert!(
+            high.is_none_or(|high| remaining <= high),
+            "{} <= {:?}",
+            remaining,
+            high
+        );
+    }
+}
+
+#[test]
+fn size_hints() {
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..NUM_TESTS / 10 {
+        let start = random_point(&mut rng, RANGE);
+        let end = random_point(&mut rng, RANGE);
+        let start_float = random_point(&mut rng, RANGE_FLOAT);
+        let end_float = random_point(&mut rng, RANGE_FLOAT);
+        let radius = rng.gen_range(0..100);
+        let radii = (rng.gen_range(0..100i64), rng.gen_range(0..100i64));
+
+        assert_size_hints(Bresenham::new(start, end));
+        assert_size_hints(Bresenham3d::new(
+            random_voxel(&mut rng, RANGE),
+            random_voxel(&mut rng, RANGE),
+        ));
+        assert_size_hints(Midpoint::<f32, isize>::new(start_float, end_float));
+        assert_size_hints(XiaolinWu::<f32, isize>::new(start_float, end_float));
+        assert_size_hints(XiaolinWuLut::new(start, end, &LINEAR_COVERAGE));
+        assert_size_hints(BresenhamCircle::new(start.0, start.1, radius));
+        assert_size_hints(BresenhamCircle::contiguous(start.0, start.1, radius));
+        assert_size_hints(MidpointEllipse::new(0, 0, radii.0, radii.1));
+        assert_size_hints(WalkGrid::new(start, end));
+        assert_size_hints(Supercover::new(start, end));
+        assert_size_hints(Supercover::new(start, end).events());
+        assert_size_hints(ThickSupercover::new(start, end));
+        assert_size_hints(WalkEdges::new(start, end));
+        assert_size_hints(SupercoverLengths::<f32, isize>::new(start, end));
+        assert_size_hints(HexLine::new(start, end));
+        assert_size_hints(HexSupercover::new(start, end));
+        assert_size_hints(RayFan::new(start, radius));
+        assert_size_hints(WalkVoxels::<f32, isize>::new(
+            random_voxel(&mut rng, RANGE_FLOAT),
+            random_voxel(&mut rng, RANGE_FLOAT),
+            VoxelOrigin::Center,
+        ));
+        assert_size_hints(WalkTiles::<f32, isize>::new(
+            start_float,
+            end_float,
+            (rng.gen_range(1.0..50.0), rng.gen_range(1.0..50.0)),
+        ));
+        assert_size_hints(WalkTriangles::<f32, isize>::new(
+            (start_float.0 / 10.0, start_float.1 / 10.0),
+            (end_float.0 / 10.0, end_float.1 / 10.0),
+        ));
+        assert_size_hints(WalkIso::<f32, isize>::new(
+            start_float,
+            end_float,
+            (2.0, 1.0),
+        ));
+        assert_size_hints(WalkOctree::<f32, isize, _>::new(
+            random_voxel(&mut rng, 40.0),
+            random_voxel(&mut rng, 40.0),
+            5,
+            |level, (x, y, z)| level > 2 || (x + y + z) & 1 == 0,
+        ));
+        assert_size_hints(GeodesicArc::<f32, isize>::new(
+            (0.0, 0.0, 0.0),
+            radius as f32,
+            random_voxel(&mut rng, 1.0),
+            random_voxel(&mut rng, 1.0),
+        ));
+
+        // The adapters
+        assert_size_hints(Bresenham::new(start, end).steps());
+        assert_size_hints(PixelPerfect::new(Bresenham::new(start, end)));
+        assert_size_hints(Mirror::new(
+            Bresenham::new(start, end),
+            Reflection::FlipX(start.0),
+        ));
+        assert_size_hints(Rotational::<f32, _, _>::new(
+            Bresenham::new(start, end),
+            (0.0, 0.0),
+            3,
+        ));
+        assert_size_hints(Decimate::new(
+            Bresenham::new(start, end),
+            rng.gen_range(1..5),
+        ));
+        assert_size_hints(Stipple::new(Bresenham::new(start, end), rng.gen(), 3));
+        assert_size_hints(Dashed::new(Bresenham::new(start, end), &[3.0f32, 2.0]));
+    }
+
+    for &(latitudes, longitudes) in &[(0, 0), (1, 0), (0, 3), (5, 4)] {
+        assert_size_hints(SphereWireframe::<f32, isize>::new(
+            (0.5, -3.0, 2.0),
+            12.5,
+            latitudes,
+            longitudes,
+        ));
+    }
+}
+
+fn walk_all<I: Iterator>(iterator: I) {
+    let (low, high) = iterator.size_hint();
+    let count = iterator.fold(0usize, |count, _| count + 1);
+
+    assert!(count >= low);
+    if let Some(high) = high {
+        assert!(count <= high);
+    }
+}
+
+#[test]
+fn try_new_walks() {
+    // Anything that `try_new` accepts should walk all the way to the end without overflowing, so
+    // the end points are picked from further than `i8` can reach
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..NUM_TESTS {
+        let (start, end) = (random_point(&mut rng, 100.0f32), random_point(&mut rng, 100.0f32));
+
+        if let Ok(line) = Midpoint::<f32, i8>::try_new(start, end) {
+            walk_all(line);
+        }
+        if let Ok(line) = XiaolinWu::<f32, i8>::try_new(start, end) {
+            walk_all(line);
+        }
+
+        let (start, end) = (random_voxel(&mut rng, 60.0f32), random_voxel(&mut rng, 60.0f32));
+
+        for &origin in &[VoxelOrigin::Corner, VoxelOrigin::Center] {
+            if let Ok(walk) = WalkVoxels::<f32, i8>::try_new(start, end, origin) {
+                walk_all(walk);
+            }
+        }
+
+        let (start, end) = rng.gen::<(Point<i8>, Point<i8>)>();
+
+        if let Ok(line) = Bresenham::try_new(start, end) {
+            walk_all(line);
+        }
+        if let Ok(line) = WalkGrid::try_new(start, end) {
+            walk_all(line);
+        }
+        if let Ok(line) = Supercover::try_new(start, end) {
+            walk_all(line);
+        }
+
+        let (start, end) = rng.gen::<(Voxel<i8>, Voxel<i8>)>();
+
+        if let Ok(line) = Bresenham3d::try_new(start, end) {
+            walk_all(line);
+        }
+
+        let (x, y, radius_x, radius_y) = rng.gen::<(i8, i8, i8, i8)>();
+
+        if let Ok(circle) = BresenhamCircle::try_new(x, y, radius_x) {
+            walk_all(circle);
+        }
+        if let Ok(ellipse) = MidpointEllipse::try_new(x, y, radius_x, radius_y) {
+            walk_all(ellipse);
+        }
+    }
+}
+
+/// Check that walking a line calls the function with the same points as the iterator, up to the
+/// one where it breaks after `stop` others.
+fn assert_walk<P, I, W>(line: I, walk: W, stop: usize)
+where
+    P: Copy + PartialEq + Debug,
+    I: Iterator<Item = P>,
+    W: FnOnce(&mut dyn FnMut(P) -> ControlFlow<P>) -> ControlFlow<P>,
+{
+    let mut points = Vec::new();
+    let result = walk(&mut |point| {
+        points.push(point);
+
+        if points.len() > stop {
+            ControlFlow::Break(point)
+        } else {
+            ControlFlow::Continue(())
+        }
+    });
+
+    let expected = line.take(stop + 1).collect::<Vec<_>>();
+    assert_eq!(points, expected);
+
+    if expected.len() > stop {
+        assert_eq!(result, ControlFlow::Break(expected[stop]));
+    } else {
+        assert_eq!(result, ControlFlow::Continue(()));
+    }
+}
+
+#[test]
+fn walks() {
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..NUM_TESTS / 10 {
+        let (start, end) = (random_point(&mut rng, RANGE), random_point(&mut rng, RANGE));
+        let stop = rng.gen_range(0..2000);
+
+        assert_walk(
+            Bresenham::new(start, end),
+            |function| Bresenham::walk(start, end, function),
+            stop,
+        );
+        assert_walk(
+            WalkGrid::new(start, end),
+            |function| WalkGrid::walk(start, end, function),
+            stop,
+        );
+        assert_walk(
+            Supercover::new(start, end),
+            |function| Supercover::walk(start, end, function),
+            stop,
+        );
+
+        let (start, end) = (random_voxel(&mut rng, RANGE), random_voxel(&mut rng, RANGE));
+
+        assert_walk(
+            Bresenham3d::new(start, end),
+            |function| Bresenham3d::walk(start, end, function),
+            stop,
+        );
+
+        let (start, end) = (
+            random_voxel(&mut rng, RANGE_FLOAT),
+            random_voxel(&mut rng, RANGE_FLOAT),
+        );
+
+        for &origin in &[VoxelOrigin::Corner, VoxelOrigin::Center] {
+            assert_walk(
+                WalkVoxels::<f32, isize>::new(start, end, origin),
+                |function| WalkVoxels::walk(start, end, origin, function),
+                stop,
+            );
+        }
+    }
+
+    // Diagonal lines and single points take the same shortcuts as the iterators
+    for &(start, end) in &[((0, 0), (5, -5)), ((3, 3), (3, 3)), ((-2, 1), (-2, 9))] {
+        for stop in 0..12 {
+            assert_walk(
+                Supercover::new(start, end),
+                |function| Supercover::walk(start, end, function),
+                stop,
+            );
+            assert_walk(
+                WalkGrid::new(start, end),
+                |function| WalkGrid::walk(start, end, function),
+                stop,
+            );
+        }
+    }
+}
+
+#[test]
+fn extreme_coordinates() {
+    // Lines near the limits of `i64` and `i128` should be the small `i32` lines shifted across
+    macro_rules! assert_shifted {
+        ($ty:ident, $offset:expr, $start:expr, $end:expr, |$a:ident, $b:ident| $iterator:expr) => {{
+            let shift = |(x, y): Point<i32>| (x as $ty + $offset, y as $ty + $offset);
+            let small = {
+                let ($a, $b) = ($start, $end);
+                $iterator.map(shift).collect::<Vec<_>>()
+            };
+            let ($a, $b) = (shift($start), shift($end));
+            assert_eq!(small, $iterator.collect::<Vec<_>>());
+        }};
+    }
+
+    macro_rules! assert_all_shifted {
+        ($ty:ident, $offset:expr, $start:expr, $end:expr) => {{
+            let (offset, start, end) = ($offset, $start, $end);
+            assert_shifted!($ty, offset, start, end, |a, b| Bresenham::new(a, b));
+            assert_shifted!($ty, offset, start, end, |a, b| WalkGrid::new(a, b));
+            assert_shifted!($ty, offset, start, end, |a, b| Supercover::new(a, b));
+            assert_shifted!($ty, offset, start, end, |a, b| ThickSupercover::new(a, b));
+            assert_shifted!($ty, offset, start, end, |a, b| HexLine::new(a, b));
+            assert_shifted!($ty, offset, start, end, |a, b| HexSupercover::new(a, b));
+            assert_shifted!($ty, offset, start, end, |a, b| {
+                XiaolinWuLut::new(a, b, &LINEAR_COVERAGE).map(|(point, _)| point)
+            });
+            assert_shifted!($ty, offset, start, end, |a, b| {
+                WalkGridRay::new(a, (b.0 - a.0, b.1 - a.1)).take(100)
+            });
+            assert_shifted!($ty, offset, start, end, |a, b| {
+                BresenhamCircle::new(a.0, a.1, (b.0 - a.0).abs() / 10)
+            });
+            assert_shifted!($ty, offset, start, end, |a, b| {
+                MidpointEllipse::new(a.0, a.1, (b.0 - a.0).abs() / 10, (b.1 - a.1).abs() / 10)
+            });
+        }};
+    }
+
+    let mut rng = rand::thread_rng();
+    let range = RANGE as i32;
+
+    for _ in 0..NUM_TESTS / 100 {
+        let start = random_point(&mut rng, range);
+        let end = random_point(&mut rng, range);
+
+        assert_all_shifted!(i64, i64::MIN + 2 * range as i64, start, end);
+        assert_all_shifted!(i64, i64::MAX - 2 * range as i64, start, end);
+        assert_all_shifted!(i128, i128::MIN + 2 * range as i128, start, end);
+        assert_all_shifted!(i128, i128::MAX - 2 * range as i128, start, end);
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn coverage_buffer() {
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..NUM_TESTS / 10 {
+        let points = (0..rng.gen_range(3..7))
+            .map(|_| random_point(&mut rng, 24.0))
+            .collect::<Vec<Point<f64>>>();
+
+        // Shapes that are cut off at the edges cover the same cells as in a buffer big enough to
+        // hold all of them
+        let mut small = CoverageBuffer::new(16, 16);
+        small.polygon(points.iter().cloned());
+        let mut big = CoverageBuffer::new(64, 64);
+        big.polygon(points.iter().map(|&(x, y)| (x + 32.0, y + 32.0)));
+
+        for &rule in &[FillRule::NonZero, FillRule::EvenOdd] {
+            let mut cells = [[0.0; 16]; 16];
+            for ((x, y), coverage) in small.coverage(rule) {
+                assert!(coverage > 0.0 && coverage <= 1.0 + 1e-9, "{:?}", points);
+                cells[y][x] = coverage;
+            }
+
+            for ((x, y), coverage) in big.coverage(rule) {
+                let expected = match (x.checked_sub(32), y.checked_sub(32)) {
+                    (Some(x), Some(y)) if x < 16 && y < 16 => cells[y][x],
+                    _ => continue,
+                };
+                assert!((coverage - expected).abs() < 1e-6, "{:?}", points);
+            }
+        }
+
+        // The coverage adds up to the area of a triangle, which can't overlap itself
+        let total = big
+            .coverage(FillRule::NonZero)
+            .map(|(_, coverage)| coverage)
+            .sum::<f64>();
+        if points.len() == 3 {
+            let (a, b, c) = (points[0], points[1], points[2]);
+            let area = ((b.0 - a.0) * (c.1 - a.1) - (c.0 - a.0) * (b.1 - a.1)).abs() / 2.0;
+            assert!((total - area).abs() < 1e-6, "{:?}", points);
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn scanline_batch() {
+    let mut rng = rand::thread_rng();
+    let mut batch = ScanlineBatch::new();
+
+    for _ in 0..NUM_TESTS / 10 {
+        let shapes = (0..3)
+            .map(|_| {
+                (0..rng.gen_range(3..8))
+                    .map(|_| random_point(&mut rng, 12))
+                    .collect::<Vec<Point<i32>>>()
+            })
+            .collect::<Vec<_>>();
+
+        batch.clear();
+        for shape in &shapes {
+            batch.polygon(shape.iter().cloned());
+        }
+
+        for &rule in &[FillRule::NonZero, FillRule::EvenOdd] {
+            let mut filled = vec![Vec::new(); shapes.len()];
+            let mut last = (i32::MIN, 0, 0);
+            for (shape, (y, (start, end))) in batch.spans(rule) {
+                assert!(start <= end);
+                assert!((y, shape, start) > last, "{:?}", shapes);
+                last = (y, shape, end + 1);
+                filled[shape].extend((start..=end).map(|x| (x, y)));
+            }
+
+            // Check each cell against the edges, counting how many times they go around it
+            for (shape, points) in shapes.iter().enumerate() {
+                let mut expected = Vec::new();
+                for y in -12..12 {
+                    for x in -12..12 {
+                        let mut winding = 0;
+                        let mut on_edge = false;
+                        for (index, &a) in points.iter().enumerate() {
+                            let b = points[(index + 1) % points.len()];
+                            let side = (b.0 - a.0) * (y - a.1) - (x - a.0) * (b.1 - a.1);
+                            let within = (a.0.min(b.0)..=a.0.max(b.0)).contains(&x)
+                                && (a.1.min(b.1)..=a.1.max(b.1)).contains(&y);
+                            on_edge |= side == 0 && within;
+                            if a.1 <= y && y < b.1 && side > 0 {
+                                winding += 1;
+                            } else if b.1 <= y && y < a.1 && side < 0 {
+                                winding -= 1;
+                            }
+                        }
+                        let inside = match rule {
+                            FillRule::NonZero => winding != 0,
+                            FillRule::EvenOdd => winding % 2 != 0,
+                        };
+                        if inside || on_edge {
+                            expected.push((x, y));
+                        }
+                    }
+                }
+                filled[shape].sort_by_key(|&(x, y)| (y, x));
+                assert_eq!(filled[shape], expected, "{:?} {:?}", rule, points);
+            }
+        }
+    }
+}
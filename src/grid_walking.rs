@@ -1,138 +1,1771 @@
-use {Point, SignedNum};
+use {Direction, FloatNum, GridBounds, IntoPoint, LineError, LineRasterizer, Point, Side,
+     SignedNum};
 use steps::Steps;
+use toroidal::Wrapped;
+use pixel_perfect::PixelPerfect;
+use bounds::partition_point;
+use error::{check_len, check_span};
+use exact::{compare_fractions, mul_div};
+use core::cmp::Ordering;
+use core::ops::ControlFlow;
+use num_traits::{cast, Bounded, CheckedAdd, One, Signed, ToPrimitive, Zero};
+
+/// Set up a walk along one axis between two floating-point coordinates.
+///
+/// Returns the cell the walk starts in, the sign of its steps, the distance to the first grid line
+/// and the total distance, both in cells, and the number of steps to take.
+#[inline]
+fn float_axis<I: FloatNum, T: SignedNum>(start: I, end: I) -> (T, T, f64, f64, T) {
+    let half = I::cast(0.5);
+    let (start_cell, end_cell) = ((start + half).floor(), (end + half).floor());
+
+    let (sign, offset) = if end > start {
+        (T::one(), start_cell + half - start)
+    } else if end < start {
+        (-T::one(), start - start_cell + half)
+    } else {
+        (T::zero(), half)
+    };
+
+    (
+        T::cast(start_cell),
+        sign,
+        offset.to_f64().unwrap(),
+        (end - start).abs().to_f64().unwrap(),
+        T::cast((end_cell - start_cell).abs()),
+    )
+}
+
+/// Convert a position in world space into floating-point grid coordinates, where cells are
+/// centered on whole numbers.
+#[inline]
+fn world_to_grid<I: FloatNum>(point: Point<I>, cell_size: Point<I>, origin: Point<I>) -> Point<I> {
+    let half = I::cast(0.5);
+
+    (
+        (point.0 - origin.0) / cell_size.0 - half,
+        (point.1 - origin.1) / cell_size.1 - half,
+    )
+}
+
+/// Find the range of step counts along one axis that keep a coordinate between `min` and `max`.
+#[inline]
+fn steps_between<T: SignedNum>(start: T, sign: T, min: T, max: T) -> (T, T) {
+    if sign > T::zero() {
+        ((min - start).max(T::zero()), max - start)
+    } else if sign < T::zero() {
+        ((start - max).max(T::zero()), start - min)
+    } else if start >= min && start <= max {
+        (T::zero(), T::zero())
+    } else {
+        (T::zero(), -T::one())
+    }
+}
+
+/// Count the grid lines crossed on the other axis by the time `count` lines have been crossed on
+/// this one, including any crossed at the same time if `ties` is set.
+///
+/// The line is `length` cells long on this axis and `other` cells long on the other one.
+#[inline]
+fn crossed_before<T: SignedNum>(count: T, length: T, other: T, ties: bool) -> T {
+    if count <= T::zero() || length == T::zero() || other == T::zero() {
+        return T::zero();
+    }
+
+    // Every line on the other axis has been crossed by the time the last one on this axis has,
+    // so counting any further doesn't change anything
+    let wide = |value: T| value.to_u128().unwrap();
+    let (length, other) = (wide(length), wide(other));
+    let count = wide(count).min(length + 1);
+
+    // Line `i` on an axis of length `n` is crossed at `(2 * i + 1) / (2 * n)` along the line, so
+    // this counts the odd numbers up to `(2 * count - 1) * other / length`
+    let (quotient, remainder) = mul_div(2 * count - 1, other, length);
+    let limit = if remainder == 0 && !ties { quotient - 1 } else { quotient };
+
+    T::cast(limit.div_ceil(2).min(other))
+}
+
+/// Find the greatest common divisor of two non-negative numbers.
+#[inline]
+fn gcd<T: SignedNum>(mut a: T, mut b: T) -> T {
+    while b != T::zero() {
+        let remainder = a % b;
+        a = b;
+        b = remainder;
+    }
+
+    a
+}
+
+/// Count the corners a walk passes through before it crosses grid line `count` on this axis.
+///
+/// The line is `length` cells long on this axis and `other` cells long on the other one. Lines `i`
+/// and `j` are crossed together when `(2 * i + 1) * other == (2 * j + 1) * length`. Dividing both
+/// lengths by their greatest common divisor leaves `p` and `q` with no common factors, so that only
+/// happens when they're both odd, and `2 * i + 1` is an odd multiple of `p`.
+#[inline]
+fn corners_before<T: SignedNum>(count: T, length: T, other: T) -> T {
+    if count <= T::zero() || length == T::zero() || other == T::zero() {
+        return T::zero();
+    }
+
+    let two = T::cast(2);
+    let divisor = gcd(length, other);
+    let (p, q) = (length / divisor, other / divisor);
+
+    if p % two == T::zero() || q % two == T::zero() {
+        T::zero()
+    } else {
+        ((two * (count - T::one()) + T::one()) / p + T::one()) / two
+    }
+}
+
+/// Find where a walk between `start` and `end` first enters `bounds`, and how many steps it can
+/// take on each axis before leaving them.
+///
+/// Returns the first point, the number of steps already taken on each axis to get there, and the
+/// highest number of steps on each axis that stays inside the bounds. If `diagonal` is set, steps
+/// across exact corners change both axes at once, otherwise they change `y` first.
+#[inline]
+fn clip_walk<T: SignedNum>(
+    start: Point<T>,
+    end: Point<T>,
+    bounds: GridBounds<T>,
+    diagonal: bool,
+) -> (Point<T>, Point<T>, Point<T>) {
+    let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+    let (nx, ny) = (dx.abs(), dy.abs());
+    let (sign_x, sign_y) = (dx.signum(), dy.signum());
+
+    let x = steps_between(start.0, sign_x, bounds.min.0, bounds.max.0);
+    let y = steps_between(start.1, sign_y, bounds.min.1, bounds.max.1);
+
+    // The first point past the lower end of both ranges is either where the walk first gets far
+    // enough along `x`, or where it first gets far enough along `y`
+    let y_at_x = crossed_before(x.0, nx, ny, true);
+
+    let (ix, iy) = if y_at_x >= y.0 {
+        (x.0, y_at_x)
+    } else {
+        (crossed_before(y.0, ny, nx, diagonal), y.0)
+    };
+
+    (
+        (start.0 + ix * sign_x, start.1 + iy * sign_y),
+        (ix, iy),
+        (nx.min(x.1), ny.min(y.1)),
+    )
+}
+
+/// How a walk decides which grid line it crosses next.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+enum Crossings<W> {
+    /// A walk between the centers of two cells, which is worked out exactly. The `error` is
+    /// `(0.5 + ix) / nx - (0.5 + iy) / ny` for the next grid lines, scaled up by `2 * nx * ny`.
+    Exact { error: W, nx: W, ny: W },
+    /// A walk between any two positions, with the distance to the first grid line and the total
+    /// distance on each axis, in cells. They're kept in `f64`, which holds any `f32` exactly.
+    Float {
+        offset: Point<f64>,
+        length: Point<f64>,
+    },
+}
+
+/// The state of a walk that steps across one grid line at a time, or two at a corner, which
+/// [`WalkGrid`] and [`Supercover`] share.
+///
+/// Everything is kept in [`SignedNum::Wide`], so that stepping past the last point can't overflow
+/// at the limits of `T`.
+///
+/// [`WalkGrid`]: struct.WalkGrid.html
+/// [`Supercover`]: struct.Supercover.html
+/// [`SignedNum::Wide`]: trait.SignedNum.html#associatedtype.Wide
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "T::Wide: ::serde::Serialize",
+        deserialize = "T::Wide: ::serde::Deserialize<'de>"
+    ))
+)]
+struct GridWalk<T: SignedNum> {
+    point: Point<T::Wide>,
+    sign: Point<T::Wide>,
+    // The number of grid lines crossed on each axis
+    crossed: Point<T::Wide>,
+    // The number of cells to step through on each axis
+    cells: Point<T::Wide>,
+    crossings: Crossings<T::Wide>,
+    // The axis of the grid lines crossed, if the walk only ever crosses them on one
+    axis: Option<Side>,
+    // Set if the walk goes at exactly 45°, so it crosses the grid lines on both axes together
+    diagonal: bool,
+}
+
+impl<T: SignedNum> GridWalk<T> {
+    #[inline]
+    fn new(start: Point<T>, end: Point<T>) -> Self {
+        let (start, end) = ((start.0.widen(), start.1.widen()), (end.0.widen(), end.1.widen()));
+
+        // Delta values between the points
+        let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+        let (nx, ny) = (dx.abs(), dy.abs());
+
+        let axis = if ny == T::Wide::zero() && nx != T::Wide::zero() {
+            Some(Side::X)
+        } else if nx == T::Wide::zero() && ny != T::Wide::zero() {
+            Some(Side::Y)
+        } else {
+            None
+        };
+
+        Self {
+            point: start,
+            sign: (dx.signum(), dy.signum()),
+            crossed: (T::Wide::zero(), T::Wide::zero()),
+            cells: (nx, ny),
+            crossings: Crossings::Exact {
+                error: ny - nx,
+                nx,
+                ny,
+            },
+            axis,
+            diagonal: nx == ny && nx != T::Wide::zero(),
+        }
+    }
+
+    #[inline]
+    fn clipped(start: Point<T>, end: Point<T>, bounds: GridBounds<T>, diagonal: bool) -> Self {
+        let mut walk = Self::new(start, end);
+
+        // The distances to the bounds can need more bits than even `T::Wide` has, so they're
+        // worked out in `i128`
+        let wide = |point: Point<T>| (point.0.to_i128().unwrap(), point.1.to_i128().unwrap());
+        let bounds = GridBounds::new(wide(bounds.min), wide(bounds.max));
+        let (point, crossed, cells) = clip_walk(wide(start), wide(end), bounds, diagonal);
+
+        let narrow = |point: Point<i128>| (T::Wide::cast(point.0), T::Wide::cast(point.1));
+        walk.point = narrow(point);
+        walk.crossed = narrow(crossed);
+        walk.cells = narrow(cells);
+
+        if !walk.is_done() {
+            walk.reset_error();
+        }
+
+        walk
+    }
+
+    /// Set up a walk between two floating-point positions.
+    ///
+    /// Ones between cell centers cross the same grid lines as integer walks, so they're worked out
+    /// exactly, which covers every position too large to have a fractional part. The rest compare
+    /// their crossings in `f64`, which is at least as precise as the positions themselves.
+    #[inline]
+    fn from_float<I: FloatNum>(start: Point<I>, end: Point<I>) -> Self {
+        let whole = |value: I| value.fract() == I::zero();
+
+        if whole(start.0) && whole(start.1) && whole(end.0) && whole(end.1) {
+            let cell = |point: Point<I>| (T::cast(point.0), T::cast(point.1));
+            return Self::new(cell(start), cell(end));
+        }
+
+        let (x, sign_x, offset_x, nx, cells_x) = float_axis::<I, T::Wide>(start.0, end.0);
+        let (y, sign_y, offset_y, ny, cells_y) = float_axis::<I, T::Wide>(start.1, end.1);
+
+        Self {
+            point: (x, y),
+            sign: (sign_x, sign_y),
+            crossed: (T::Wide::zero(), T::Wide::zero()),
+            cells: (cells_x, cells_y),
+            crossings: Crossings::Float {
+                offset: (offset_x, offset_y),
+                length: (nx, ny),
+            },
+            axis: None,
+            diagonal: false,
+        }
+    }
+
+    /// Check whether the walk has stepped past its last cell.
+    #[inline]
+    fn is_done(&self) -> bool {
+        self.crossed.0 > self.cells.0 || self.crossed.1 > self.cells.1
+    }
+
+    /// The current point.
+    #[inline]
+    fn point(&self) -> Point<T> {
+        (T::cast(self.point.0), T::cast(self.point.1))
+    }
+
+    /// Compare when grid line `k` on the x axis is crossed with when line `j` on the y axis is,
+    /// which is `Less` if the x one comes first.
+    #[inline]
+    fn compare(&self, k: T::Wide, j: T::Wide) -> Ordering {
+        match self.crossings {
+            Crossings::Exact { nx, ny, .. } => {
+                let wide = |value: T::Wide| value.to_u128().unwrap();
+                let zero = T::Wide::zero();
+
+                // Line `k` is crossed at `(2 * k + 1) / (2 * nx)` along the line, and the lines on
+                // an axis the walk doesn't move along are never crossed
+                match (nx == zero, ny == zero) {
+                    (true, true) => Ordering::Equal,
+                    (true, false) => Ordering::Greater,
+                    (false, true) => Ordering::Less,
+                    (false, false) => {
+                        compare_fractions(2 * wide(k) + 1, wide(nx), 2 * wide(j) + 1, wide(ny))
+                    }
+                }
+            }
+            Crossings::Float { offset, length } => {
+                let (k, j) = (k.to_f64().unwrap(), j.to_f64().unwrap());
+                let difference = (offset.0 + k) / length.0 - (offset.1 + j) / length.1;
+
+                if difference < 0.0 {
+                    Ordering::Less
+                } else if difference == 0.0 {
+                    Ordering::Equal
+                } else {
+                    Ordering::Greater
+                }
+            }
+        }
+    }
+
+    /// Compare the next grid lines on each axis, like [`compare`].
+    ///
+    /// [`compare`]: #method.compare
+    #[inline]
+    fn next_crossing(&self) -> Ordering {
+        match self.crossings {
+            Crossings::Exact { error, .. } => error.cmp(&T::Wide::zero()),
+            Crossings::Float { .. } => self.compare(self.crossed.0, self.crossed.1),
+        }
+    }
+
+    #[inline]
+    fn step_x(&mut self) {
+        self.crossed.0 += T::Wide::one();
+
+        // Don't step past the last cell, which may be at the limit of the type
+        if self.crossed.0 <= self.cells.0 {
+            self.point.0 += self.sign.0;
+        }
+
+        if let Crossings::Exact { ref mut error, ny, .. } = self.crossings {
+            *error += ny + ny;
+        }
+    }
+
+    #[inline]
+    fn step_y(&mut self) {
+        self.crossed.1 += T::Wide::one();
+
+        // Don't step past the last cell, which may be at the limit of the type
+        if self.crossed.1 <= self.cells.1 {
+            self.point.1 += self.sign.1;
+        }
+
+        if let Crossings::Exact { ref mut error, nx, .. } = self.crossings {
+            *error -= nx + nx;
+        }
+    }
+
+    /// Return the current point and step along a walk that only crosses grid lines on one axis,
+    /// without working out which line comes next.
+    #[inline]
+    fn next_along(&mut self, side: Side) -> Option<Point<T>> {
+        if self.is_done() {
+            return None;
+        }
+
+        let point = self.point();
+
+        match side {
+            Side::X => self.step_x(),
+            Side::Y => self.step_y(),
+        }
+
+        Some(point)
+    }
+
+    /// Step through the next corner of a walk at exactly 45°, without working out which grid line
+    /// comes next.
+    #[inline]
+    fn step_diagonal(&mut self) {
+        let one = T::Wide::one();
+        self.crossed = (self.crossed.0 + one, self.crossed.1 + one);
+
+        // The error moves by `2 * ny` and back by `2 * nx`, so it stays the same
+        if !self.is_done() {
+            self.point = (self.point.0 + self.sign.0, self.point.1 + self.sign.1);
+        }
+    }
+
+    /// Count the grid lines crossed on the other axis by the time line `line` has been crossed on
+    /// this one, in an exact walk, like [`crossed_before`].
+    ///
+    /// [`crossed_before`]: fn.crossed_before.html
+    #[inline]
+    fn crossed_before(line: T::Wide, length: T::Wide, other: T::Wide, ties: bool) -> T::Wide {
+        crossed_before(line + T::Wide::one(), length, other, ties)
+    }
+
+    /// Work out the error of an exact walk from the grid lines it has crossed, after jumping ahead.
+    #[inline]
+    fn reset_error(&mut self) {
+        let wide = |value: T::Wide| value.to_i128().unwrap();
+        let (k, j) = (wide(self.crossed.0), wide(self.crossed.1));
+
+        // The products can overflow even `i128`, but the error itself is never more than twice
+        // the length of the line, so wrapping around in between still gives the right answer
+        if let Crossings::Exact {
+            ref mut error,
+            nx,
+            ny,
+        } = self.crossings
+        {
+            let term = |count: i128, length: T::Wide| (2 * count + 1).wrapping_mul(wide(length));
+            *error = T::Wide::cast(term(k, ny).wrapping_sub(term(j, nx)));
+        }
+    }
+
+    /// Count the grid lines crossed on the `y` axis by the time line `k` has been crossed on the
+    /// `x` axis, including one crossed at the same time.
+    #[inline]
+    fn crossed_y_by(&self, k: T::Wide) -> T::Wide {
+        match self.crossings {
+            Crossings::Exact { nx, ny, .. } => Self::crossed_before(k, nx, ny, true),
+            Crossings::Float { .. } => Self::count_while(self.cells.1 + T::Wide::one(), |j| {
+                self.compare(k, j) != Ordering::Less
+            }),
+        }
+    }
+
+    /// Count the steps the walk has taken once it has crossed `crossed` grid lines on each axis.
+    ///
+    /// A `diagonal` walk crosses both lines at a corner in one step, which is only counted for
+    /// exact walks.
+    #[inline]
+    fn steps_to(&self, crossed: Point<T::Wide>, diagonal: bool) -> T::Wide {
+        let steps = crossed.0 + crossed.1;
+
+        match self.crossings {
+            Crossings::Exact { nx, ny, .. } if diagonal => {
+                steps - corners_before(crossed.0, nx, ny)
+            }
+            _ => steps,
+        }
+    }
+
+    /// Count the steps left before the walk leaves its cells, if that fits in a `usize`.
+    ///
+    /// Every step crosses a grid line on one axis, or both at a corner if `diagonal` is set, which
+    /// like [`steps_to`] is only counted for exact walks.
+    ///
+    /// [`steps_to`]: #method.steps_to
+    #[inline]
+    fn remaining(&self, diagonal: bool) -> Option<usize> {
+        let end = self.end(diagonal);
+        let (x, y) = (end.0 - self.crossed.0, end.1 - self.crossed.1);
+
+        let zero = T::Wide::zero();
+
+        let corners = match self.crossings {
+            // A single cell is left through its corner
+            Crossings::Exact { nx, ny, .. } if diagonal && nx == zero && ny == zero => x,
+            Crossings::Exact { nx, ny, .. } if diagonal => {
+                corners_before(end.0, nx, ny) - corners_before(self.crossed.0, nx, ny)
+            }
+            _ => zero,
+        };
+
+        (x - corners).checked_add(&y)?.to_usize()
+    }
+
+    /// Jump `count` steps ahead without taking them one at a time, or to the end of the walk if
+    /// there aren't that many left.
+    ///
+    /// Returns `false` if the walk ended. This can't be used on a `diagonal` walk between
+    /// floating-point positions, since [`steps_to`] doesn't count its corners.
+    ///
+    /// [`steps_to`]: #method.steps_to
+    #[inline]
+    fn skip(&mut self, count: usize, diagonal: bool) -> bool {
+        if count == 0 {
+            return true;
+        }
+
+        let end = self.end(diagonal);
+        let count = cast(count).unwrap_or_else(T::Wide::max_value);
+        let steps = self.steps_to(self.crossed, diagonal).checked_add(&count);
+
+        let steps = match steps {
+            Some(steps) if steps < self.steps_to(end, diagonal) => steps,
+            _ => {
+                self.crossed = end;
+                return false;
+            }
+        };
+
+        // Line `k` on the x axis has been crossed if the step that crosses it is one of them. The
+        // walk doesn't end, so it can't cross the line that leaves the last cell
+        let last_x = end.0.min(self.cells.0) - T::Wide::one();
+        let crossed_x = partition_point(self.crossed.0, last_x, |k| {
+            self.steps_to((k + T::Wide::one(), self.crossed_y_by(k)), diagonal) <= steps
+        });
+        let crossed = (
+            crossed_x,
+            steps - self.steps_to((crossed_x, T::Wide::zero()), diagonal),
+        );
+
+        self.point.0 += self.sign.0 * (crossed.0 - self.crossed.0);
+        self.point.1 += self.sign.1 * (crossed.1 - self.crossed.1);
+        self.crossed = crossed;
+        self.reset_error();
+
+        true
+    }
+
+    /// Count how many of the grid lines from `0` up to but not including `limit` pass `test`,
+    /// where the ones that do all come first.
+    #[inline]
+    fn count_while<F: Fn(T::Wide) -> bool>(limit: T::Wide, test: F) -> T::Wide {
+        partition_point(T::Wide::zero(), limit - T::Wide::one(), test)
+    }
+
+    /// Find how many grid lines the walk will have crossed on each axis once it has left its
+    /// cells.
+    ///
+    /// Every step crosses the next grid line on one axis, or both at a corner if `diagonal` is
+    /// set. Otherwise the `y` one is crossed first.
+    #[inline]
+    fn end(&self, diagonal: bool) -> Point<T::Wide> {
+        let one = T::Wide::one();
+        let (cells_x, cells_y) = self.cells;
+
+        match self.compare(cells_x, cells_y) {
+            Ordering::Less => (cells_x + one, self.crossed_y_by(cells_x)),
+            Ordering::Equal if diagonal => (cells_x + one, cells_y + one),
+            _ => {
+                let crossed = if let Crossings::Exact { nx, ny, .. } = self.crossings {
+                    Self::crossed_before(cells_y, ny, nx, diagonal)
+                } else {
+                    Self::count_while(cells_x + one, |k| {
+                        let order = self.compare(k, cells_y);
+                        order == Ordering::Less || (diagonal && order == Ordering::Equal)
+                    })
+                };
+
+                (crossed, cells_y + one)
+            }
+        }
+    }
+
+    /// Find the last cell of the walk.
+    ///
+    /// The step that leaves the cells is never taken, so only the lines crossed before it count.
+    /// When that step goes through a corner, the line it crosses on the other axis is left out as
+    /// well, unless the walk is not `diagonal` and crosses the `y` line first.
+    #[inline]
+    fn last(&self, diagonal: bool) -> Point<T> {
+        let one = T::Wide::one();
+        let (cells_x, cells_y) = self.cells;
+        let x_first = self.compare(cells_x, cells_y) == Ordering::Less;
+
+        let end = if let Crossings::Exact { nx, ny, .. } = self.crossings {
+            if x_first {
+                (cells_x, Self::crossed_before(cells_x, nx, ny, !diagonal))
+            } else {
+                (Self::crossed_before(cells_y, ny, nx, false), cells_y)
+            }
+        } else if x_first {
+            let crossed = Self::count_while(cells_y + one, |j| {
+                let order = self.compare(cells_x, j);
+                order == Ordering::Greater || (!diagonal && order == Ordering::Equal)
+            });
+
+            (cells_x, crossed)
+        } else {
+            let crossed = Self::count_while(cells_x + one, |k| {
+                self.compare(k, cells_y) == Ordering::Less
+            });
+
+            (crossed, cells_y)
+        };
+
+        (
+            T::cast(self.point.0 + self.sign.0 * (end.0 - self.crossed.0)),
+            T::cast(self.point.1 + self.sign.1 * (end.1 - self.crossed.1)),
+        )
+    }
+}
 
 /// Walk along a grid, taking only orthogonal steps.
 ///
 /// See [this section] of the [article] for an interactive demonstration.
 ///
-/// Note that this algorithm isn't symetrical; if you swap `start` and `end`, the reversed line
-/// might not be the same.
+/// Note that this algorithm isn't symetrical; if you swap `start` and `end`, the reversed line
+/// might not be the same.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::WalkGrid;
+///
+/// fn main() {
+///     for (x, y) in WalkGrid::new((0, 0), (5, 3)) {
+///         print!("({}, {}), ", x, y);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// (0, 0), (1, 0), (1, 1), (2, 1), (2, 2), (3, 2), (4, 2), (4, 3), (5, 3),
+/// ```
+///
+/// The crossings are compared with exact integer math in [`SignedNum::Wide`], so long lines and
+/// ones far from the origin are walked the same as short ones near it.
+///
+/// [this section]: http://www.redblobgames.com/grids/line-drawing.html#org3c085ed
+/// [article]: http://www.redblobgames.com/grids/line-drawing.html
+/// [`SignedNum::Wide`]: trait.SignedNum.html#associatedtype.Wide
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "T: ::serde::Serialize, T::Wide: ::serde::Serialize",
+        deserialize = "T: ::serde::Deserialize<'de>, T::Wide: ::serde::Deserialize<'de>"
+    ))
+)]
+pub struct WalkGrid<T: SignedNum> {
+    walk: GridWalk<T>,
+}
+
+impl<T: SignedNum> WalkGrid<T> {
+    #[inline]
+    pub fn new(start: impl IntoPoint<T>, end: impl IntoPoint<T>) -> WalkGrid<T> {
+        WalkGrid {
+            walk: GridWalk::new(start.into_point(), end.into_point()),
+        }
+    }
+
+    /// Create a line like [`new`], but return an error instead of overflowing when the points are
+    /// too far apart, or too close to the limits of `T::Wide`. That can only happen with `i128`,
+    /// which has nothing wider to do the math in. Lines with too many points to count in a `usize`
+    /// are an error as well.
+    ///
+    /// [`new`]: #method.new
+    #[inline]
+    pub fn try_new(start: impl IntoPoint<T>, end: impl IntoPoint<T>) -> Result<Self, LineError> {
+        let (start, end) = (start.into_point(), end.into_point());
+
+        check_span(start.0.widen(), end.0.widen())?;
+        check_span(start.1.widen(), end.1.widen())?;
+
+        let line = Self::new(start, end);
+        check_len(line.walk.cells.0 + line.walk.cells.1)?;
+
+        Ok(line)
+    }
+
+    /// Create a new `WalkGrid` iterator that only returns the points inside `bounds`.
+    ///
+    /// This produces the same points as filtering the full walk, but skips straight to the first
+    /// point inside the bounds without stepping through the ones before it.
+    #[inline]
+    pub fn clipped(
+        start: impl IntoPoint<T>,
+        end: impl IntoPoint<T>,
+        bounds: GridBounds<T>,
+    ) -> WalkGrid<T> {
+        WalkGrid {
+            walk: GridWalk::clipped(start.into_point(), end.into_point(), bounds, false),
+        }
+    }
+
+    /// Walk between two floating-point positions, visiting the cells they pass through.
+    ///
+    /// Each cell `(x, y)` covers the area within `0.5` of its center, so the cells that the start
+    /// and end points are in are found by rounding. Using the exact positions instead of rounding
+    /// them first keeps the line going through the right cells in between.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// extern crate line_drawing;
+    /// use line_drawing::WalkGrid;
+    ///
+    /// fn main() {
+    ///     for (x, y) in WalkGrid::<i8>::from_float((0.4, 0.3), (1.4, 1.3)) {
+    ///         print!("({}, {}), ", x, y);
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// ```text
+    /// (0, 0), (1, 0), (1, 1),
+    /// ```
+    #[inline]
+    pub fn from_float<I: FloatNum>(
+        start: impl IntoPoint<I>,
+        end: impl IntoPoint<I>,
+    ) -> WalkGrid<T> {
+        WalkGrid {
+            walk: GridWalk::from_float(start.into_point(), end.into_point()),
+        }
+    }
+
+    /// Walk between two positions in world space, on a grid with cells of the given size.
+    ///
+    /// Cell `(0, 0)` has its top left corner at `origin`, so cell `(x, y)` covers the area from
+    /// `origin + (x, y) * cell_size` up to but not including `origin + (x + 1, y + 1) * cell_size`.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// extern crate line_drawing;
+    /// use line_drawing::WalkGrid;
+    ///
+    /// fn main() {
+    ///     let walk = WalkGrid::<i8>::with_cells((5.0, 5.0), (40.0, 20.0), (16.0, 16.0), (0.0, 0.0));
+    ///
+    ///     for (x, y) in walk {
+    ///         print!("({}, {}), ", x, y);
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// ```text
+    /// (0, 0), (1, 0), (1, 1), (2, 1),
+    /// ```
+    #[inline]
+    pub fn with_cells<I: FloatNum>(
+        start: impl IntoPoint<I>,
+        end: impl IntoPoint<I>,
+        cell_size: Point<I>,
+        origin: Point<I>,
+    ) -> WalkGrid<T> {
+        let (start, end) = (start.into_point(), end.into_point());
+
+        Self::from_float(
+            world_to_grid(start, cell_size, origin),
+            world_to_grid(end, cell_size, origin),
+        )
+    }
+
+    /// Like [`LineRasterizer::steps`], without needing the trait imported.
+    ///
+    /// [`LineRasterizer::steps`]: trait.LineRasterizer.html#method.steps
+    #[inline]
+    pub fn steps(self) -> Steps<Point<T>, Self> {
+        LineRasterizer::steps(self)
+    }
+
+    /// Wrap the points around the edges of a grid of the given size.
+    #[inline]
+    pub fn wrapped(self, size: Point<T>) -> Wrapped<T, Self> {
+        Wrapped::new(self, size)
+    }
+
+    /// Remove the corners of L-shaped steps, for pixel-perfect strokes.
+    #[inline]
+    pub fn pixel_perfect(self) -> PixelPerfect<T, Self> {
+        PixelPerfect::new(self)
+    }
+
+    /// Also return the direction that each cell was entered from.
+    ///
+    /// See [`WalkGridDirections`] for details.
+    ///
+    /// [`WalkGridDirections`]: struct.WalkGridDirections.html
+    #[inline]
+    pub fn directions(self) -> WalkGridDirections<T> {
+        WalkGridDirections {
+            walk: self,
+            entered: None,
+        }
+    }
+
+    /// Return the next point along with the direction of the step taken after it.
+    #[inline]
+    fn advance(&mut self) -> Option<(Point<T>, Direction)> {
+        let walk = &mut self.walk;
+
+        if walk.is_done() {
+            return None;
+        }
+
+        let point = walk.point();
+
+        let direction = if walk.next_crossing() == Ordering::Less {
+            walk.step_x();
+
+            if walk.sign.0 < T::Wide::zero() {
+                Direction::West
+            } else {
+                Direction::East
+            }
+        } else {
+            walk.step_y();
+
+            if walk.sign.1 < T::Wide::zero() {
+                Direction::North
+            } else {
+                Direction::South
+            }
+        };
+
+        Some((point, direction))
+    }
+}
+
+impl<T: SignedNum> LineRasterizer<T, T> for WalkGrid<T> {
+    #[inline]
+    fn new(start: Point<T>, end: Point<T>) -> Self {
+        WalkGrid::new(start, end)
+    }
+
+    /// Keeps the error in a local, without counting the grid lines crossed on each axis like the
+    /// iterator does to know where it ends.
+    #[inline]
+    fn walk<B, F>(start: Point<T>, end: Point<T>, mut function: F) -> ControlFlow<B>
+    where
+        F: FnMut(Point<T>) -> ControlFlow<B>,
+    {
+        let walk = GridWalk::<T>::new(start, end);
+        let ((nx, ny), sign) = (walk.cells, walk.sign);
+        let (one, zero) = (T::Wide::one(), T::Wide::zero());
+
+        let mut point = walk.point;
+        let mut error = ny - nx;
+        let mut remaining = nx + ny;
+
+        loop {
+            function((T::cast(point.0), T::cast(point.1)))?;
+
+            if remaining == zero {
+                return ControlFlow::Continue(());
+            }
+
+            remaining -= one;
+
+            if error < zero {
+                point.0 += sign.0;
+                error += ny + ny;
+            } else {
+                point.1 += sign.1;
+                error -= nx + nx;
+            }
+        }
+    }
+}
+
+impl<T: SignedNum> Iterator for WalkGrid<T> {
+    type Item = Point<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(side) = self.walk.axis {
+            return self.walk.next_along(side);
+        }
+
+        self.advance().map(|(point, _)| point)
+    }
+
+    /// Capped at `usize::MAX`, like [`Bresenham`](struct.Bresenham.html#method.size_hint).
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let walk = &self.walk;
+
+        if walk.is_done() {
+            return (0, Some(0));
+        }
+
+        let remaining = walk.remaining(false).unwrap_or(usize::MAX);
+        (remaining, Some(remaining))
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if self.walk.is_done() || !self.walk.skip(n, false) {
+            return None;
+        }
+
+        self.next()
+    }
+
+    #[inline]
+    fn last(self) -> Option<Self::Item> {
+        if self.walk.is_done() {
+            return None;
+        }
+
+        Some(self.walk.last(false))
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.len()
+    }
+}
+
+impl<T: SignedNum> ExactSizeIterator for WalkGrid<T> {}
+
+/// A [`WalkGrid`] that also returns the direction of the step into each cell.
+///
+/// This is the direction something walking along the line would be facing when it arrives at the
+/// cell, so it's `None` for the first cell. The directions come from the same decisions that pick
+/// the cells, so they always match the steps between them.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::WalkGrid;
+///
+/// fn main() {
+///     for (point, direction) in WalkGrid::new((0, 0), (2, -1)).directions() {
+///         print!("({:?}, {:?}), ", point, direction);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// ((0, 0), None), ((1, 0), Some(East)), ((1, -1), Some(North)), ((2, -1), Some(East)),
+/// ```
+///
+/// [`WalkGrid`]: struct.WalkGrid.html
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "T: ::serde::Serialize, T::Wide: ::serde::Serialize",
+        deserialize = "T: ::serde::Deserialize<'de>, T::Wide: ::serde::Deserialize<'de>"
+    ))
+)]
+pub struct WalkGridDirections<T: SignedNum> {
+    walk: WalkGrid<T>,
+    entered: Option<Direction>,
+}
+
+impl<T: SignedNum> Iterator for WalkGridDirections<T> {
+    type Item = (Point<T>, Option<Direction>);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (point, direction) = self.walk.advance()?;
+        let entered = self.entered.replace(direction);
+
+        Some((point, entered))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.walk.size_hint()
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.len()
+    }
+}
+
+impl<T: SignedNum> ExactSizeIterator for WalkGridDirections<T> {}
+
+/// Like [`WalkGrid`] but takes diagonal steps if the line passes directly over a corner.
+///
+/// See [this section][section] of the [article] for an interactive demonstration.
+///
+/// This algorithm should always be symetrical.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::Supercover;
+///
+/// fn main() {
+///     for (x, y) in Supercover::new((0, 0), (5, 5)) {
+///         print!("({}, {}), ", x, y);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// (0, 0), (1, 1), (2, 2), (3, 3), (4, 4), (5, 5),
+/// ```
+///
+/// [`WalkGrid`]: struct.WalkGrid.html
+/// [section]: http://www.redblobgames.com/grids/line-drawing.html#org1da485d
+/// [article]: http://www.redblobgames.com/grids/line-drawing.html
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "T: ::serde::Serialize, T::Wide: ::serde::Serialize",
+        deserialize = "T: ::serde::Deserialize<'de>, T::Wide: ::serde::Deserialize<'de>"
+    ))
+)]
+pub struct Supercover<T: SignedNum> {
+    walk: GridWalk<T>,
+}
+
+impl<T: SignedNum> Supercover<T> {
+    #[inline]
+    pub fn new(start: impl IntoPoint<T>, end: impl IntoPoint<T>) -> Self {
+        Self {
+            walk: GridWalk::new(start.into_point(), end.into_point()),
+        }
+    }
+
+    /// Create a line like [`new`], but return an error instead of overflowing when the points are
+    /// too far apart, or too close to the limits of `T::Wide`. That can only happen with `i128`,
+    /// which has nothing wider to do the math in. Lines with too many points to count in a `usize`
+    /// are an error as well.
+    ///
+    /// [`new`]: #method.new
+    #[inline]
+    pub fn try_new(start: impl IntoPoint<T>, end: impl IntoPoint<T>) -> Result<Self, LineError> {
+        let (start, end) = (start.into_point(), end.into_point());
+
+        check_span(start.0.widen(), end.0.widen())?;
+        check_span(start.1.widen(), end.1.widen())?;
+
+        let line = Self::new(start, end);
+        check_len(line.walk.cells.0 + line.walk.cells.1)?;
+
+        Ok(line)
+    }
+
+    /// Create a new `Supercover` iterator that only returns the points inside `bounds`.
+    ///
+    /// This produces the same points as filtering the full line, but skips straight to the first
+    /// point inside the bounds without stepping through the ones before it.
+    #[inline]
+    pub fn clipped(
+        start: impl IntoPoint<T>,
+        end: impl IntoPoint<T>,
+        bounds: GridBounds<T>,
+    ) -> Self {
+        Self {
+            walk: GridWalk::clipped(start.into_point(), end.into_point(), bounds, true),
+        }
+    }
+
+    /// Walk between two floating-point positions, like [`WalkGrid::from_float`].
+    ///
+    /// [`WalkGrid::from_float`]: struct.WalkGrid.html#method.from_float
+    #[inline]
+    pub fn from_float<I: FloatNum>(start: impl IntoPoint<I>, end: impl IntoPoint<I>) -> Self {
+        Self {
+            walk: GridWalk::from_float(start.into_point(), end.into_point()),
+        }
+    }
+
+    /// Walk between two positions in world space, like [`WalkGrid::with_cells`].
+    ///
+    /// [`WalkGrid::with_cells`]: struct.WalkGrid.html#method.with_cells
+    #[inline]
+    pub fn with_cells<I: FloatNum>(
+        start: impl IntoPoint<I>,
+        end: impl IntoPoint<I>,
+        cell_size: Point<I>,
+        origin: Point<I>,
+    ) -> Self {
+        let (start, end) = (start.into_point(), end.into_point());
+
+        Self::from_float(
+            world_to_grid(start, cell_size, origin),
+            world_to_grid(end, cell_size, origin),
+        )
+    }
+
+    /// Like [`LineRasterizer::steps`], without needing the trait imported.
+    ///
+    /// [`LineRasterizer::steps`]: trait.LineRasterizer.html#method.steps
+    #[inline]
+    pub fn steps(self) -> Steps<Point<T>, Self> {
+        LineRasterizer::steps(self)
+    }
+
+    /// Wrap the points around the edges of a grid of the given size.
+    #[inline]
+    pub fn wrapped(self, size: Point<T>) -> Wrapped<T, Self> {
+        Wrapped::new(self, size)
+    }
+
+    /// The range of lengths the line could have left, from the number of grid lines it still
+    /// has to cross on the longer axis to the number on both, without working out how many
+    /// corners it passes through.
+    #[inline]
+    fn crossing_bounds(&self) -> (usize, Option<usize>) {
+        let walk = &self.walk;
+
+        if walk.is_done() {
+            return (0, Some(0));
+        }
+
+        let end = walk.end(true);
+        let x = (end.0 - walk.crossed.0).to_usize();
+        let y = (end.1 - walk.crossed.1).to_usize();
+
+        match (x, y) {
+            (Some(x), Some(y)) => (x.max(y), x.checked_add(y)),
+            _ => (usize::MAX, None),
+        }
+    }
+
+    /// Report where the line passes exactly through a corner, as well as the cells it visits.
+    ///
+    /// See [`SupercoverEvents`] for details.
+    ///
+    /// [`SupercoverEvents`]: struct.SupercoverEvents.html
+    #[inline]
+    pub fn events(self) -> SupercoverEvents<T> {
+        SupercoverEvents {
+            line: self,
+            previous: None,
+            pending: None,
+        }
+    }
+}
+
+impl<T: SignedNum> LineRasterizer<T, T> for Supercover<T> {
+    #[inline]
+    fn new(start: Point<T>, end: Point<T>) -> Self {
+        Supercover::new(start, end)
+    }
+
+    /// Keeps the error in a local, like [`WalkGrid::walk`].
+    ///
+    /// [`WalkGrid::walk`]: struct.WalkGrid.html#method.walk
+    #[inline]
+    fn walk<B, F>(start: Point<T>, end: Point<T>, mut function: F) -> ControlFlow<B>
+    where
+        F: FnMut(Point<T>) -> ControlFlow<B>,
+    {
+        let walk = GridWalk::<T>::new(start, end);
+        let ((nx, ny), sign) = (walk.cells, walk.sign);
+        let (one, zero) = (T::Wide::one(), T::Wide::zero());
+
+        let mut point = walk.point;
+        let mut error = ny - nx;
+        let mut remaining = nx + ny;
+
+        loop {
+            function((T::cast(point.0), T::cast(point.1)))?;
+
+            if remaining == zero {
+                return ControlFlow::Continue(());
+            }
+
+            // If both grid lines are crossed at once then jump diagonally
+            let crossing = error.cmp(&zero);
+
+            if crossing != Ordering::Greater {
+                point.0 += sign.0;
+                error += ny + ny;
+                remaining -= one;
+            }
+            if crossing != Ordering::Less {
+                point.1 += sign.1;
+                error -= nx + nx;
+                remaining -= one;
+            }
+        }
+    }
+}
+
+impl<T: SignedNum> Iterator for Supercover<T> {
+    type Item = Point<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(side) = self.walk.axis {
+            return self.walk.next_along(side);
+        }
+
+        if self.walk.is_done() {
+            return None;
+        }
+
+        let point = self.walk.point();
+
+        if self.walk.diagonal {
+            self.walk.step_diagonal();
+            return Some(point);
+        }
+
+        // If both grid lines are crossed at once then jump diagonally
+        let crossing = self.walk.next_crossing();
+
+        if crossing != Ordering::Greater {
+            self.walk.step_x();
+        }
+        if crossing != Ordering::Less {
+            self.walk.step_y();
+        }
+
+        Some(point)
+    }
+
+    /// Lines between cell centers count the corners they pass through straight away. Ones from
+    /// [`from_float`] step through a copy of the line to find them, since where they pass through
+    /// corners depends on how floats round. Like [`Bresenham`], it's capped at `usize::MAX`.
+    ///
+    /// [`from_float`]: #method.from_float
+    /// [`Bresenham`]: struct.Bresenham.html#method.size_hint
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let walk = &self.walk;
+
+        if walk.is_done() {
+            return (0, Some(0));
+        }
+
+        let remaining = match walk.crossings {
+            Crossings::Exact { .. } => walk.remaining(true).unwrap_or(usize::MAX),
+            Crossings::Float { .. } => self.clone().fold(0, |count, _| count + 1),
+        };
+
+        (remaining, Some(remaining))
+    }
+
+    /// Lines between cell centers jump straight to the point. Ones from [`from_float`] step through
+    /// the points before it, since where they pass through corners depends on how floats round.
+    ///
+    /// [`from_float`]: #method.from_float
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if let Crossings::Float { .. } = self.walk.crossings {
+            for _ in 0..n {
+                self.next()?;
+            }
+
+            return self.next();
+        }
+
+        if self.walk.is_done() || !self.walk.skip(n, true) {
+            return None;
+        }
+
+        self.next()
+    }
+
+    #[inline]
+    fn last(self) -> Option<Self::Item> {
+        if self.walk.is_done() {
+            return None;
+        }
+
+        Some(self.walk.last(true))
+    }
+
+    /// Only lines between cell centers count their points without stepping through them, like
+    /// [`size_hint`](#method.size_hint).
+    #[inline]
+    fn count(self) -> usize {
+        self.len()
+    }
+}
+
+impl<T: SignedNum> ExactSizeIterator for Supercover<T> {}
+
+/// Something that happens along a [`SupercoverEvents`] line.
+///
+/// [`SupercoverEvents`]: struct.SupercoverEvents.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SupercoverEvent<T> {
+    /// The line visited a cell.
+    Cell(Point<T>),
+    /// The line passed exactly through the corner shared by the cells `from` and `to`, which are
+    /// diagonal neighbours. The other two cells around the corner, `(to.0, from.1)` and
+    /// `(from.0, to.1)`, were only touched at their corners.
+    Corner { from: Point<T>, to: Point<T> },
+}
+
+/// A [`Supercover`] line that also reports when it passes exactly through a corner.
+///
+/// A plain [`Supercover`] takes a diagonal step at these corners without saying so. This returns
+/// a [`Corner`] event between the two cells on either side of each diagonal step, and a [`Cell`]
+/// event for every cell along the line.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{Supercover, SupercoverEvent};
+///
+/// fn main() {
+///     for event in Supercover::new((0, 0), (3, 1)).events() {
+///         match event {
+///             SupercoverEvent::Cell(point) => print!("{:?}, ", point),
+///             SupercoverEvent::Corner { from, to } => print!("{:?}-{:?}, ", from, to),
+///         }
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// (0, 0), (1, 0), (1, 0)-(2, 1), (2, 1), (3, 1),
+/// ```
+///
+/// [`Supercover`]: struct.Supercover.html
+/// [`Corner`]: enum.SupercoverEvent.html#variant.Corner
+/// [`Cell`]: enum.SupercoverEvent.html#variant.Cell
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "T: ::serde::Serialize, T::Wide: ::serde::Serialize",
+        deserialize = "T: ::serde::Deserialize<'de>, T::Wide: ::serde::Deserialize<'de>"
+    ))
+)]
+pub struct SupercoverEvents<T: SignedNum> {
+    line: Supercover<T>,
+    previous: Option<Point<T>>,
+    pending: Option<Point<T>>,
+}
+
+impl<T: SignedNum> Iterator for SupercoverEvents<T> {
+    type Item = SupercoverEvent<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(point) = self.pending.take() {
+            return Some(SupercoverEvent::Cell(point));
+        }
+
+        let point = self.line.next()?;
+        let previous = self.previous.replace(point);
+
+        match previous {
+            // Supercover only steps diagonally when passing exactly through a corner
+            Some(from) if from.0 != point.0 && from.1 != point.1 => {
+                self.pending = Some(point);
+                Some(SupercoverEvent::Corner { from, to: point })
+            }
+            _ => Some(SupercoverEvent::Cell(point)),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Every step across a corner crosses two grid lines, which covers its event, apart from
+        // the one into the next cell if that's already been taken
+        let (low, high) = self.line.crossing_bounds();
+        let pending = self.pending.is_some() as usize;
+
+        (
+            low.saturating_add(pending),
+            high.and_then(|high| high.checked_add(1 + pending)),
+        )
+    }
+}
+
+/// Like [`Supercover`] but never steps diagonally.
+///
+/// Where the line passes exactly through a corner, both of the cells that it would squeeze between
+/// are included instead of stepping diagonally past them. This is the conservative choice for
+/// collision or lighting, where nothing should slip through a gap between two diagonal walls.
+///
+/// Like [`Supercover`], this is symmetrical.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::ThickSupercover;
+///
+/// fn main() {
+///     for (x, y) in ThickSupercover::new((0, 0), (2, 2)) {
+///         print!("({}, {}), ", x, y);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// (0, 0), (1, 0), (0, 1), (1, 1), (2, 1), (1, 2), (2, 2),
+/// ```
+///
+/// [`Supercover`]: struct.Supercover.html
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "T: ::serde::Serialize, T::Wide: ::serde::Serialize",
+        deserialize = "T: ::serde::Deserialize<'de>, T::Wide: ::serde::Deserialize<'de>"
+    ))
+)]
+pub struct ThickSupercover<T: SignedNum> {
+    events: SupercoverEvents<T>,
+    pending: Option<Point<T>>,
+}
+
+impl<T: SignedNum> ThickSupercover<T> {
+    #[inline]
+    pub fn new(start: impl IntoPoint<T>, end: impl IntoPoint<T>) -> Self {
+        let (start, end) = (start.into_point(), end.into_point());
+
+        Self {
+            events: Supercover::new(start, end).events(),
+            pending: None,
+        }
+    }
+
+    /// Like [`LineRasterizer::steps`], without needing the trait imported.
+    ///
+    /// [`LineRasterizer::steps`]: trait.LineRasterizer.html#method.steps
+    #[inline]
+    pub fn steps(self) -> Steps<Point<T>, Self> {
+        LineRasterizer::steps(self)
+    }
+}
+
+impl<T: SignedNum> LineRasterizer<T, T> for ThickSupercover<T> {
+    #[inline]
+    fn new(start: Point<T>, end: Point<T>) -> Self {
+        ThickSupercover::new(start, end)
+    }
+}
+
+impl<T: SignedNum> Iterator for ThickSupercover<T> {
+    type Item = Point<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(point) = self.pending.take() {
+            return Some(point);
+        }
+
+        match self.events.next()? {
+            SupercoverEvent::Cell(point) => Some(point),
+            SupercoverEvent::Corner { from, to } => {
+                self.pending = Some((from.0, to.1));
+                Some((to.0, from.1))
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Each corner is replaced by two cells
+        let (low, high) = self.events.size_hint();
+        let pending = self.pending.is_some() as usize;
+
+        (
+            low.saturating_add(pending),
+            high.and_then(|high| high.checked_mul(2)?.checked_add(pending)),
+        )
+    }
+}
+
+/// An edge between two neighbouring cells of a grid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GridEdge<T> {
+    /// The cell with the lower coordinate on the side of the edge.
+    pub cell: Point<T>,
+    /// Which axis the edge is crossed along. The edge is between `cell` and the next cell
+    /// along `x` for [`Side::X`], or along `y` for [`Side::Y`].
+    ///
+    /// [`Side::X`]: enum.Side.html#variant.X
+    /// [`Side::Y`]: enum.Side.html#variant.Y
+    pub side: Side,
+}
+
+impl<T: SignedNum> GridEdge<T> {
+    /// The two cells on either side of the edge, lowest first.
+    #[inline]
+    pub fn cells(&self) -> (Point<T>, Point<T>) {
+        let (x, y) = self.cell;
+
+        match self.side {
+            Side::X => ((x, y), (x + T::one(), y)),
+            Side::Y => ((x, y), (x, y + T::one())),
+        }
+    }
+}
+
+/// Walk along a grid like [`WalkGrid`], but return the edges between cells that are crossed
+/// instead of the cells themselves.
+///
+/// Each edge is returned the same way whichever direction it's crossed in, so they can be used to
+/// look up walls or portals directly. Where the line passes exactly through a corner, the edges
+/// are those on the side that [`WalkGrid`] goes around.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::WalkEdges;
+///
+/// fn main() {
+///     for edge in WalkEdges::new((0, 0), (-2, 1)) {
+///         print!("({:?}, {:?}), ", edge.cell, edge.side);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// ((-1, 0), X), ((-1, 0), Y), ((-2, 1), X),
+/// ```
+///
+/// [`WalkGrid`]: struct.WalkGrid.html
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "T: ::serde::Serialize, T::Wide: ::serde::Serialize",
+        deserialize = "T: ::serde::Deserialize<'de>, T::Wide: ::serde::Deserialize<'de>"
+    ))
+)]
+pub struct WalkEdges<T: SignedNum> {
+    walk: WalkGrid<T>,
+    previous: Option<Point<T>>,
+}
+
+impl<T: SignedNum> WalkEdges<T> {
+    #[inline]
+    pub fn new(start: impl IntoPoint<T>, end: impl IntoPoint<T>) -> Self {
+        let (start, end) = (start.into_point(), end.into_point());
+
+        let mut walk = WalkGrid::new(start, end);
+        let previous = walk.next();
+
+        Self { walk, previous }
+    }
+}
+
+impl<T: SignedNum> Iterator for WalkEdges<T> {
+    type Item = GridEdge<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let previous = self.previous?;
+        let point = self.walk.next()?;
+        self.previous = Some(point);
+
+        let side = if previous.0 != point.0 {
+            Side::X
+        } else {
+            Side::Y
+        };
+
+        Some(GridEdge {
+            cell: (
+                core::cmp::min(previous.0, point.0),
+                core::cmp::min(previous.1, point.1),
+            ),
+            side,
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // There's an edge between the last cell and each one after it
+        if self.previous.is_some() {
+            self.walk.size_hint()
+        } else {
+            (0, Some(0))
+        }
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.len()
+    }
+}
+
+impl<T: SignedNum> ExactSizeIterator for WalkEdges<T> {}
+
+/// Like [`Supercover`] but also returns the length of the line inside each cell.
+///
+/// The line goes between the centers of the `start` and `end` cells, and each cell covers the
+/// area within `0.5` of its center. The lengths add up to the distance between the two points, so
+/// dividing by it gives the fraction of the line in each cell. Cells that the line only touches at
+/// a corner aren't included, just like with [`Supercover`].
 ///
 /// Example:
 ///
 /// ```
 /// extern crate line_drawing;
-/// use line_drawing::WalkGrid;
+/// use line_drawing::SupercoverLengths;
 ///
 /// fn main() {
-///     for (x, y) in WalkGrid::new((0, 0), (5, 3)) {
-///         print!("({}, {}), ", x, y);
+///     for ((x, y), length) in SupercoverLengths::<f32, i8>::new((0, 0), (4, 0)) {
+///         print!("(({}, {}), {}), ", x, y, length);
 ///     }
 /// }
 /// ```
 ///
 /// ```text
-/// (0, 0), (1, 0), (1, 1), (2, 1), (2, 2), (3, 2), (4, 2), (4, 3), (5, 3),
+/// ((0, 0), 0.5), ((1, 0), 1), ((2, 0), 1), ((3, 0), 1), ((4, 0), 0.5),
 /// ```
 ///
-/// [this section]: http://www.redblobgames.com/grids/line-drawing.html#org3c085ed
-/// [article]: http://www.redblobgames.com/grids/line-drawing.html
-pub struct WalkGrid<T> {
-    point: Point<T>,
-    ix: f32,
-    iy: f32,
-    sign_x: T,
-    sign_y: T,
-    ny: f32,
-    nx: f32,
+/// [`Supercover`]: struct.Supercover.html
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SupercoverLengths<I, O> {
+    point: Point<O>,
+    sign_x: O,
+    sign_y: O,
+    steps: Point<I>,
+    size: Point<I>,
+    t: I,
+    length: I,
+    done: bool,
 }
 
-impl<T: SignedNum> WalkGrid<T> {
+impl<I: FloatNum, O: SignedNum> SupercoverLengths<I, O> {
     #[inline]
-    pub fn new(start: Point<T>, end: Point<T>) -> WalkGrid<T> {
-        // Delta values between the points
+    pub fn new(start: impl IntoPoint<O>, end: impl IntoPoint<O>) -> Self {
+        let (start, end) = (start.into_point(), end.into_point());
+
         let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+        let (nx, ny): (I, I) = (I::cast(dx.abs()), I::cast(dy.abs()));
 
-        WalkGrid {
+        Self {
             point: start,
-            ix: 0.0,
-            iy: 0.0,
             sign_x: dx.signum(),
             sign_y: dy.signum(),
-            nx: dx.abs().to_f32().unwrap(),
-            ny: dy.abs().to_f32().unwrap(),
+            steps: (I::zero(), I::zero()),
+            size: (nx, ny),
+            t: I::zero(),
+            length: nx.hypot(ny),
+            done: false,
         }
     }
 
     #[inline]
-    pub fn steps(self) -> Steps<Point<T>, WalkGrid<T>> {
+    pub fn steps(self) -> Steps<(Point<O>, I), Self> {
         Steps::new(self)
     }
 }
 
-impl<T: SignedNum> Iterator for WalkGrid<T> {
-    type Item = Point<T>;
+impl<I: FloatNum, O: SignedNum> Iterator for SupercoverLengths<I, O> {
+    type Item = (Point<O>, I);
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        if self.ix <= self.nx && self.iy <= self.ny {
-            let point = self.point;
+        if self.done {
+            return None;
+        }
+
+        let point = self.point;
 
-            if (0.5 + self.ix) / self.nx < (0.5 + self.iy) / self.ny {
+        // Where the line crosses the next grid line on each axis, from 0 at the start to 1 at the
+        // end. These are worked out from scratch each time so that exact corners are found.
+        let half = I::cast(0.5);
+        let next_x = (half + self.steps.0) / self.size.0;
+        let next_y = (half + self.steps.1) / self.size.1;
+
+        let exit = next_x.min(next_y).min(I::one());
+        let length = (exit - self.t) * self.length;
+
+        if exit >= I::one() {
+            self.done = true;
+        } else {
+            // If both grid lines are crossed at once then jump diagonally
+            if next_x == exit {
                 self.point.0 += self.sign_x;
-                self.ix += 1.0;
-            } else {
+                self.steps.0 += I::one();
+            }
+
+            if next_y == exit {
                 self.point.1 += self.sign_y;
-                self.iy += 1.0;
+                self.steps.1 += I::one();
             }
 
-            Some(point)
-        } else {
-            None
+            self.t = exit;
+        }
+
+        Some((point, length))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
         }
+
+        // Each step crosses a grid line on one or both axes
+        let x = (self.size.0 - self.steps.0).to_usize().unwrap_or(0);
+        let y = (self.size.1 - self.steps.1).to_usize().unwrap_or(0);
+
+        (x.max(y) + 1, Some(x + y + 1))
     }
 }
 
-/// Like [`WalkGrid`] but takes diagonal steps if the line passes directly over a corner.
+/// Like [`WalkGrid`] but takes a direction instead of an end point and walks forever.
 ///
-/// See [this section][section] of the [article] for an interactive demonstration.
+/// This visits the same cells as a [`WalkGrid`] to `origin + direction` and keeps on going in the
+/// same way, so it's up to the caller to stop it, e.g. with `take_while`. Because the direction
+/// isn't scaled up to some far away end point, exact ties are decided the same way however far the
+/// walk goes.
 ///
-/// This algorithm should always be symetrical.
+/// A zero direction only returns the origin.
 ///
 /// Example:
 ///
 /// ```
 /// extern crate line_drawing;
-/// use line_drawing::Supercover;
+/// use line_drawing::WalkGridRay;
 ///
 /// fn main() {
-///     for (x, y) in Supercover::new((0, 0), (5, 5)) {
+///     for (x, y) in WalkGridRay::new((0, 0), (2, 1)).take(8) {
 ///         print!("({}, {}), ", x, y);
 ///     }
 /// }
 /// ```
 ///
 /// ```text
-/// (0, 0), (1, 1), (2, 2), (3, 3), (4, 4), (5, 5),
+/// (0, 0), (1, 0), (1, 1), (2, 1), (3, 1), (3, 2), (4, 2), (5, 2),
 /// ```
 ///
 /// [`WalkGrid`]: struct.WalkGrid.html
-/// [section]: http://www.redblobgames.com/grids/line-drawing.html#org1da485d
-/// [article]: http://www.redblobgames.com/grids/line-drawing.html
-pub struct Supercover<T> {
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WalkGridRay<T> {
     point: Point<T>,
-    ix: f32,
-    iy: f32,
     sign_x: T,
     sign_y: T,
-    ny: f32,
-    nx: f32,
+    step_x: T,
+    step_y: T,
+    error: T,
+    done: bool,
 }
 
-impl<T: SignedNum> Supercover<T> {
+impl<T: SignedNum> WalkGridRay<T> {
     #[inline]
-    pub fn new(start: Point<T>, end: Point<T>) -> Self {
-        // Delta values between the points
-        let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+    pub fn new(origin: impl IntoPoint<T>, direction: impl IntoPoint<T>) -> Self {
+        let (origin, direction) = (origin.into_point(), direction.into_point());
+
+        let (nx, ny) = (direction.0.abs(), direction.1.abs());
 
         Self {
-            point: start,
-            ix: 0.0,
-            iy: 0.0,
-            sign_x: dx.signum(),
-            sign_y: dy.signum(),
-            nx: dx.abs().to_f32().unwrap(),
-            ny: dy.abs().to_f32().unwrap(),
+            point: origin,
+            sign_x: direction.0.signum(),
+            sign_y: direction.1.signum(),
+            step_x: ny * T::cast(2),
+            step_y: nx * T::cast(2),
+            // (0.5 + ix) / nx - (0.5 + iy) / ny, scaled up by 2 * nx * ny
+            error: ny - nx,
+            done: false,
         }
     }
 
@@ -142,34 +1775,131 @@ impl<T: SignedNum> Supercover<T> {
     }
 }
 
-impl<T: SignedNum> Iterator for Supercover<T> {
+impl<T: SignedNum> Iterator for WalkGridRay<T> {
     type Item = Point<T>;
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        if self.ix <= self.nx && self.iy <= self.ny {
-            let point = self.point;
+        if self.done {
+            return None;
+        }
 
-            let comparison = ((0.5 + self.ix) / self.nx) - ((0.5 + self.iy) / self.ny);
+        let point = self.point;
 
-            // If the comparison is equal then jump diagonally
-            if comparison == 0.0 {
-                self.point.0 += self.sign_x;
-                self.point.1 += self.sign_y;
-                self.ix += 1.0;
-                self.iy += 1.0;
-            } else if comparison < 0.0 {
-                self.point.0 += self.sign_x;
-                self.ix += 1.0;
-            } else {
-                self.point.1 += self.sign_y;
-                self.iy += 1.0;
-            }
+        if self.error < T::zero() {
+            self.point.0 += self.sign_x;
+            self.error += self.step_x;
+        } else {
+            self.point.1 += self.sign_y;
+            self.error -= self.step_y;
+        }
+
+        self.done = self.sign_x == T::zero() && self.sign_y == T::zero();
+
+        Some(point)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        ray_size_hint(self)
+    }
+}
+
+/// The size of a ray, which is endless unless it has no direction.
+#[inline]
+fn ray_size_hint<T: SignedNum>(walk: &WalkGridRay<T>) -> (usize, Option<usize>) {
+    if walk.done {
+        (0, Some(0))
+    } else if walk.sign_x == T::zero() && walk.sign_y == T::zero() {
+        (1, Some(1))
+    } else {
+        (usize::MAX, None)
+    }
+}
+
+/// Like [`Supercover`] but takes a direction instead of an end point and walks forever.
+///
+/// This visits the same cells as a [`Supercover`] to `origin + direction` and keeps on going in
+/// the same way, so it's up to the caller to stop it, e.g. with `take_while`. Because the
+/// direction isn't scaled up to some far away end point, exact corner crossings are found the same
+/// way however far the walk goes.
+///
+/// A zero direction only returns the origin.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::SupercoverRay;
+///
+/// fn main() {
+///     for (x, y) in SupercoverRay::new((0, 0), (3, 1)).take(8) {
+///         print!("({}, {}), ", x, y);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// (0, 0), (1, 0), (2, 1), (3, 1), (4, 1), (5, 2), (6, 2), (7, 2),
+/// ```
+///
+/// [`Supercover`]: struct.Supercover.html
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SupercoverRay<T> {
+    walk: WalkGridRay<T>,
+}
+
+impl<T: SignedNum> SupercoverRay<T> {
+    #[inline]
+    pub fn new(origin: impl IntoPoint<T>, direction: impl IntoPoint<T>) -> Self {
+        let (origin, direction) = (origin.into_point(), direction.into_point());
+
+        Self {
+            walk: WalkGridRay::new(origin, direction),
+        }
+    }
+
+    #[inline]
+    pub fn steps(self) -> Steps<Point<T>, Self> {
+        Steps::new(self)
+    }
+}
+
+impl<T: SignedNum> Iterator for SupercoverRay<T> {
+    type Item = Point<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let walk = &mut self.walk;
+
+        if walk.done {
+            return None;
+        }
 
-            Some(point)
+        let point = walk.point;
+
+        // If the comparison is equal then jump diagonally
+        if walk.error == T::zero() {
+            walk.point.0 += walk.sign_x;
+            walk.point.1 += walk.sign_y;
+            walk.error += walk.step_x - walk.step_y;
+        } else if walk.error < T::zero() {
+            walk.point.0 += walk.sign_x;
+            walk.error += walk.step_x;
         } else {
-            None
+            walk.point.1 += walk.sign_y;
+            walk.error -= walk.step_y;
         }
+
+        walk.done = walk.sign_x == T::zero() && walk.sign_y == T::zero();
+
+        Some(point)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        ray_size_hint(&self.walk)
     }
 }
 
@@ -198,11 +1928,217 @@ fn walk_grid_tests() {
         [(0, 0), (1, 0), (2, 0), (3, 0), (4, 0), (5, 0)]
     );
 
+    assert_eq!(WalkGrid::new((0, 0), (5, -3)).len(), 9);
+    assert_eq!(WalkGrid::new((2, 2), (2, 2)).len(), 1);
+    assert_eq!(WalkGrid::<i32>::from_float((0.4, 0.3), (3.2, -1.9)).len(), 6);
+
+    // lines too long for `f32` to tell the crossings apart are still walked exactly
+    let far = (1i64 << 40, 3);
+    assert_eq!(WalkGrid::new((0, 0), far).len(), (1 << 40) + 4);
+    assert_eq!(WalkGrid::new((0, 0), far).last(), Some(far));
+    assert_eq!(WalkGrid::new((0, 0), far).nth((1 << 40) + 3), Some(far));
+    assert_eq!(WalkGrid::new((0, 0), far).nth((1 << 40) + 4), None);
+    assert_eq!(WalkGrid::new((0, 0), far).nth(1 << 39), Some(((1 << 39) - 1, 1)));
+    assert_eq!(
+        WalkGrid::new((i128::MAX - 3, i128::MIN), (i128::MAX, i128::MIN + 2)).collect::<Vec<_>>(),
+        walk_grid((0, 0), (3, 2))
+            .into_iter()
+            .map(|(x, y)| (i128::MAX - 3 + x as i128, i128::MIN + y as i128))
+            .collect::<Vec<_>>()
+    );
+
+    // and so are ones across most of the range of `i64`, where comparing the crossings takes
+    // more bits than even `i128` has
+    let (start, end) = ((i64::MIN, i64::MIN), (i64::MAX, i64::MAX - 1));
+    let (nx, ny) = (u64::MAX as u128, u64::MAX as u128 - 1);
+
+    for &steps in &[1usize << 62, 1 << 63, usize::MAX] {
+        let (x, y) = WalkGrid::new(start, end).nth(steps).unwrap();
+        let (kx, ky) = ((x as u64 ^ 1 << 63) as u128, (y as u64 ^ 1 << 63) as u128);
+
+        // Every line crossed comes before the next one on the other axis, and `y` goes first
+        assert_eq!(kx + ky, steps as u128);
+        assert!((2 * kx - 1) * ny < (2 * ky + 1) * nx);
+        assert!((2 * ky - 1) * nx <= (2 * kx + 1) * ny);
+    }
+
+    assert_eq!(WalkGrid::new(start, end).last(), Some(end));
+    assert_eq!(WalkGrid::new(start, end).len(), usize::MAX);
+    assert_eq!(WalkGrid::try_new(start, end), Err(LineError::Overflow));
+
+    let bounds = GridBounds::new((-5, -5), (5, 5));
+    let clipped = WalkGrid::clipped(start, end, bounds).collect::<Vec<_>>();
+    assert_eq!(clipped.len(), 21);
+    assert!(clipped.iter().all(|&point| bounds.contains(point)));
+    assert!(clipped.windows(2).all(|pair| {
+        (pair[1].0 - pair[0].0) + (pair[1].1 - pair[0].1) == 1
+    }));
+
     // by default, walk grid is asymmetrical
     assert_ne!(
         walk_grid((0, 0), (2, 2)),
         reverse_slice(&walk_grid((2, 2), (0, 0)))
     );
+
+    // each direction matches the step into the cell
+    for &(a, b) in &[
+        ((0, 0), (5, 3)),
+        ((4, -2), (-3, 6)),
+        ((0, 0), (-4, -4)),
+        ((1, 1), (1, 1)),
+    ] {
+        let directions = WalkGrid::new(a, b).directions().collect::<Vec<_>>();
+
+        assert_eq!(
+            directions
+                .iter()
+                .map(|&(point, _)| point)
+                .collect::<Vec<_>>(),
+            walk_grid(a, b)
+        );
+        assert_eq!(directions[0].1, None);
+
+        for pair in directions.windows(2) {
+            let ((x, y), _) = pair[0];
+            let (dx, dy): Point<i32> = pair[1].1.unwrap().offset();
+            assert_eq!((x + dx, y + dy), pair[1].0);
+        }
+    }
+}
+
+#[test]
+fn clipped_tests() {
+    let bounds = GridBounds::new((-3, -2), (4, 6));
+
+    for &(start, end) in &[
+        ((-10, -10), (10, 10)),
+        ((-20, 3), (20, 1)),
+        ((7, -9), (-6, 12)),
+        ((0, 0), (2, 3)),
+        ((-8, 30), (2, -25)),
+        ((10, 10), (20, 5)),
+        ((-5, 5), (-5, 5)),
+        ((1, 1), (1, 1)),
+        ((5, -4), (-9, -1)),
+        ((-7, -6), (8, 9)),
+        ((4, -20), (4, 20)),
+        ((-30, 0), (30, 0)),
+        ((8, -18), (8, 2)),
+    ] {
+        // The length is known exactly for walks, and is within the hinted bounds for supercovers
+        let mut walk = WalkGrid::clipped(start, end, bounds);
+        assert_eq!(walk.len(), walk.by_ref().count());
+        assert_eq!(walk.len(), 0);
+
+        let line = Supercover::clipped(start, end, bounds);
+        let (low, high) = line.size_hint();
+        let count = line.count();
+        assert!(low <= count && count <= high.unwrap());
+
+        assert_eq!(
+            WalkGrid::clipped(start, end, bounds).collect::<Vec<_>>(),
+            WalkGrid::new(start, end)
+                .filter(|&point| bounds.contains(point))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Supercover::clipped(start, end, bounds).collect::<Vec<_>>(),
+            Supercover::new(start, end)
+                .filter(|&point| bounds.contains(point))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    // Leaving the bounds through a corner doesn't step on the other axis first
+    let bounds = GridBounds::new((0, 0), (1, 3));
+    assert_eq!(Supercover::clipped((0, 0), (4, 4), bounds).last(), Some((1, 1)));
+
+    let bounds = GridBounds::new((-379, 175), (485, 233));
+    assert_eq!(
+        Supercover::clipped((17, 194), (292, 29), bounds).last(),
+        Some((49, 175))
+    );
+}
+
+#[test]
+fn from_float_tests() {
+    let walk_grid = |a, b| WalkGrid::<i32>::from_float::<f64>(a, b).collect::<Vec<_>>();
+    let supercover = |a, b| Supercover::<i32>::from_float::<f64>(a, b).collect::<Vec<_>>();
+
+    // Points at the cell centers are the same as the integer versions
+    for &(a, b) in &[
+        ((0, 0), (5, 3)),
+        ((4, -2), (-3, 6)),
+        ((0, 0), (-4, -4)),
+        ((1, 1), (1, 1)),
+    ] {
+        let float = |(x, y): Point<i32>| (f64::from(x), f64::from(y));
+
+        assert_eq!(
+            walk_grid(float(a), float(b)),
+            WalkGrid::new(a, b).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            supercover(float(a), float(b)),
+            Supercover::new(a, b).collect::<Vec<_>>()
+        );
+    }
+
+    // including ones too far apart for floats to tell the crossings apart
+    let far = ((1u64 << 60) as f64, 3.0);
+    assert_eq!(
+        WalkGrid::<i64>::from_float((0.0, 0.0), far),
+        WalkGrid::new((0, 0), (1 << 60, 3))
+    );
+    assert_eq!(
+        Supercover::<i64>::from_float((0.0, 0.0), far),
+        Supercover::new((0, 0), (1 << 60, 3))
+    );
+
+    // Crossings more than 2^24 cells along still come out in order
+    let line = || WalkGrid::<i64>::from_float((-0.25, 0.0), ((1 << 30) as f64 - 0.25, 1.0));
+    assert_eq!(line().nth(1 << 29), Some((1 << 29, 0)));
+    assert_eq!(line().nth((1 << 29) + 1), Some((1 << 29, 1)));
+
+    // Rounding the ends first would give (0, 0), (0, 1), (1, 1)
+    assert_eq!(walk_grid((0.4, 0.3), (1.4, 1.3)), [(0, 0), (1, 0), (1, 1)]);
+
+    assert_eq!(walk_grid((-0.6, 0.2), (-0.6, 0.2)), [(-1, 0)]);
+    assert_eq!(
+        supercover((0.1, 0.1), (-1.2, -0.9)),
+        [(0, 0), (-1, 0), (-1, -1)]
+    );
+
+    // Crossing exactly through a corner
+    assert_eq!(supercover((0.2, 0.2), (1.2, 1.2)), [(0, 0), (1, 1)]);
+
+    // World space with a cell size and origin
+    let cells = |a, b, size, origin| {
+        WalkGrid::<i32>::with_cells::<f64>(a, b, size, origin).collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        cells((5.0, 5.0), (40.0, 20.0), (16.0, 16.0), (0.0, 0.0)),
+        walk_grid(
+            (5.0 / 16.0 - 0.5, 5.0 / 16.0 - 0.5),
+            (40.0 / 16.0 - 0.5, 20.0 / 16.0 - 0.5)
+        )
+    );
+
+    // Points on the edge of a cell belong to the cell after it, and negative cells work too
+    assert_eq!(
+        cells((-10.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 0.0)),
+        [(-1, 0), (0, 0), (1, 0)]
+    );
+    assert_eq!(
+        cells((100.0, 50.0), (100.0, 70.0), (8.0, 4.0), (96.0, 46.0)),
+        [(0, 1), (0, 2), (0, 3), (0, 4), (0, 5), (0, 6)]
+    );
+    assert_eq!(
+        Supercover::<i32>::with_cells::<f64>((1.0, 1.0), (31.0, 31.0), (10.0, 10.0), (0.0, 0.0))
+            .collect::<Vec<_>>(),
+        [(0, 0), (1, 1), (2, 2), (3, 3)]
+    );
 }
 
 #[test]
@@ -218,6 +2154,7 @@ fn supercover_tests() {
     );
 
     assert_eq!(supercover((0, 0), (3, 1)), [(0, 0), (1, 0), (2, 1), (3, 1)]);
+    assert_eq!(Supercover::new((0, 0), (3, 1)).len(), 4);
 
     assert_eq!(
         supercover((0, 0), (0, 5)),
@@ -237,4 +2174,237 @@ fn supercover_tests() {
 
     // otherwise it should do the same as walk grid
     assert_eq!(supercover((0, 0), (4, 5)), walk_grid((0, 0), (4, 5)));
+
+    // the length counts each corner as one step, wherever the line starts or is cut short
+    let walk = |line: Supercover<i32>| line.fold(0, |count, _| count + 1);
+    let bounds = GridBounds::new((-3, -4), (5, 2));
+
+    for x in -7..8 {
+        for y in -7..8 {
+            for &start in &[(0, 0), (1, -2), (-5, 3)] {
+                let line = Supercover::new(start, (x, y));
+                assert_eq!(line.len(), walk(line.clone()));
+                assert_eq!(line.clone().count(), walk(line.clone()));
+
+                let mut rest = line.clone();
+                assert_eq!(rest.nth(2), line.clone().collect::<Vec<_>>().get(2).cloned());
+                assert_eq!(rest.len(), walk(line).saturating_sub(3));
+
+                let line = Supercover::clipped(start, (x, y), bounds);
+                assert_eq!(line.len(), walk(line.clone()));
+            }
+        }
+    }
+
+    let line = Supercover::<i32>::from_float((0.5, 0.5), (4.5, 4.5));
+    assert_eq!(line.len(), 5);
+    let line = Supercover::<i32>::from_float((-0.25, 1.5), (3.75, -2.5));
+    assert_eq!(line.len(), walk(line.clone()));
+
+    // skipping ahead jumps straight to the point, even on long lines
+    let far = (3i64 << 40, -(1 << 40));
+    let mut line = Supercover::new((0, 0), far);
+    assert_eq!(line.nth(3 << 39), Some((3 << 39, -(1 << 39))));
+    assert_eq!(line.nth((3 << 39) - 1), Some(far));
+    assert_eq!(line.next(), None);
+    assert_eq!(Supercover::new((0, 0), far).nth(usize::MAX), None);
+
+    // even when the line is more than half the range of `i128` long
+    let (start, end) = ((i128::MIN / 4, 0), (i128::MAX / 4, 5));
+    assert_eq!(
+        Supercover::new(start, end).nth(1 << 62),
+        Some((i128::MIN / 4 + (1 << 62), 0))
+    );
+    assert_eq!(Supercover::new(start, end).last(), Some(end));
+    assert_eq!(Supercover::new(start, end).len(), usize::MAX);
+
+    let (start, end) = ((i64::MIN, 0), (i64::MAX, 3));
+    assert_eq!(Supercover::new(start, end).len(), usize::MAX);
+    assert_eq!(Supercover::new(start, end).last(), Some(end));
+    assert_eq!(Supercover::try_new(start, end), Err(LineError::Overflow));
+
+    let events = |a, b| Supercover::new(a, b).events().collect::<Vec<_>>();
+
+    assert_eq!(
+        events((2, 2), (0, 0)),
+        [
+            SupercoverEvent::Cell((2, 2)),
+            SupercoverEvent::Corner {
+                from: (2, 2),
+                to: (1, 1)
+            },
+            SupercoverEvent::Cell((1, 1)),
+            SupercoverEvent::Corner {
+                from: (1, 1),
+                to: (0, 0)
+            },
+            SupercoverEvent::Cell((0, 0)),
+        ]
+    );
+
+    // Without any corners there are only cells
+    for &(a, b) in &[((0, 0), (4, 5)), ((3, 3), (3, 3)), ((-2, 7), (9, -1))] {
+        let cells = events(a, b)
+            .into_iter()
+            .filter_map(|event| match event {
+                SupercoverEvent::Cell(point) => Some(point),
+                SupercoverEvent::Corner { .. } => None,
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(cells, supercover(a, b));
+    }
+}
+
+#[test]
+fn thick_supercover_tests() {
+    use fuzzing::reverse_slice;
+    let thick = |a, b| ThickSupercover::<i32>::new(a, b).collect::<Vec<_>>();
+
+    assert_eq!(
+        thick((0, 0), (3, 1)),
+        [(0, 0), (1, 0), (2, 0), (1, 1), (2, 1), (3, 1)]
+    );
+    assert_eq!(
+        thick((0, 0), (4, 5)),
+        Supercover::new((0, 0), (4, 5)).collect::<Vec<_>>()
+    );
+    assert_eq!(thick((1, 1), (1, 1)), [(1, 1)]);
+
+    for &(a, b) in &[
+        ((0, 0), (6, 6)),
+        ((3, -3), (-3, 3)),
+        ((0, 0), (9, 3)),
+        ((5, 2), (-7, 8)),
+    ] {
+        let forward = thick(a, b);
+        assert_eq!(forward, reverse_slice(&thick(b, a)));
+
+        // Every cell shares an edge with the one before or after it
+        let edge = |a: Point<i32>, b: Point<i32>| (b.0 - a.0).abs() + (b.1 - a.1).abs() == 1;
+        for (i, &point) in forward.iter().enumerate() {
+            assert!(
+                (i > 0 && edge(forward[i - 1], point))
+                    || (i + 1 < forward.len() && edge(point, forward[i + 1]))
+            );
+        }
+    }
+}
+
+#[test]
+fn supercover_lengths_tests() {
+    let lengths = |a, b| SupercoverLengths::<f64, i32>::new(a, b).collect::<Vec<_>>();
+
+    assert_eq!(lengths((2, 2), (2, 2)), [((2, 2), 0.0)]);
+    assert_eq!(
+        lengths((0, 0), (0, -2)),
+        [((0, 0), 0.5), ((0, -1), 1.0), ((0, -2), 0.5)]
+    );
+
+    let diagonal = lengths((0, 0), (2, 2));
+    assert_eq!(
+        diagonal.iter().map(|&(point, _)| point).collect::<Vec<_>>(),
+        [(0, 0), (1, 1), (2, 2)]
+    );
+    assert!((diagonal[1].1 - 2f64.sqrt()).abs() < 1e-9);
+
+    // Visits the same cells as supercover, and the lengths add up to the whole line
+    for &(a, b) in &[
+        ((0, 0), (5, 3)),
+        ((4, -2), (-3, 6)),
+        ((0, 0), (-4, -4)),
+        ((0, 0), (9, 3)),
+        ((7, 1), (-2, 1)),
+    ] {
+        let lengths = lengths(a, b);
+
+        assert_eq!(
+            lengths.iter().map(|&(point, _)| point).collect::<Vec<_>>(),
+            Supercover::new(a, b).collect::<Vec<_>>()
+        );
+
+        let total = lengths.iter().map(|&(_, length)| length).sum::<f64>();
+        let (dx, dy) = (f64::from(b.0 - a.0), f64::from(b.1 - a.1));
+        assert!((total - dx.hypot(dy)).abs() < 1e-9);
+        assert!(lengths.iter().all(|&(_, length)| length > 0.0));
+    }
+}
+
+#[test]
+fn walk_edges_tests() {
+    let edges = |a, b| WalkEdges::<i32>::new(a, b).collect::<Vec<_>>();
+
+    assert_eq!(edges((3, 3), (3, 3)), []);
+    assert_eq!(
+        edges((0, 0), (2, 0)),
+        [
+            GridEdge {
+                cell: (0, 0),
+                side: Side::X
+            },
+            GridEdge {
+                cell: (1, 0),
+                side: Side::X
+            }
+        ]
+    );
+
+    // Each edge is between two consecutive cells of the walk
+    for &(a, b) in &[((0, 0), (5, 3)), ((4, -2), (-3, 6)), ((0, 0), (-4, -4))] {
+        let cells = WalkGrid::new(a, b).collect::<Vec<_>>();
+        let edges = edges(a, b);
+
+        assert_eq!(edges.len(), cells.len() - 1);
+
+        for (edge, pair) in edges.iter().zip(cells.windows(2)) {
+            let (low, high) = edge.cells();
+            assert!((low, high) == (pair[0], pair[1]) || (high, low) == (pair[0], pair[1]));
+        }
+    }
+}
+
+#[test]
+fn ray_tests() {
+    // The rays follow the same path as walking to the end of the direction
+    for &(origin, direction) in &[
+        ((0, 0), (5, 3)),
+        ((3, -2), (-4, 7)),
+        ((10, 10), (-6, -6)),
+        ((0, 0), (0, 5)),
+        ((0, 0), (-5, 0)),
+        ((-7, 1), (2, -9)),
+    ] {
+        let end = (origin.0 + direction.0, origin.1 + direction.1);
+        let length = WalkGrid::new(origin, end).count();
+
+        assert_eq!(
+            WalkGridRay::new(origin, direction)
+                .take(length)
+                .collect::<Vec<_>>(),
+            WalkGrid::new(origin, end).collect::<Vec<_>>()
+        );
+
+        let length = Supercover::new(origin, end).count();
+
+        assert_eq!(
+            SupercoverRay::new(origin, direction)
+                .take(length)
+                .collect::<Vec<_>>(),
+            Supercover::new(origin, end).collect::<Vec<_>>()
+        );
+    }
+
+    // Corners are found exactly far along the ray
+    assert!(SupercoverRay::new((0, 0), (3, 1))
+        .take(1_000_000)
+        .any(|point| point == (300_000, 100_000)));
+
+    assert_eq!(
+        WalkGridRay::new((4, 4), (0, 0)).collect::<Vec<_>>(),
+        [(4, 4)]
+    );
+    assert_eq!(
+        SupercoverRay::new((4, 4), (0, 0)).collect::<Vec<_>>(),
+        [(4, 4)]
+    );
 }
@@ -0,0 +1,82 @@
+use heapless::Vec;
+
+/// The error from [`collect_into`] when some of the points didn't fit.
+///
+/// [`collect_into`]: fn.collect_into.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Truncated {
+    /// How many of the points were left out.
+    pub missing: usize,
+}
+
+/// Add the points of a line, or any other shape, to the end of a fixed-capacity `heapless::Vec`,
+/// for holding on to them without allocating.
+///
+/// If there's no room left for all of them, the vec is filled up with the first ones and the
+/// number that were left out is returned as a [`Truncated`] error.
+///
+/// Example:
+///
+/// ```
+/// extern crate heapless;
+/// extern crate line_drawing;
+/// use line_drawing::{collect_into, Bresenham, Point, Truncated};
+///
+/// fn main() {
+///     let mut points = heapless::Vec::<Point<i8>, 4>::new();
+///
+///     assert_eq!(collect_into(Bresenham::new((0, 0), (2, 1)), &mut points), Ok(()));
+///     assert_eq!(
+///         collect_into(Bresenham::new((5, 5), (5, 9)), &mut points),
+///         Err(Truncated { missing: 4 })
+///     );
+///     assert_eq!(points, [(0, 0), (1, 0), (2, 1), (5, 5)]);
+/// }
+/// ```
+///
+/// [`Truncated`]: struct.Truncated.html
+pub fn collect_into<I, const N: usize>(
+    points: I,
+    vec: &mut Vec<I::Item, N>,
+) -> Result<(), Truncated>
+where
+    I: IntoIterator,
+{
+    let mut points = points.into_iter();
+
+    while let Some(point) = points.next() {
+        if vec.push(point).is_err() {
+            return Err(Truncated {
+                missing: 1 + points.count(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn tests() {
+    use {Bresenham3d, BresenhamCircle, Voxel};
+
+    let mut voxels = Vec::<Voxel<i32>, 16>::new();
+    assert_eq!(collect_into(Bresenham3d::new((0, 0, 0), (3, -2, 1)), &mut voxels), Ok(()));
+    assert_eq!(
+        &voxels[..],
+        &Bresenham3d::new((0, 0, 0), (3, -2, 1)).collect::<std::vec::Vec<_>>()[..]
+    );
+
+    let mut points = Vec::<_, 5>::new();
+    let circle = BresenhamCircle::new(0, 0, 3);
+    let count = circle.clone().count();
+    assert_eq!(
+        collect_into(circle.clone(), &mut points),
+        Err(Truncated { missing: count - 5 })
+    );
+    assert_eq!(&points[..], &circle.take(5).collect::<std::vec::Vec<_>>()[..]);
+
+    // A full vec still takes an empty iterator
+    assert_eq!(collect_into(None, &mut points), Ok(()));
+    assert_eq!(collect_into(Some((1, 1)), &mut points), Err(Truncated { missing: 1 }));
+}
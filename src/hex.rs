@@ -0,0 +1,446 @@
+use {IntoPoint, LineRasterizer, Point, SignedNum};
+use steps::Steps;
+use core::cmp::max;
+
+/// The cube coordinates of an axial hex coordinate.
+#[inline]
+fn cube<T: SignedNum>(hex: Point<T>) -> (T, T, T) {
+    (hex.0, hex.1, -hex.0 - hex.1)
+}
+
+/// Round `numerator / denominator + nudge * epsilon` to the nearest integer, where epsilon is an
+/// infinitely small positive number.
+///
+/// Returns the rounded value and its distance from the unrounded one, as an `(exact, epsilon)`
+/// pair scaled up by the denominator.
+#[inline]
+fn round_nudged<T: SignedNum>(numerator: T, denominator: T, nudge: T) -> (T, (T, T)) {
+    let two = T::cast(2);
+    let doubled = two * numerator + denominator;
+    let mut rounded = doubled / (two * denominator);
+
+    // Floor the division for negative values
+    if doubled % (two * denominator) != T::zero() && doubled < T::zero() {
+        rounded -= T::one();
+    }
+
+    // If the value is exactly halfway, the nudge decides which way it goes
+    if doubled % (two * denominator) == T::zero() && nudge < T::zero() {
+        rounded -= T::one();
+    }
+
+    let difference = rounded * denominator - numerator;
+
+    let distance = if difference > T::zero() {
+        (difference, -nudge)
+    } else if difference < T::zero() {
+        (-difference, nudge)
+    } else {
+        (T::zero(), nudge.abs())
+    };
+
+    (rounded, distance)
+}
+
+/// Round a fractional cube coordinate to the nearest hex, keeping `q + r + s == 0`.
+///
+/// The coordinate is `numerators / denominator`, moved by an infinitely small `nudge` to decide
+/// which hex points exactly on an edge or corner belong to.
+#[inline]
+pub(crate) fn hex_round<T: SignedNum>(
+    numerators: (T, T, T),
+    denominator: T,
+    nudge: (T, T, T),
+) -> Point<T> {
+    let (q, q_distance) = round_nudged(numerators.0, denominator, nudge.0);
+    let (r, r_distance) = round_nudged(numerators.1, denominator, nudge.1);
+    let (s, s_distance) = round_nudged(numerators.2, denominator, nudge.2);
+
+    // Recalculate the coordinate that was rounded the furthest from the other two
+    if q_distance > r_distance && q_distance > s_distance {
+        (-r - s, r)
+    } else if r_distance > s_distance {
+        (q, -q - s)
+    } else {
+        (q, r)
+    }
+}
+
+/// Draw a line between two hexes, using [linear interpolation and rounding].
+///
+/// The hexes use axial `(q, r)` coordinates, where the third cube coordinate is `s = -q - r`. The
+/// interpolation is done with exact integer arithmetic, relative to the start so that lines far
+/// from the origin don't overflow. Points that land exactly on the edge between hexes are nudged
+/// in a consistent direction, so the line isn't symmetrical.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::HexLine;
+///
+/// fn main() {
+///     for (q, r) in HexLine::new((0, 0), (4, -2)) {
+///         print!("({}, {}), ", q, r);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// (0, 0), (1, 0), (2, -1), (3, -1), (4, -2),
+/// ```
+///
+/// [linear interpolation and rounding]: https://www.redblobgames.com/grids/hexagons/#line-drawing
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HexLine<T> {
+    start: Point<T>,
+    delta: (T, T, T),
+    distance: T,
+    step: T,
+}
+
+impl<T: SignedNum> HexLine<T> {
+    #[inline]
+    pub fn new(start: impl IntoPoint<T>, end: impl IntoPoint<T>) -> Self {
+        let (start, end) = (start.into_point(), end.into_point());
+
+        let delta = cube((end.0 - start.0, end.1 - start.1));
+
+        Self {
+            start,
+            delta,
+            distance: max(delta.0.abs(), max(delta.1.abs(), delta.2.abs())),
+            step: T::zero(),
+        }
+    }
+
+    /// Like [`LineRasterizer::steps`], without needing the trait imported.
+    ///
+    /// [`LineRasterizer::steps`]: trait.LineRasterizer.html#method.steps
+    #[inline]
+    pub fn steps(self) -> Steps<Point<T>, Self> {
+        LineRasterizer::steps(self)
+    }
+}
+
+impl<T: SignedNum> HexLine<T> {
+    /// The offset of the current point from the start, in cube coordinates scaled up by the
+    /// distance.
+    #[inline]
+    fn numerators(&self) -> (T, T, T) {
+        (
+            self.delta.0 * self.step,
+            self.delta.1 * self.step,
+            self.delta.2 * self.step,
+        )
+    }
+}
+
+impl<T: SignedNum> LineRasterizer<T, T> for HexLine<T> {
+    #[inline]
+    fn new(start: Point<T>, end: Point<T>) -> Self {
+        HexLine::new(start, end)
+    }
+}
+
+impl<T: SignedNum> Iterator for HexLine<T> {
+    type Item = Point<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.step <= self.distance {
+            let hex = if self.distance == T::zero() {
+                self.start
+            } else {
+                let offset = hex_round(
+                    self.numerators(),
+                    self.distance,
+                    (T::one(), T::cast(2), -T::cast(3)),
+                );
+
+                (self.start.0 + offset.0, self.start.1 + offset.1)
+            };
+
+            self.step += T::one();
+
+            Some(hex)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.distance - self.step + T::one())
+            .to_usize()
+            .unwrap_or(0);
+        (remaining, Some(remaining))
+    }
+
+    #[inline]
+    fn last(self) -> Option<Self::Item> {
+        if self.step <= self.distance {
+            Some((self.start.0 + self.delta.0, self.start.1 + self.delta.1))
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.len()
+    }
+}
+
+impl<T: SignedNum> ExactSizeIterator for HexLine<T> {}
+
+/// Like [`HexLine`] but includes every hex touching the line where it passes exactly along an
+/// edge or through a corner.
+///
+/// Instead of nudging these points one way, all of the hexes around them are included. To keep
+/// the line symmetrical, they are ordered by how far along the line their centers are, and then
+/// by which side of the line they are on. This means that swapping `start` and `end` gives exactly
+/// the same hexes in reverse.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::HexSupercover;
+///
+/// fn main() {
+///     for (q, r) in HexSupercover::new((0, 0), (2, -4)) {
+///         print!("({}, {}), ", q, r);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// (0, 0), (1, -1), (0, -1), (1, -2), (2, -3), (1, -3), (2, -4),
+/// ```
+///
+/// [`HexLine`]: struct.HexLine.html
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HexSupercover<T> {
+    line: HexLine<T>,
+    hexes: [Point<T>; 3],
+    count: usize,
+    index: usize,
+}
+
+impl<T: SignedNum> HexSupercover<T> {
+    #[inline]
+    pub fn new(start: impl IntoPoint<T>, end: impl IntoPoint<T>) -> Self {
+        let (start, end) = (start.into_point(), end.into_point());
+
+        Self {
+            line: HexLine::new(start, end),
+            hexes: [start; 3],
+            count: 0,
+            index: 0,
+        }
+    }
+
+    /// Like [`LineRasterizer::steps`], without needing the trait imported.
+    ///
+    /// [`LineRasterizer::steps`]: trait.LineRasterizer.html#method.steps
+    #[inline]
+    pub fn steps(self) -> Steps<Point<T>, Self> {
+        LineRasterizer::steps(self)
+    }
+
+    /// Find all the hexes around the next point along the line.
+    #[inline]
+    fn next_hexes(&mut self) -> bool {
+        let line = &mut self.line;
+
+        if line.step > line.distance {
+            return false;
+        }
+
+        let previous = if self.count > 0 {
+            Some(self.hexes[self.count - 1])
+        } else {
+            None
+        };
+
+        self.count = 0;
+        self.index = 0;
+
+        if line.distance == T::zero() {
+            self.hexes[0] = line.start;
+            self.count = 1;
+        } else {
+            let numerators = line.numerators();
+            let start = line.start;
+
+            // Nudging the point in enough directions finds every hex that it touches
+            let (one, two, three) = (T::one(), T::cast(2), T::cast(3));
+            let nudges = [
+                (one, two, -three),
+                (one, -three, two),
+                (two, one, -three),
+                (two, -three, one),
+                (-three, one, two),
+                (-three, two, one),
+            ];
+
+            for &(a, b, c) in &nudges {
+                for &sign in &[one, -one] {
+                    let offset =
+                        hex_round(numerators, line.distance, (a * sign, b * sign, c * sign));
+                    let hex = (start.0 + offset.0, start.1 + offset.1);
+
+                    if !self.hexes[..self.count].contains(&hex) && Some(hex) != previous {
+                        self.hexes[self.count] = hex;
+                        self.count += 1;
+                    }
+                }
+            }
+
+            // Order by the distance along the line, then by the side of the line
+            let delta = line.delta;
+            let key = |hex: &Point<T>| {
+                let (q, r, s) = cube((hex.0 - start.0, hex.1 - start.1));
+                (q * delta.0 + r * delta.1 + s * delta.2, q * delta.1 - r * delta.0)
+            };
+
+            self.hexes[..self.count].sort_unstable_by_key(key);
+        }
+
+        line.step += T::one();
+
+        true
+    }
+}
+
+impl<T: SignedNum> LineRasterizer<T, T> for HexSupercover<T> {
+    #[inline]
+    fn new(start: Point<T>, end: Point<T>) -> Self {
+        HexSupercover::new(start, end)
+    }
+}
+
+impl<T: SignedNum> Iterator for HexSupercover<T> {
+    type Item = Point<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.index >= self.count {
+            if !self.next_hexes() {
+                return None;
+            }
+        }
+
+        self.index += 1;
+
+        Some(self.hexes[self.index - 1])
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Each point along the line is in at least one new hex, and touches at most three
+        let (steps, _) = self.line.size_hint();
+        let buffered = self.count - self.index.min(self.count);
+
+        (
+            buffered.saturating_add(steps),
+            steps
+                .checked_mul(3)
+                .and_then(|high| high.checked_add(buffered)),
+        )
+    }
+}
+
+#[test]
+fn tests() {
+    let hex_line = |a, b| HexLine::new(a, b).collect::<Vec<_>>();
+
+    assert_eq!(hex_line((0, 0), (0, 0)), [(0, 0)]);
+    assert_eq!(hex_line((0, 0), (3, 0)), [(0, 0), (1, 0), (2, 0), (3, 0)]);
+    assert_eq!(
+        hex_line((2, -1), (-1, 2)),
+        [(2, -1), (1, 0), (0, 1), (-1, 2)]
+    );
+
+    // Lines along the edges between hexes are decided by the nudge
+    assert_eq!(
+        hex_line((0, 0), (2, -4)),
+        [(0, 0), (1, -1), (1, -2), (2, -3), (2, -4)]
+    );
+
+    // Should match the floating-point version from the article
+    let float_hex_line = |a: Point<i32>, b: Point<i32>| {
+        let distance = HexLine::new(a, b).distance;
+
+        (0..=distance)
+            .map(|i| {
+                let t = if distance == 0 {
+                    0.0
+                } else {
+                    f64::from(i) / f64::from(distance)
+                };
+                let lerp = |a: i32, b: i32, nudge: f64| {
+                    f64::from(a) + nudge + (f64::from(b) - f64::from(a)) * t
+                };
+
+                let q = lerp(a.0, b.0, 1e-6);
+                let r = lerp(a.1, b.1, 2e-6);
+                let s = lerp(-a.0 - a.1, -b.0 - b.1, -3e-6);
+
+                let (mut rq, mut rr, rs) = (q.round(), r.round(), s.round());
+                let (dq, dr, ds) = ((rq - q).abs(), (rr - r).abs(), (rs - s).abs());
+
+                if dq > dr && dq > ds {
+                    rq = -rr - rs;
+                } else if dr > ds {
+                    rr = -rq - rs;
+                }
+
+                (rq as i32, rr as i32)
+            })
+            .collect::<Vec<_>>()
+    };
+
+    for &(a, b) in &[
+        ((0, 0), (7, -3)),
+        ((-5, 9), (11, -2)),
+        ((4, 4), (-8, 0)),
+        ((0, 0), (-6, 3)),
+        ((3, -7), (3, 12)),
+    ] {
+        assert_eq!(hex_line(a, b), float_hex_line(a, b));
+    }
+
+    let hex_supercover = |a, b| HexSupercover::new(a, b).collect::<Vec<_>>();
+
+    // Includes both hexes where the line runs along an edge
+    assert_eq!(
+        hex_supercover((0, 0), (2, -4)),
+        [(0, 0), (1, -1), (0, -1), (1, -2), (2, -3), (1, -3), (2, -4)]
+    );
+    assert_eq!(hex_supercover((0, 0), (3, 0)), hex_line((0, 0), (3, 0)));
+    assert_eq!(hex_supercover((5, 5), (5, 5)), [(5, 5)]);
+
+    for &(a, b) in &[
+        ((0, 0), (2, -4)),
+        ((0, 0), (7, -3)),
+        ((-5, 9), (11, -2)),
+        ((4, 4), (-8, 0)),
+        ((3, 3), (-3, -3)),
+        ((-6, 0), (6, 0)),
+    ] {
+        use fuzzing::reverse_slice;
+
+        let forward = hex_supercover(a, b);
+        assert_eq!(forward, reverse_slice(&hex_supercover(b, a)));
+
+        // Every hex is next to the one before it
+        for pair in forward.windows(2) {
+            let (q, r, s) = cube((pair[1].0 - pair[0].0, pair[1].1 - pair[0].1));
+            assert_eq!(max(q.abs(), max(r.abs(), s.abs())), 1);
+        }
+    }
+}
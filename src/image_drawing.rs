@@ -0,0 +1,156 @@
+use core::ops::{Deref, DerefMut};
+use image::{ImageBuffer, Pixel};
+use num_traits::{NumCast, ToPrimitive, Zero};
+use {rasterize, IntoPoint, LineAlgorithm, Point, RealNum, SignedNum, XiaolinWu};
+
+/// Get the pixel at a point, if it's inside the image.
+#[inline]
+fn pixel_mut<P, C, T>(image: &mut ImageBuffer<P, C>, (x, y): Point<T>) -> Option<&mut P>
+where
+    P: Pixel + 'static,
+    P::Subpixel: 'static,
+    C: Deref<Target = [P::Subpixel]> + DerefMut,
+    T: ToPrimitive,
+{
+    let (x, y) = (x.to_u32()?, y.to_u32()?);
+
+    if x < image.width() && y < image.height() {
+        Some(image.get_pixel_mut(x, y))
+    } else {
+        None
+    }
+}
+
+/// Draw a line onto an image with one of the [`LineAlgorithm`]s, setting each pixel it goes
+/// through to `color`.
+///
+/// The parts of the line that are outside of the image are left out.
+///
+/// Example:
+///
+/// ```
+/// extern crate image;
+/// extern crate line_drawing;
+/// use image::{Luma, GrayImage};
+/// use line_drawing::{draw_line, LineAlgorithm};
+///
+/// fn main() {
+///     let mut image = GrayImage::new(4, 4);
+///     draw_line(&mut image, LineAlgorithm::Bresenham, (0, 0), (7, 7), Luma([255]));
+///
+///     assert_eq!(image.get_pixel(3, 3), &Luma([255]));
+///     assert_eq!(image.get_pixel(3, 2), &Luma([0]));
+/// }
+/// ```
+///
+/// [`LineAlgorithm`]: enum.LineAlgorithm.html
+pub fn draw_line<P, C, T>(
+    image: &mut ImageBuffer<P, C>,
+    algorithm: LineAlgorithm,
+    start: impl IntoPoint<T>,
+    end: impl IntoPoint<T>,
+    color: P,
+) where
+    P: Pixel + 'static,
+    P::Subpixel: 'static,
+    C: Deref<Target = [P::Subpixel]> + DerefMut,
+    T: SignedNum,
+{
+    for point in rasterize(start.into_point(), end.into_point(), algorithm) {
+        if let Some(pixel) = pixel_mut(image, point) {
+            *pixel = color;
+        }
+    }
+}
+
+/// Draw an anti-aliased line onto an image with [`XiaolinWu`], blending each pixel towards
+/// `color` by how much of it the line covers.
+///
+/// Every channel is blended, including alpha. The parts of the line that are outside of the image
+/// are left out.
+///
+/// Example:
+///
+/// ```
+/// extern crate image;
+/// extern crate line_drawing;
+/// use image::{Rgb, RgbImage};
+/// use line_drawing::draw_line_aa;
+///
+/// fn main() {
+///     let mut image = RgbImage::new(4, 4);
+///     draw_line_aa(&mut image, (0.0, 1.0), (3.0, 1.0), Rgb([255, 0, 0]));
+///     draw_line_aa(&mut image, (0.0, 2.5), (3.0, 2.5), Rgb([255, 0, 0]));
+///
+///     assert_eq!(image.get_pixel(1, 1), &Rgb([255, 0, 0]));
+///     assert_eq!(image.get_pixel(1, 2), &Rgb([128, 0, 0]));
+///     assert_eq!(image.get_pixel(1, 3), &Rgb([128, 0, 0]));
+/// }
+/// ```
+///
+/// [`XiaolinWu`]: struct.XiaolinWu.html
+pub fn draw_line_aa<P, C, I>(
+    image: &mut ImageBuffer<P, C>,
+    start: impl IntoPoint<I>,
+    end: impl IntoPoint<I>,
+    color: P,
+) where
+    P: Pixel + 'static,
+    P::Subpixel: 'static,
+    C: Deref<Target = [P::Subpixel]> + DerefMut,
+    I: RealNum,
+{
+    // Integer channels are rounded to the nearest value, and float ones are left as they are
+    let integer = <P::Subpixel as NumCast>::from(0.5) == Some(P::Subpixel::zero());
+
+    for (point, coverage) in XiaolinWu::<I, i64>::new(start, end) {
+        if let Some(pixel) = pixel_mut(image, point) {
+            *pixel = pixel.map2(&color, |from, to| {
+                let (from, to) = (I::cast(from), I::cast(to));
+                let value = from + (to - from) * coverage;
+
+                NumCast::from(if integer { value.round() } else { value }).unwrap()
+            });
+        }
+    }
+}
+
+#[test]
+fn tests() {
+    use image::{GrayImage, ImageBuffer, Luma, Rgba, RgbaImage};
+
+    let mut image = GrayImage::new(5, 5);
+    draw_line(&mut image, LineAlgorithm::WalkGrid, (-2, 1), (8, 3), Luma([9]));
+
+    let drawn = image
+        .enumerate_pixels()
+        .filter(|&(_, _, pixel)| pixel[0] == 9)
+        .map(|(x, y, _)| (x as i32, y as i32))
+        .collect::<Vec<_>>();
+    let mut expected = rasterize((-2, 1), (8, 3), LineAlgorithm::WalkGrid)
+        .filter(|&(x, y)| (0..5).contains(&x) && (0..5).contains(&y))
+        .collect::<Vec<_>>();
+    expected.sort_by_key(|&(x, y)| (y, x));
+    assert_eq!(drawn, expected);
+
+    // Blending towards the color, from whatever was there before
+    let mut image = RgbaImage::from_pixel(4, 1, Rgba([0, 100, 200, 255]));
+    draw_line_aa(&mut image, (0.0, 0.0), (3.0, 0.0), Rgba([255, 200, 0, 255]));
+    assert_eq!(image.get_pixel(1, 0), &Rgba([255, 200, 0, 255]));
+
+    let mut image = RgbaImage::from_pixel(3, 3, Rgba([0, 100, 200, 0]));
+    draw_line_aa(&mut image, (0.0, 0.5), (2.0, 0.5), Rgba([255, 200, 0, 255]));
+    assert_eq!(image.get_pixel(1, 0), &Rgba([128, 150, 100, 128]));
+    assert_eq!(image.get_pixel(1, 1), &Rgba([128, 150, 100, 128]));
+
+    // Float channels aren't rounded
+    let mut image = ImageBuffer::<Luma<f32>, Vec<f32>>::new(3, 3);
+    draw_line_aa(&mut image, (0.0f32, 0.5), (2.0, 0.5), Luma([1.0]));
+    assert_eq!(image.get_pixel(1, 0), &Luma([0.5]));
+
+    // Nothing is drawn outside of the image
+    let mut image = GrayImage::new(2, 2);
+    draw_line(&mut image, LineAlgorithm::Bresenham, (5, 5), (9, -3), Luma([1]));
+    draw_line_aa(&mut image, (-5.0, -5.0), (-1.0, 9.0), Luma([1]));
+    assert!(image.pixels().all(|pixel| pixel[0] == 0));
+}
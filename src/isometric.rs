@@ -0,0 +1,158 @@
+use {FloatNum, IntoPoint, Point, SignedNum};
+use steps::Steps;
+
+/// Walk along an isometric grid of diamond-shaped tiles, taking only orthogonal steps.
+///
+/// The points are given in screen space, along with the width and height of a tile on screen. The
+/// tiles are returned in isometric `(column, row)` coordinates, so there's no need to convert the
+/// line into tile space and round it.
+///
+/// The top corner of tile `(0, 0)` is at the screen origin, with columns going down and to the
+/// right and rows going down and to the left:
+///
+/// ```text
+/// screen_x = (column - row) * width / 2
+/// screen_y = (column + row) * height / 2
+/// ```
+///
+/// Every tile that the line passes through is visited, stepping to a neighbouring tile across one
+/// of its edges each time. When the line passes exactly through a corner, the column is changed
+/// first.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::WalkIso;
+///
+/// fn main() {
+///     for (column, row) in WalkIso::<f32, i8>::new((0.0, 8.0), (32.0, 72.0), (64.0, 32.0)) {
+///         print!("({}, {}), ", column, row);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// (0, 0), (1, 0), (1, 1), (2, 1),
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WalkIso<I, O> {
+    tile: Point<O>,
+    sign: Point<O>,
+    next_t: Point<I>,
+    delta_t: Point<I>,
+    count: O,
+}
+
+impl<I: FloatNum, O: SignedNum> WalkIso<I, O> {
+    #[inline]
+    pub fn new(start: impl IntoPoint<I>, end: impl IntoPoint<I>, tile_size: Point<I>) -> Self {
+        let (start, end) = (start.into_point(), end.into_point());
+
+        let to_tile = |point: Point<I>| {
+            let x = point.0 / tile_size.0;
+            let y = point.1 / tile_size.1;
+            (y + x, y - x)
+        };
+
+        let start = to_tile(start);
+        let end = to_tile(end);
+
+        let axis = |start: I, end: I| {
+            let tile = start.floor();
+            let delta = end - start;
+
+            if delta > I::zero() {
+                (O::one(), (tile + I::one() - start) / delta, delta.recip())
+            } else if delta < I::zero() {
+                (-O::one(), (tile - start) / delta, -delta.recip())
+            } else {
+                (O::zero(), I::infinity(), I::zero())
+            }
+        };
+
+        let (sign_x, next_x, delta_x) = axis(start.0, end.0);
+        let (sign_y, next_y, delta_y) = axis(start.1, end.1);
+
+        let tile = (O::cast(start.0.floor()), O::cast(start.1.floor()));
+        let end_tile: Point<O> = (O::cast(end.0.floor()), O::cast(end.1.floor()));
+
+        Self {
+            tile,
+            sign: (sign_x, sign_y),
+            next_t: (next_x, next_y),
+            delta_t: (delta_x, delta_y),
+            count: (end_tile.0 - tile.0).abs() + (end_tile.1 - tile.1).abs(),
+        }
+    }
+
+    #[inline]
+    pub fn steps(self) -> Steps<Point<O>, Self> {
+        Steps::new(self)
+    }
+}
+
+impl<I: FloatNum, O: SignedNum> Iterator for WalkIso<I, O> {
+    type Item = Point<O>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.count >= O::zero() {
+            let tile = self.tile;
+
+            if self.next_t.0 <= self.next_t.1 {
+                self.tile.0 += self.sign.0;
+                self.next_t.0 += self.delta_t.0;
+            } else {
+                self.tile.1 += self.sign.1;
+                self.next_t.1 += self.delta_t.1;
+            }
+
+            self.count -= O::one();
+
+            Some(tile)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.count + O::one()).to_usize().unwrap_or(0);
+        (remaining, Some(remaining))
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.len()
+    }
+}
+
+impl<I: FloatNum, O: SignedNum> ExactSizeIterator for WalkIso<I, O> {}
+
+#[test]
+fn tests() {
+    let walk_iso = |a, b| WalkIso::<f64, i32>::new(a, b, (64.0, 32.0)).collect::<Vec<_>>();
+
+    // Moving straight down the screen crosses both columns and rows
+    assert_eq!(walk_iso((0.0, 8.0), (0.0, 40.0)), [(0, 0), (1, 0), (1, 1)]);
+
+    // Moving along the direction of a column only changes the column
+    assert_eq!(
+        walk_iso((0.0, 16.0), (96.0, 64.0)),
+        [(0, 0), (1, 0), (2, 0), (3, 0)]
+    );
+
+    assert_eq!(
+        walk_iso((0.0, 16.0), (-96.0, 64.0)),
+        [(0, 0), (0, 1), (0, 2), (0, 3)]
+    );
+
+    assert_eq!(walk_iso((5.0, 16.0), (5.0, 16.0)), [(0, 0)]);
+
+    assert_eq!(
+        walk_iso((0.0, 8.0), (32.0, 72.0)),
+        [(0, 0), (1, 0), (1, 1), (2, 1)]
+    );
+}
@@ -0,0 +1,173 @@
+use {Point, SignedNum};
+use rand::Rng;
+
+/// Which neighbours of a cell count as connected to it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Connectivity {
+    /// Only the four cells that share an edge.
+    Four,
+    /// All eight cells that share an edge or a corner.
+    Eight,
+}
+
+/// An iterator that wobbles the points of a line for a hand-drawn look.
+///
+/// Each point is moved by an offset of up to `amplitude` cells on each axis. The offset wanders a
+/// little at a time rather than jumping around, so the line bends smoothly. Where the wobble would
+/// leave a gap, the cells in between are filled in, so the result is always connected in the way
+/// that `connectivity` asks for.
+///
+/// This needs the `rand` feature.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// extern crate rand;
+/// use line_drawing::{Bresenham, Connectivity, Jitter};
+///
+/// fn main() {
+///     let line = Bresenham::new((0, 0), (20, 5));
+///     let jittered = Jitter::new(line, rand::thread_rng(), 2, Connectivity::Eight);
+///
+///     for (x, y) in jittered {
+///         print!("({}, {}), ", x, y);
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Jitter<T, I, R> {
+    iterator: I,
+    rng: R,
+    amplitude: T,
+    connectivity: Connectivity,
+    offset: Point<T>,
+    last: Option<Point<T>>,
+    target: Option<Point<T>>,
+}
+
+impl<T: SignedNum, I: Iterator<Item = Point<T>>, R: Rng> Jitter<T, I, R> {
+    #[inline]
+    pub fn new(iterator: I, rng: R, amplitude: T, connectivity: Connectivity) -> Self {
+        Self {
+            iterator,
+            rng,
+            amplitude,
+            connectivity,
+            offset: (T::zero(), T::zero()),
+            last: None,
+            target: None,
+        }
+    }
+
+    /// Move one part of the offset by at most one cell, keeping it within the amplitude.
+    #[inline]
+    fn wander(&mut self, offset: T) -> T {
+        // Only change direction every few points, to keep the line smooth
+        let change = match self.rng.gen_range(0..8) {
+            0 => -T::one(),
+            1 => T::one(),
+            _ => T::zero(),
+        };
+
+        (offset + change).max(-self.amplitude).min(self.amplitude)
+    }
+}
+
+impl<T: SignedNum, I: Iterator<Item = Point<T>>, R: Rng> Iterator for Jitter<T, I, R> {
+    type Item = Point<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let target = match self.target {
+                Some(target) => target,
+                None => {
+                    let point = self.iterator.next()?;
+                    let offset = self.offset;
+                    self.offset = (self.wander(offset.0), self.wander(offset.1));
+
+                    let target = (point.0 + self.offset.0, point.1 + self.offset.1);
+                    self.target = Some(target);
+                    target
+                }
+            };
+
+            let last = match self.last {
+                Some(last) => last,
+                None => {
+                    self.last = Some(target);
+                    self.target = None;
+                    return Some(target);
+                }
+            };
+
+            if last == target {
+                self.target = None;
+                continue;
+            }
+
+            // Step towards the target, filling in any gap left by the offset changing
+            let (dx, dy) = (target.0 - last.0, target.1 - last.1);
+
+            let point = match self.connectivity {
+                Connectivity::Eight => (last.0 + dx.signum(), last.1 + dy.signum()),
+                Connectivity::Four if dx.abs() >= dy.abs() => (last.0 + dx.signum(), last.1),
+                Connectivity::Four => (last.0, last.1 + dy.signum()),
+            };
+
+            if point == target {
+                self.target = None;
+            }
+
+            self.last = Some(point);
+
+            return Some(point);
+        }
+    }
+}
+
+#[test]
+fn tests() {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use {Bresenham, WalkGrid};
+
+    for seed in 0..20 {
+        let line = Bresenham::new((0, 0), (40, 13)).collect::<Vec<_>>();
+        let rng = StdRng::seed_from_u64(seed);
+        let jittered = Jitter::new(line.iter().cloned(), rng, 3, Connectivity::Eight)
+            .collect::<Vec<Point<i32>>>();
+
+        for pair in jittered.windows(2) {
+            let (dx, dy) = (pair[1].0 - pair[0].0, pair[1].1 - pair[0].1);
+            assert!(dx.abs() <= 1 && dy.abs() <= 1 && (dx, dy) != (0, 0));
+        }
+
+        // Every point stays close to the line
+        for &(x, y) in &jittered {
+            assert!(line
+                .iter()
+                .any(|&(lx, ly)| (lx - x).abs() <= 3 && (ly - y).abs() <= 3));
+        }
+
+        let rng = StdRng::seed_from_u64(seed);
+        let jittered = Jitter::new(WalkGrid::new((5, 5), (-20, 30)), rng, 2, Connectivity::Four)
+            .collect::<Vec<Point<i32>>>();
+
+        for pair in jittered.windows(2) {
+            let (dx, dy) = (pair[1].0 - pair[0].0, pair[1].1 - pair[0].1);
+            assert_eq!(dx.abs() + dy.abs(), 1);
+        }
+    }
+
+    // Without any amplitude the line isn't changed
+    let rng = StdRng::seed_from_u64(0);
+    assert_eq!(
+        Jitter::new(Bresenham::new((0, 0), (9, 4)), rng, 0, Connectivity::Eight)
+            .collect::<Vec<_>>(),
+        Bresenham::new((0, 0), (9, 4)).collect::<Vec<_>>()
+    );
+}
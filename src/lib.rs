@@ -3,62 +3,351 @@
 //! Currently implemented:
 //!
 //! * [`Bresenham`] - An implementation of [Bresenham's line algorithm].
+//! * [`BresenhamDepth`] - Bresenham with a depth at each end, for z-buffered lines.
 //! * [`Bresenham3d`] - A 3-Dimensional implementation of bresenham.
 //! * [`BresenhamCircle`] - Bresenham's circle algorithm.
 //! * [`Midpoint`] - The [mid-point line algorithm].
+//! * [`MidpointEllipse`] - The mid-point ellipse algorithm, in order around the outline.
+//! * [`XiaolinWuEllipse`] - An anti-aliased ellipse, in the style of [Xiaolin Wu's line algorithm].
 //! * [`WalkGrid`] and [`Supercover`] - implemented from [this article by Red Blob Games][article].
+//! * [`ThickSupercover`] - A supercover line that never steps diagonally.
+//! * [`SupercoverLengths`] - A supercover line with the length of the line inside each cell.
+//! * [`WalkEdges`] - The edges between cells that a grid walk crosses.
+//! * [`WalkTiles`] - The tiles of any size that a segment passes through.
+//! * [`WalkGridRay`] and [`SupercoverRay`] - Unbounded versions that take a direction instead of an end point.
 //! * [`WalkVoxels`] - A similar 3-Dimensional algorithm that only takes orthogonal steps.
 //! * [`XiaolinWu`] - [Xiaolin Wu's line algorithm].
+//! * [`XiaolinWuAlpha`] - Xiaolin Wu's line algorithm with `u8` or `u16` coverage, in fixed-point.
+//! * [`XiaolinWuLut`] - An anti-aliased line with only integer math, for chips without an FPU.
+//! * [`WalkOctree`] - Walks a segment through a sparse octree, skipping over empty nodes.
+//! * [`SphereWireframe`] - The latitude and longitude rings of a voxel sphere.
+//! * [`GeodesicArc`] - The great-circle arc between two points on a voxel sphere.
+//! * [`HexLine`] and [`HexSupercover`] - Lines between two hexes, in axial coordinates.
+//! * [`WalkTriangles`] - Walks along a grid of alternating up and down triangles.
+//! * [`WalkIso`] - Walks along an isometric grid of diamond-shaped tiles.
+//! * [`GridRay`] - The DDA loop used by grid-based raycasting renderers.
+//! * [`RayFan`] - Lines from a point out to the edge of a circle, for ray-cast field of view.
+//! * [`line_of_sight`] - Check if anything blocks the view between two points.
+//! * [`terrain_line_of_sight`] - Check the view between two points over a heightmap.
+//! * [`shadowcast`] - Symmetric recursive shadowcasting, for field of view on a grid.
+//! * [`rasterize`] - Draw a line with a [`LineAlgorithm`] that's picked at runtime.
+//! * [`draw_points`] - Draw any of the algorithms straight onto a [`PlotTarget`], like a pixel buffer.
+//! * [`PointSink`] - Pour the points of any of the algorithms into a slice, `Vec` or closure.
+//! * [`Circle`], [`Ellipse`], [`Rect`] and [`Triangle`] - Shapes that can be outlined, anti-aliased or filled in.
+//! * [`BarycentricFill`] - A filled-in triangle with the barycentric coordinates of each point.
+//! * [`FillSpans`] - The rows of a filled-in shape as spans, for scanline renderers.
+//! * [`LineSegment`] - A line to be drawn later, with whichever algorithm.
+//! * [`LineExt`] - Chain adapters like clipping, dashing and mirroring onto any of the iterators.
+//! * [`BufferIndices`] - Turn points into indices into a flat pixel buffer.
+//! * [`GammaCorrected`] and [`GammaTable`] - Encode anti-aliased coverage for blending in sRGB.
+//! * [`Interpolate`] - Blend a color, height or any other value along a line.
+//! * [`Vertices`] and [`ExpandVertices`] - Shrink a line down to the corners where it turns, and back.
+//! * [`MortonCodes`] and [`WithMorton`] - The Morton codes of points, for Z-order storage.
+//! * [`Q16`] - A fixed-point number that [`Midpoint`], [`XiaolinWu`] and [`WalkVoxels`] can use without an FPU.
+//! * `LineCache` - Reuses the shapes of lines that have already been drawn (needs the `alloc` feature).
+//! * `RayTable` - Precomputed rays for table-driven field of view (needs the `alloc` feature).
+//! * `bresenham_points`, `circle_points` and so on - The points of a shape as a `Vec` (needs the `alloc` feature).
+//! * `sorted_spans` - Any points sorted into spans a row at a time (needs the `alloc` feature).
+//! * `Supersampled` - Anti-alias any shape by drawing it at a higher resolution (needs the `alloc` feature).
+//! * `CoverageBuffer` - Fill polygons and paths with exact anti-aliasing, like a font rasterizer (needs the `alloc` feature).
+//! * `ScanlineBatch` - Fill lots of polygons at once a row at a time, with an active edge table (needs the `alloc` feature).
+//! * `Path` - Lines, curves and arcs drawn as one path, like in a vector drawing program (needs the `alloc` feature).
+//! * `Jitter` - Wobbles a line for a hand-drawn look (needs the `rand` feature).
+//! * `par_rasterize` and `par_rasterize_with` - Draw lots of segments at once, spread across threads (needs the `rayon` feature).
+//! * `collect_into` - Collect the points of a shape into a `heapless::Vec` without allocating (needs the `heapless` feature).
+//! * `draw_line` and `draw_line_aa` - Draw lines onto an `image::ImageBuffer` (needs the `image` feature).
+//! * `set_along` and `accumulate_along` - Fill in or add up values along a line in an `ndarray` array (needs the `ndarray` feature).
+//!
+//! With the `serde` feature, the iterators can be serialized and deserialized part of the way
+//! through, so a walk that's in progress can be saved and carried on with later.
+//!
+//! With the `euclid` feature, euclid's points and vectors can be used as end points, and the
+//! points of a line can be turned back into them with [`Converted`], keeping their units.
+//!
+//! With the `svg-path` feature, a `Path` can be parsed from SVG path data with `Path::from_svg`.
+//!
+//! With the `rational` feature, [`Midpoint`], [`XiaolinWu`] and [`WalkVoxels`] can work in
+//! `num_rational::Ratio` instead of floating-point, so the lines are exact and the same on every
+//! platform.
+//!
+//! With the `ffi` feature, the `ffi` module has a C API for some of the algorithms.
+//!
+//! With the `simd` feature and a nightly compiler, the `batch` module draws several lines at once,
+//! one in each SIMD lane.
 //!
 //! [`Bresenham`]: struct.Bresenham.html
 //! [Bresenham's line algorithm]: https://en.wikipedia.org/wiki/Bresenham's_line_algorithm
+//! [`BresenhamDepth`]: struct.BresenhamDepth.html
 //! [`Bresenham3d`]: struct.Bresenham3d.html
 //! [`BresenhamCircle`]: struct.BresenhamCircle.html
 //! [`Midpoint`]: struct.Midpoint.html
+//! [`MidpointEllipse`]: struct.MidpointEllipse.html
 //! [mid-point line algorithm]: http://www.mat.univie.ac.at/~kriegl/Skripten/CG/node25.html
 //! [`WalkGrid`]: struct.WalkGrid.html
 //! [`Supercover`]: struct.Supercover.html
+//! [`ThickSupercover`]: struct.ThickSupercover.html
+//! [`SupercoverLengths`]: struct.SupercoverLengths.html
+//! [`WalkEdges`]: struct.WalkEdges.html
+//! [`WalkGridRay`]: struct.WalkGridRay.html
+//! [`WalkTiles`]: struct.WalkTiles.html
+//! [`SupercoverRay`]: struct.SupercoverRay.html
 //! [article]: http://www.redblobgames.com/grids/line-drawing.html
 //! [`XiaolinWu`]: struct.XiaolinWu.html
+//! [`XiaolinWuAlpha`]: struct.XiaolinWuAlpha.html
+//! [`XiaolinWuLut`]: struct.XiaolinWuLut.html
 //! [Xiaolin Wu's line algorithm]: https://en.wikipedia.org/wiki/Xiaolin_Wu%27s_line_algorithm
 //! [`WalkVoxels`]: struct.WalkVoxels.html
+//! [`WalkOctree`]: struct.WalkOctree.html
+//! [`SphereWireframe`]: struct.SphereWireframe.html
+//! [`GeodesicArc`]: struct.GeodesicArc.html
+//! [`HexLine`]: struct.HexLine.html
+//! [`HexSupercover`]: struct.HexSupercover.html
+//! [`WalkTriangles`]: struct.WalkTriangles.html
+//! [`WalkIso`]: struct.WalkIso.html
+//! [`GridRay`]: struct.GridRay.html
+//! [`RayFan`]: struct.RayFan.html
+//! [`line_of_sight`]: fn.line_of_sight.html
+//! [`shadowcast`]: fn.shadowcast.html
+//! [`terrain_line_of_sight`]: fn.terrain_line_of_sight.html
+//! [`rasterize`]: fn.rasterize.html
+//! [`LineAlgorithm`]: enum.LineAlgorithm.html
+//! [`draw_points`]: fn.draw_points.html
+//! [`PlotTarget`]: trait.PlotTarget.html
+//! [`PointSink`]: trait.PointSink.html
+//! [`Converted`]: struct.Converted.html
+//! [`XiaolinWuEllipse`]: struct.XiaolinWuEllipse.html
+//! [`Circle`]: struct.Circle.html
+//! [`Ellipse`]: struct.Ellipse.html
+//! [`Rect`]: struct.Rect.html
+//! [`Triangle`]: struct.Triangle.html
+//! [`BarycentricFill`]: struct.BarycentricFill.html
+//! [`FillSpans`]: struct.FillSpans.html
+//! [`LineSegment`]: struct.LineSegment.html
+//! [`LineExt`]: trait.LineExt.html
+//! [`BufferIndices`]: struct.BufferIndices.html
+//! [`GammaCorrected`]: struct.GammaCorrected.html
+//! [`GammaTable`]: struct.GammaTable.html
+//! [`Interpolate`]: struct.Interpolate.html
+//! [`Vertices`]: struct.Vertices.html
+//! [`ExpandVertices`]: struct.ExpandVertices.html
+//! [`MortonCodes`]: struct.MortonCodes.html
+//! [`WithMorton`]: struct.WithMorton.html
+//! [`Q16`]: struct.Q16.html
 
 #![cfg_attr(not(test), no_std)]
+#![cfg_attr(feature = "simd", feature(portable_simd))]
 
 #[cfg(test)]
 use std as core;
 
 extern crate num_traits;
+#[cfg(feature = "rational")]
+extern crate num_rational;
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "euclid")]
+extern crate euclid;
+#[cfg(feature = "heapless")]
+extern crate heapless;
+#[cfg(feature = "image")]
+extern crate image;
+#[cfg(feature = "ndarray")]
+extern crate ndarray;
+#[cfg(feature = "rand")]
+extern crate rand;
+#[cfg(feature = "rayon")]
+extern crate rayon;
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde;
 
 pub mod steps;
 pub mod octant;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "simd")]
+pub mod batch;
 
 mod bresenham;
+mod bresenham_depth;
 mod midpoint;
 mod xiaolin_wu;
+mod xiaolin_wu_lut;
 mod grid_walking;
 mod fuzzing;
 mod bresenham_3d;
 mod walk_voxels;
 mod bresenham_circle;
+mod ellipse;
+mod octree;
+mod chunked;
+mod bounds;
+mod sphere;
+mod hex;
+mod triangle_grid;
+mod isometric;
+mod raycast;
+mod fov;
+mod direction;
+mod toroidal;
+mod tiles;
+mod pixel_perfect;
+mod stipple;
+mod replay;
+mod progress;
+mod endpoints;
+mod adapters;
+mod error;
+mod exact;
+mod convert;
+mod plot;
+mod sink;
+mod algorithm;
+mod rasterizer;
+mod shapes;
+mod segment;
+mod fixed_point;
+#[cfg(feature = "alloc")]
+mod cache;
+#[cfg(feature = "alloc")]
+mod points;
+#[cfg(feature = "alloc")]
+mod path;
+#[cfg(feature = "alloc")]
+mod supersample;
+#[cfg(feature = "alloc")]
+mod coverage_buffer;
+#[cfg(feature = "alloc")]
+mod scanline;
+#[cfg(feature = "svg-path")]
+mod svg_path;
+#[cfg(feature = "rand")]
+mod jitter;
+#[cfg(feature = "rayon")]
+mod parallel;
+#[cfg(feature = "rational")]
+mod rational;
+#[cfg(feature = "euclid")]
+mod euclid_points;
+#[cfg(feature = "heapless")]
+mod heapless_points;
+#[cfg(feature = "image")]
+mod image_drawing;
+#[cfg(feature = "ndarray")]
+mod ndarray_drawing;
 
 pub use bresenham::*;
+pub use bresenham_depth::*;
 pub use midpoint::*;
 pub use xiaolin_wu::*;
+pub use xiaolin_wu_lut::*;
 pub use grid_walking::*;
 pub use bresenham_3d::*;
 pub use walk_voxels::*;
 pub use bresenham_circle::*;
+pub use ellipse::*;
+pub use octree::*;
+pub use chunked::*;
+pub use bounds::*;
+pub use sphere::*;
+pub use hex::*;
+pub use triangle_grid::*;
+pub use isometric::*;
+pub use raycast::*;
+pub use fov::*;
+pub use direction::*;
+pub use toroidal::*;
+pub use tiles::*;
+pub use pixel_perfect::*;
+pub use stipple::*;
+pub use replay::*;
+pub use progress::*;
+pub use endpoints::*;
+pub use adapters::*;
+pub use error::*;
+pub use convert::*;
+pub use plot::*;
+pub use sink::*;
+pub use algorithm::*;
+pub use rasterizer::*;
+pub use shapes::*;
+pub use segment::*;
+pub use fixed_point::*;
+#[cfg(feature = "alloc")]
+pub use cache::*;
+#[cfg(feature = "alloc")]
+pub use points::*;
+#[cfg(feature = "alloc")]
+pub use path::*;
+#[cfg(feature = "alloc")]
+pub use supersample::*;
+#[cfg(feature = "alloc")]
+pub use coverage_buffer::*;
+#[cfg(feature = "alloc")]
+pub use scanline::*;
+#[cfg(feature = "svg-path")]
+pub use svg_path::*;
+#[cfg(feature = "rand")]
+pub use jitter::*;
+#[cfg(feature = "rayon")]
+pub use parallel::*;
+#[cfg(feature = "heapless")]
+pub use heapless_points::*;
+#[cfg(feature = "image")]
+pub use image_drawing::*;
+#[cfg(feature = "ndarray")]
+pub use ndarray_drawing::*;
 
-use num_traits::{Float, NumAssignOps, NumCast, Signed};
+use core::fmt::Debug;
+use core::ops::Neg;
+use num_traits::{
+    CheckedAdd, CheckedMul, CheckedNeg, CheckedSub, Float, Num, NumAssignOps, NumCast, PrimInt,
+    Signed, ToPrimitive,
+};
 
 /// A point in 2D space.
 pub type Point<T> = (T, T);
 /// An point in 3D space.
 pub type Voxel<T> = (T, T, T);
 
+/// Any number that can be used as a coordinate, whether it's an integer or floating-point.
+///
+/// This is what the `from_coords` constructors take, so that the algorithms that work in
+/// floating-point can be given integers and the ones that work on the integer grid can be given
+/// floats, without casting them first.
+pub trait Coord: NumCast + Copy {
+    /// Convert to floating-point, or any other [`RealNum`] like [`Q16`].
+    ///
+    /// [`RealNum`]: trait.RealNum.html
+    /// [`Q16`]: struct.Q16.html
+    #[inline]
+    fn to_float<I: RealNum>(self) -> I {
+        I::cast(self)
+    }
+
+    /// Convert to an integer, rounding to the nearest one with ties going away from zero, the
+    /// same as [`f64::round`]. Integers are left as they are.
+    ///
+    /// [`f64::round`]: https://doc.rust-lang.org/std/primitive.f64.html#method.round
+    #[inline]
+    fn to_cell<O: SignedNum>(self) -> O {
+        let whole = O::cast(self);
+        let fraction = self.to_f64().unwrap() - whole.to_f64().unwrap();
+
+        if fraction >= 0.5 {
+            whole + O::one()
+        } else if fraction <= -0.5 {
+            whole - O::one()
+        } else {
+            whole
+        }
+    }
+}
+
+impl<T: NumCast + Copy> Coord for T {}
+
 /// All the floating-point primitives.
-pub trait FloatNum: Float + NumAssignOps {
+pub trait FloatNum: Coord + Float + NumAssignOps {
     #[inline]
     fn cast<T: NumCast>(value: T) -> Self {
         NumCast::from(value).unwrap()
@@ -67,12 +356,221 @@ pub trait FloatNum: Float + NumAssignOps {
 
 impl<T: Float + NumAssignOps> FloatNum for T {}
 
+/// Any number with a fractional part, which is what [`Midpoint`], [`XiaolinWu`] and
+/// [`WalkVoxels`] need for their end points.
+///
+/// It's implemented for the floating-point primitives and for the [`Q16`] fixed-point type, so
+/// those algorithms can be used on chips without a floating-point unit. With the `rational`
+/// feature, it's also implemented for `num_rational::Ratio` of the signed integers up to `i64`,
+/// where all of the math is exact and comes out the same on every platform.
+///
+/// [`Midpoint`]: struct.Midpoint.html
+/// [`XiaolinWu`]: struct.XiaolinWu.html
+/// [`WalkVoxels`]: struct.WalkVoxels.html
+/// [`Q16`]: struct.Q16.html
+pub trait RealNum: Copy + Num + NumAssignOps + Neg<Output = Self> + PartialOrd + ToPrimitive {
+    /// Convert any primitive number. Panics if it doesn't fit.
+    fn cast<T: NumCast>(value: T) -> Self;
+
+    /// Convert to an integer, rounding towards zero like an `as` cast. Panics if it doesn't fit.
+    fn to_int<O: SignedNum>(self) -> O;
+
+    /// The largest whole number that's less than or equal to this one.
+    fn floor(self) -> Self;
+
+    /// The smallest whole number that's greater than or equal to this one.
+    fn ceil(self) -> Self;
+
+    /// The nearest whole number, with ties going away from zero.
+    fn round(self) -> Self;
+
+    fn abs(self) -> Self;
+
+    /// Whether the number isn't NaN or infinite.
+    fn is_finite(self) -> bool;
+
+    #[inline]
+    fn min(self, other: Self) -> Self {
+        if other < self {
+            other
+        } else {
+            self
+        }
+    }
+
+    #[inline]
+    fn max(self, other: Self) -> Self {
+        if other > self {
+            other
+        } else {
+            self
+        }
+    }
+}
+
+macro_rules! real_num_float {
+    ($($type:ty),*) => {
+        $(
+            impl RealNum for $type {
+                #[inline]
+                fn cast<V: NumCast>(value: V) -> Self {
+                    NumCast::from(value).unwrap()
+                }
+
+                #[inline]
+                fn to_int<O: SignedNum>(self) -> O {
+                    O::cast(self)
+                }
+
+                #[inline]
+                fn floor(self) -> Self {
+                    Float::floor(self)
+                }
+
+                #[inline]
+                fn ceil(self) -> Self {
+                    Float::ceil(self)
+                }
+
+                #[inline]
+                fn round(self) -> Self {
+                    Float::round(self)
+                }
+
+                #[inline]
+                fn abs(self) -> Self {
+                    Float::abs(self)
+                }
+
+                #[inline]
+                fn is_finite(self) -> bool {
+                    Float::is_finite(self)
+                }
+
+                #[inline]
+                fn min(self, other: Self) -> Self {
+                    Float::min(self, other)
+                }
+
+                #[inline]
+                fn max(self, other: Self) -> Self {
+                    Float::max(self, other)
+                }
+            }
+        )*
+    };
+}
+
+real_num_float!(f32, f64);
+
+mod sealed {
+    pub trait Sealed {}
+}
+
 /// All the signed integer primitives.
-pub trait SignedNum: Signed + Ord + Copy + NumCast + NumAssignOps {
+///
+/// This trait is sealed: every integer needs a [`Wide`] type to do its math in, and only the
+/// primitives have one, so it can't be implemented outside of this crate.
+///
+/// [`Wide`]: #associatedtype.Wide
+pub trait SignedNum: Coord + Signed + Ord + NumAssignOps + sealed::Sealed {
+    /// A type with twice the bits, which [`Bresenham`] and [`Bresenham3d`] do their math in so
+    /// that lines across the whole range of the type don't overflow. `i128` has nothing wider, so
+    /// it's its own.
+    ///
+    /// [`Bresenham`]: struct.Bresenham.html
+    /// [`Bresenham3d`]: struct.Bresenham3d.html
+    type Wide: SignedNum + PrimInt + CheckedAdd + CheckedMul + CheckedNeg + CheckedSub + Debug;
+
     #[inline]
     fn cast<T: NumCast>(value: T) -> Self {
         NumCast::from(value).unwrap()
     }
+
+    /// Convert to the wide type, which always fits.
+    #[inline]
+    fn widen(self) -> Self::Wide {
+        <Self::Wide as SignedNum>::cast(self)
+    }
+}
+
+macro_rules! signed_num {
+    ($($type:ty => $wide:ty),*) => {
+        $(
+            impl sealed::Sealed for $type {}
+
+            impl SignedNum for $type {
+                type Wide = $wide;
+            }
+        )*
+    };
 }
 
-impl<T: Signed + Ord + Copy + NumCast + NumAssignOps> SignedNum for T {}
+signed_num!(i8 => i16, i16 => i32, i32 => i64, i64 => i128, i128 => i128, isize => i128);
+
+/// Divide rounding towards negative infinity, for a positive divisor. Also returns the remainder,
+/// which is always in `0..divisor`.
+#[inline]
+pub(crate) fn div_floor<T: SignedNum>(value: T, divisor: T) -> (T, T) {
+    let quotient = value / divisor;
+    let remainder = value % divisor;
+
+    if remainder < T::zero() {
+        (quotient - T::one(), remainder + divisor)
+    } else {
+        (quotient, remainder)
+    }
+}
+
+#[test]
+fn coords() {
+    assert_eq!(2.5f32.to_cell::<i32>(), 3);
+    assert_eq!((-2.5f64).to_cell::<i8>(), -3);
+    assert_eq!(0.49f64.to_cell::<i16>(), 0);
+    assert_eq!((-7.2f32).to_cell::<i32>(), -7);
+    assert_eq!(i64::MAX.to_cell::<i64>(), i64::MAX);
+    assert_eq!((-5i8).to_cell::<i64>(), -5);
+    assert_eq!(3i32.to_float::<f32>(), 3.0);
+    assert_eq!(0.25f32.to_float::<f64>(), 0.25);
+
+    assert_eq!(
+        WalkVoxels::<f32, i32>::from_coords((0, 0, 0), (3, -2, 1), VoxelOrigin::Corner),
+        WalkVoxels::new((0.0, 0.0, 0.0), (3.0, -2.0, 1.0), VoxelOrigin::Corner)
+    );
+    assert_eq!(
+        Bresenham3d::<i16>::from_coords((0.5, 1.5, -0.5), (9.0, 2.4, 1.6)),
+        Bresenham3d::new((1, 2, -1), (9, 2, 2))
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde() {
+    use serde::Serialize;
+    use serde::de::DeserializeOwned;
+    use steps::{Headings, Moves, Steps};
+
+    fn assert_serde<T: Serialize + DeserializeOwned>() {}
+
+    assert_serde::<Bresenham<i32>>();
+    assert_serde::<BresenhamDepth<i32, f32>>();
+    assert_serde::<WalkVoxels<f32, i32>>();
+    assert_serde::<XiaolinWu<f64, i64>>();
+    assert_serde::<XiaolinWuAlpha<i64, u8>>();
+    assert_serde::<Midpoint<Q16, i32>>();
+    #[cfg(feature = "rational")]
+    assert_serde::<Midpoint<num_rational::Ratio<i64>, i32>>();
+    #[cfg(feature = "alloc")]
+    assert_serde::<(CoverageBuffer<f32>, FillRule)>();
+    #[cfg(feature = "alloc")]
+    assert_serde::<ScanlineBatch<i16>>();
+    assert_serde::<GammaCorrected<f64, XiaolinWu<f64, i64>>>();
+    assert_serde::<Interpolate<[u8; 4], Bresenham<i64>>>();
+    assert_serde::<Steps<Point<i32>, Supercover<i32>>>();
+    assert_serde::<Decimate<Chunked<i32, Bresenham3d<i32>>>>();
+    assert_serde::<Mirror<i32, BresenhamCircle<i32>>>();
+    assert_serde::<Replay<Midpoint<f32, i8>>>();
+    assert_serde::<Headings<Voxel<i16>, WalkVoxels<f64, i16>>>();
+    assert_serde::<Moves<Point<i32>, WalkGrid<i32>>>();
+    assert_serde::<Converted<[i32; 2], Bresenham<i32>>>();
+}
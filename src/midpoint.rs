@@ -1,10 +1,12 @@
-use {FloatNum, Point, SignedNum};
+use {Coord, IntoPoint, LineError, LineRasterizer, Point, RealNum, SignedNum};
 use octant::Octant;
 use steps::Steps;
+use error::{check_cells, check_float};
 
 /// An implementation of the [mid-point line drawing algorithm].
 ///
 /// The biggest difference between this algorithm and [`Bresenham`] is that it uses floating-point points.
+/// They can also be [`Q16`] fixed-point numbers.
 ///
 /// Example:
 ///
@@ -25,6 +27,9 @@ use steps::Steps;
 ///
 /// [mid-point line drawing algorithm]: http://www.mat.univie.ac.at/~kriegl/Skripten/CG/node25.html
 /// [`Bresenham`]: struct.bresenham.html
+/// [`Q16`]: struct.Q16.html
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Midpoint<I, O> {
     octant: Octant,
     point: Point<O>,
@@ -34,9 +39,11 @@ pub struct Midpoint<I, O> {
     end_x: O,
 }
 
-impl<I: FloatNum, O: SignedNum> Midpoint<I, O> {
+impl<I: RealNum, O: SignedNum> Midpoint<I, O> {
     #[inline]
-    pub fn new(start: Point<I>, end: Point<I>) -> Self {
+    pub fn new(start: impl IntoPoint<I>, end: impl IntoPoint<I>) -> Self {
+        let (start, end) = (start.into_point(), end.into_point());
+
         // Get the octant to use
         let octant = Octant::new(start, end);
 
@@ -54,19 +61,72 @@ impl<I: FloatNum, O: SignedNum> Midpoint<I, O> {
             octant,
             a,
             b,
-            point: (O::cast(start.0.round()), O::cast(start.1.round())),
+            point: (start.0.round().to_int(), start.1.round().to_int()),
             k: a * (start.0.round() + I::one()) + b * (start.1.round() + I::cast(0.5)) + c,
-            end_x: O::cast(end.0.round()),
+            end_x: end.0.round().to_int(),
+        }
+    }
+
+    /// Create a line like [`new`], but return an error if the points are NaN or infinite, or if
+    /// the cells around them or the distance between them don't fit in `O`.
+    ///
+    /// [`new`]: #method.new
+    #[inline]
+    pub fn try_new(start: impl IntoPoint<I>, end: impl IntoPoint<I>) -> Result<Self, LineError> {
+        let (start, end) = (start.into_point(), end.into_point());
+
+        for &value in &[start.0, start.1, end.0, end.1] {
+            check_float::<I, O>(value)?;
         }
+
+        let cell = |value: I| -> O { value.round().to_int() };
+        check_cells(cell(start.0), cell(end.0))?;
+        check_cells(cell(start.1), cell(end.1))?;
+
+        Ok(Self::new(start, end))
+    }
+
+    /// Create a line between end points of any number type, converting integers to
+    /// floating-point.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// extern crate line_drawing;
+    /// use line_drawing::Midpoint;
+    ///
+    /// fn main() {
+    ///     let line = Midpoint::<f32, i32>::from_coords((0, 0), (5, 2));
+    ///     assert_eq!(line, Midpoint::new((0.0, 0.0), (5.0, 2.0)));
+    /// }
+    /// ```
+    #[inline]
+    pub fn from_coords<C: Coord>(start: impl IntoPoint<C>, end: impl IntoPoint<C>) -> Self {
+        let (start, end) = (start.into_point(), end.into_point());
+
+        Self::new(
+            (start.0.to_float(), start.1.to_float()),
+            (end.0.to_float(), end.1.to_float()),
+        )
     }
 
+    /// Like [`LineRasterizer::steps`], without needing the trait imported.
+    ///
+    /// [`LineRasterizer::steps`]: trait.LineRasterizer.html#method.steps
     #[inline]
     pub fn steps(self) -> Steps<Point<O>, Self> {
-        Steps::new(self)
+        LineRasterizer::steps(self)
     }
 }
 
-impl<I: FloatNum, O: SignedNum> Iterator for Midpoint<I, O> {
+impl<I: RealNum, O: SignedNum> LineRasterizer<I, O> for Midpoint<I, O> {
+    #[inline]
+    fn new(start: Point<I>, end: Point<I>) -> Self {
+        Midpoint::new(start, end)
+    }
+}
+
+impl<I: RealNum, O: SignedNum> Iterator for Midpoint<I, O> {
     type Item = Point<O>;
 
     #[inline]
@@ -89,8 +149,23 @@ impl<I: FloatNum, O: SignedNum> Iterator for Midpoint<I, O> {
             None
         }
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.end_x - self.point.0 + O::one())
+            .to_usize()
+            .unwrap_or(0);
+        (remaining, Some(remaining))
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.len()
+    }
 }
 
+impl<I: RealNum, O: SignedNum> ExactSizeIterator for Midpoint<I, O> {}
+
 #[test]
 fn tests() {
     let midpoint = |a, b| Midpoint::new(a, b).collect::<Vec<_>>();
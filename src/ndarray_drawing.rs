@@ -0,0 +1,148 @@
+use core::ops::AddAssign;
+use ndarray::{ArrayBase, DataMut, Dimension, Ix2, Ix3};
+use num_traits::ToPrimitive;
+use {Point, Voxel};
+
+/// The points that can be used to index into an array with [`set_along`] and
+/// [`accumulate_along`].
+///
+/// 2D points index into 2D arrays as `[x, y]`, and 3D points into 3D arrays as `[x, y, z]`.
+/// Points with a negative coordinate don't have an index.
+///
+/// [`set_along`]: fn.set_along.html
+/// [`accumulate_along`]: fn.accumulate_along.html
+pub trait ArrayIndex<D: Dimension> {
+    /// The index of the point, if it has one.
+    fn array_index(self) -> Option<D>;
+}
+
+impl<T: ToPrimitive> ArrayIndex<Ix2> for Point<T> {
+    #[inline]
+    fn array_index(self) -> Option<Ix2> {
+        Some(Ix2(self.0.to_usize()?, self.1.to_usize()?))
+    }
+}
+
+impl<T: ToPrimitive> ArrayIndex<Ix3> for Voxel<T> {
+    #[inline]
+    fn array_index(self) -> Option<Ix3> {
+        Some(Ix3(self.0.to_usize()?, self.1.to_usize()?, self.2.to_usize()?))
+    }
+}
+
+/// Set the elements of an array along a line, or any other iterator of points, to `value`.
+///
+/// Works with 2D and 3D arrays, indexed as described in [`ArrayIndex`]. For `[y, x]` indexing,
+/// pass in a view with the axes reversed. Points that are outside of the array are left out.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// extern crate ndarray;
+/// use line_drawing::{set_along, Bresenham};
+/// use ndarray::Array2;
+///
+/// fn main() {
+///     let mut occupied = Array2::from_elem((4, 3), false);
+///     set_along(&mut occupied, Bresenham::new((0, 0), (3, 2)), true);
+///
+///     assert!(occupied[[0, 0]] && occupied[[3, 2]]);
+///     assert_eq!(occupied.iter().filter(|&&cell| cell).count(), 4);
+/// }
+/// ```
+///
+/// [`ArrayIndex`]: trait.ArrayIndex.html
+pub fn set_along<S, D, I>(array: &mut ArrayBase<S, D>, points: I, value: S::Elem)
+where
+    S: DataMut,
+    S::Elem: Clone,
+    D: Dimension,
+    I: IntoIterator,
+    I::Item: ArrayIndex<D>,
+{
+    for point in points {
+        if let Some(element) = point.array_index().and_then(|index| array.get_mut(index)) {
+            *element = value.clone();
+        }
+    }
+}
+
+/// Add `value` to the elements of an array along a line, or any other iterator of points.
+///
+/// This works like [`set_along`], but builds up the values instead, so drawing many trajectories
+/// into the same array makes a heatmap of them. A point that comes up more than once is added to
+/// each time.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// extern crate ndarray;
+/// use line_drawing::{accumulate_along, WalkVoxels, VoxelOrigin};
+/// use ndarray::Array3;
+///
+/// fn main() {
+///     let mut visits = Array3::<u32>::zeros((3, 3, 3));
+///
+///     for &end in &[(2.0, 0.0, 0.0), (2.0, 2.0, 0.0)] {
+///         let walk = WalkVoxels::<f32, i32>::new((0.0, 0.0, 0.0), end, VoxelOrigin::Corner);
+///         accumulate_along(&mut visits, walk, 1);
+///     }
+///
+///     assert_eq!(visits[[0, 0, 0]], 2);
+///     assert_eq!(visits[[1, 0, 0]], 1);
+///     assert_eq!(visits[[2, 2, 0]], 1);
+/// }
+/// ```
+///
+/// [`set_along`]: fn.set_along.html
+pub fn accumulate_along<S, D, I>(array: &mut ArrayBase<S, D>, points: I, value: S::Elem)
+where
+    S: DataMut,
+    S::Elem: AddAssign + Clone,
+    D: Dimension,
+    I: IntoIterator,
+    I::Item: ArrayIndex<D>,
+{
+    for point in points {
+        if let Some(element) = point.array_index().and_then(|index| array.get_mut(index)) {
+            *element += value.clone();
+        }
+    }
+}
+
+#[test]
+fn tests() {
+    use ndarray::{Array2, Array3};
+    use {BresenhamCircle, Bresenham3d, Supercover};
+
+    let mut array = Array2::zeros((5, 4));
+    set_along(&mut array, Supercover::new((-2, -1), (6, 3)), 1);
+
+    let mut expected = Array2::zeros((5, 4));
+    for (x, y) in Supercover::new((-2, -1), (6, 3)) {
+        if (0..5).contains(&x) && (0..4).contains(&y) {
+            expected[[x as usize, y as usize]] = 1;
+        }
+    }
+    assert_eq!(array, expected);
+    assert!(array.sum() > 0);
+
+    // Indexing the other way around with a reversed view
+    let mut array = Array2::zeros((2, 3));
+    set_along(&mut array.view_mut().reversed_axes(), vec![(2, 1), (0, 1)], 7);
+    assert_eq!(array, Array2::from_shape_vec((2, 3), vec![0, 0, 0, 7, 0, 7]).unwrap());
+
+    // Circles go through some points more than once
+    let mut array = Array2::<u8>::zeros((3, 3));
+    accumulate_along(&mut array, BresenhamCircle::new(1, 1, 1), 1);
+    assert_eq!(array.sum() as usize, BresenhamCircle::new(1, 1, 1).count());
+
+    let mut array = Array3::<f64>::zeros((3, 3, 3));
+    accumulate_along(&mut array, Bresenham3d::new((0, 0, 0), (2, 2, 2)), 0.5);
+    accumulate_along(&mut array, Bresenham3d::new((2, 2, 2), (9, 9, 9)), 0.5);
+    assert_eq!(array[[1, 1, 1]], 0.5);
+    assert_eq!(array[[2, 2, 2]], 1.0);
+    assert_eq!(array.sum(), 2.0);
+}
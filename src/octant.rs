@@ -1,10 +1,17 @@
-//! A simple octant struct for transforming line points.
+//! Simple octant structs for transforming line points, in 2D and 3D.
 
-use Point;
-use core::ops::{Neg, Sub};
+use {FloatNum, Point, Voxel};
+use core::ops::{Neg, Range, Sub};
 use num_traits::Zero;
 
 /// A simple octant struct for transforming line points.
+///
+/// The octants are numbered from `0` to `7`, going around from the positive `x` axis towards the
+/// positive `y` axis, so octant `k` holds the directions with an angle between `k * π / 4` and
+/// `(k + 1) * π / 4`. Each one covers the end of its range but not the start, except that the
+/// positive `x` axis is in octant `0` instead of `7`, as is a line that doesn't go anywhere.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Octant {
     value: u8,
 }
@@ -40,6 +47,41 @@ impl Octant {
         Self { value }
     }
 
+    /// Get the octant with the given number, if it's from `0` to `7`.
+    #[inline]
+    pub fn from_index(index: u8) -> Option<Self> {
+        if index < 8 {
+            Some(Self { value: index })
+        } else {
+            None
+        }
+    }
+
+    /// Get the octant that a direction at an angle in radians falls into.
+    ///
+    /// The angle goes from the positive `x` axis towards the positive `y` axis, and can be outside
+    /// of `0` to `2π`. Angles right on the edge of two octants can end up in either one, because
+    /// of rounding.
+    #[inline]
+    pub fn from_angle<T: FloatNum>(radians: T) -> Self {
+        Self::new((T::zero(), T::zero()), (radians.cos(), radians.sin()))
+    }
+
+    /// The number of the octant, from `0` to `7`.
+    #[inline]
+    pub fn index(&self) -> u8 {
+        self.value
+    }
+
+    /// The range of angles in radians that the octant covers, from the positive `x` axis towards
+    /// the positive `y` axis.
+    #[inline]
+    pub fn angles<T: FloatNum>(&self) -> Range<T> {
+        let eighth = T::cast(core::f64::consts::FRAC_PI_4);
+
+        T::cast(self.value) * eighth..T::cast(self.value + 1) * eighth
+    }
+
     /// Convert a point to its position in the octant.
     #[inline]
     pub fn to<T: Neg<Output = T>>(&self, point: Point<T>) -> Point<T> {
@@ -72,3 +114,180 @@ impl Octant {
         }
     }
 }
+
+/// The order that the axes are put in for each of the 3D octants, from the longest to the shortest.
+const AXES: [[usize; 3]; 6] = [[0, 1, 2], [0, 2, 1], [1, 0, 2], [1, 2, 0], [2, 0, 1], [2, 1, 0]];
+
+/// The 3D version of [`Octant`], for transforming voxels.
+///
+/// There are 48 of them, one for each way of flipping the signs of the axes and putting them in
+/// order. Converting a voxel to its position in the octant that a line is in makes the line go
+/// towards positive `x`, `y` and `z`, with `x` changing the most and `z` the least, so an algorithm
+/// only has to handle that one case.
+///
+/// The octants are numbered from `0` to `47`. The number divided by `6` says which axes are
+/// flipped, with `1` for `x`, `2` for `y` and `4` for `z`, and the remainder says what order they
+/// go in. When two axes change by the same amount, the first one stays first.
+///
+/// [`Octant`]: struct.Octant.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Octant3d {
+    value: u8,
+}
+
+impl Octant3d {
+    /// Get the relevant octant from a start and end voxel.
+    #[inline]
+    pub fn new<T>(start: Voxel<T>, end: Voxel<T>) -> Self
+    where
+        T: Copy + Sub<Output = T> + Neg<Output = T> + PartialOrd + Zero,
+    {
+        let mut delta = [end.0 - start.0, end.1 - start.1, end.2 - start.2];
+        let mut flipped = 0;
+
+        for (axis, value) in delta.iter_mut().enumerate() {
+            if *value < T::zero() {
+                *value = -*value;
+                flipped += 1 << axis;
+            }
+        }
+
+        let order = AXES
+            .iter()
+            .position(|axes| delta[axes[0]] >= delta[axes[1]] && delta[axes[1]] >= delta[axes[2]])
+            .unwrap_or(0);
+
+        Self {
+            value: flipped * 6 + order as u8,
+        }
+    }
+
+    /// Get the octant with the given number, if it's from `0` to `47`.
+    #[inline]
+    pub fn from_index(index: u8) -> Option<Self> {
+        if index < 48 {
+            Some(Self { value: index })
+        } else {
+            None
+        }
+    }
+
+    /// The number of the octant, from `0` to `47`.
+    #[inline]
+    pub fn index(&self) -> u8 {
+        self.value
+    }
+
+    /// Convert a voxel to its position in the octant.
+    #[inline]
+    pub fn to<T: Copy + Neg<Output = T>>(&self, voxel: Voxel<T>) -> Voxel<T> {
+        let voxel = self.flip([voxel.0, voxel.1, voxel.2]);
+        let axes = AXES[(self.value % 6) as usize];
+
+        (voxel[axes[0]], voxel[axes[1]], voxel[axes[2]])
+    }
+
+    /// Convert a voxel from its position in the octant.
+    #[inline]
+    pub fn from<T: Copy + Neg<Output = T>>(&self, voxel: Voxel<T>) -> Voxel<T> {
+        let axes = AXES[(self.value % 6) as usize];
+        let mut unordered = [voxel.0, voxel.1, voxel.2];
+
+        for (&axis, &value) in axes.iter().zip(&[voxel.0, voxel.1, voxel.2]) {
+            unordered[axis] = value;
+        }
+
+        let voxel = self.flip(unordered);
+        (voxel[0], voxel[1], voxel[2])
+    }
+
+    /// Flip the signs of the axes that the octant flips, which undoes itself.
+    #[inline]
+    fn flip<T: Copy + Neg<Output = T>>(&self, mut voxel: [T; 3]) -> [T; 3] {
+        let flipped = self.value / 6;
+
+        for (axis, value) in voxel.iter_mut().enumerate() {
+            if flipped & (1 << axis) != 0 {
+                *value = -*value;
+            }
+        }
+
+        voxel
+    }
+}
+
+#[test]
+fn tests() {
+    use core::f64::consts::PI;
+
+    for index in 0..8 {
+        let octant = Octant::from_index(index).unwrap();
+        assert_eq!(octant.index(), index);
+
+        // The middle of the range is in the octant, and so is a point in that direction
+        let range = octant.angles::<f64>();
+        let middle = (range.start + range.end) / 2.0;
+        assert_eq!(Octant::from_angle(middle), octant);
+        assert_eq!(Octant::from_angle(middle - 2.0 * PI), octant);
+
+        let point = (middle.cos() * 10.0, middle.sin() * 10.0);
+        assert_eq!(Octant::new((0.0, 0.0), point), octant);
+        assert_eq!(octant.from(octant.to(point)), point);
+
+        // Points in the octant end up in the first one
+        let (x, y) = octant.to(point);
+        assert!(0.0 <= y && y <= x);
+    }
+
+    assert_eq!(Octant::from_index(8), None);
+    assert_eq!(Octant::new((0, 0), (0, 0)).index(), 0);
+    assert_eq!(Octant::new((0, 0), (3, 3)).index(), 0);
+    assert_eq!(Octant::new((0, 0), (0, 3)).index(), 1);
+    assert_eq!(Octant::new((0, 0), (-3, 0)).index(), 3);
+    assert_eq!(Octant::new((0, 0), (2, -5)).index(), 6);
+    assert_eq!(Octant::new((0, 0), (5, -2)).index(), 7);
+    assert_eq!(Octant::new((0, 0), (5, -2)).angles::<f32>().end, 2.0 * PI as f32);
+}
+
+#[test]
+fn tests_3d() {
+    let deltas = [
+        (5, 3, 1),
+        (-5, 3, 1),
+        (1, -7, 2),
+        (0, 2, -9),
+        (-4, -4, 4),
+        (2, 0, 0),
+        (-1, -6, -3),
+        (0, 0, 0),
+    ];
+
+    for &delta in &deltas {
+        let start = (3, -2, 7);
+        let end = (start.0 + delta.0, start.1 + delta.1, start.2 + delta.2);
+        let octant = Octant3d::new(start, end);
+
+        // The line goes the same way in every octant once it's been converted
+        let (x, y, z) = octant.to(delta);
+        assert!(x >= y && y >= z && z >= 0, "{:?} -> {:?}", delta, (x, y, z));
+        assert_eq!(octant.from((x, y, z)), delta);
+        assert_eq!(Octant3d::from_index(octant.index()), Some(octant));
+    }
+
+    // Every octant can be undone, and they're all different
+    let voxel = (1, -20, 300);
+    let mut seen = Vec::new();
+
+    for index in 0..48 {
+        let octant = Octant3d::from_index(index).unwrap();
+        assert_eq!(octant.from(octant.to(voxel)), voxel);
+        assert_eq!(octant.to(octant.from(voxel)), voxel);
+
+        assert!(!seen.contains(&octant.to(voxel)));
+        seen.push(octant.to(voxel));
+    }
+
+    assert_eq!(Octant3d::from_index(48), None);
+    assert_eq!(Octant3d::new((0, 0, 0), (1, 2, 3)).to((1, 2, 3)), (3, 2, 1));
+}
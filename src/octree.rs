@@ -0,0 +1,250 @@
+use {FloatNum, IntoVoxel, SignedNum, Voxel};
+use bounds::clip_axis;
+use core::fmt;
+
+/// Walk a segment through a sparse octree, only descending into subdivided nodes.
+///
+/// The octree has `depth` levels and covers the voxels `0..2^depth` on each axis, with voxel
+/// corners on the integer grid (like [`VoxelOrigin::Corner`]). Nodes are identified by their
+/// `(level, coords)` where level `0` is a single voxel and a node at level `n` spans `2^n` voxels
+/// along each axis.
+///
+/// The `subdivided` closure is asked whether a node has children worth visiting. Nodes it
+/// returns `false` for are yielded whole, so large empty regions are skipped in a single step.
+/// The parts of the segment outside of the octree are ignored.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::WalkOctree;
+///
+/// fn main() {
+///     // Only the node at the origin is subdivided down to single voxels.
+///     let subdivided = |level, node| level == 2 || (level == 1 && node == (0, 0, 0));
+///
+///     for (level, (x, y, z)) in WalkOctree::<f32, i32, _>::new((0.5, 0.5, 0.5), (2.5, 1.5, 0.5), 2, subdivided) {
+///         print!("({}, ({}, {}, {})), ", level, x, y, z);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// (0, (0, 0, 0)), (0, (1, 0, 0)), (0, (1, 1, 0)), (1, (1, 0, 0)),
+/// ```
+///
+/// [`VoxelOrigin::Corner`]: enum.VoxelOrigin.html
+#[derive(Clone)]
+pub struct WalkOctree<I, O, F> {
+    start: Voxel<I>,
+    delta: Voxel<I>,
+    end: I,
+    depth: u8,
+    size: O,
+    voxel: Option<Voxel<O>>,
+    subdivided: F,
+}
+
+// The closure can't be printed, so it's left out
+impl<I: fmt::Debug, O: fmt::Debug, F> fmt::Debug for WalkOctree<I, O, F> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("WalkOctree")
+            .field("start", &self.start)
+            .field("delta", &self.delta)
+            .field("end", &self.end)
+            .field("depth", &self.depth)
+            .field("size", &self.size)
+            .field("voxel", &self.voxel)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<I, O, F> WalkOctree<I, O, F>
+where
+    I: FloatNum,
+    O: SignedNum,
+    F: FnMut(u8, Voxel<O>) -> bool,
+{
+    /// Create a new `WalkOctree` iterator over an octree with `depth` levels below the root.
+    #[inline]
+    pub fn new(start: impl IntoVoxel<I>, end: impl IntoVoxel<I>, depth: u8, subdivided: F) -> Self {
+        let (start, end) = (start.into_voxel(), end.into_voxel());
+
+        let size = O::cast(1u64 << depth);
+        let delta = (end.0 - start.0, end.1 - start.1, end.2 - start.2);
+
+        // Clip the segment to the bounds of the root node
+        let mut range = (I::zero(), I::one());
+
+        for &(start, delta) in &[(start.0, delta.0), (start.1, delta.1), (start.2, delta.2)] {
+            clip_axis(start, delta, I::zero(), I::cast(size), &mut range);
+        }
+
+        let (enter, exit) = range;
+
+        let voxel = if enter <= exit {
+            let clamp = |value: I| O::cast(value.floor()).max(O::zero()).min(size - O::one());
+
+            Some((
+                clamp(start.0 + delta.0 * enter),
+                clamp(start.1 + delta.1 * enter),
+                clamp(start.2 + delta.2 * enter),
+            ))
+        } else {
+            None
+        };
+
+        Self {
+            start,
+            delta,
+            end: exit,
+            depth,
+            size,
+            voxel,
+            subdivided,
+        }
+    }
+
+    /// Find the position the segment leaves a node along one axis, returning the `t` value and
+    /// the voxel coordinate on the other side.
+    #[inline]
+    fn exit(start: I, delta: I, min: O, max: O) -> Option<(I, O)> {
+        if delta > I::zero() {
+            Some(((I::cast(max) - start) / delta, max))
+        } else if delta < I::zero() {
+            Some(((I::cast(min) - start) / delta, min - O::one()))
+        } else {
+            None
+        }
+    }
+}
+
+impl<I, O, F> Iterator for WalkOctree<I, O, F>
+where
+    I: FloatNum,
+    O: SignedNum,
+    F: FnMut(u8, Voxel<O>) -> bool,
+{
+    type Item = (u8, Voxel<O>);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let voxel = self.voxel?;
+
+        // Descend from the root to the leaf containing the current voxel
+        let mut level = self.depth;
+        let mut scale = self.size;
+        let mut node = (O::zero(), O::zero(), O::zero());
+
+        while level > 0 && (self.subdivided)(level, node) {
+            level -= 1;
+            scale /= O::cast(2);
+            node = (voxel.0 / scale, voxel.1 / scale, voxel.2 / scale);
+        }
+
+        let min = (node.0 * scale, node.1 * scale, node.2 * scale);
+        let max = (min.0 + scale, min.1 + scale, min.2 + scale);
+
+        let exits = [
+            Self::exit(self.start.0, self.delta.0, min.0, max.0),
+            Self::exit(self.start.1, self.delta.1, min.1, max.1),
+            Self::exit(self.start.2, self.delta.2, min.2, max.2),
+        ];
+
+        let t = exits
+            .iter()
+            .filter_map(|exit| exit.map(|(t, _)| t))
+            .fold(I::infinity(), I::min);
+
+        self.voxel = if t < self.end {
+            // Step across every plane that is crossed at the same time, staying inside the node
+            // on the other axes
+            let next = |exit: Option<(I, O)>, start: I, delta: I, min: O, max: O| match exit {
+                Some((exit_t, coord)) if exit_t == t => coord,
+                _ => O::cast((start + delta * t).floor())
+                    .max(min)
+                    .min(max - O::one()),
+            };
+
+            let voxel = (
+                next(exits[0], self.start.0, self.delta.0, min.0, max.0),
+                next(exits[1], self.start.1, self.delta.1, min.1, max.1),
+                next(exits[2], self.start.2, self.delta.2, min.2, max.2),
+            );
+
+            let inside = |coord: O| coord >= O::zero() && coord < self.size;
+
+            if inside(voxel.0) && inside(voxel.1) && inside(voxel.2) {
+                Some(voxel)
+            } else {
+                None
+            }
+        } else {
+            None
+        };
+
+        Some((level, node))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // A segment passes through at most `3 * size - 2` voxels of a cube, and fewer nodes
+        match self.voxel {
+            Some(_) => (
+                1,
+                self.size
+                    .to_usize()
+                    .and_then(|size| size.checked_mul(3))
+                    .map(|voxels| voxels - 2),
+            ),
+            None => (0, Some(0)),
+        }
+    }
+}
+
+#[test]
+fn tests() {
+    let walk_octree = |a, b, subdivided: fn(u8, Voxel<i32>) -> bool| {
+        WalkOctree::<f32, i32, _>::new(a, b, 2, subdivided).collect::<Vec<_>>()
+    };
+
+    let sparse = |level, node| level == 2 || (level == 1 && node == (0, 0, 0));
+
+    assert_eq!(
+        walk_octree((0.5, 0.5, 0.5), (3.5, 0.5, 0.5), sparse),
+        [(0, (0, 0, 0)), (0, (1, 0, 0)), (1, (1, 0, 0))]
+    );
+
+    assert_eq!(
+        walk_octree((3.5, 0.5, 0.5), (0.5, 0.5, 0.5), sparse),
+        [(1, (1, 0, 0)), (0, (1, 0, 0)), (0, (0, 0, 0))]
+    );
+
+    // A fully subdivided octree visits the same voxels as a regular voxel walk
+    assert_eq!(
+        walk_octree((0.5, 0.5, 0.5), (3.5, 0.5, 0.5), |_, _| true),
+        [(0, (0, 0, 0)), (0, (1, 0, 0)), (0, (2, 0, 0)), (0, (3, 0, 0))]
+    );
+
+    // Segments are clipped to the bounds of the octree
+    assert_eq!(
+        walk_octree((-10.5, 0.5, 0.5), (10.5, 0.5, 0.5), |_, _| false),
+        [(2, (0, 0, 0))]
+    );
+
+    assert_eq!(
+        walk_octree((-10.0, 0.5, 0.5), (-5.0, 0.5, 0.5), |_, _| true),
+        []
+    );
+
+    // Everything but the closure is printed
+    assert_eq!(
+        format!(
+            "{:?}",
+            WalkOctree::<f32, i32, _>::new((0.5, 0.5, 0.5), (0.5, 0.5, 0.5), 1, sparse)
+        ),
+        "WalkOctree { start: (0.5, 0.5, 0.5), delta: (0.0, 0.0, 0.0), end: 1.0, depth: 1, \
+         size: 2, voxel: Some((0, 0, 0)), .. }"
+    );
+}
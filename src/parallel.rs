@@ -0,0 +1,120 @@
+//! Functions that draw lots of segments at once, spread across threads with rayon.
+
+use {LineAlgorithm, LineSegment, Point, SignedNum};
+use alloc::vec::Vec;
+use rayon::prelude::*;
+
+/// Draw every segment with an algorithm on rayon's thread pool, and get the points of each one,
+/// in the same order as the segments.
+///
+/// This needs the `rayon` feature, as does [`par_rasterize_with`].
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{par_rasterize, LineAlgorithm, LineSegment};
+///
+/// fn main() {
+///     let segments = [LineSegment::new((0, 0), (3, 1)), LineSegment::new((2, 2), (2, 0))];
+///
+///     for points in par_rasterize(&segments, LineAlgorithm::Bresenham) {
+///         println!("{:?}", points);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// [(0, 0), (1, 0), (2, 0), (3, 1)]
+/// [(2, 2), (2, 1), (2, 0)]
+/// ```
+///
+/// [`par_rasterize_with`]: fn.par_rasterize_with.html
+#[inline]
+pub fn par_rasterize<T>(segments: &[LineSegment<T>], algorithm: LineAlgorithm) -> Vec<Vec<Point<T>>>
+where
+    T: SignedNum + Send + Sync,
+{
+    segments
+        .par_iter()
+        .map(|segment| segment.rasterize(algorithm).collect())
+        .collect()
+}
+
+/// Draw every segment with an algorithm on rayon's thread pool, and give each point to a sink
+/// that's shared between the threads, along with the index of its segment.
+///
+/// The points of each segment arrive in order, but the segments are drawn at the same time as
+/// each other, so their points are mixed together. This skips collecting the points when they're
+/// going straight into something that's safe to share, like a grid of atomic counters.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{par_rasterize_with, LineAlgorithm, LineSegment};
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// fn main() {
+///     let segments = [LineSegment::new((0, 0), (3, 3)), LineSegment::new((0, 3), (3, 0))];
+///     let hits = (0..16).map(|_| AtomicUsize::new(0)).collect::<Vec<_>>();
+///
+///     par_rasterize_with(&segments, LineAlgorithm::Bresenham, |_, (x, y)| {
+///         hits[y as usize * 4 + x as usize].fetch_add(1, Ordering::Relaxed);
+///     });
+///
+///     for row in hits.chunks(4) {
+///         println!("{:?}", row.iter().map(|hit| hit.load(Ordering::Relaxed)).collect::<Vec<_>>());
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// [1, 0, 0, 1]
+/// [0, 1, 1, 0]
+/// [0, 1, 1, 0]
+/// [1, 0, 0, 1]
+/// ```
+#[inline]
+pub fn par_rasterize_with<T, F>(segments: &[LineSegment<T>], algorithm: LineAlgorithm, sink: F)
+where
+    T: SignedNum + Send + Sync,
+    F: Fn(usize, Point<T>) + Send + Sync,
+{
+    segments
+        .par_iter()
+        .enumerate()
+        .for_each(|(index, segment)| {
+            for point in segment.rasterize(algorithm) {
+                sink(index, point);
+            }
+        });
+}
+
+#[test]
+fn tests() {
+    use std::sync::Mutex;
+
+    let segments = (0..200)
+        .map(|i| LineSegment::new((i % 7, -i), (i * 3 % 11 - 5, i % 13)))
+        .collect::<Vec<_>>();
+
+    for &algorithm in &LineAlgorithm::ALL {
+        let lines = segments
+            .iter()
+            .map(|segment| segment.rasterize(algorithm).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        // The points come back in the order of the segments
+        assert_eq!(par_rasterize(&segments, algorithm), lines);
+
+        // The sink gets every point, in order within each segment
+        let seen = Mutex::new(vec![Vec::new(); segments.len()]);
+        par_rasterize_with(&segments, algorithm, |index, point| {
+            seen.lock().unwrap()[index].push(point);
+        });
+        assert_eq!(seen.into_inner().unwrap(), lines);
+    }
+
+    assert!(par_rasterize::<i32>(&[], LineAlgorithm::Bresenham).is_empty());
+}
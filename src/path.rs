@@ -0,0 +1,677 @@
+use {Bresenham, CoverageBuffer, FloatNum, IntoPoint, LineError, Point, RealNum, SignedNum,
+     XiaolinWu};
+use error::check_float;
+use alloc::collections::{btree_map, BTreeMap, BTreeSet};
+use alloc::vec::Vec;
+use core::slice;
+
+/// How far the straight pieces that curves are split into can stray from the real curve, in cells.
+const TOLERANCE: f64 = 0.25;
+
+/// One of the commands that make up a [`Path`].
+///
+/// These are the same as the commands in SVG path data, with absolute coordinates.
+///
+/// [`Path`]: struct.Path.html
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PathSegment<I> {
+    /// Start a new sub-path at a point, without drawing anything.
+    MoveTo(Point<I>),
+    /// A straight line to a point.
+    LineTo(Point<I>),
+    /// A quadratic Bézier curve, with a control point and an end point.
+    QuadTo(Point<I>, Point<I>),
+    /// A cubic Bézier curve, with two control points and an end point.
+    CubicTo(Point<I>, Point<I>, Point<I>),
+    /// An elliptical arc to `end`, picked out of the four possible ones by `large_arc` and
+    /// `sweep`. The `rotation` of the ellipse is in radians.
+    ArcTo {
+        radius: Point<I>,
+        rotation: I,
+        large_arc: bool,
+        sweep: bool,
+        end: Point<I>,
+    },
+    /// A straight line back to the start of the sub-path.
+    Close,
+}
+
+/// A path made out of lines and curves, built up a piece at a time like a path in a vector drawing
+/// program.
+///
+/// The whole path is drawn as one iterator with [`points`], which doesn't return any point twice,
+/// or with anti-aliasing with [`coverage`]. Curves are split into straight pieces that are drawn
+/// with [`Bresenham`] or [`XiaolinWu`], and the coordinates are rounded to the nearest cell.
+///
+/// This needs the `alloc` feature.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::Path;
+///
+/// fn main() {
+///     let path = Path::<f32>::new()
+///         .move_to((0.0, 0.0))
+///         .line_to((4.0, 0.0))
+///         .quad_to((4.0, 4.0), (0.0, 4.0))
+///         .close();
+///
+///     for y in 0..5 {
+///         let row = (0..5).map(|x| path.points().any(|point| point == (x, y)));
+///         println!("{}", row.map(|drawn| if drawn { '#' } else { '.' }).collect::<String>());
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// #####
+/// #...#
+/// #...#
+/// #..#.
+/// ###..
+/// ```
+///
+/// [`points`]: #method.points
+/// [`coverage`]: #method.coverage
+/// [`Bresenham`]: struct.Bresenham.html
+/// [`XiaolinWu`]: struct.XiaolinWu.html
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Path<I> {
+    segments: Vec<PathSegment<I>>,
+}
+
+impl<I: FloatNum> Path<I> {
+    /// Create an empty path. Until [`move_to`] is used, it starts at `(0, 0)`.
+    ///
+    /// [`move_to`]: #method.move_to
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            segments: Vec::new(),
+        }
+    }
+
+    /// Add a segment to the end of the path.
+    #[inline]
+    pub fn push(mut self, segment: PathSegment<I>) -> Self {
+        self.segments.push(segment);
+        self
+    }
+
+    #[inline]
+    pub fn move_to(self, point: impl IntoPoint<I>) -> Self {
+        self.push(PathSegment::MoveTo(point.into_point()))
+    }
+
+    #[inline]
+    pub fn line_to(self, point: impl IntoPoint<I>) -> Self {
+        self.push(PathSegment::LineTo(point.into_point()))
+    }
+
+    #[inline]
+    pub fn quad_to(self, control: impl IntoPoint<I>, end: impl IntoPoint<I>) -> Self {
+        self.push(PathSegment::QuadTo(control.into_point(), end.into_point()))
+    }
+
+    #[inline]
+    pub fn cubic_to(
+        self,
+        first: impl IntoPoint<I>,
+        second: impl IntoPoint<I>,
+        end: impl IntoPoint<I>,
+    ) -> Self {
+        self.push(PathSegment::CubicTo(
+            first.into_point(),
+            second.into_point(),
+            end.into_point(),
+        ))
+    }
+
+    /// Add an elliptical arc, the same as the `A` command in SVG path data, except that the
+    /// `rotation` is in radians.
+    #[inline]
+    pub fn arc_to(
+        self,
+        radius: impl IntoPoint<I>,
+        rotation: I,
+        large_arc: bool,
+        sweep: bool,
+        end: impl IntoPoint<I>,
+    ) -> Self {
+        self.push(PathSegment::ArcTo {
+            radius: radius.into_point(),
+            rotation,
+            large_arc,
+            sweep,
+            end: end.into_point(),
+        })
+    }
+
+    #[inline]
+    pub fn close(self) -> Self {
+        self.push(PathSegment::Close)
+    }
+
+    /// The segments that the path is made out of.
+    #[inline]
+    pub fn segments(&self) -> &[PathSegment<I>] {
+        &self.segments
+    }
+
+    /// Draw the path, in order, leaving out any points that have already been drawn.
+    ///
+    /// Panics if a point on the path doesn't fit in `O`. Use [`try_points`] for paths that can't be
+    /// trusted, like ones parsed from SVG.
+    ///
+    /// [`try_points`]: #method.try_points
+    #[inline]
+    pub fn points<O: SignedNum>(&self) -> PathPoints<'_, I, O> {
+        PathPoints {
+            pieces: Flatten::new(&self.segments, false),
+            line: None,
+            seen: BTreeSet::new(),
+        }
+    }
+
+    /// Draw the path like [`points`], but return an error instead of panicking when a point on it
+    /// isn't finite or doesn't fit in `O`.
+    ///
+    /// [`points`]: #method.points
+    #[inline]
+    pub fn try_points<O: SignedNum>(&self) -> Result<PathPoints<'_, I, O>, LineError>
+    where
+        I: RealNum,
+    {
+        for (start, end) in Flatten::new(&self.segments, false) {
+            for &value in &[start.0, start.1, end.0, end.1] {
+                check_float::<I, O>(value)?;
+            }
+
+            let cell = |point: Point<I>| (point.0.to_cell::<O>(), point.1.to_cell::<O>());
+            Bresenham::try_new(cell(start), cell(end))?;
+        }
+
+        Ok(self.points())
+    }
+
+    /// Draw the path anti-aliased, with how much of each point it covers.
+    ///
+    /// Where the path goes over the same point more than once, the most that it's covered by is
+    /// used. This means the whole path has to be drawn before anything is returned, so the points
+    /// come out sorted instead of in order along the path.
+    ///
+    /// Panics if a point on the path doesn't fit in `O`, the same as [`points`].
+    ///
+    /// [`points`]: #method.points
+    #[inline]
+    pub fn coverage<O: SignedNum>(&self) -> PathCoverage<I, O>
+    where
+        I: RealNum,
+    {
+        let mut coverage = BTreeMap::new();
+
+        for (start, end) in Flatten::new(&self.segments, false) {
+            for (point, amount) in XiaolinWu::<I, O>::new(start, end) {
+                if amount.is_zero() {
+                    continue;
+                }
+
+                let entry = coverage.entry(point).or_insert(amount);
+
+                if amount > *entry {
+                    *entry = amount;
+                }
+            }
+        }
+
+        PathCoverage {
+            points: coverage.into_iter(),
+        }
+    }
+
+    /// Draw the path anti-aliased like [`coverage`], but return an error instead of panicking when
+    /// a point on it isn't finite or doesn't fit in `O`.
+    ///
+    /// [`coverage`]: #method.coverage
+    #[inline]
+    pub fn try_coverage<O: SignedNum>(&self) -> Result<PathCoverage<I, O>, LineError>
+    where
+        I: RealNum,
+    {
+        for (start, end) in Flatten::new(&self.segments, false) {
+            XiaolinWu::<I, O>::try_new(start, end)?;
+        }
+
+        Ok(self.coverage())
+    }
+
+    /// Fill the path into a [`CoverageBuffer`], with the exact area of each cell that it covers.
+    ///
+    /// Any sub-paths that aren't closed are closed with a straight line, the same as when a path
+    /// is filled in SVG.
+    ///
+    /// [`CoverageBuffer`]: struct.CoverageBuffer.html
+    #[inline]
+    pub fn accumulate(&self, buffer: &mut CoverageBuffer<I>)
+    where
+        I: RealNum,
+    {
+        for (start, end) in Flatten::new(&self.segments, true) {
+            buffer.line(start, end);
+        }
+    }
+}
+
+/// The points of a [`Path`], made with [`Path::points`].
+///
+/// [`Path`]: struct.Path.html
+/// [`Path::points`]: struct.Path.html#method.points
+#[derive(Clone, Debug)]
+pub struct PathPoints<'a, I: 'a, O: SignedNum> {
+    pieces: Flatten<'a, I>,
+    line: Option<Bresenham<O>>,
+    seen: BTreeSet<Point<O>>,
+}
+
+impl<'a, I: FloatNum, O: SignedNum> Iterator for PathPoints<'a, I, O> {
+    type Item = Point<O>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            while let Some(point) = self.line.as_mut().and_then(Iterator::next) {
+                if self.seen.insert(point) {
+                    return Some(point);
+                }
+            }
+
+            let (start, end) = self.pieces.next()?;
+            let cell = |point: Point<I>| (point.0.to_cell(), point.1.to_cell());
+            self.line = Some(Bresenham::new(cell(start), cell(end)));
+        }
+    }
+}
+
+/// The anti-aliased points of a [`Path`], made with [`Path::coverage`].
+///
+/// [`Path`]: struct.Path.html
+/// [`Path::coverage`]: struct.Path.html#method.coverage
+#[derive(Debug)]
+pub struct PathCoverage<I, O> {
+    points: btree_map::IntoIter<Point<O>, I>,
+}
+
+impl<I, O> Iterator for PathCoverage<I, O> {
+    type Item = (Point<O>, I);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.points.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.points.size_hint()
+    }
+}
+
+impl<I, O> ExactSizeIterator for PathCoverage<I, O> {}
+
+/// A curve that's being split into straight pieces.
+#[derive(Clone, Debug)]
+enum Curve<I> {
+    Quad([Point<I>; 3]),
+    Cubic([Point<I>; 4]),
+    Arc {
+        center: Point<I>,
+        radius: Point<I>,
+        rotation: Point<I>,
+        angle: I,
+        sweep: I,
+    },
+}
+
+impl<I: FloatNum> Curve<I> {
+    #[inline]
+    fn at(&self, t: I) -> Point<I> {
+        let lerp = |a: Point<I>, b: Point<I>| (a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t);
+
+        match *self {
+            Curve::Quad([a, b, c]) => lerp(lerp(a, b), lerp(b, c)),
+            Curve::Cubic([a, b, c, d]) => {
+                let (ab, bc, cd) = (lerp(a, b), lerp(b, c), lerp(c, d));
+                lerp(lerp(ab, bc), lerp(bc, cd))
+            }
+            Curve::Arc {
+                center,
+                radius,
+                rotation: (cos, sin),
+                angle,
+                sweep,
+            } => {
+                let angle = angle + sweep * t;
+                let (x, y) = (radius.0 * angle.cos(), radius.1 * angle.sin());
+                (center.0 + x * cos - y * sin, center.1 + x * sin + y * cos)
+            }
+        }
+    }
+
+    /// How many straight pieces the curve needs to be split into to stay within the tolerance.
+    #[inline]
+    fn steps(&self) -> u32 {
+        let tolerance = I::cast(TOLERANCE);
+        let bend = |a: Point<I>, b: Point<I>, c: Point<I>| {
+            let two = I::one() + I::one();
+            (a.0 - two * b.0 + c.0).hypot(a.1 - two * b.1 + c.1)
+        };
+
+        let steps = match *self {
+            Curve::Quad([a, b, c]) => (bend(a, b, c) / (I::cast(4) * tolerance)).sqrt(),
+            Curve::Cubic([a, b, c, d]) => {
+                let bend = bend(a, b, c).max(bend(b, c, d));
+                (I::cast(3) * bend / (I::cast(4) * tolerance)).sqrt()
+            }
+            Curve::Arc { radius, sweep, .. } => {
+                let radius = radius.0.max(radius.1);
+
+                if radius <= tolerance {
+                    I::one()
+                } else {
+                    let step = I::cast(2) * (I::one() - tolerance / radius).acos();
+                    sweep.abs() / step
+                }
+            }
+        };
+
+        steps.ceil().to_u32().unwrap_or(1).max(1)
+    }
+}
+
+/// The straight pieces of a path, with the curves split up.
+#[derive(Clone, Debug)]
+struct Flatten<'a, I: 'a> {
+    segments: slice::Iter<'a, PathSegment<I>>,
+    pen: Point<I>,
+    start: Point<I>,
+    curve: Option<(Curve<I>, Point<I>)>,
+    step: u32,
+    steps: u32,
+    closed: bool,
+}
+
+impl<'a, I: FloatNum> Flatten<'a, I> {
+    /// Split up the segments, with a straight piece back to the start of each sub-path that isn't
+    /// closed if `closed` is set, like for filling.
+    #[inline]
+    fn new(segments: &'a [PathSegment<I>], closed: bool) -> Self {
+        let origin = (I::zero(), I::zero());
+
+        Self {
+            segments: segments.iter(),
+            pen: origin,
+            start: origin,
+            curve: None,
+            step: 0,
+            steps: 0,
+            closed,
+        }
+    }
+
+    #[inline]
+    fn start_curve(&mut self, curve: Curve<I>, end: Point<I>) {
+        self.step = 0;
+        self.steps = curve.steps();
+        self.curve = Some((curve, end));
+    }
+
+    /// Turn an SVG-style arc into its center and angles.
+    #[inline]
+    fn arc(
+        &self,
+        radius: Point<I>,
+        rotation: I,
+        large_arc: bool,
+        sweep: bool,
+        end: Point<I>,
+    ) -> Curve<I> {
+        let (start, two) = (self.pen, I::one() + I::one());
+        let (sin, cos) = rotation.sin_cos();
+
+        // The start point relative to the middle, in the ellipse's own rotation
+        let (dx, dy) = ((start.0 - end.0) / two, (start.1 - end.1) / two);
+        let (x, y) = (cos * dx + sin * dy, cos * dy - sin * dx);
+
+        // Make the ellipse bigger if it doesn't reach from one end to the other
+        let (mut rx, mut ry) = (radius.0.abs(), radius.1.abs());
+        let scale = (x * x) / (rx * rx) + (y * y) / (ry * ry);
+
+        if scale > I::one() {
+            rx *= scale.sqrt();
+            ry *= scale.sqrt();
+        }
+
+        let (rx2, ry2) = (rx * rx, ry * ry);
+        let numerator = rx2 * ry2 - rx2 * y * y - ry2 * x * x;
+        let mut factor = (numerator / (rx2 * y * y + ry2 * x * x)).max(I::zero()).sqrt();
+
+        if large_arc == sweep {
+            factor = -factor;
+        }
+
+        let (cx, cy) = (factor * rx * y / ry, -factor * ry * x / rx);
+        let center = (
+            cos * cx - sin * cy + (start.0 + end.0) / two,
+            sin * cx + cos * cy + (start.1 + end.1) / two,
+        );
+
+        let angle = ((y - cy) / ry).atan2((x - cx) / rx);
+        let mut delta = ((-y - cy) / ry).atan2((-x - cx) / rx) - angle;
+        let turn = I::cast(core::f64::consts::PI * 2.0);
+
+        if sweep && delta < I::zero() {
+            delta += turn;
+        } else if !sweep && delta > I::zero() {
+            delta -= turn;
+        }
+
+        Curve::Arc {
+            center,
+            radius: (rx, ry),
+            rotation: (cos, sin),
+            angle,
+            sweep: delta,
+        }
+    }
+
+    /// The piece that closes the current sub-path, if it needs one.
+    #[inline]
+    fn close(&mut self) -> Option<(Point<I>, Point<I>)> {
+        if self.closed && self.pen != self.start {
+            let start = self.start;
+            Some(self.piece(start))
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn piece(&mut self, end: Point<I>) -> (Point<I>, Point<I>) {
+        let start = self.pen;
+        self.pen = end;
+
+        (start, end)
+    }
+}
+
+impl<'a, I: FloatNum> Iterator for Flatten<'a, I> {
+    type Item = (Point<I>, Point<I>);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((ref curve, end)) = self.curve {
+                if self.step < self.steps {
+                    self.step += 1;
+
+                    // The last piece goes right to the end, whatever rounding errors there are
+                    let point = if self.step == self.steps {
+                        end
+                    } else {
+                        curve.at(I::cast(self.step) / I::cast(self.steps))
+                    };
+
+                    return Some(self.piece(point));
+                }
+            }
+
+            self.curve = None;
+
+            let segment = match self.segments.next() {
+                Some(segment) => segment,
+                None => return self.close(),
+            };
+
+            match *segment {
+                PathSegment::MoveTo(point) => {
+                    let close = self.close();
+                    self.pen = point;
+                    self.start = point;
+
+                    if close.is_some() {
+                        return close;
+                    }
+                }
+                PathSegment::LineTo(point) => return Some(self.piece(point)),
+                PathSegment::QuadTo(control, end) => {
+                    self.start_curve(Curve::Quad([self.pen, control, end]), end);
+                }
+                PathSegment::CubicTo(first, second, end) => {
+                    self.start_curve(Curve::Cubic([self.pen, first, second, end]), end);
+                }
+                PathSegment::ArcTo {
+                    radius,
+                    rotation,
+                    large_arc,
+                    sweep,
+                    end,
+                } => {
+                    // Like in SVG, an arc without a radius is a straight line
+                    if radius.0.is_zero() || radius.1.is_zero() || end == self.pen {
+                        return Some(self.piece(end));
+                    }
+
+                    let arc = self.arc(radius, rotation, large_arc, sweep, end);
+                    self.start_curve(arc, end);
+                }
+                PathSegment::Close => {
+                    let start = self.start;
+                    return Some(self.piece(start));
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn tests() {
+    use Rect;
+
+    let touching = |points: &[Point<i32>]| {
+        points
+            .windows(2)
+            .all(|pair| (pair[0].0 - pair[1].0).abs() <= 1 && (pair[0].1 - pair[1].1).abs() <= 1)
+    };
+
+    // Straight lines are the same as a rectangle's outline
+    let path = Path::<f64>::new()
+        .move_to((-2.0, 1.0))
+        .line_to((3.0, 1.0))
+        .line_to((3.0, 4.0))
+        .line_to((-2.0, 4.0))
+        .close();
+    assert_eq!(
+        path.points::<i32>().collect::<Vec<_>>(),
+        Rect::new((-2, 1), (3, 4)).outline().collect::<Vec<_>>()
+    );
+
+    // Points that are gone over again are only returned once
+    let back = Path::<f32>::new().line_to((5.0, 0.0)).line_to((2.0, 0.0));
+    assert_eq!(back.points::<i32>().count(), 6);
+    assert_eq!(Path::<f32>::new().move_to((1.0, 1.0)).points::<i32>().count(), 0);
+    assert_eq!(
+        Path::<f32>::new().move_to((0.6, 1.4)).line_to((0.6, 1.4)).points().collect::<Vec<_>>(),
+        [(1, 1)]
+    );
+
+    // The curves go from one end to the other without any gaps
+    let curves = Path::<f64>::new()
+        .move_to((0.0, 0.0))
+        .quad_to((10.0, 0.0), (10.0, 10.0))
+        .cubic_to((10.0, 20.0), (-10.0, 0.0), (-10.0, 20.0))
+        .arc_to((15.0, 10.0), 0.5, true, false, (5.0, 30.0));
+    let points = curves.points::<i32>().collect::<Vec<_>>();
+    assert!(touching(&points));
+    assert_eq!(points.first(), Some(&(0, 0)));
+    assert_eq!(points.last(), Some(&(5, 30)));
+    assert!(points.contains(&(10, 10)) && points.contains(&(-10, 20)));
+
+    // Half of a circle stays on the circle, with the sweep going clockwise like in SVG
+    for &sweep in &[false, true] {
+        let arc = Path::<f64>::new()
+            .move_to((-20.0, 0.0))
+            .arc_to((20.0, 20.0), 0.0, false, sweep, (20.0, 0.0));
+        let points = arc.points::<i32>().collect::<Vec<_>>();
+        assert!(touching(&points));
+
+        for &(x, y) in &points {
+            let distance = f64::from(x * x + y * y).sqrt();
+            assert!((distance - 20.0).abs() < 1.0);
+            assert!(if sweep { y <= 0 } else { y >= 0 });
+        }
+    }
+
+    // A radius that's too small is made big enough to reach
+    let small = Path::<f64>::new()
+        .move_to((0.0, 0.0))
+        .arc_to((1.0, 1.0), 0.0, false, true, (10.0, 0.0));
+    assert!(small.points::<i32>().any(|point| point == (5, -5)));
+
+    let straight = Path::<f64>::new().arc_to((0.0, 3.0), 0.0, false, true, (4.0, 0.0));
+    assert_eq!(straight.points::<i32>().count(), 5);
+
+    // The coverage of each point is only returned once
+    let coverage = curves.coverage::<i32>().collect::<Vec<_>>();
+    assert_eq!(coverage.len(), curves.coverage::<i32>().len());
+    assert!(coverage.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    assert!(coverage.iter().all(|&(_, amount)| amount > 0.0 && amount <= 1.0));
+
+    for &(x, y) in &points {
+        let near = |&((cx, cy), _): &(Point<i32>, f64)| (cx - x).abs() <= 1 && (cy - y).abs() <= 1;
+        assert!(coverage.iter().any(near));
+    }
+
+    assert_eq!(path.segments().len(), 5);
+    assert_eq!(path.segments()[4], PathSegment::Close);
+
+    // Paths that don't fit in the output type are errors instead of panics
+    let far = Path::<f32>::new().move_to((0.0, 0.0)).line_to((3e9, 0.0));
+    assert_eq!(far.try_points::<i32>().err(), Some(LineError::Overflow));
+    assert_eq!(far.try_coverage::<i32>().err(), Some(LineError::Overflow));
+    assert!(far.try_points::<i64>().is_ok());
+
+    let bent = Path::<f64>::new().move_to((0.0, 0.0)).quad_to((10.0, 300.0), (20.0, 0.0));
+    assert_eq!(bent.try_points::<i8>().err(), Some(LineError::Overflow));
+    assert_eq!(
+        bent.try_points::<i32>().unwrap().collect::<Vec<_>>(),
+        bent.points::<i32>().collect::<Vec<_>>()
+    );
+    assert_eq!(bent.try_coverage::<i32>().unwrap().len(), bent.coverage::<i32>().len());
+    assert_eq!(
+        Path::new().line_to((f32::NAN, 0.0)).try_points::<i32>().err(),
+        Some(LineError::NotFinite)
+    );
+}
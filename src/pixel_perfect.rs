@@ -0,0 +1,156 @@
+use {Point, SignedNum};
+
+/// Check if two points are next to each other horizontally or vertically.
+#[inline]
+fn orthogonal<T: SignedNum>(a: Point<T>, b: Point<T>) -> bool {
+    (a.0 - b.0).abs() + (a.1 - b.1).abs() == T::one()
+}
+
+/// An iterator that removes the corners of L-shaped steps from a line, as in pixel-art tools.
+///
+/// Whenever a line takes a horizontal and a vertical step in a row, like the ones from
+/// [`WalkGrid`], the point between them is dropped so that the line steps diagonally instead.
+/// This gives the thin "pixel-perfect" strokes used for drawing pixel art, where no pixel touches
+/// more than two others. The first and last points are always kept.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::WalkGrid;
+///
+/// fn main() {
+///     for (x, y) in WalkGrid::new((0, 0), (5, 2)).pixel_perfect() {
+///         print!("({}, {}), ", x, y);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// (0, 0), (1, 1), (2, 1), (3, 1), (4, 2), (5, 2),
+/// ```
+///
+/// [`WalkGrid`]: struct.WalkGrid.html
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PixelPerfect<T, I> {
+    iterator: I,
+    previous: Option<Point<T>>,
+    pending: Option<Point<T>>,
+}
+
+impl<T: SignedNum, I: Iterator<Item = Point<T>>> PixelPerfect<T, I> {
+    #[inline]
+    pub fn new(iterator: I) -> Self {
+        Self {
+            iterator,
+            previous: None,
+            pending: None,
+        }
+    }
+}
+
+impl<T: SignedNum, I: Iterator<Item = Point<T>>> Iterator for PixelPerfect<T, I> {
+    type Item = Point<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let current = match self.pending {
+                Some(point) => point,
+                None => {
+                    self.pending = Some(self.iterator.next()?);
+                    continue;
+                }
+            };
+
+            let next = match self.iterator.next() {
+                Some(point) => point,
+                None => {
+                    self.pending = None;
+                    return Some(current);
+                }
+            };
+
+            self.pending = Some(next);
+
+            if let Some(previous) = self.previous {
+                // Skip the corner of an L shape
+                if orthogonal(previous, current)
+                    && orthogonal(current, next)
+                    && previous.0 != next.0
+                    && previous.1 != next.1
+                {
+                    continue;
+                }
+            }
+
+            self.previous = Some(current);
+
+            return Some(current);
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // A corner is never dropped straight after another one, and the ends are always kept
+        let (low, high) = self.iterator.size_hint();
+        let pending = self.pending.is_some() as usize;
+
+        (
+            low.saturating_add(pending).saturating_add(1) / 2,
+            high.and_then(|high| high.checked_add(pending)),
+        )
+    }
+}
+
+#[test]
+fn tests() {
+    use WalkGrid;
+
+    let pixel_perfect = |a, b| {
+        WalkGrid::<i32>::new(a, b)
+            .pixel_perfect()
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        pixel_perfect((0, 0), (3, 3)),
+        [(0, 0), (1, 1), (2, 2), (3, 3)]
+    );
+    assert_eq!(
+        pixel_perfect((0, 0), (4, 0)),
+        [(0, 0), (1, 0), (2, 0), (3, 0), (4, 0)]
+    );
+    assert_eq!(pixel_perfect((2, 2), (2, 2)), [(2, 2)]);
+    assert_eq!(pixel_perfect((0, 0), (1, 1)), [(0, 0), (1, 1)]);
+    assert_eq!(
+        PixelPerfect::new(Vec::new().into_iter()).collect::<Vec<Point<i32>>>(),
+        []
+    );
+
+    // No point ends up touching more than two others, and every column gets exactly one point
+    for &(a, b) in &[((0, 0), (9, 4)), ((5, -3), (-7, 2)), ((0, 0), (12, 11))] {
+        let points = pixel_perfect(a, b);
+
+        for (i, &point) in points.iter().enumerate() {
+            let touching = points
+                .iter()
+                .filter(|&&other| {
+                    other != point
+                        && (other.0 - point.0).abs() <= 1
+                        && (other.1 - point.1).abs() <= 1
+                })
+                .count();
+
+            let ends = if i == 0 || i == points.len() - 1 {
+                1
+            } else {
+                2
+            };
+            assert_eq!(touching, ends);
+        }
+
+        assert_eq!(points.len() as i32, (b.0 - a.0).abs() + 1);
+    }
+}
@@ -0,0 +1,176 @@
+use num_traits::ToPrimitive;
+use {Point, Voxel};
+
+/// Something that points can be drawn onto, like a pixel buffer.
+///
+/// This is the other way around from the iterators: instead of taking the points out of a line,
+/// the line is drawn straight into the target with [`draw_points`]. It's implemented for any
+/// closure that takes the `x` and `y` of a point.
+///
+/// [`draw_points`]: fn.draw_points.html
+pub trait PlotTarget<T> {
+    /// Draw a point.
+    fn plot(&mut self, x: T, y: T);
+
+    /// Draw a point that's only partly covered by an anti-aliased line, from `0.0` to `1.0`.
+    ///
+    /// By default, the points that are at least half covered are drawn with [`plot`], and the rest
+    /// are skipped.
+    ///
+    /// [`plot`]: #tymethod.plot
+    #[inline]
+    fn plot_aa(&mut self, x: T, y: T, coverage: f32) {
+        if coverage >= 0.5 {
+            self.plot(x, y);
+        }
+    }
+}
+
+impl<T, F: FnMut(T, T)> PlotTarget<T> for F {
+    #[inline]
+    fn plot(&mut self, x: T, y: T) {
+        self(x, y)
+    }
+}
+
+/// Something that voxels can be drawn into, like a voxel grid.
+///
+/// This is the 3D version of [`PlotTarget`], for use with [`draw_voxels`]. It's implemented for
+/// any closure that takes the `x`, `y` and `z` of a voxel.
+///
+/// [`PlotTarget`]: trait.PlotTarget.html
+/// [`draw_voxels`]: fn.draw_voxels.html
+pub trait PlotTarget3d<T> {
+    /// Draw a voxel.
+    fn plot(&mut self, x: T, y: T, z: T);
+}
+
+impl<T, F: FnMut(T, T, T)> PlotTarget3d<T> for F {
+    #[inline]
+    fn plot(&mut self, x: T, y: T, z: T) {
+        self(x, y, z)
+    }
+}
+
+/// Draw the points of a line, or any other shape, onto a [`PlotTarget`].
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{draw_points, Bresenham, PlotTarget};
+///
+/// struct Canvas {
+///     pixels: [[bool; 4]; 4],
+/// }
+///
+/// impl PlotTarget<i32> for Canvas {
+///     fn plot(&mut self, x: i32, y: i32) {
+///         self.pixels[y as usize][x as usize] = true;
+///     }
+/// }
+///
+/// fn main() {
+///     let mut canvas = Canvas { pixels: [[false; 4]; 4] };
+///     draw_points(&mut canvas, Bresenham::new((0, 0), (3, 3)));
+///
+///     assert!((0..4).all(|i| canvas.pixels[i][i]));
+/// }
+/// ```
+///
+/// [`PlotTarget`]: trait.PlotTarget.html
+#[inline]
+pub fn draw_points<T, P, I>(target: &mut P, points: I)
+where
+    P: PlotTarget<T> + ?Sized,
+    I: IntoIterator<Item = Point<T>>,
+{
+    points.into_iter().for_each(|(x, y)| target.plot(x, y));
+}
+
+/// Draw the points of an anti-aliased line, like [`XiaolinWu`], onto a [`PlotTarget`] with
+/// [`plot_aa`].
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{draw_points_aa, XiaolinWu};
+///
+/// fn main() {
+///     let mut plotted = Vec::new();
+///     draw_points_aa(
+///         &mut |x, y| plotted.push((x, y)),
+///         XiaolinWu::<f32, i32>::new((0.0, 0.0), (4.0, 1.0)),
+///     );
+///
+///     assert_eq!(plotted, [(0, 0), (1, 0), (2, 0), (2, 1), (3, 1), (4, 1)]);
+/// }
+/// ```
+///
+/// [`XiaolinWu`]: struct.XiaolinWu.html
+/// [`PlotTarget`]: trait.PlotTarget.html
+/// [`plot_aa`]: trait.PlotTarget.html#method.plot_aa
+#[inline]
+pub fn draw_points_aa<T, C, P, I>(target: &mut P, points: I)
+where
+    C: ToPrimitive,
+    P: PlotTarget<T> + ?Sized,
+    I: IntoIterator<Item = (Point<T>, C)>,
+{
+    points.into_iter().for_each(|((x, y), coverage)| {
+        target.plot_aa(x, y, coverage.to_f32().unwrap())
+    });
+}
+
+/// Draw the voxels of a 3D line onto a [`PlotTarget3d`].
+///
+/// [`PlotTarget3d`]: trait.PlotTarget3d.html
+#[inline]
+pub fn draw_voxels<T, P, I>(target: &mut P, voxels: I)
+where
+    P: PlotTarget3d<T> + ?Sized,
+    I: IntoIterator<Item = Voxel<T>>,
+{
+    voxels.into_iter().for_each(|(x, y, z)| target.plot(x, y, z));
+}
+
+#[test]
+fn tests() {
+    use {Bresenham, Bresenham3d, WalkGrid, XiaolinWu};
+
+    let mut points = Vec::new();
+    draw_points(&mut |x, y| points.push((x, y)), WalkGrid::new((0, 0), (3, 2)));
+    assert_eq!(points, WalkGrid::new((0, 0), (3, 2)).collect::<Vec<_>>());
+
+    let mut voxels = Vec::new();
+    draw_voxels(&mut |x, y, z| voxels.push((x, y, z)), Bresenham3d::new((0, 0, 0), (2, 1, 3)));
+    assert_eq!(voxels, Bresenham3d::new((0, 0, 0), (2, 1, 3)).collect::<Vec<_>>());
+
+    // Overriding the anti-aliasing
+    struct Coverage(Vec<(Point<i32>, f32)>);
+
+    impl PlotTarget<i32> for Coverage {
+        fn plot(&mut self, x: i32, y: i32) {
+            self.plot_aa(x, y, 1.0);
+        }
+
+        fn plot_aa(&mut self, x: i32, y: i32, coverage: f32) {
+            self.0.push(((x, y), coverage));
+        }
+    }
+
+    let line = XiaolinWu::<f64, i32>::new((0.0, 0.0), (5.0, 2.0));
+    let mut coverage = Coverage(Vec::new());
+    draw_points_aa(&mut coverage, line.clone());
+    assert_eq!(
+        coverage.0,
+        line.map(|(point, value)| (point, value as f32)).collect::<Vec<_>>()
+    );
+
+    // Through a trait object
+    let mut coverage = Coverage(Vec::new());
+    let target: &mut dyn PlotTarget<i32> = &mut coverage;
+    draw_points(target, Bresenham::new((0, 0), (2, 0)));
+    assert_eq!(coverage.0, [((0, 0), 1.0), ((1, 0), 1.0), ((2, 0), 1.0)]);
+}
@@ -0,0 +1,221 @@
+//! Functions that collect the points of a shape into a `Vec`, for when the iterator isn't needed.
+
+use {Bresenham, Bresenham3d, BresenhamCircle, Midpoint, MidpointEllipse, Point, RealNum,
+     SignedNum, Supercover, ThickSupercover, Voxel, VoxelOrigin, WalkGrid, WalkVoxels, XiaolinWu};
+use alloc::vec::Vec;
+
+/// Get the points of a [`Bresenham`] line.
+///
+/// This needs the `alloc` feature, as do the other `_points` functions.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::bresenham_points;
+///
+/// fn main() {
+///     println!("{:?}", bresenham_points((0, 0), (5, 2)));
+/// }
+/// ```
+///
+/// ```text
+/// [(0, 0), (1, 0), (2, 0), (3, 1), (4, 1), (5, 2)]
+/// ```
+///
+/// [`Bresenham`]: struct.Bresenham.html
+#[inline]
+pub fn bresenham_points<T: SignedNum>(start: Point<T>, end: Point<T>) -> Vec<Point<T>> {
+    Bresenham::new(start, end).collect()
+}
+
+/// Get the points of a [`Midpoint`] line.
+///
+/// [`Midpoint`]: struct.Midpoint.html
+#[inline]
+pub fn midpoint_points<I: RealNum, O: SignedNum>(
+    start: Point<I>,
+    end: Point<I>,
+) -> Vec<Point<O>> {
+    Midpoint::new(start, end).collect()
+}
+
+/// Get the points and their coverage for a [`XiaolinWu`] line.
+///
+/// [`XiaolinWu`]: struct.XiaolinWu.html
+#[inline]
+pub fn xiaolin_wu_points<I: RealNum, O: SignedNum>(
+    start: Point<I>,
+    end: Point<I>,
+) -> Vec<(Point<O>, I)> {
+    XiaolinWu::new(start, end).collect()
+}
+
+/// Get the points of a [`WalkGrid`] line.
+///
+/// [`WalkGrid`]: struct.WalkGrid.html
+#[inline]
+pub fn walk_grid_points<T: SignedNum>(start: Point<T>, end: Point<T>) -> Vec<Point<T>> {
+    WalkGrid::new(start, end).collect()
+}
+
+/// Get the points of a [`Supercover`] line.
+///
+/// [`Supercover`]: struct.Supercover.html
+#[inline]
+pub fn supercover_points<T: SignedNum>(start: Point<T>, end: Point<T>) -> Vec<Point<T>> {
+    Supercover::new(start, end).collect()
+}
+
+/// Get the points of a [`ThickSupercover`] line.
+///
+/// [`ThickSupercover`]: struct.ThickSupercover.html
+#[inline]
+pub fn thick_supercover_points<T: SignedNum>(start: Point<T>, end: Point<T>) -> Vec<Point<T>> {
+    ThickSupercover::new(start, end).collect()
+}
+
+/// Get the voxels of a [`Bresenham3d`] line.
+///
+/// [`Bresenham3d`]: struct.Bresenham3d.html
+#[inline]
+pub fn bresenham_3d_points<T: SignedNum>(start: Voxel<T>, end: Voxel<T>) -> Vec<Voxel<T>> {
+    Bresenham3d::new(start, end).collect()
+}
+
+/// Get the voxels of a [`WalkVoxels`] line.
+///
+/// [`WalkVoxels`]: struct.WalkVoxels.html
+#[inline]
+pub fn walk_voxels_points<I: RealNum, O: SignedNum>(
+    start: Voxel<I>,
+    end: Voxel<I>,
+    origin: VoxelOrigin,
+) -> Vec<Voxel<O>> {
+    WalkVoxels::new(start, end, origin).collect()
+}
+
+/// Get the points of a [`BresenhamCircle`], in order around the circle.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::circle_points;
+///
+/// fn main() {
+///     println!("{:?}", circle_points((0, 0), 1));
+/// }
+/// ```
+///
+/// ```text
+/// [(1, 0), (0, 1), (-1, 0), (0, -1)]
+/// ```
+///
+/// [`BresenhamCircle`]: struct.BresenhamCircle.html
+#[inline]
+pub fn circle_points<T: SignedNum>(center: Point<T>, radius: T) -> Vec<Point<T>> {
+    BresenhamCircle::contiguous(center.0, center.1, radius).collect()
+}
+
+/// Get the points of a [`MidpointEllipse`], in order around the ellipse.
+///
+/// [`MidpointEllipse`]: struct.MidpointEllipse.html
+#[inline]
+pub fn ellipse_points<T: SignedNum>(center: Point<T>, radius: Point<T>) -> Vec<Point<T>> {
+    MidpointEllipse::new(center.0, center.1, radius.0, radius.1).collect()
+}
+
+/// Sort any points into rows from the top, joining the points next to each other on each row into
+/// spans of `(y, (start_x, end_x))`.
+///
+/// This is for scanline renderers that need the points of lines or other shapes a row at a time.
+/// Repeated points are only counted once.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{sorted_spans, BresenhamCircle};
+///
+/// fn main() {
+///     for (y, (start, end)) in sorted_spans(BresenhamCircle::new(0, 0, 2)) {
+///         println!("{}: {}..={}", y, start, end);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// -2: -1..=1
+/// -1: -2..=-2
+/// -1: 2..=2
+/// 0: -2..=-2
+/// 0: 2..=2
+/// 1: -2..=-2
+/// 1: 2..=2
+/// 2: -1..=1
+/// ```
+#[inline]
+pub fn sorted_spans<T: SignedNum>(
+    points: impl IntoIterator<Item = Point<T>>,
+) -> Vec<(T, Point<T>)> {
+    let mut points = points.into_iter().collect::<Vec<_>>();
+    points.sort_unstable_by_key(|&(x, y)| (y, x));
+    points.dedup();
+
+    let mut spans: Vec<(T, Point<T>)> = Vec::new();
+
+    for (x, y) in points {
+        match spans.last_mut() {
+            Some(&mut (row, (_, ref mut end))) if row == y && *end + T::one() == x => *end = x,
+            _ => spans.push((y, (x, x))),
+        }
+    }
+
+    spans
+}
+
+#[test]
+fn tests() {
+    assert_eq!(
+        bresenham_points((3, -1), (-4, 6)),
+        Bresenham::new((3, -1), (-4, 6)).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        midpoint_points::<f32, i32>((0.5, 1.5), (7.5, -2.5)),
+        Midpoint::new((0.5, 1.5), (7.5, -2.5)).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        supercover_points((0, 0), (4, 4)),
+        Supercover::new((0, 0), (4, 4)).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        walk_voxels_points::<f64, i32>((0.0, 0.0, 0.0), (2.0, -3.0, 1.0), VoxelOrigin::Center),
+        WalkVoxels::new((0.0, 0.0, 0.0), (2.0, -3.0, 1.0), VoxelOrigin::Center)
+            .collect::<Vec<_>>()
+    );
+
+    let circle = circle_points((5, 5), 10);
+    assert_eq!(circle.len(), BresenhamCircle::new(5, 5, 10).len());
+    assert_eq!(
+        ellipse_points((0, 0), (1, 1)),
+        [(1, 0), (0, 1), (-1, 0), (0, -1)]
+    );
+
+    assert_eq!(
+        sorted_spans(vec![(3, 1), (0, 0), (1, 0), (5, 1), (4, 1), (1, 0), (-2, 0)]),
+        [(0, (-2, -2)), (0, (0, 1)), (1, (3, 5))]
+    );
+    assert!(sorted_spans(Vec::<Point<i8>>::new()).is_empty());
+
+    let spans = sorted_spans(BresenhamCircle::new(0, 0, 7));
+    let mut points = spans
+        .iter()
+        .flat_map(|&(y, (start, end))| (start..=end).map(move |x| (x, y)))
+        .collect::<Vec<_>>();
+    let mut circle = circle_points((0, 0), 7);
+    points.sort();
+    circle.sort();
+    circle.dedup();
+    assert_eq!(points, circle);
+}
@@ -0,0 +1,98 @@
+/// An iterator that returns how far along the line each point is, as well as the point.
+///
+/// The fraction goes from `0.0` at the first point to `1.0` at the last, spaced evenly by the
+/// number of points, which is what's needed to reveal a line over time or color it with a
+/// gradient. A line with only one point gives `0.0` for it. The total is taken from the length of
+/// the iterator when it's created, so it works with any [`ExactSizeIterator`], including the 3D
+/// algorithms.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{Bresenham, Progress};
+///
+/// fn main() {
+///     for ((x, y), t) in Progress::new(Bresenham::new((0, 0), (4, 1))) {
+///         print!("(({}, {}), {}), ", x, y, t);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// ((0, 0), 0), ((1, 0), 0.25), ((2, 0), 0.5), ((3, 0), 0.75), ((4, 1), 1),
+/// ```
+///
+/// [`ExactSizeIterator`]: https://doc.rust-lang.org/core/iter/trait.ExactSizeIterator.html
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Progress<I> {
+    iterator: I,
+    index: usize,
+    last: usize,
+}
+
+impl<I: ExactSizeIterator> Progress<I> {
+    #[inline]
+    pub fn new(iterator: I) -> Self {
+        Self {
+            last: iterator.len().saturating_sub(1),
+            iterator,
+            index: 0,
+        }
+    }
+}
+
+impl<I: ExactSizeIterator> Iterator for Progress<I> {
+    type Item = (I::Item, f32);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.iterator.next()?;
+
+        let fraction = if self.last == 0 {
+            0.0
+        } else {
+            self.index as f32 / self.last as f32
+        };
+
+        self.index += 1;
+
+        Some((item, fraction))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iterator.size_hint()
+    }
+}
+
+impl<I: ExactSizeIterator> ExactSizeIterator for Progress<I> {}
+
+#[test]
+fn tests() {
+    use {Bresenham, Bresenham3d};
+
+    let fractions = |progress: Progress<Bresenham<i32>>| {
+        progress.map(|(_, fraction)| fraction).collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        fractions(Progress::new(Bresenham::new((0, 0), (-2, 5)))),
+        [0.0, 0.2, 0.4, 0.6, 0.8, 1.0]
+    );
+    assert_eq!(
+        fractions(Progress::new(Bresenham::new((3, 3), (3, 3)))),
+        [0.0]
+    );
+
+    // Taking points before it's created makes the rest of them the whole line
+    let mut line = Bresenham::new((0, 0), (8, 0));
+    line.nth(3);
+    assert_eq!(fractions(Progress::new(line)), [0.0, 0.25, 0.5, 0.75, 1.0]);
+
+    assert_eq!(
+        Progress::new(Bresenham3d::new((0, 0, 0), (2, 2, 2))).collect::<Vec<_>>(),
+        [((0, 0, 0), 0.0), ((1, 1, 1), 0.5), ((2, 2, 2), 1.0)]
+    );
+}
@@ -0,0 +1,219 @@
+use {Endpoints, Point};
+use steps::Steps;
+use core::ops::ControlFlow;
+
+/// A 2D line algorithm that goes from one point to another, for writing code that works with any
+/// of them.
+///
+/// `In` is the type of the end points and `Out` is the type of the points that are returned, which
+/// are only different for the algorithms that take floating-point end points, like [`Midpoint`].
+/// The trait also gives them all `steps()`, `exclude_start()` and `exclude_end()` functions. It
+/// needs to be imported to call the last two, or any of them on a generic line, but each line has
+/// its own `steps()` as well.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{Bresenham, LineRasterizer, WalkGrid};
+///
+/// fn diagonal_steps<L: LineRasterizer<i32, i32>>(start: (i32, i32), end: (i32, i32)) -> usize {
+///     L::new(start, end)
+///         .steps()
+///         .filter(|&(a, b)| a.0 != b.0 && a.1 != b.1)
+///         .count()
+/// }
+///
+/// fn main() {
+///     println!("{}", diagonal_steps::<Bresenham<_>>((0, 0), (5, 3)));
+///     println!("{}", diagonal_steps::<WalkGrid<_>>((0, 0), (5, 3)));
+/// }
+/// ```
+///
+/// ```text
+/// 3
+/// 0
+/// ```
+///
+/// [`Midpoint`]: struct.Midpoint.html
+pub trait LineRasterizer<In, Out>: Iterator<Item = Point<Out>> + Sized {
+    /// Start a line from `start` to `end`.
+    fn new(start: Point<In>, end: Point<In>) -> Self;
+
+    /// Turn the line into a [`Steps`] iterator.
+    ///
+    /// [`Steps`]: steps/struct.Steps.html
+    #[inline]
+    fn steps(self) -> Steps<Point<Out>, Self>
+    where
+        Out: Copy,
+    {
+        Steps::new(self)
+    }
+
+    /// Leave out the first point of the line.
+    ///
+    /// See [`Endpoints`] for details.
+    ///
+    /// [`Endpoints`]: struct.Endpoints.html
+    #[inline]
+    fn exclude_start(self) -> Endpoints<Self> {
+        Endpoints::new(self, false, true)
+    }
+
+    /// Leave out the last point of the line.
+    ///
+    /// See [`Endpoints`] for details.
+    ///
+    /// [`Endpoints`]: struct.Endpoints.html
+    #[inline]
+    fn exclude_end(self) -> Endpoints<Self> {
+        Endpoints::new(self, true, false)
+    }
+
+    /// Call a function with each point of a line from `start` to `end`, stopping early if it
+    /// returns [`ControlFlow::Break`].
+    ///
+    /// The value it breaks with is returned, which makes things like finding the first wall along
+    /// a line simple. [`Bresenham`], [`WalkGrid`] and [`Supercover`] go through the points faster
+    /// this way than with the iterator, and the rest use [`Iterator::try_for_each`]. The 3D lines
+    /// have the same thing in [`Bresenham3d::walk`] and [`WalkVoxels::walk`].
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// extern crate line_drawing;
+    /// use line_drawing::{Bresenham, LineRasterizer};
+    /// use std::ops::ControlFlow;
+    ///
+    /// fn main() {
+    ///     let wall = Bresenham::walk((0, 0), (6, 3), |(x, y)| {
+    ///         if x + y >= 5 {
+    ///             ControlFlow::Break((x, y))
+    ///         } else {
+    ///             ControlFlow::Continue(())
+    ///         }
+    ///     });
+    ///
+    ///     println!("{:?}", wall);
+    /// }
+    /// ```
+    ///
+    /// ```text
+    /// Break((4, 2))
+    /// ```
+    ///
+    /// [`ControlFlow::Break`]: https://doc.rust-lang.org/core/ops/enum.ControlFlow.html
+    /// [`Bresenham`]: struct.Bresenham.html
+    /// [`WalkGrid`]: struct.WalkGrid.html
+    /// [`Supercover`]: struct.Supercover.html
+    /// [`Iterator::try_for_each`]: https://doc.rust-lang.org/core/iter/trait.Iterator.html#method.try_for_each
+    /// [`Bresenham3d::walk`]: struct.Bresenham3d.html#method.walk
+    /// [`WalkVoxels::walk`]: struct.WalkVoxels.html#method.walk
+    #[inline]
+    fn walk<B, F>(start: Point<In>, end: Point<In>, function: F) -> ControlFlow<B>
+    where
+        F: FnMut(Point<Out>) -> ControlFlow<B>,
+    {
+        Self::new(start, end).try_for_each(function)
+    }
+
+    /// Collect up to `N` points of the line into an array, along with how many of them were
+    /// filled in.
+    ///
+    /// The rest of the array is left as `(0, 0)`, and any points after the first `N` are left out,
+    /// so a count of `N` can mean that the line was cut short. It needs no allocation, for holding
+    /// on to short lines in hot paths.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// extern crate line_drawing;
+    /// use line_drawing::{Bresenham, LineRasterizer};
+    ///
+    /// fn main() {
+    ///     let (points, count) = Bresenham::new((0, 0), (2, 1)).to_array::<4>();
+    ///     println!("{:?}", &points[..count]);
+    /// }
+    /// ```
+    ///
+    /// ```text
+    /// [(0, 0), (1, 0), (2, 1)]
+    /// ```
+    #[inline]
+    fn to_array<const N: usize>(self) -> ([Point<Out>; N], usize)
+    where
+        Out: Copy + Default,
+    {
+        let mut points = [(Out::default(), Out::default()); N];
+        let mut count = 0;
+
+        for (slot, point) in points.iter_mut().zip(self) {
+            *slot = point;
+            count += 1;
+        }
+
+        (points, count)
+    }
+}
+
+#[test]
+fn tests() {
+    use {Bresenham, HexLine, HexSupercover, Midpoint, Supercover, ThickSupercover, WalkGrid};
+
+    fn line<L: LineRasterizer<i32, i32>>() -> Vec<(i32, i32)> {
+        L::new((-2, 5), (9, -1)).collect()
+    }
+
+    assert_eq!(
+        line::<Bresenham<_>>(),
+        Bresenham::new((-2, 5), (9, -1)).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        line::<WalkGrid<_>>(),
+        WalkGrid::new((-2, 5), (9, -1)).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        line::<Supercover<_>>(),
+        Supercover::new((-2, 5), (9, -1)).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        line::<ThickSupercover<_>>(),
+        ThickSupercover::new((-2, 5), (9, -1)).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        line::<HexLine<_>>(),
+        HexLine::new((-2, 5), (9, -1)).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        line::<HexSupercover<_>>(),
+        HexSupercover::new((-2, 5), (9, -1)).collect::<Vec<_>>()
+    );
+
+    // Walking stops at the first point it's told to
+    assert_eq!(
+        Supercover::walk((0, 0), (5, 3), |point| if point.0 >= point.1 + 2 {
+            ControlFlow::Break(point)
+        } else {
+            ControlFlow::Continue(())
+        }),
+        ControlFlow::Break((4, 2))
+    );
+
+    assert_eq!(
+        <Midpoint<f32, i32> as LineRasterizer<_, _>>::new((0.0, 0.0), (2.0, 1.0))
+            .steps()
+            .collect::<Vec<_>>(),
+        [((0, 0), (1, 1)), ((1, 1), (2, 1))]
+    );
+
+    let (points, count) = WalkGrid::new((0, 0), (2, -1)).to_array::<6>();
+    assert_eq!(count, 4);
+    assert_eq!(points, [(0, 0), (1, 0), (1, -1), (2, -1), (0, 0), (0, 0)]);
+
+    // Lines that don't fit are cut short
+    let mut line = Bresenham::new((0, 0), (9, 0));
+    assert_eq!(line.by_ref().take(2).count(), 2);
+    assert_eq!(line.to_array::<3>(), ([(2, 0), (3, 0), (4, 0)], 3));
+    assert_eq!(Supercover::new((1, 1), (1, 1)).to_array::<0>(), ([], 0));
+}
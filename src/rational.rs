@@ -0,0 +1,129 @@
+use {RealNum, SignedNum};
+use num_rational::Ratio;
+use num_traits::{Float, NumCast, Signed};
+
+macro_rules! real_num_ratio {
+    ($($type:ty),*) => {
+        $(
+            impl RealNum for Ratio<$type> {
+                /// Convert whole numbers exactly, and anything else to the closest fraction that
+                /// fits.
+                #[inline]
+                fn cast<T: NumCast>(value: T) -> Self {
+                    let float = value.to_f64().unwrap();
+
+                    if Float::fract(float) == 0.0 {
+                        Ratio::from_integer(<$type as NumCast>::from(value).unwrap())
+                    } else {
+                        Ratio::approximate_float(float).unwrap()
+                    }
+                }
+
+                #[inline]
+                fn to_int<O: SignedNum>(self) -> O {
+                    O::cast(self.to_integer())
+                }
+
+                #[inline]
+                fn floor(self) -> Self {
+                    Ratio::floor(&self)
+                }
+
+                #[inline]
+                fn ceil(self) -> Self {
+                    Ratio::ceil(&self)
+                }
+
+                #[inline]
+                fn round(self) -> Self {
+                    Ratio::round(&self)
+                }
+
+                #[inline]
+                fn abs(self) -> Self {
+                    Signed::abs(&self)
+                }
+
+                #[inline]
+                fn is_finite(self) -> bool {
+                    true
+                }
+            }
+        )*
+    };
+}
+
+real_num_ratio!(i8, i16, i32, i64, isize);
+
+#[test]
+fn tests() {
+    use {Midpoint, VoxelOrigin, WalkVoxels, XiaolinWu};
+
+    let ratio = |numer, denom| Ratio::<i64>::new(numer, denom);
+
+    assert_eq!(Ratio::<i64>::cast(0.5), ratio(1, 2));
+    assert_eq!(Ratio::<i64>::cast(-0.375f32), ratio(-3, 8));
+    assert_eq!(Ratio::<i64>::cast(i64::MAX), ratio(i64::MAX, 1));
+    assert_eq!(Ratio::<i32>::cast(7u8), Ratio::from_integer(7));
+    assert_eq!(ratio(-7, 2).to_int::<i32>(), -3);
+    assert_eq!(RealNum::round(ratio(-5, 2)), ratio(-3, 1));
+    assert_eq!(RealNum::floor(ratio(-1, 3)), ratio(-1, 1));
+    assert_eq!(RealNum::ceil(ratio(1, 3)), ratio(1, 1));
+    assert_eq!(RealNum::abs(ratio(-4, 9)), ratio(4, 9));
+
+    // The algorithms give the same points as with floats, for end points that floats hold exactly
+    let ends = [
+        ((0.25, 0.5), (2.75, 7.75)),
+        ((-4.5, 3.125), (9.0, -1.0)),
+        ((6.0, 3.0), (0.0, 0.0)),
+        ((1.5, -2.5), (1.5, 8.5)),
+    ];
+
+    for &(start, end) in &ends {
+        let exact = |(x, y): (f64, f64)| (Ratio::<i64>::cast(x), Ratio::cast(y));
+
+        assert_eq!(
+            Midpoint::<Ratio<i64>, i32>::new(exact(start), exact(end)).collect::<Vec<_>>(),
+            Midpoint::<f64, i32>::new(start, end).collect::<Vec<_>>()
+        );
+
+        let voxel = |(x, y): (f64, f64)| (x, y, x - y);
+        let exact_voxel = |point| {
+            let (x, y, z) = voxel(point);
+            (Ratio::<i64>::cast(x), Ratio::cast(y), Ratio::cast(z))
+        };
+
+        for &origin in &[VoxelOrigin::Center, VoxelOrigin::Corner] {
+            assert_eq!(
+                WalkVoxels::<Ratio<i64>, i32>::new(exact_voxel(start), exact_voxel(end), origin)
+                    .collect::<Vec<_>>(),
+                WalkVoxels::<f64, i32>::new(voxel(start), voxel(end), origin).collect::<Vec<_>>()
+            );
+        }
+    }
+
+    // Thirds add up to exactly one, so the middle of the line lands right on a point and only
+    // covers that one
+    let line = XiaolinWu::<Ratio<i64>, i32>::from_coords((0, 0), (6, 2));
+    assert_eq!(
+        line.collect::<Vec<_>>(),
+        [
+            ((0, 0), ratio(1, 1)),
+            ((1, 0), ratio(2, 3)),
+            ((1, 1), ratio(1, 3)),
+            ((2, 0), ratio(1, 3)),
+            ((2, 1), ratio(2, 3)),
+            ((3, 1), ratio(1, 1)),
+            ((4, 1), ratio(2, 3)),
+            ((4, 2), ratio(1, 3)),
+            ((5, 1), ratio(1, 3)),
+            ((5, 2), ratio(2, 3)),
+            ((6, 2), ratio(1, 1)),
+        ]
+    );
+
+    assert!(Midpoint::<Ratio<i32>, i8>::try_new(
+        (Ratio::from_integer(0), Ratio::from_integer(0)),
+        (Ratio::from_integer(200), Ratio::from_integer(0))
+    ).is_err());
+}
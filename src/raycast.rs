@@ -0,0 +1,185 @@
+use {FloatNum, IntoPoint, Point, SignedNum};
+
+/// Which kind of grid line a ray crossed to enter a cell.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Side {
+    /// A vertical line, where the `x` coordinate changes.
+    X,
+    /// A horizontal line, where the `y` coordinate changes.
+    Y,
+}
+
+/// A cell that a [`GridRay`] entered.
+///
+/// [`GridRay`]: struct.GridRay.html
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RayHit<I, O> {
+    /// The cell that was entered.
+    pub cell: Point<O>,
+    /// The side of the cell that the ray came through.
+    pub side: Side,
+    /// How far along the ray the cell was entered, in multiples of the direction vector.
+    pub distance: I,
+}
+
+/// Cast a ray through a grid using the [DDA algorithm] from grid-based raycasting renderers.
+///
+/// Starting from a floating-point `origin`, where cell `(x, y)` covers the area from `(x, y)` to
+/// `(x + 1, y + 1)`, this returns every cell that the ray enters along with the side it came
+/// through and the distance to it. The cell containing the origin isn't returned. The ray goes on
+/// forever, so it's up to the caller to stop, for example by using `find` to look for a wall or
+/// `take_while` to limit the distance.
+///
+/// The distance is measured in multiples of `direction`, so if the direction for each column of the
+/// screen is `camera_direction + camera_plane * camera_x`, it's the perpendicular distance to the
+/// camera plane that's needed to draw walls without a fisheye effect.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::GridRay;
+///
+/// fn main() {
+///     for hit in GridRay::<f32, i8>::new((0.5, 0.5), (1.0, 0.5)).take(4) {
+///         print!("({:?}, {:?}, {}), ", hit.cell, hit.side, hit.distance);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// ((1, 0), X, 0.5), ((1, 1), Y, 1), ((2, 1), X, 1.5), ((3, 1), X, 2.5),
+/// ```
+///
+/// [DDA algorithm]: https://lodev.org/cgtutor/raycasting.html
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GridRay<I, O> {
+    cell: Point<O>,
+    step: Point<O>,
+    side_distance: Point<I>,
+    delta_distance: Point<I>,
+}
+
+impl<I: FloatNum, O: SignedNum> GridRay<I, O> {
+    #[inline]
+    pub fn new(origin: impl IntoPoint<I>, direction: impl IntoPoint<I>) -> Self {
+        let (origin, direction) = (origin.into_point(), direction.into_point());
+
+        let cell = (origin.0.floor(), origin.1.floor());
+
+        let axis = |origin: I, cell: I, direction: I| {
+            if direction < I::zero() {
+                (-O::one(), (origin - cell) / -direction, -direction.recip())
+            } else if direction > I::zero() {
+                (
+                    O::one(),
+                    (cell + I::one() - origin) / direction,
+                    direction.recip(),
+                )
+            } else {
+                (O::zero(), I::infinity(), I::infinity())
+            }
+        };
+
+        let (step_x, side_x, delta_x) = axis(origin.0, cell.0, direction.0);
+        let (step_y, side_y, delta_y) = axis(origin.1, cell.1, direction.1);
+
+        Self {
+            cell: (O::cast(cell.0), O::cast(cell.1)),
+            step: (step_x, step_y),
+            side_distance: (side_x, side_y),
+            delta_distance: (delta_x, delta_y),
+        }
+    }
+}
+
+impl<I: FloatNum, O: SignedNum> Iterator for GridRay<I, O> {
+    type Item = RayHit<I, O>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (side, distance) = if self.side_distance.0 < self.side_distance.1 {
+            self.cell.0 += self.step.0;
+            let distance = self.side_distance.0;
+            self.side_distance.0 += self.delta_distance.0;
+            (Side::X, distance)
+        } else if self.side_distance.1.is_finite() {
+            self.cell.1 += self.step.1;
+            let distance = self.side_distance.1;
+            self.side_distance.1 += self.delta_distance.1;
+            (Side::Y, distance)
+        } else {
+            // A zero direction doesn't go anywhere
+            return None;
+        };
+
+        Some(RayHit {
+            cell: self.cell,
+            side,
+            distance,
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.side_distance.0.is_finite() || self.side_distance.1.is_finite() {
+            (usize::MAX, None)
+        } else {
+            (0, Some(0))
+        }
+    }
+}
+
+#[test]
+fn tests() {
+    let cast = |origin, direction, count| {
+        GridRay::<f64, i32>::new(origin, direction)
+            .take(count)
+            .map(|hit| (hit.cell, hit.side, hit.distance))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        cast((0.5, 0.5), (1.0, 0.0), 3),
+        [
+            ((1, 0), Side::X, 0.5),
+            ((2, 0), Side::X, 1.5),
+            ((3, 0), Side::X, 2.5)
+        ]
+    );
+
+    assert_eq!(
+        cast((2.25, 3.5), (0.0, -2.0), 3),
+        [
+            ((2, 2), Side::Y, 0.25),
+            ((2, 1), Side::Y, 0.75),
+            ((2, 0), Side::Y, 1.25)
+        ]
+    );
+
+    assert_eq!(
+        cast((0.5, 0.5), (-1.0, 0.5), 4),
+        [
+            ((-1, 0), Side::X, 0.5),
+            ((-1, 1), Side::Y, 1.0),
+            ((-2, 1), Side::X, 1.5),
+            ((-3, 1), Side::X, 2.5)
+        ]
+    );
+
+    assert_eq!(cast((0.5, 0.5), (0.0, 0.0), 3), []);
+
+    // Find the first wall in a small map
+    let map = ["#####", "#...#", "#..##", "#####"];
+    let wall = |(x, y): Point<i32>| map[y as usize].as_bytes()[x as usize] == b'#';
+
+    let hit = GridRay::<f64, i32>::new((1.5, 1.5), (1.0, 0.6))
+        .find(|hit| wall(hit.cell))
+        .unwrap();
+
+    assert_eq!(hit.cell, (3, 2));
+    assert_eq!(hit.side, Side::X);
+}
@@ -0,0 +1,96 @@
+/// An iterator that can be reset back to where it started, to go through the same line again.
+///
+/// A copy of the iterator is kept from when it was created, so resetting it is only a copy and
+/// none of the setup work of the line is done again. This is handy for things like particles that
+/// follow the same line every frame. It works with any iterator that can be cloned, which includes
+/// all of the lines in this crate.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{Bresenham, Replay};
+///
+/// fn main() {
+///     let mut line = Replay::new(Bresenham::new((0, 0), (4, 2)));
+///
+///     for frame in 0..2 {
+///         print!("{}: ", frame);
+///
+///         for (x, y) in line.by_ref() {
+///             print!("({}, {}), ", x, y);
+///         }
+///
+///         println!();
+///         line.reset();
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// 0: (0, 0), (1, 0), (2, 1), (3, 1), (4, 2),
+/// 1: (0, 0), (1, 0), (2, 1), (3, 1), (4, 2),
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Replay<I> {
+    start: I,
+    iterator: I,
+}
+
+impl<I: Iterator + Clone> Replay<I> {
+    #[inline]
+    pub fn new(iterator: I) -> Self {
+        Self {
+            start: iterator.clone(),
+            iterator,
+        }
+    }
+
+    /// Go back to the start, so the same items are returned again.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.iterator.clone_from(&self.start);
+    }
+}
+
+impl<I: Iterator + Clone> Iterator for Replay<I> {
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iterator.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iterator.size_hint()
+    }
+}
+
+impl<I: ExactSizeIterator + Clone> ExactSizeIterator for Replay<I> {}
+
+#[test]
+fn tests() {
+    use {Bresenham, VoxelOrigin, WalkVoxels};
+
+    let mut line = Replay::new(Bresenham::new((3, -2), (-5, 7)));
+    let points = line.by_ref().take(4).collect::<Vec<_>>();
+    assert_eq!(line.len(), 6);
+
+    line.reset();
+    assert_eq!(line.len(), 10);
+    assert_eq!(line.by_ref().take(4).collect::<Vec<_>>(), points);
+
+    // Resetting after the end starts over too
+    let mut walk = Replay::new(WalkVoxels::<f32, i32>::new(
+        (0.5, 0.2, -1.0),
+        (3.1, -2.4, 0.7),
+        VoxelOrigin::Center,
+    ));
+    let voxels = walk.by_ref().collect::<Vec<_>>();
+    assert_eq!(walk.next(), None);
+
+    walk.reset();
+    assert_eq!(walk.collect::<Vec<_>>(), voxels);
+}
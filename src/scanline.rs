@@ -0,0 +1,441 @@
+use {FillRule, IntoPoint, Point, SignedNum};
+use alloc::vec::Vec;
+use core::slice;
+use num_traits::{One, Zero};
+
+/// One edge of a shape in a [`ScanlineBatch`], going down from `top` to `bottom`.
+///
+/// [`ScanlineBatch`]: struct.ScanlineBatch.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct Edge<T> {
+    shape: usize,
+    top: Point<T>,
+    bottom: Point<T>,
+    /// Whether the edge goes down (`1`) or up (`-1`) in the shape, for the non-zero fill rule.
+    winding: i32,
+}
+
+/// An edge that the current row goes through, with where it crosses the row kept as a whole
+/// number of cells and the remainder of a fraction over the edge's height.
+#[derive(Clone, Copy, Debug)]
+struct ActiveEdge<T: SignedNum> {
+    edge: Edge<T>,
+    x: T::Wide,
+    remainder: T::Wide,
+    step: T::Wide,
+    step_remainder: T::Wide,
+    height: T::Wide,
+}
+
+impl<T: SignedNum> ActiveEdge<T> {
+    #[inline]
+    fn new(edge: Edge<T>) -> Self {
+        let height = (edge.bottom.1 - edge.top.1).widen();
+        let (step, step_remainder) = if height.is_zero() {
+            (T::Wide::zero(), T::Wide::zero())
+        } else {
+            div_floor((edge.bottom.0 - edge.top.0).widen(), height)
+        };
+
+        Self {
+            edge,
+            x: edge.top.0.widen(),
+            remainder: T::Wide::zero(),
+            step,
+            step_remainder,
+            height,
+        }
+    }
+
+    /// Move down to the next row.
+    #[inline]
+    fn advance(&mut self) {
+        self.x += self.step;
+        self.remainder += self.step_remainder;
+
+        if self.remainder >= self.height {
+            self.x += T::Wide::one();
+            self.remainder -= self.height;
+        }
+    }
+}
+
+#[inline]
+fn div_floor<T: SignedNum>(value: T, divisor: T) -> (T, T) {
+    let mut quotient = value / divisor;
+    let mut remainder = value % divisor;
+
+    if remainder < T::zero() {
+        quotient -= T::one();
+        remainder += divisor;
+    }
+
+    (quotient, remainder)
+}
+
+/// Fill lots of polygons at once with an active edge table, a row at a time from the top.
+///
+/// The edges of all the shapes go into one table sorted by their top row. Going down the rows,
+/// the edges that start on a row are added to a list of active ones, and the ones that have ended
+/// are taken out, so each row only looks at the edges that actually cross it. Where each edge
+/// crosses the row is moved along with a couple of additions, like [`Bresenham`], so setting up an
+/// edge is only done once for the whole batch instead of once a row. This is much faster than the
+/// per-shape iterators like [`Triangle::spans`] for filling hundreds of shapes, like the lights on
+/// a tile map.
+///
+/// A cell is filled when its center is inside a shape or right on one of its edges. That's the
+/// same as [`Triangle::fill`] and [`Rect::fill`] for edges that are horizontal, vertical or
+/// diagonal, but it doesn't include all of the cells that a sloped edge passes through. The
+/// shapes can be any polygon, including ones with holes or that cross over themselves, which are
+/// filled in with a [`FillRule`].
+///
+/// This needs the `alloc` feature.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{FillRule, ScanlineBatch};
+///
+/// fn main() {
+///     let mut batch = ScanlineBatch::new();
+///     batch.polygon(vec![(0, 0), (6, 0), (0, 3)]);
+///     batch.polygon(vec![(3, 2), (5, 2), (5, 4), (3, 4)]);
+///
+///     for (shape, (y, (start, end))) in batch.spans(FillRule::NonZero) {
+///         println!("{}: {}: {}..={}", y, shape, start, end);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// 0: 0: 0..=6
+/// 1: 0: 0..=4
+/// 2: 0: 0..=2
+/// 2: 1: 3..=5
+/// 3: 0: 0..=0
+/// 3: 1: 3..=5
+/// 4: 1: 3..=5
+/// ```
+///
+/// [`Bresenham`]: struct.Bresenham.html
+/// [`Triangle::spans`]: struct.Triangle.html#method.spans
+/// [`Triangle::fill`]: struct.Triangle.html#method.fill
+/// [`Rect::fill`]: struct.Rect.html#method.fill
+/// [`FillRule`]: enum.FillRule.html
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ScanlineBatch<T> {
+    edges: Vec<Edge<T>>,
+    shapes: usize,
+    unsorted: bool,
+}
+
+impl<T: SignedNum> ScanlineBatch<T> {
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            edges: Vec::new(),
+            shapes: 0,
+            unsorted: false,
+        }
+    }
+
+    /// How many shapes have been added.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.shapes
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.shapes == 0
+    }
+
+    /// Take all of the shapes out, keeping the memory to fill the next batch with.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.edges.clear();
+        self.shapes = 0;
+        self.unsorted = false;
+    }
+
+    /// Add a polygon, with an edge from the last point back to the first, and return its index.
+    ///
+    /// The index is what the polygon's spans are returned with. Every call adds a shape, even if
+    /// it doesn't have any points.
+    pub fn polygon<P: IntoPoint<T>>(&mut self, points: impl IntoIterator<Item = P>) -> usize {
+        let shape = self.shapes;
+        self.shapes += 1;
+
+        let mut points = points.into_iter().map(IntoPoint::into_point);
+
+        let first = match points.next() {
+            Some(point) => point,
+            None => return shape,
+        };
+
+        let last = points.fold(first, |previous, point| {
+            self.edge(shape, previous, point);
+            point
+        });
+
+        self.edge(shape, last, first);
+        shape
+    }
+
+    /// Fill all of the shapes, a row at a time from the top, as `(shape, (y, (start_x, end_x)))`.
+    ///
+    /// The spans on each row are sorted by the shape they're from, and then from left to right.
+    /// Shapes that overlap each other are kept apart, so their spans can overlap too.
+    #[inline]
+    pub fn spans(&mut self, rule: FillRule) -> BatchSpans<'_, T> {
+        if self.unsorted {
+            self.edges.sort_by_key(|edge| edge.top.1);
+            self.unsorted = false;
+        }
+
+        BatchSpans {
+            edges: self.edges.iter().peekable(),
+            rule,
+            y: T::zero(),
+            active: Vec::new(),
+            crossings: Vec::new(),
+            spans: Vec::new(),
+            span: 0,
+        }
+    }
+
+    #[inline]
+    fn edge(&mut self, shape: usize, start: Point<T>, end: Point<T>) {
+        let (top, bottom, winding) = if (start.1, start.0) <= (end.1, end.0) {
+            (start, end, 1)
+        } else {
+            (end, start, -1)
+        };
+
+        if let Some(last) = self.edges.last() {
+            self.unsorted |= last.top.1 > top.1;
+        }
+
+        self.edges.push(Edge {
+            shape,
+            top,
+            bottom,
+            winding,
+        });
+    }
+}
+
+/// The spans of all the shapes in a [`ScanlineBatch`], made with [`ScanlineBatch::spans`].
+///
+/// [`ScanlineBatch`]: struct.ScanlineBatch.html
+/// [`ScanlineBatch::spans`]: struct.ScanlineBatch.html#method.spans
+#[derive(Clone, Debug)]
+pub struct BatchSpans<'a, T: SignedNum + 'a> {
+    edges: core::iter::Peekable<slice::Iter<'a, Edge<T>>>,
+    rule: FillRule,
+    y: T,
+    active: Vec<ActiveEdge<T>>,
+    /// Where the edges cross the current row, as `(shape, x, whether it's past x, winding)`.
+    crossings: Vec<(usize, T::Wide, bool, i32)>,
+    spans: Vec<(usize, Point<T::Wide>)>,
+    span: usize,
+}
+
+impl<'a, T: SignedNum> BatchSpans<'a, T> {
+    /// Work out the spans of the current row.
+    fn row(&mut self) {
+        let y = self.y;
+        self.crossings.clear();
+        self.spans.clear();
+        self.span = 0;
+
+        for active in &self.active {
+            let edge = active.edge;
+
+            if edge.top.1 == edge.bottom.1 {
+                // Horizontal edges don't cross the row, they're part of it
+                let (start, end) = (edge.top.0.widen(), edge.bottom.0.widen());
+                self.spans.push((edge.shape, (start.min(end), start.max(end))));
+                continue;
+            }
+
+            // A cell that an edge goes right through the middle of is on the edge, which includes
+            // the bottom row that isn't counted as a crossing
+            if active.remainder.is_zero() {
+                self.spans.push((edge.shape, (active.x, active.x)));
+            }
+
+            // Each edge only crosses from its top row up to but not including its bottom row, so
+            // the corners between two edges aren't counted twice
+            if y < edge.bottom.1 {
+                let past = !active.remainder.is_zero();
+                self.crossings.push((edge.shape, active.x, past, edge.winding));
+            }
+        }
+
+        // Which of the crossings are exactly on a cell doesn't matter for the order, since that's
+        // only needed to know which cells are between them
+        self.crossings.sort_unstable_by_key(|&(shape, x, past, _)| (shape, x, past));
+
+        let mut winding = 0;
+
+        for (index, &(shape, x, past, direction)) in self.crossings.iter().enumerate() {
+            winding += direction;
+
+            let inside = match self.rule {
+                FillRule::NonZero => winding != 0,
+                FillRule::EvenOdd => winding % 2 != 0,
+            };
+
+            match self.crossings.get(index + 1) {
+                Some(&(next_shape, next_x, _, _)) if next_shape == shape => {
+                    // Crossings that are close together can both be between the same two cells
+                    let start = if past { x + T::Wide::one() } else { x };
+
+                    if inside && start <= next_x {
+                        self.spans.push((shape, (start, next_x)));
+                    }
+                }
+                _ => winding = 0,
+            }
+        }
+
+        // Join up the spans that touch, now that they're all known
+        self.spans.sort_unstable();
+
+        let mut joined = 0;
+
+        for index in 0..self.spans.len() {
+            let (shape, (start, end)) = self.spans[index];
+
+            match self.spans[..joined].last_mut() {
+                Some(&mut (last_shape, (_, ref mut last_end)))
+                    if last_shape == shape && start <= *last_end + T::Wide::one() =>
+                {
+                    *last_end = (*last_end).max(end);
+                }
+                _ => {
+                    self.spans[joined] = (shape, (start, end));
+                    joined += 1;
+                }
+            }
+        }
+
+        self.spans.truncate(joined);
+    }
+}
+
+impl<'a, T: SignedNum> Iterator for BatchSpans<'a, T> {
+    type Item = (usize, (T, Point<T>));
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(&(shape, (start, end))) = self.spans.get(self.span) {
+                self.span += 1;
+
+                // The spans are all on edges, so they fit
+                return Some((shape, (self.y, (T::cast(start), T::cast(end)))));
+            }
+
+            // Move down to the next row, skipping straight to the next edge if there's a gap
+            if self.active.is_empty() {
+                self.y = self.edges.peek()?.top.1;
+            } else {
+                let y = self.y;
+                self.active.retain(|active| active.edge.bottom.1 > y);
+                self.active.iter_mut().for_each(ActiveEdge::advance);
+                self.y += T::one();
+            }
+
+            let y = self.y;
+
+            while let Some(edge) = self.edges.next_if(|edge| edge.top.1 == y) {
+                self.active.push(ActiveEdge::new(*edge));
+            }
+
+            if self.active.is_empty() {
+                continue;
+            }
+
+            self.row();
+        }
+    }
+}
+
+#[test]
+fn tests() {
+    use {Rect, Triangle};
+
+    let spans = |batch: &mut ScanlineBatch<i32>, rule| batch.spans(rule).collect::<Vec<_>>();
+
+    // Rectangles and triangles with straight and diagonal edges are the same as their own fills
+    let mut batch = ScanlineBatch::new();
+    assert_eq!(batch.polygon(vec![(-2, 1), (3, 1), (3, 4), (-2, 4)]), 0);
+    assert_eq!(
+        spans(&mut batch, FillRule::NonZero),
+        Rect::new((-2, 1), (3, 4)).spans().map(|span| (0, span)).collect::<Vec<_>>()
+    );
+
+    for &(a, b, c) in &[
+        ((0, 0), (5, 5), (0, 5)),
+        ((0, 0), (-4, 4), (4, 4)),
+        ((2, -3), (2, 3), (-1, 0)),
+        ((0, 0), (0, 3), (3, 0)),
+    ] {
+        batch.clear();
+        batch.polygon(vec![a, b, c]);
+        assert_eq!(
+            spans(&mut batch, FillRule::EvenOdd),
+            Triangle::new(a, b, c).spans().map(|span| (0, span)).collect::<Vec<_>>()
+        );
+    }
+
+    // Cells that a sloped edge passes through are only filled if their centers are inside
+    batch.clear();
+    batch.polygon(vec![(0, 0), (4, 1), (0, 2)]);
+    assert_eq!(
+        spans(&mut batch, FillRule::NonZero),
+        [(0, (0, (0, 0))), (0, (1, (0, 4))), (0, (2, (0, 0)))]
+    );
+
+    // Shapes that are added in any order come out a row at a time, and the ones that overlap are
+    // kept apart
+    batch.clear();
+    batch.polygon(vec![(0, 5), (2, 5), (2, 6), (0, 6)]);
+    batch.polygon(vec![(1, 0), (1, 5)]);
+    batch.polygon(Vec::<Point<i32>>::new());
+    batch.polygon(vec![(1, 5), (3, 5), (3, 6)]);
+    assert_eq!(batch.len(), 4);
+    assert_eq!(
+        spans(&mut batch, FillRule::NonZero),
+        [
+            (1, (0, (1, 1))),
+            (1, (1, (1, 1))),
+            (1, (2, (1, 1))),
+            (1, (3, (1, 1))),
+            (1, (4, (1, 1))),
+            (0, (5, (0, 2))),
+            (1, (5, (1, 1))),
+            (3, (5, (1, 3))),
+            (0, (6, (0, 2))),
+            (3, (6, (3, 3))),
+        ]
+    );
+
+    // A star that crosses over itself has a hole in the middle with even-odd, but not non-zero
+    batch.clear();
+    batch.polygon(vec![(0, 8), (4, -4), (8, 8), (-2, 0), (10, 0)]);
+    let middle = |batch: &mut ScanlineBatch<i32>, rule| -> Vec<Point<i32>> {
+        batch.spans(rule).filter(|&(_, (y, _))| y == 2).map(|(_, (_, span))| span).collect()
+    };
+    assert_eq!(middle(&mut batch, FillRule::NonZero), vec![(1, 7)]);
+    assert_eq!(middle(&mut batch, FillRule::EvenOdd), vec![(1, 2), (6, 7)]);
+
+    // An empty batch doesn't have anything to fill
+    batch.clear();
+    assert_eq!(spans(&mut batch, FillRule::NonZero), []);
+    assert!(batch.is_empty());
+}
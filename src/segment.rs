@@ -0,0 +1,218 @@
+use {rasterize, Bresenham, FloatNum, IntoPoint, Line, LineAlgorithm, Point, SignedNum, Supercover,
+     WalkGrid};
+use num_traits::{CheckedNeg, CheckedSub, ToPrimitive, Zero};
+
+/// A line between two points on the grid, to be drawn later.
+///
+/// It's just the two end points, with methods to draw it with any of the algorithms and to
+/// measure it.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::LineSegment;
+///
+/// fn main() {
+///     let segment = LineSegment::new((0, 0), (3, 2));
+///     let other = LineSegment::new((0, 2), (3, 0));
+///
+///     println!("{:?}", segment.bresenham().collect::<Vec<_>>());
+///     println!("{:?}", segment.walk_grid().collect::<Vec<_>>());
+///     println!("{} {} {}", segment.cells(), segment.length::<f32>(), segment.intersects(&other));
+/// }
+/// ```
+///
+/// ```text
+/// [(0, 0), (1, 0), (2, 1), (3, 2)]
+/// [(0, 0), (1, 0), (1, 1), (2, 1), (2, 2), (3, 2)]
+/// 4 3.6055512 true
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LineSegment<T> {
+    pub start: Point<T>,
+    pub end: Point<T>,
+}
+
+impl<T: SignedNum> LineSegment<T> {
+    #[inline]
+    pub fn new(start: impl IntoPoint<T>, end: impl IntoPoint<T>) -> Self {
+        let (start, end) = (start.into_point(), end.into_point());
+
+        Self { start, end }
+    }
+
+    /// The same segment, going the other way.
+    #[inline]
+    pub fn reversed(&self) -> Self {
+        Self::new(self.end, self.start)
+    }
+
+    /// Draw the segment with [`Bresenham`].
+    ///
+    /// [`Bresenham`]: struct.Bresenham.html
+    #[inline]
+    pub fn bresenham(&self) -> Bresenham<T> {
+        Bresenham::new(self.start, self.end)
+    }
+
+    /// Draw the segment with [`Supercover`].
+    ///
+    /// [`Supercover`]: struct.Supercover.html
+    #[inline]
+    pub fn supercover(&self) -> Supercover<T> {
+        Supercover::new(self.start, self.end)
+    }
+
+    /// Draw the segment with [`WalkGrid`].
+    ///
+    /// [`WalkGrid`]: struct.WalkGrid.html
+    #[inline]
+    pub fn walk_grid(&self) -> WalkGrid<T> {
+        WalkGrid::new(self.start, self.end)
+    }
+
+    /// Draw the segment with an algorithm that's picked at runtime, like [`rasterize`].
+    ///
+    /// [`rasterize`]: fn.rasterize.html
+    #[inline]
+    pub fn rasterize(&self, algorithm: LineAlgorithm) -> Line<T> {
+        rasterize(self.start, self.end, algorithm)
+    }
+
+    /// The distance between the end points.
+    #[inline]
+    pub fn length<I: FloatNum>(&self) -> I {
+        let dx = I::cast(self.end.0) - I::cast(self.start.0);
+        let dy = I::cast(self.end.1) - I::cast(self.start.1);
+
+        dx.hypot(dy)
+    }
+
+    /// The number of cells that [`bresenham`] covers, without having to walk the line.
+    ///
+    /// Lines with more cells than fit in a `usize` give `usize::MAX`.
+    ///
+    /// [`bresenham`]: #method.bresenham
+    #[inline]
+    pub fn cells(&self) -> usize {
+        let dx = distance(self.start.0, self.end.0);
+        let dy = distance(self.start.1, self.end.1);
+
+        dx.zip(dy)
+            .and_then(|(dx, dy)| dx.max(dy).checked_add(1))
+            .unwrap_or(usize::MAX)
+    }
+
+    /// Check if two segments cross or touch, including at their end points.
+    ///
+    /// This is exact, but the coordinates are multiplied together, so they need to stay under the
+    /// square root of the largest value `T` can hold.
+    #[inline]
+    pub fn intersects(&self, other: &Self) -> bool {
+        let (a, b) = (self.start, self.end);
+        let (c, d) = (other.start, other.end);
+
+        let (abc, abd) = (orientation(a, b, c), orientation(a, b, d));
+        let (cda, cdb) = (orientation(c, d, a), orientation(c, d, b));
+
+        if abc * abd < T::zero() && cda * cdb < T::zero() {
+            return true;
+        }
+
+        // Otherwise they can only meet where an end point lies on the other segment
+        (abc.is_zero() && on_segment(a, b, c))
+            || (abd.is_zero() && on_segment(a, b, d))
+            || (cda.is_zero() && on_segment(c, d, a))
+            || (cdb.is_zero() && on_segment(c, d, b))
+    }
+}
+
+/// How far apart two coordinates are, in the wide type so that it can't overflow, if it fits in a
+/// `usize`.
+#[inline]
+fn distance<T: SignedNum>(a: T, b: T) -> Option<usize> {
+    let delta = b.widen().checked_sub(&a.widen())?;
+
+    if delta < T::Wide::zero() {
+        delta.checked_neg()?.to_usize()
+    } else {
+        delta.to_usize()
+    }
+}
+
+/// Which side of the line through `a` and `b` that `c` is on, as -1, 0 or 1.
+#[inline]
+fn orientation<T: SignedNum>(a: Point<T>, b: Point<T>, c: Point<T>) -> T {
+    ((b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0)).signum()
+}
+
+/// Check if a point that's on the line through `a` and `b` is between them.
+#[inline]
+fn on_segment<T: SignedNum>(a: Point<T>, b: Point<T>, point: Point<T>) -> bool {
+    a.0.min(b.0) <= point.0
+        && point.0 <= a.0.max(b.0)
+        && a.1.min(b.1) <= point.1
+        && point.1 <= a.1.max(b.1)
+}
+
+#[test]
+fn tests() {
+    let segment = LineSegment::new((3, -1), [-4, 6]);
+
+    assert_eq!(segment.start, (3, -1));
+    assert_eq!(segment.reversed(), LineSegment::new((-4, 6), (3, -1)));
+    assert_eq!(
+        segment.bresenham().collect::<Vec<_>>(),
+        Bresenham::new((3, -1), (-4, 6)).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        segment.supercover().collect::<Vec<_>>(),
+        Supercover::new((3, -1), (-4, 6)).collect::<Vec<_>>()
+    );
+
+    for &algorithm in &LineAlgorithm::ALL {
+        assert_eq!(
+            segment.rasterize(algorithm).collect::<Vec<_>>(),
+            rasterize((3, -1), (-4, 6), algorithm).collect::<Vec<_>>()
+        );
+    }
+
+    for x in -6..7 {
+        for y in -6..7 {
+            let segment = LineSegment::new((1, -2), (x, y));
+            assert_eq!(segment.cells(), segment.bresenham().count());
+        }
+    }
+
+    // Lines across the whole range of the type don't overflow
+    assert_eq!(LineSegment::new((-100i8, 0), (100, 0)).cells(), 201);
+    assert_eq!(LineSegment::new((i8::MIN, i8::MAX), (i8::MAX, i8::MIN)).cells(), 256);
+    assert_eq!(LineSegment::new((i64::MIN, 0), (i64::MAX, 0)).cells(), usize::MAX);
+    assert_eq!(LineSegment::new((i128::MIN, 0), (i128::MAX, 1)).cells(), usize::MAX);
+    assert_eq!(LineSegment::new((-100i8, 0), (100, 0)).length::<f32>(), 200.0);
+
+    assert_eq!(LineSegment::new((0, 0), (3, 4)).length::<f64>(), 5.0);
+    assert_eq!(LineSegment::new((2, 2), (2, 2)).length::<f32>(), 0.0);
+
+    let crossing = |a: Point<i32>, b, c, d| {
+        let (first, second) = (LineSegment::new(a, b), LineSegment::new(c, d));
+        let result = first.intersects(&second);
+
+        // It doesn't matter which way round they are
+        assert_eq!(second.intersects(&first), result);
+        assert_eq!(first.reversed().intersects(&second), result);
+        result
+    };
+
+    assert!(crossing((0, 0), (4, 4), (0, 4), (4, 0)));
+    assert!(crossing((0, 0), (4, 4), (2, 2), (9, -3)));
+    assert!(crossing((0, 0), (4, 0), (4, 0), (4, 7)));
+    assert!(crossing((0, 0), (4, 0), (2, 0), (8, 0)));
+    assert!(crossing((1, 1), (1, 1), (0, 0), (2, 2)));
+    assert!(!crossing((0, 0), (4, 4), (1, 0), (5, 4)));
+    assert!(!crossing((0, 0), (4, 0), (5, 0), (8, 0)));
+    assert!(!crossing((0, 0), (4, 4), (3, 2), (9, -3)));
+    assert!(!crossing((1, 1), (1, 1), (0, 0), (2, 3)));
+}
@@ -0,0 +1,973 @@
+use {Bresenham, BresenhamCircle, Endpoints, FloatNum, GridBounds, IntoPoint, MidpointEllipse,
+     Point, RealNum, SignedNum, XiaolinWu, XiaolinWuEllipse};
+
+/// A circle, drawn with [`BresenhamCircle`].
+///
+/// Like the other shapes, it can be drawn as an outline, an anti-aliased outline or filled in.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::Circle;
+///
+/// fn main() {
+///     let circle = Circle::new((0, 0), 2);
+///
+///     for y in -2..3 {
+///         let row = (-2..3).map(|x| if circle.contains((x, y)) { '#' } else { '.' });
+///         println!("{}", row.collect::<String>());
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// .###.
+/// #####
+/// #####
+/// #####
+/// .###.
+/// ```
+///
+/// [`BresenhamCircle`]: struct.BresenhamCircle.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Circle<T> {
+    pub center: Point<T>,
+    pub radius: T,
+}
+
+impl<T: SignedNum> Circle<T> {
+    #[inline]
+    pub fn new(center: impl IntoPoint<T>, radius: T) -> Self {
+        let center = center.into_point();
+
+        Self { center, radius }
+    }
+
+    /// The points around the edge of the circle, in order.
+    #[inline]
+    pub fn outline(&self) -> BresenhamCircle<T> {
+        BresenhamCircle::contiguous(self.center.0, self.center.1, self.radius)
+    }
+
+    /// The points around the edge of the circle, with how much of each one the edge covers.
+    #[inline]
+    pub fn outline_aa<I: FloatNum>(&self) -> XiaolinWuEllipse<I, T> {
+        let radius = I::cast(self.radius);
+        XiaolinWuEllipse::new(self.center.0, self.center.1, radius, radius)
+    }
+
+    /// All of the points inside the circle, including the outline.
+    #[inline]
+    pub fn fill(&self) -> Fill<T, BresenhamCircle<T>> {
+        Fill::new(self.outline(), self.bounding_box())
+    }
+
+    /// The rows of [`fill`], as `(y, (start_x, end_x))`. See [`FillSpans`].
+    ///
+    /// [`fill`]: #method.fill
+    /// [`FillSpans`]: struct.FillSpans.html
+    #[inline]
+    pub fn spans(&self) -> FillSpans<T, BresenhamCircle<T>> {
+        FillSpans::new(self.outline(), self.bounding_box())
+    }
+
+    /// Check if a point is one of the points of [`fill`].
+    ///
+    /// [`fill`]: #method.fill
+    #[inline]
+    pub fn contains(&self, point: Point<T>) -> bool {
+        span_contains(self.outline(), point)
+    }
+
+    /// The smallest bounds that the circle fits in.
+    #[inline]
+    pub fn bounding_box(&self) -> GridBounds<T> {
+        let (x, y) = self.center;
+        let radius = self.radius.abs();
+
+        GridBounds::new((x - radius, y - radius), (x + radius, y + radius))
+    }
+}
+
+/// An axis-aligned ellipse, drawn with [`MidpointEllipse`].
+///
+/// See [`Circle`] for an example.
+///
+/// [`MidpointEllipse`]: struct.MidpointEllipse.html
+/// [`Circle`]: struct.Circle.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Ellipse<T> {
+    pub center: Point<T>,
+    pub radius: Point<T>,
+}
+
+impl<T: SignedNum> Ellipse<T> {
+    #[inline]
+    pub fn new(center: impl IntoPoint<T>, radius: impl IntoPoint<T>) -> Self {
+        let (center, radius) = (center.into_point(), radius.into_point());
+
+        Self { center, radius }
+    }
+
+    /// The points around the edge of the ellipse, in order.
+    #[inline]
+    pub fn outline(&self) -> MidpointEllipse<T> {
+        MidpointEllipse::new(self.center.0, self.center.1, self.radius.0, self.radius.1)
+    }
+
+    /// The points around the edge of the ellipse, with how much of each one the edge covers.
+    #[inline]
+    pub fn outline_aa<I: FloatNum>(&self) -> XiaolinWuEllipse<I, T> {
+        XiaolinWuEllipse::new(
+            self.center.0,
+            self.center.1,
+            I::cast(self.radius.0),
+            I::cast(self.radius.1),
+        )
+    }
+
+    /// All of the points inside the ellipse, including the outline.
+    #[inline]
+    pub fn fill(&self) -> Fill<T, MidpointEllipse<T>> {
+        Fill::new(self.outline(), self.bounding_box())
+    }
+
+    /// The rows of [`fill`], as `(y, (start_x, end_x))`. See [`FillSpans`].
+    ///
+    /// [`fill`]: #method.fill
+    /// [`FillSpans`]: struct.FillSpans.html
+    #[inline]
+    pub fn spans(&self) -> FillSpans<T, MidpointEllipse<T>> {
+        FillSpans::new(self.outline(), self.bounding_box())
+    }
+
+    /// Check if a point is one of the points of [`fill`].
+    ///
+    /// [`fill`]: #method.fill
+    #[inline]
+    pub fn contains(&self, point: Point<T>) -> bool {
+        span_contains(self.outline(), point)
+    }
+
+    /// The smallest bounds that the ellipse fits in.
+    #[inline]
+    pub fn bounding_box(&self) -> GridBounds<T> {
+        let (x, y) = self.center;
+        let (radius_x, radius_y) = (self.radius.0.abs(), self.radius.1.abs());
+
+        GridBounds::new((x - radius_x, y - radius_y), (x + radius_x, y + radius_y))
+    }
+}
+
+/// An axis-aligned rectangle, including both the `min` and `max` corners.
+///
+/// See [`Circle`] for an example.
+///
+/// [`Circle`]: struct.Circle.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Rect<T> {
+    pub min: Point<T>,
+    pub max: Point<T>,
+}
+
+impl<T: SignedNum> Rect<T> {
+    /// Create a rectangle between two opposite corners, in any order.
+    #[inline]
+    pub fn new(corner: impl IntoPoint<T>, opposite: impl IntoPoint<T>) -> Self {
+        let (a, b) = (corner.into_point(), opposite.into_point());
+
+        Self {
+            min: (a.0.min(b.0), a.1.min(b.1)),
+            max: (a.0.max(b.0), a.1.max(b.1)),
+        }
+    }
+
+    #[inline]
+    fn corners(&self) -> [Point<T>; 4] {
+        let (min, max) = (self.min, self.max);
+        [min, (max.0, min.1), max, (min.0, max.1)]
+    }
+
+    /// The points around the edge of the rectangle, in order, starting from `min`.
+    #[inline]
+    pub fn outline(&self) -> Outline<T> {
+        Outline::new(&self.corners())
+    }
+
+    /// The points around the edge of the rectangle, with how much of each one the edge covers.
+    ///
+    /// See [`Outline::aa`] for details.
+    ///
+    /// [`Outline::aa`]: struct.Outline.html#method.aa
+    #[inline]
+    pub fn outline_aa<I: RealNum>(&self) -> OutlineAa<I, T> {
+        self.outline().aa()
+    }
+
+    /// All of the points inside the rectangle, including the outline.
+    #[inline]
+    pub fn fill(&self) -> Fill<T, Outline<T>> {
+        Fill::new(self.outline(), self.bounding_box())
+    }
+
+    /// The rows of [`fill`], as `(y, (start_x, end_x))`. See [`FillSpans`].
+    ///
+    /// [`fill`]: #method.fill
+    /// [`FillSpans`]: struct.FillSpans.html
+    #[inline]
+    pub fn spans(&self) -> FillSpans<T, Outline<T>> {
+        FillSpans::new(self.outline(), self.bounding_box())
+    }
+
+    /// Check if a point is inside the rectangle, including the outline.
+    #[inline]
+    pub fn contains(&self, point: Point<T>) -> bool {
+        self.bounding_box().contains(point)
+    }
+
+    /// The same rectangle, as bounds.
+    #[inline]
+    pub fn bounding_box(&self) -> GridBounds<T> {
+        GridBounds::new(self.min, self.max)
+    }
+}
+
+/// A triangle, with its edges drawn with [`Bresenham`].
+///
+/// See [`Circle`] for an example.
+///
+/// [`Bresenham`]: struct.Bresenham.html
+/// [`Circle`]: struct.Circle.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Triangle<T> {
+    pub points: [Point<T>; 3],
+}
+
+impl<T: SignedNum> Triangle<T> {
+    #[inline]
+    pub fn new(a: impl IntoPoint<T>, b: impl IntoPoint<T>, c: impl IntoPoint<T>) -> Self {
+        Self {
+            points: [a.into_point(), b.into_point(), c.into_point()],
+        }
+    }
+
+    /// The points around the edge of the triangle, in order, starting from the first corner.
+    #[inline]
+    pub fn outline(&self) -> Outline<T> {
+        Outline::new(&self.points)
+    }
+
+    /// The points around the edge of the triangle, with how much of each one the edge covers.
+    ///
+    /// See [`Outline::aa`] for details.
+    ///
+    /// [`Outline::aa`]: struct.Outline.html#method.aa
+    #[inline]
+    pub fn outline_aa<I: RealNum>(&self) -> OutlineAa<I, T> {
+        self.outline().aa()
+    }
+
+    /// All of the points inside the triangle, including the outline.
+    #[inline]
+    pub fn fill(&self) -> Fill<T, Outline<T>> {
+        Fill::new(self.outline(), self.bounding_box())
+    }
+
+    /// The rows of [`fill`], as `(y, (start_x, end_x))`. See [`FillSpans`].
+    ///
+    /// [`fill`]: #method.fill
+    /// [`FillSpans`]: struct.FillSpans.html
+    #[inline]
+    pub fn spans(&self) -> FillSpans<T, Outline<T>> {
+        FillSpans::new(self.outline(), self.bounding_box())
+    }
+
+    /// All of the points of [`fill`], with their barycentric coordinates. See [`BarycentricFill`].
+    ///
+    /// [`fill`]: #method.fill
+    /// [`BarycentricFill`]: struct.BarycentricFill.html
+    #[inline]
+    pub fn fill_barycentric<I: FloatNum>(&self) -> BarycentricFill<I, T> {
+        BarycentricFill {
+            fill: self.fill(),
+            triangle: *self,
+            area: edge(self.points[0], self.points[1], self.points[2]),
+        }
+    }
+
+    /// Get the barycentric coordinates of a point: how much of each corner it's made of.
+    ///
+    /// They add up to `1`, and are all between `0` and `1` for points inside the triangle. If the
+    /// triangle has no area, the point is measured along its longest edge instead.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// extern crate line_drawing;
+    /// use line_drawing::Triangle;
+    ///
+    /// fn main() {
+    ///     let triangle = Triangle::new((0, 0), (4, 0), (0, 4));
+    ///
+    ///     assert_eq!(triangle.barycentric::<f32>((0, 0)), [1.0, 0.0, 0.0]);
+    ///     assert_eq!(triangle.barycentric::<f32>((2, 0)), [0.5, 0.5, 0.0]);
+    ///     assert_eq!(triangle.barycentric::<f32>((1, 2)), [0.25, 0.25, 0.5]);
+    /// }
+    /// ```
+    #[inline]
+    pub fn barycentric<I: FloatNum>(&self, point: Point<T>) -> [I; 3] {
+        let area = edge(self.points[0], self.points[1], self.points[2]);
+
+        self.weights(area, point)
+    }
+
+    /// Get the barycentric coordinates of a point, given twice the signed area of the triangle.
+    #[inline]
+    fn weights<I: FloatNum>(&self, area: I, point: Point<T>) -> [I; 3] {
+        let [a, b, c] = self.points;
+
+        if !area.is_zero() {
+            return [
+                edge::<I, T>(b, c, point) / area,
+                edge::<I, T>(c, a, point) / area,
+                edge::<I, T>(a, b, point) / area,
+            ];
+        }
+
+        // Measure along the longest edge of a flat triangle
+        let float = |point: Point<T>| (I::cast(point.0), I::cast(point.1));
+        let length = |(start, end): (usize, usize)| {
+            let (start, end) = (float(self.points[start]), float(self.points[end]));
+            (end.0 - start.0).powi(2) + (end.1 - start.1).powi(2)
+        };
+        let (start, end) = [(0, 1), (1, 2), (2, 0)]
+            .iter()
+            .cloned()
+            .fold((0, 1), |longest, pair| {
+                if length(pair) > length(longest) {
+                    pair
+                } else {
+                    longest
+                }
+            });
+
+        let mut weights = [I::zero(); 3];
+        let squared = length((start, end));
+
+        if squared.is_zero() {
+            weights[0] = I::one();
+        } else {
+            let (from, to) = (float(self.points[start]), float(self.points[end]));
+            let point = float(point);
+            let t = ((point.0 - from.0) * (to.0 - from.0) + (point.1 - from.1) * (to.1 - from.1))
+                / squared;
+
+            weights[start] = I::one() - t;
+            weights[end] = t;
+        }
+
+        weights
+    }
+
+    /// Check if a point is one of the points of [`fill`].
+    ///
+    /// [`fill`]: #method.fill
+    #[inline]
+    pub fn contains(&self, point: Point<T>) -> bool {
+        span_contains(self.outline(), point)
+    }
+
+    /// The smallest bounds that the triangle fits in.
+    #[inline]
+    pub fn bounding_box(&self) -> GridBounds<T> {
+        let [a, b, c] = self.points;
+
+        GridBounds::new(
+            (a.0.min(b.0).min(c.0), a.1.min(b.1).min(c.1)),
+            (a.0.max(b.0).max(c.0), a.1.max(b.1).max(c.1)),
+        )
+    }
+}
+
+/// The points around the edges of a [`Rect`] or [`Triangle`], in order.
+///
+/// Each edge is drawn with [`Bresenham`], and the corners are only returned once. Corners that are
+/// in the same place are merged, so a rectangle or triangle that's squashed down to a line is
+/// drawn as that line, and one that's squashed down to a point is that point.
+///
+/// [`Rect`]: struct.Rect.html
+/// [`Triangle`]: struct.Triangle.html
+/// [`Bresenham`]: struct.Bresenham.html
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "T: ::serde::Serialize, T::Wide: ::serde::Serialize",
+        deserialize = "T: ::serde::Deserialize<'de>, T::Wide: ::serde::Deserialize<'de>"
+    ))
+)]
+pub struct Outline<T: SignedNum> {
+    corners: [Point<T>; 4],
+    len: usize,
+    edge: usize,
+    line: Endpoints<Bresenham<T>>,
+}
+
+impl<T: SignedNum> Outline<T> {
+    #[inline]
+    fn new(points: &[Point<T>]) -> Self {
+        let mut corners = [points[0]; 4];
+        let mut len = 0;
+
+        for &point in points {
+            if len == 0 || corners[len - 1] != point {
+                corners[len] = point;
+                len += 1;
+            }
+        }
+
+        if len > 1 && corners[len - 1] == corners[0] {
+            len -= 1;
+        }
+
+        let mut outline = Self {
+            corners,
+            len,
+            edge: 0,
+            line: Endpoints::new(Bresenham::new(points[0], points[0]), true, true),
+        };
+
+        outline.line = outline.edge_line(0);
+        outline
+    }
+
+    /// The number of edges, which for a line is just the one from one end to the other.
+    #[inline]
+    fn edges(&self) -> usize {
+        if self.len == 2 {
+            1
+        } else {
+            self.len
+        }
+    }
+
+    #[inline]
+    fn edge_ends(&self, edge: usize) -> (Point<T>, Point<T>) {
+        (self.corners[edge], self.corners[(edge + 1) % self.len])
+    }
+
+    #[inline]
+    fn edge_line(&self, edge: usize) -> Endpoints<Bresenham<T>> {
+        let (start, end) = self.edge_ends(edge);
+
+        // The end of each edge is the start of the next, apart from on a line or a point
+        Endpoints::new(Bresenham::new(start, end), true, self.len <= 2)
+    }
+
+    /// Draw the edges anti-aliased with [`XiaolinWu`] instead.
+    ///
+    /// Each edge is drawn separately, so the points at the corners are returned once for each of
+    /// the edges that meet there.
+    ///
+    /// [`XiaolinWu`]: struct.XiaolinWu.html
+    #[inline]
+    pub fn aa<I: RealNum>(&self) -> OutlineAa<I, T> {
+        OutlineAa {
+            outline: Self {
+                edge: 0,
+                ..self.clone()
+            },
+            line: None,
+        }
+    }
+}
+
+impl<T: SignedNum> Iterator for Outline<T> {
+    type Item = Point<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(point) = self.line.next() {
+                return Some(point);
+            }
+
+            if self.edge + 1 >= self.edges() {
+                return None;
+            }
+
+            self.edge += 1;
+            self.line = self.edge_line(self.edge);
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let rest = (self.edge + 1..self.edges())
+            .map(|edge| {
+                let (start, end) = self.edge_ends(edge);
+                let length = (end.0 - start.0).abs().max((end.1 - start.1).abs());
+                length.to_usize().unwrap()
+            })
+            .sum::<usize>();
+        let len = self.line.len() + rest;
+
+        (len, Some(len))
+    }
+}
+
+impl<T: SignedNum> ExactSizeIterator for Outline<T> {}
+
+/// The anti-aliased edges of a [`Rect`] or [`Triangle`], made with [`Outline::aa`].
+///
+/// [`Rect`]: struct.Rect.html
+/// [`Triangle`]: struct.Triangle.html
+/// [`Outline::aa`]: struct.Outline.html#method.aa
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "I: ::serde::Serialize, T: ::serde::Serialize, T::Wide: ::serde::Serialize",
+        deserialize = "I: ::serde::Deserialize<'de>, T: ::serde::Deserialize<'de>, \
+            T::Wide: ::serde::Deserialize<'de>"
+    ))
+)]
+pub struct OutlineAa<I, T: SignedNum> {
+    outline: Outline<T>,
+    line: Option<XiaolinWu<I, T>>,
+}
+
+impl<I: RealNum, T: SignedNum> Iterator for OutlineAa<I, T> {
+    type Item = (Point<T>, I);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(point) = self.line.as_mut().and_then(Iterator::next) {
+                return Some(point);
+            }
+
+            if self.outline.edge >= self.outline.edges() {
+                return None;
+            }
+
+            let (start, end) = self.outline.edge_ends(self.outline.edge);
+            let float = |point: Point<T>| (I::cast(point.0), I::cast(point.1));
+            self.line = Some(XiaolinWu::new(float(start), float(end)));
+            self.outline.edge += 1;
+        }
+    }
+}
+
+/// All of the points inside one of the shapes, including its outline, a row at a time from the
+/// top.
+///
+/// Each row goes from the leftmost to the rightmost point of the outline on that row, so the fill
+/// always lines up with the outline. The outline is walked through again for each row, which keeps
+/// it from needing to allocate.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Fill<T, O> {
+    spans: FillSpans<T, O>,
+    point: Point<T>,
+    end_x: T,
+}
+
+impl<T: SignedNum, O: Iterator<Item = Point<T>> + Clone> Fill<T, O> {
+    #[inline]
+    fn new(outline: O, bounds: GridBounds<T>) -> Self {
+        Self {
+            spans: FillSpans::new(outline, bounds),
+            point: (T::one(), bounds.min.1 - T::one()),
+            end_x: T::zero(),
+        }
+    }
+}
+
+impl<T: SignedNum, O: Iterator<Item = Point<T>> + Clone> Iterator for Fill<T, O> {
+    type Item = Point<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.point.0 > self.end_x {
+            let (y, (start, end)) = self.spans.next()?;
+
+            self.point = (start, y);
+            self.end_x = end;
+        }
+
+        let point = self.point;
+        self.point.0 += T::one();
+
+        Some(point)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let bounds = self.spans.bounds;
+        let row = (self.end_x - self.point.0 + T::one()).max(T::zero());
+        let rows = bounds.max.1 - self.spans.y;
+        let width = bounds.max.0 - bounds.min.0 + T::one();
+
+        (
+            row.to_usize().unwrap(),
+            (row + rows.max(T::zero()) * width).to_usize(),
+        )
+    }
+}
+
+/// The rows of one of the shapes when it's filled in, from the top, as `(y, (start_x, end_x))`.
+///
+/// This is the same as [`Fill`], but with each row as one span instead of a point at a time, for
+/// things that draw a row at a time, like scanline renderers and compositors. Rows that the shape
+/// doesn't reach are left out.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::Circle;
+///
+/// fn main() {
+///     for (y, (start, end)) in Circle::new((0, 0), 2).spans() {
+///         println!("{}: {}..={}", y, start, end);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// -2: -1..=1
+/// -1: -2..=2
+/// 0: -2..=2
+/// 1: -2..=2
+/// 2: -1..=1
+/// ```
+///
+/// [`Fill`]: struct.Fill.html
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FillSpans<T, O> {
+    outline: O,
+    bounds: GridBounds<T>,
+    y: T,
+}
+
+impl<T: SignedNum, O: Iterator<Item = Point<T>> + Clone> FillSpans<T, O> {
+    #[inline]
+    fn new(outline: O, bounds: GridBounds<T>) -> Self {
+        Self {
+            outline,
+            y: bounds.min.1 - T::one(),
+            bounds,
+        }
+    }
+}
+
+impl<T: SignedNum, O: Iterator<Item = Point<T>> + Clone> Iterator for FillSpans<T, O> {
+    type Item = (T, Point<T>);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.y < self.bounds.max.1 {
+            self.y += T::one();
+
+            if let Some(span) = span(self.outline.clone(), self.y) {
+                return Some((self.y, span));
+            }
+        }
+
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, (self.bounds.max.1 - self.y).max(T::zero()).to_usize())
+    }
+}
+
+/// The points of a filled-in [`Triangle`], with their barycentric coordinates, made with
+/// [`Triangle::fill_barycentric`].
+///
+/// The coordinates say how much of each corner a point is made of, so a color, texture coordinate
+/// or height at each corner can be blended across the triangle by adding up the corners' values
+/// multiplied by them. The points are the same as [`Triangle::fill`], which includes the cells that
+/// the edges pass through, so the coordinates can be a little below `0` or above `1` at the edges.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::Triangle;
+///
+/// fn main() {
+///     let heights = [0.0, 8.0, 4.0];
+///
+///     for ((x, y), [a, b, c]) in Triangle::new((0, 0), (4, 0), (0, 2)).fill_barycentric::<f32>() {
+///         print!("(({}, {}), {}), ", x, y, a * heights[0] + b * heights[1] + c * heights[2]);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// ((0, 0), 0), ((1, 0), 2), ((2, 0), 4), ((3, 0), 6), ((4, 0), 8), ((0, 1), 2), ((1, 1), 4), ((2, 1), 6), ((0, 2), 4),
+/// ```
+///
+/// [`Triangle`]: struct.Triangle.html
+/// [`Triangle::fill_barycentric`]: struct.Triangle.html#method.fill_barycentric
+/// [`Triangle::fill`]: struct.Triangle.html#method.fill
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "I: ::serde::Serialize, T: ::serde::Serialize, T::Wide: ::serde::Serialize",
+        deserialize = "I: ::serde::Deserialize<'de>, T: ::serde::Deserialize<'de>, \
+            T::Wide: ::serde::Deserialize<'de>"
+    ))
+)]
+pub struct BarycentricFill<I, T: SignedNum> {
+    fill: Fill<T, Outline<T>>,
+    triangle: Triangle<T>,
+    area: I,
+}
+
+impl<I: FloatNum, T: SignedNum> Iterator for BarycentricFill<I, T> {
+    type Item = (Point<T>, [I; 3]);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let point = self.fill.next()?;
+
+        Some((point, self.triangle.weights(self.area, point)))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.fill.size_hint()
+    }
+}
+
+/// Twice the signed area of the triangle between three points.
+#[inline]
+fn edge<I: FloatNum, T: SignedNum>(a: Point<T>, b: Point<T>, point: Point<T>) -> I {
+    let (a, b, point) = (
+        (I::cast(a.0), I::cast(a.1)),
+        (I::cast(b.0), I::cast(b.1)),
+        (I::cast(point.0), I::cast(point.1)),
+    );
+
+    (b.0 - a.0) * (point.1 - a.1) - (b.1 - a.1) * (point.0 - a.0)
+}
+
+/// Find the leftmost and rightmost points of an outline on a row.
+#[inline]
+fn span<T: SignedNum, O: Iterator<Item = Point<T>>>(outline: O, y: T) -> Option<(T, T)> {
+    outline
+        .filter(|point| point.1 == y)
+        .fold(None, |span, (x, _)| match span {
+            None => Some((x, x)),
+            Some((start, end)) => Some((x.min(start), x.max(end))),
+        })
+}
+
+#[inline]
+fn span_contains<T: SignedNum, O: Iterator<Item = Point<T>>>(outline: O, (x, y): Point<T>) -> bool {
+    span(outline, y).is_some_and(|(start, end)| start <= x && x <= end)
+}
+
+#[test]
+fn tests() {
+    use std::collections::HashSet;
+
+    let sorted = |points: &mut dyn Iterator<Item = Point<i32>>| {
+        let mut points = points.collect::<Vec<_>>();
+        points.sort();
+        points
+    };
+
+    // The outlines come from the existing algorithms
+    let circle = Circle::new((3, -2), 5);
+    assert_eq!(
+        sorted(&mut circle.outline()),
+        sorted(&mut BresenhamCircle::new(3, -2, 5))
+    );
+    assert_eq!(
+        Ellipse::new((0, 0), (4, 2)).outline().collect::<Vec<_>>(),
+        MidpointEllipse::new(0, 0, 4, 2).collect::<Vec<_>>()
+    );
+
+    assert_eq!(
+        Rect::new((2, 1), (0, 0)).outline().collect::<Vec<_>>(),
+        [(0, 0), (1, 0), (2, 0), (2, 1), (1, 1), (0, 1)]
+    );
+    assert_eq!(
+        Triangle::new((0, 0), (4, 0), (0, 2)).outline().collect::<Vec<_>>(),
+        [(0, 0), (1, 0), (2, 0), (3, 0), (4, 0), (3, 0), (2, 1), (1, 1), (0, 2), (0, 1)]
+    );
+
+    // Squashed shapes are lines and points
+    assert_eq!(
+        Rect::new((0, 0), (3, 0)).outline().collect::<Vec<_>>(),
+        [(0, 0), (1, 0), (2, 0), (3, 0)]
+    );
+    assert_eq!(Rect::new((5, 5), (5, 5)).outline().collect::<Vec<_>>(), [(5, 5)]);
+    assert_eq!(
+        Triangle::new((1, 1), (1, 1), (1, 3)).outline().collect::<Vec<_>>(),
+        [(1, 1), (1, 2), (1, 3)]
+    );
+
+    // The outline is part of the fill, which has no gaps in its rows
+    fn check<F: Iterator<Item = Point<i32>>, O: Iterator<Item = Point<i32>>>(fill: F, outline: O) {
+        let (low, high) = fill.size_hint();
+        let points = fill.collect::<Vec<_>>();
+        assert!(low <= points.len() && points.len() <= high.unwrap());
+
+        let unique = points.iter().cloned().collect::<HashSet<_>>();
+        assert_eq!(unique.len(), points.len());
+
+        for point in outline {
+            assert!(unique.contains(&point));
+        }
+
+        for pair in points.windows(2) {
+            assert!(pair[0].1 < pair[1].1 || pair[1] == (pair[0].0 + 1, pair[0].1));
+        }
+    }
+
+    let ellipse = Ellipse::new((1, 1), (6, 3));
+    let triangle = Triangle::new((-3, 7), (9, 2), (0, -4));
+    let rect = Rect::new((-3, 7), (9, 2));
+    check(circle.fill(), circle.outline());
+    check(ellipse.fill(), ellipse.outline());
+    check(triangle.fill(), triangle.outline());
+    check(rect.fill(), rect.outline());
+    let line = Triangle::new((0, 0), (0, 0), (5, 1));
+    check(line.fill(), line.outline());
+
+    assert_eq!(Rect::new((0, 0), (2, 3)).fill().count(), 12);
+    assert_eq!(Circle::new((0, 0), 0).fill().count(), 0);
+
+    // Containing a point is the same as filling it in
+    let fill = triangle.fill().collect::<HashSet<_>>();
+    let bounds = triangle.bounding_box();
+    assert_eq!(bounds, GridBounds::new((-3, -4), (9, 7)));
+
+    for x in -5..12 {
+        for y in -6..10 {
+            assert_eq!(triangle.contains((x, y)), fill.contains(&(x, y)));
+            assert!(!fill.contains(&(x, y)) || bounds.contains((x, y)));
+        }
+    }
+
+    assert!(Rect::new((0, 0), (2, 3)).contains((2, 3)));
+    assert!(!Circle::new((0, 0), 3).contains((3, 3)));
+    assert_eq!(
+        Ellipse::new((1, 2), (-3, 4)).bounding_box(),
+        GridBounds::new((-2, -2), (4, 6))
+    );
+
+    // The anti-aliased outlines
+    let aa = Rect::new((0, 0), (3, 2)).outline_aa::<f32>().collect::<Vec<_>>();
+    assert!(aa.iter().all(|&(_, coverage)| coverage == 1.0));
+    assert_eq!(aa.len(), 4 + 3 + 4 + 3);
+
+    let aa = Triangle::new((0, 0), (4, 0), (0, 2)).outline_aa::<f64>().collect::<Vec<_>>();
+    let covered = aa.iter().map(|&(point, _)| point).collect::<HashSet<_>>();
+    assert!(Triangle::new((0, 0), (4, 0), (0, 2)).outline().all(|point| covered.contains(&point)));
+
+    assert_eq!(
+        Circle::new((1, 1), 4).outline_aa::<f32>().collect::<Vec<_>>(),
+        XiaolinWuEllipse::new(1, 1, 4.0, 4.0).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn spans() {
+    // The spans are the rows of the fill
+    fn check<O: Iterator<Item = Point<i32>> + Clone>(spans: FillSpans<i32, O>, fill: Fill<i32, O>) {
+        let (_, high) = spans.size_hint();
+        let spans = spans.collect::<Vec<_>>();
+        assert!(spans.len() <= high.unwrap());
+        assert!(spans.windows(2).all(|pair| pair[0].0 < pair[1].0));
+
+        let points = spans
+            .iter()
+            .flat_map(|&(y, (start, end))| (start..=end).map(move |x| (x, y)))
+            .collect::<Vec<_>>();
+        assert_eq!(points, fill.collect::<Vec<_>>());
+    }
+
+    let circle = Circle::new((3, -2), 5);
+    let ellipse = Ellipse::new((1, 1), (6, 3));
+    let rect = Rect::new((-3, 7), (9, 2));
+    let triangle = Triangle::new((-3, 7), (9, 2), (0, -4));
+    check(circle.spans(), circle.fill());
+    check(ellipse.spans(), ellipse.fill());
+    check(rect.spans(), rect.fill());
+    check(triangle.spans(), triangle.fill());
+
+    assert_eq!(
+        Rect::new((0, 0), (2, 1)).spans().collect::<Vec<_>>(),
+        [(0, (0, 2)), (1, (0, 2))]
+    );
+    assert_eq!(Circle::new((0, 0), 0).spans().count(), 0);
+}
+
+#[test]
+fn barycentric() {
+    let triangle = Triangle::new((-3, 7), (9, 2), (0, -4));
+    let [a, b, c] = triangle.points;
+
+    // The corners are all of themselves
+    assert_eq!(triangle.barycentric::<f64>(a), [1.0, 0.0, 0.0]);
+    assert_eq!(triangle.barycentric::<f64>(b), [0.0, 1.0, 0.0]);
+    assert_eq!(triangle.barycentric::<f64>(c), [0.0, 0.0, 1.0]);
+
+    // They're the same points as the fill, and they rebuild the point they came from
+    let fill = triangle.fill_barycentric::<f64>();
+    let (low, high) = fill.size_hint();
+    let points = fill.collect::<Vec<_>>();
+    assert!(low <= points.len() && points.len() <= high.unwrap());
+    assert_eq!(
+        points.iter().map(|&(point, _)| point).collect::<Vec<_>>(),
+        triangle.fill().collect::<Vec<_>>()
+    );
+
+    for &((x, y), weights) in &points {
+        assert!((weights.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!(weights.iter().all(|&weight| weight > -0.2 && weight < 1.2));
+
+        let rebuilt = [a, b, c]
+            .iter()
+            .zip(&weights)
+            .fold((0.0, 0.0), |sum, (corner, weight)| {
+                (sum.0 + f64::from(corner.0) * weight, sum.1 + f64::from(corner.1) * weight)
+            });
+        assert!((rebuilt.0 - f64::from(x)).abs() < 1e-9);
+        assert!((rebuilt.1 - f64::from(y)).abs() < 1e-9);
+    }
+
+    // The winding doesn't matter
+    let flipped = Triangle::new(a, c, b);
+    let weights = flipped.barycentric::<f32>((1, 1));
+    assert_eq!(triangle.barycentric::<f32>((1, 1)), [weights[0], weights[2], weights[1]]);
+
+    // Flat triangles are measured along their longest edge
+    let flat = Triangle::new((2, 2), (0, 0), (4, 4));
+    assert_eq!(flat.barycentric::<f32>((3, 3)), [0.0, 0.25, 0.75]);
+    assert_eq!(
+        flat.fill_barycentric::<f32>().map(|(_, weights)| weights[1]).collect::<Vec<_>>(),
+        [1.0, 0.75, 0.5, 0.25, 0.0]
+    );
+    assert_eq!(
+        Triangle::new((1, 1), (1, 1), (1, 1)).fill_barycentric::<f32>().collect::<Vec<_>>(),
+        [((1, 1), [1.0, 0.0, 0.0])]
+    );
+}
@@ -0,0 +1,110 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::mem;
+
+/// Somewhere that the points of a line can be poured into, with [`rasterize_into`].
+///
+/// It's implemented for closures that take a point, for `&mut [P]`, which fills the slice from
+/// the start and moves past the points that have been written like `std::io::Write` does, and for
+/// `Vec` with the `alloc` feature.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{Bresenham, LineExt};
+///
+/// fn main() {
+///     let mut buffer = [(0, 0); 4];
+///     let written = {
+///         let mut cursor = &mut buffer[..];
+///         Bresenham::new((0, 0), (9, 3)).rasterize_into(&mut cursor)
+///     };
+///
+///     println!("{} {:?}", written, buffer);
+/// }
+/// ```
+///
+/// ```text
+/// 4 [(0, 0), (1, 0), (2, 0), (3, 1)]
+/// ```
+///
+/// [`rasterize_into`]: trait.LineExt.html#method.rasterize_into
+pub trait PointSink<P> {
+    /// Add a point, returning `false` if there's no room for it.
+    fn push_point(&mut self, point: P) -> bool;
+}
+
+impl<P, F: FnMut(P)> PointSink<P> for F {
+    #[inline]
+    fn push_point(&mut self, point: P) -> bool {
+        self(point);
+        true
+    }
+}
+
+impl<P> PointSink<P> for &mut [P] {
+    #[inline]
+    fn push_point(&mut self, point: P) -> bool {
+        match mem::take(self).split_first_mut() {
+            Some((first, rest)) => {
+                *first = point;
+                *self = rest;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<P> PointSink<P> for Vec<P> {
+    #[inline]
+    fn push_point(&mut self, point: P) -> bool {
+        self.push(point);
+        true
+    }
+}
+
+#[test]
+fn tests() {
+    use {Bresenham, LineExt, VoxelOrigin, WalkVoxels};
+
+    let line = || Bresenham::new((0, 0), (5, 2));
+    let points = line().collect::<Vec<_>>();
+
+    // A closure is given every point
+    let mut seen = Vec::new();
+    assert_eq!(line().rasterize_into(&mut |point| seen.push(point)), 6);
+    assert_eq!(seen, points);
+
+    // A slice is filled up until it runs out of room
+    let mut buffer = [(0, 0); 4];
+    let mut cursor = &mut buffer[..];
+    assert_eq!(line().rasterize_into(&mut cursor), 4);
+    assert!(cursor.is_empty());
+    assert_eq!(buffer, points[..4]);
+
+    let mut buffer = [(9, 9); 10];
+    let mut cursor = &mut buffer[..];
+    assert_eq!(line().rasterize_into(&mut cursor), 6);
+    assert_eq!(line().rasterize_into(&mut cursor), 4);
+    assert_eq!(buffer[..6], points[..]);
+    assert_eq!(buffer[6..], points[..4]);
+
+    // Anything can be poured, like voxels
+    let mut voxels = [(0, 0, 0); 3];
+    let walk = WalkVoxels::<f32, i32>::new((0.0, 0.0, 0.0), (1.0, 1.0, 0.0), VoxelOrigin::Corner);
+    assert_eq!(walk.clone().rasterize_into(&mut &mut voxels[..]), 3);
+    assert_eq!(voxels[..], walk.collect::<Vec<_>>()[..]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn vec() {
+    use {Bresenham, LineExt};
+
+    let mut points = vec![(-1, -1)];
+    assert_eq!(Bresenham::new((0, 0), (3, 1)).rasterize_into(&mut points), 4);
+    assert_eq!(points, [(-1, -1), (0, 0), (1, 0), (2, 0), (3, 1)]);
+}
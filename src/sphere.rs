@@ -0,0 +1,446 @@
+use {Bresenham3d, FloatNum, IntoVoxel, SignedNum, Voxel};
+use steps::Steps;
+
+/// Draw the latitude and longitude rings of a sphere as connected chains of voxels.
+///
+/// Returns `(ring, voxel)` pairs, where `ring` is the index of the ring the voxel belongs to.
+/// The `latitudes` rings come first, evenly spaced from the top of the sphere (positive `z`) to
+/// the bottom without including the poles. They are followed by the `longitudes` great circles
+/// through both poles, which are evenly spaced around the `z` axis.
+///
+/// Every ring is walked in order and is closed, with each voxel touching the next one by at least
+/// a corner. The rings are sampled finely enough that there are no gaps and then joined up with
+/// [`Bresenham3d`].
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::SphereWireframe;
+///
+/// fn main() {
+///     for (ring, (x, y, z)) in SphereWireframe::<f32, i32>::new((0.0, 0.0, 0.0), 2.0, 1, 0) {
+///         print!("{}: ({}, {}, {}), ", ring, x, y, z);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// 0: (2, 1, 0), 0: (1, 2, 0), 0: (0, 2, 0), 0: (-1, 2, 0), 0: (-1, 1, 0), 0: (-2, 0, 0), 0: (-1, -1, 0), 0: (-1, -2, 0), 0: (0, -2, 0), 0: (1, -2, 0), 0: (2, -1, 0), 0: (2, 0, 0),
+/// ```
+///
+/// [`Bresenham3d`]: struct.Bresenham3d.html
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "I: ::serde::Serialize, O: ::serde::Serialize, O::Wide: ::serde::Serialize",
+        deserialize = "I: ::serde::Deserialize<'de>, \
+            O: ::serde::Deserialize<'de>, O::Wide: ::serde::Deserialize<'de>"
+    ))
+)]
+pub struct SphereWireframe<I, O: SignedNum> {
+    center: Voxel<I>,
+    radius: I,
+    latitudes: usize,
+    longitudes: usize,
+    ring: usize,
+    segment: usize,
+    segments: usize,
+    line: Bresenham3d<O>,
+}
+
+impl<I: FloatNum, O: SignedNum> SphereWireframe<I, O> {
+    #[inline]
+    pub fn new(center: impl IntoVoxel<I>, radius: I, latitudes: usize, longitudes: usize) -> Self {
+        let center = center.into_voxel();
+
+        let mut wireframe = Self {
+            center,
+            radius,
+            latitudes,
+            longitudes,
+            ring: 0,
+            segment: 0,
+            segments: 0,
+            line: Bresenham3d::new(
+                (O::zero(), O::zero(), O::zero()),
+                (O::zero(), O::zero(), O::zero()),
+            ),
+        };
+
+        wireframe.line.next();
+
+        if latitudes + longitudes > 0 {
+            wireframe.start_ring();
+        }
+
+        wireframe
+    }
+
+    /// Get the radius of a ring.
+    #[inline]
+    fn ring_radius(&self, ring: usize) -> I {
+        if ring < self.latitudes {
+            let polar =
+                I::cast(ring + 1) * I::cast(core::f64::consts::PI) / I::cast(self.latitudes + 1);
+            self.radius * polar.sin()
+        } else {
+            self.radius
+        }
+    }
+
+    /// Get the voxel at an angle around a ring.
+    #[inline]
+    fn ring_voxel(&self, ring: usize, angle: I) -> Voxel<O> {
+        let pi = I::cast(core::f64::consts::PI);
+
+        let (x, y, z) = if ring < self.latitudes {
+            let polar = I::cast(ring + 1) * pi / I::cast(self.latitudes + 1);
+            let radius = self.radius * polar.sin();
+            (
+                radius * angle.cos(),
+                radius * angle.sin(),
+                self.radius * polar.cos(),
+            )
+        } else {
+            let azimuth = I::cast(ring - self.latitudes) * pi / I::cast(self.longitudes);
+            let radius = self.radius * angle.sin();
+            (
+                radius * azimuth.cos(),
+                radius * azimuth.sin(),
+                self.radius * angle.cos(),
+            )
+        };
+
+        (
+            O::cast((self.center.0 + x).round()),
+            O::cast((self.center.1 + y).round()),
+            O::cast((self.center.2 + z).round()),
+        )
+    }
+
+    /// Get the number of segments a ring is sampled with.
+    #[inline]
+    fn ring_segments(&self, ring: usize) -> usize {
+        // Sample at least once per voxel of circumference
+        let circumference = self.ring_radius(ring) * I::cast(2.0 * core::f64::consts::PI);
+        circumference.ceil().max(I::cast(4)).to_usize().unwrap_or(4)
+    }
+
+    /// Set up the segments for the current ring.
+    #[inline]
+    fn start_ring(&mut self) {
+        self.segments = self.ring_segments(self.ring);
+        self.segment = 0;
+        self.start_segment();
+    }
+
+    /// Set up the line for the current segment.
+    #[inline]
+    fn start_segment(&mut self) {
+        let angle = |segment: usize| {
+            I::cast(segment) * I::cast(2.0 * core::f64::consts::PI) / I::cast(self.segments)
+        };
+
+        let start = self.ring_voxel(self.ring, angle(self.segment));
+        let end = self.ring_voxel(self.ring, angle(self.segment + 1));
+
+        // Skip the start of each segment as it's the end of the previous one
+        self.line = Bresenham3d::new(start, end);
+        self.line.next();
+    }
+}
+
+impl<I: FloatNum, O: SignedNum> Iterator for SphereWireframe<I, O> {
+    type Item = (usize, Voxel<O>);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.ring < self.latitudes + self.longitudes {
+            if let Some(voxel) = self.line.next() {
+                return Some((self.ring, voxel));
+            }
+
+            self.segment += 1;
+
+            if self.segment < self.segments {
+                self.start_segment();
+            } else {
+                self.ring += 1;
+
+                if self.ring < self.latitudes + self.longitudes {
+                    self.start_ring();
+                }
+            }
+        }
+
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (low, high) = self.line.size_hint();
+
+        // The samples are at most a voxel apart, so after rounding each segment adds at most two
+        let rest = (self.ring + 1..self.latitudes + self.longitudes)
+            .map(|ring| self.ring_segments(ring))
+            .fold(
+                self.segments.saturating_sub(self.segment + 1),
+                |rest, segments| rest.saturating_add(segments),
+            );
+
+        (
+            low,
+            high.and_then(|high| high.checked_add(rest.checked_mul(2)?)),
+        )
+    }
+}
+
+/// Draw the shortest great-circle arc between two points on a sphere.
+///
+/// The points are projected onto the surface of the sphere first, so they only need to be
+/// roughly on it. The arc is sampled finely enough that there are no gaps and then joined up with
+/// [`Bresenham3d`], so each voxel touches the next one by at least a corner. For points on exact
+/// opposite sides of the sphere, one of the many shortest arcs is picked arbitrarily.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::GeodesicArc;
+///
+/// fn main() {
+///     for (x, y, z) in GeodesicArc::<f32, i32>::new((0.0, 0.0, 0.0), 5.0, (5.0, 0.0, 0.0), (0.0, 0.0, 5.0)) {
+///         print!("({}, {}, {}), ", x, y, z);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// (5, 0, 0), (5, 0, 1), (5, 0, 2), (4, 0, 3), (4, 0, 4), (3, 0, 4), (2, 0, 5), (1, 0, 5), (0, 0, 5),
+/// ```
+///
+/// [`Bresenham3d`]: struct.Bresenham3d.html
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "I: ::serde::Serialize, O: ::serde::Serialize, O::Wide: ::serde::Serialize",
+        deserialize = "I: ::serde::Deserialize<'de>, \
+            O: ::serde::Deserialize<'de>, O::Wide: ::serde::Deserialize<'de>"
+    ))
+)]
+pub struct GeodesicArc<I, O: SignedNum> {
+    center: Voxel<I>,
+    radius: I,
+    start: Voxel<I>,
+    tangent: Voxel<I>,
+    angle: I,
+    segment: usize,
+    segments: usize,
+    line: Bresenham3d<O>,
+}
+
+impl<I: FloatNum, O: SignedNum> GeodesicArc<I, O> {
+    #[inline]
+    pub fn new(
+        center: impl IntoVoxel<I>,
+        radius: I,
+        start: impl IntoVoxel<I>,
+        end: impl IntoVoxel<I>,
+    ) -> Self {
+        let (center, start, end) = (center.into_voxel(), start.into_voxel(), end.into_voxel());
+
+        let normalize = |point: Voxel<I>| {
+            let length = (point.0 * point.0 + point.1 * point.1 + point.2 * point.2).sqrt();
+
+            if length > I::zero() {
+                (point.0 / length, point.1 / length, point.2 / length)
+            } else {
+                (I::zero(), I::zero(), I::zero())
+            }
+        };
+
+        let start = normalize((start.0 - center.0, start.1 - center.1, start.2 - center.2));
+        let end = normalize((end.0 - center.0, end.1 - center.1, end.2 - center.2));
+
+        let dot = start.0 * end.0 + start.1 * end.1 + start.2 * end.2;
+        let angle = dot.max(-I::one()).min(I::one()).acos();
+
+        // The direction to move away from the start in, in the plane of the arc
+        let mut tangent = normalize((
+            end.0 - start.0 * dot,
+            end.1 - start.1 * dot,
+            end.2 - start.2 * dot,
+        ));
+
+        if tangent == (I::zero(), I::zero(), I::zero()) {
+            // The points are on opposite sides, so pick any direction perpendicular to the start
+            tangent = if start.0.abs() < I::cast(0.9) {
+                normalize((I::zero(), start.2, -start.1))
+            } else {
+                normalize((start.1, -start.0, I::zero()))
+            };
+        }
+
+        // Sample at least once per voxel of arc length
+        let segments = (angle * radius)
+            .ceil()
+            .max(I::one())
+            .to_usize()
+            .unwrap_or(1);
+
+        let mut arc = Self {
+            center,
+            radius,
+            start,
+            tangent,
+            angle,
+            segment: 0,
+            segments,
+            line: Bresenham3d::new(
+                (O::zero(), O::zero(), O::zero()),
+                (O::zero(), O::zero(), O::zero()),
+            ),
+        };
+
+        arc.start_segment();
+        arc
+    }
+
+    /// Get the voxel a fraction of the way along the arc.
+    #[inline]
+    fn arc_voxel(&self, segment: usize) -> Voxel<O> {
+        let angle = self.angle * I::cast(segment) / I::cast(self.segments);
+        let (sin, cos) = angle.sin_cos();
+
+        let point = |center: I, start: I, tangent: I| {
+            O::cast((center + (start * cos + tangent * sin) * self.radius).round())
+        };
+
+        (
+            point(self.center.0, self.start.0, self.tangent.0),
+            point(self.center.1, self.start.1, self.tangent.1),
+            point(self.center.2, self.start.2, self.tangent.2),
+        )
+    }
+
+    /// Set up the line for the current segment.
+    #[inline]
+    fn start_segment(&mut self) {
+        let start = self.arc_voxel(self.segment);
+        let end = self.arc_voxel(self.segment + 1);
+
+        // Skip the start of each segment after the first as it's the end of the previous one
+        self.line = Bresenham3d::new(start, end);
+
+        if self.segment > 0 {
+            self.line.next();
+        }
+    }
+
+    #[inline]
+    pub fn steps(self) -> Steps<Voxel<O>, Self> {
+        Steps::new(self)
+    }
+}
+
+impl<I: FloatNum, O: SignedNum> Iterator for GeodesicArc<I, O> {
+    type Item = Voxel<O>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.segment < self.segments {
+            if let Some(voxel) = self.line.next() {
+                return Some(voxel);
+            }
+
+            self.segment += 1;
+
+            if self.segment < self.segments {
+                self.start_segment();
+            }
+        }
+
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (low, high) = self.line.size_hint();
+
+        // The samples are at most a voxel apart, so after rounding each segment adds at most two
+        let rest = self.segments.saturating_sub(self.segment + 1);
+        (
+            low,
+            high.and_then(|high| high.checked_add(rest.checked_mul(2)?)),
+        )
+    }
+}
+
+#[test]
+fn tests() {
+    let connected = |a: Voxel<i32>, b: Voxel<i32>| {
+        a != b && (a.0 - b.0).abs() <= 1 && (a.1 - b.1).abs() <= 1 && (a.2 - b.2).abs() <= 1
+    };
+
+    let center = (3.0, -2.0, 7.0);
+    let wireframe = SphereWireframe::<f64, i32>::new(center, 20.0, 5, 4).collect::<Vec<_>>();
+
+    for ring in 0..9 {
+        let voxels = wireframe
+            .iter()
+            .filter(|&&(r, _)| r == ring)
+            .map(|&(_, voxel)| voxel)
+            .collect::<Vec<_>>();
+
+        assert!(voxels.len() > 4);
+
+        // Every voxel is next to the one before it, including around the end of the ring
+        for (i, &voxel) in voxels.iter().enumerate() {
+            assert!(connected(voxel, voxels[(i + 1) % voxels.len()]));
+
+            let distance = ((f64::from(voxel.0) - center.0).powi(2)
+                + (f64::from(voxel.1) - center.1).powi(2)
+                + (f64::from(voxel.2) - center.2).powi(2))
+            .sqrt();
+            assert!((distance - 20.0).abs() < 1.5);
+        }
+    }
+
+    assert_eq!(SphereWireframe::<f64, i32>::new(center, 20.0, 0, 0).count(), 0);
+
+    // The equator stays flat
+    assert!(SphereWireframe::<f64, i32>::new(center, 20.0, 1, 0).all(|(_, voxel)| voxel.2 == 7));
+
+    for &(start, end) in &[
+        ((23.0, -2.0, 7.0), (3.0, 18.0, 7.0)),
+        ((10.0, 10.0, 30.0), (-20.0, -5.0, -1.0)),
+        ((23.0, -2.0, 7.0), (-17.0, -2.0, 7.0)),
+        ((3.0, -2.0, 50.0), (3.0, -2.0, -50.0)),
+    ] {
+        let arc = GeodesicArc::<f64, i32>::new(center, 20.0, start, end).collect::<Vec<_>>();
+
+        for pair in arc.windows(2) {
+            assert!(connected(pair[0], pair[1]));
+        }
+
+        let round = |point: (f64, f64, f64)| {
+            let direction = (point.0 - center.0, point.1 - center.1, point.2 - center.2);
+            let length = (direction.0.powi(2) + direction.1.powi(2) + direction.2.powi(2)).sqrt();
+            let scale = 20.0 / length;
+
+            (
+                (center.0 + direction.0 * scale).round() as i32,
+                (center.1 + direction.1 * scale).round() as i32,
+                (center.2 + direction.2 * scale).round() as i32,
+            )
+        };
+
+        assert_eq!(arc.first(), Some(&round(start)));
+        assert_eq!(arc.last(), Some(&round(end)));
+    }
+}
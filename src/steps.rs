@@ -1,5 +1,7 @@
 //! An iterator that returns `(start, end)` tuples from the walk.
 
+use {Direction, Point, SignedNum, Voxel};
+
 /// An iterator that returns `(start, end)` tuples from the walk.
 ///
 /// All the algorithms in this crate should have a `steps()` function associated with them to turn
@@ -31,9 +33,13 @@
 ///
 /// [`Steps`]: struct.Steps.html
 /// [`WalkGrid`]: ../struct.WalkGrid.html
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Steps<T, I> {
     iterator: I,
     prev: Option<T>,
+    // The start of the last step taken from the back, which is the end of the one before it
+    back: Option<T>,
 }
 
 impl<T: Copy, I: Iterator<Item = T>> Steps<T, I> {
@@ -42,24 +48,433 @@ impl<T: Copy, I: Iterator<Item = T>> Steps<T, I> {
         Self {
             prev: iterator.next(),
             iterator,
+            back: None,
         }
     }
 }
 
+impl<T: StepHeading, I: Iterator<Item = T>> Steps<T, I> {
+    /// Turn each step into the direction it was taken in.
+    ///
+    /// See [`Headings`] for details.
+    ///
+    /// [`Headings`]: struct.Headings.html
+    #[inline]
+    pub fn headings(self) -> Headings<T, I> {
+        Headings { steps: self }
+    }
+}
+
+impl<T: StepMove, I: Iterator<Item = T>> Steps<T, I> {
+    /// Turn each step into the change in position it makes.
+    ///
+    /// See [`Moves`] for details.
+    ///
+    /// [`Moves`]: struct.Moves.html
+    #[inline]
+    pub fn moves(self) -> Moves<T, I> {
+        Moves { steps: self }
+    }
+}
+
 impl<T: Copy, I: Iterator<Item = T>> Iterator for Steps<T, I> {
     type Item = (T, T);
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        self.iterator.next().and_then(|next| {
-            self.prev.map(|prev| {
-                self.prev = Some(next);
-                (prev, next)
-            })
+        let next = self.iterator.next().or_else(|| self.back.take())?;
+
+        self.prev.map(|prev| {
+            self.prev = Some(next);
+            (prev, next)
         })
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Each step ends at the next point, so there's one less step than there are points
+        if self.prev.is_some() {
+            let back = self.back.is_some() as usize;
+            let (low, high) = self.iterator.size_hint();
+
+            (
+                low.saturating_add(back),
+                high.and_then(|high| high.checked_add(back)),
+            )
+        } else {
+            (0, Some(0))
+        }
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        if self.prev.is_some() {
+            self.iterator.count() + self.back.is_some() as usize
+        } else {
+            0
+        }
+    }
+}
+
+impl<T: Copy, I: DoubleEndedIterator<Item = T>> DoubleEndedIterator for Steps<T, I> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let end = self.back.take().or_else(|| self.iterator.next_back())?;
+
+        match self.iterator.next_back() {
+            Some(start) => {
+                self.back = Some(start);
+                Some((start, end))
+            }
+            // Only the first point is left before it, so this is the last step
+            None => self.prev.take().map(|start| (start, end)),
+        }
+    }
+}
+
+impl<T: Copy, I: ExactSizeIterator<Item = T>> ExactSizeIterator for Steps<T, I> {}
+
+/// One of the eight directions of a step on a grid, including the diagonals.
+///
+/// Like [`Direction`], `x` increases to the east and `y` increases to the south.
+///
+/// [`Direction`]: ../enum.Direction.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Heading {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Heading {
+    /// The change in position from taking a step in this direction.
+    #[inline]
+    pub fn offset<T: SignedNum>(self) -> Point<T> {
+        let (x, y) = match self {
+            Heading::North => (0, -1),
+            Heading::NorthEast => (1, -1),
+            Heading::East => (1, 0),
+            Heading::SouthEast => (1, 1),
+            Heading::South => (0, 1),
+            Heading::SouthWest => (-1, 1),
+            Heading::West => (-1, 0),
+            Heading::NorthWest => (-1, -1),
+        };
+
+        (T::cast(x), T::cast(y))
+    }
+
+    /// The direction pointing the other way.
+    #[inline]
+    pub fn opposite(self) -> Self {
+        match self {
+            Heading::North => Heading::South,
+            Heading::NorthEast => Heading::SouthWest,
+            Heading::East => Heading::West,
+            Heading::SouthEast => Heading::NorthWest,
+            Heading::South => Heading::North,
+            Heading::SouthWest => Heading::NorthEast,
+            Heading::West => Heading::East,
+            Heading::NorthWest => Heading::SouthEast,
+        }
+    }
+
+    /// Whether the step changes both `x` and `y`.
+    #[inline]
+    pub fn is_diagonal(self) -> bool {
+        matches!(
+            self,
+            Heading::NorthEast | Heading::SouthEast | Heading::SouthWest | Heading::NorthWest
+        )
+    }
+
+    /// The direction of a step from one point to another, going by which way it moves along each
+    /// axis. Returns `None` if it doesn't move at all.
+    #[inline]
+    pub fn between<T: SignedNum>(start: Point<T>, end: Point<T>) -> Option<Self> {
+        let x = (end.0 - start.0).signum();
+        let y = (end.1 - start.1).signum();
+        let (zero, one) = (T::zero(), T::one());
+
+        match (x == zero, y == zero) {
+            (true, true) => None,
+            (true, false) if y == one => Some(Heading::South),
+            (true, false) => Some(Heading::North),
+            (false, true) if x == one => Some(Heading::East),
+            (false, true) => Some(Heading::West),
+            (false, false) => Some(match (x == one, y == one) {
+                (true, true) => Heading::SouthEast,
+                (true, false) => Heading::NorthEast,
+                (false, true) => Heading::SouthWest,
+                (false, false) => Heading::NorthWest,
+            }),
+        }
+    }
+}
+
+impl From<Direction> for Heading {
+    #[inline]
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::North => Heading::North,
+            Direction::East => Heading::East,
+            Direction::South => Heading::South,
+            Direction::West => Heading::West,
+        }
+    }
+}
+
+/// Which way a 3D step moves along the `z` axis. `Up` is the way `z` increases.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Vertical {
+    Up,
+    Down,
+}
+
+/// The direction of a step through a 3D grid, split into the part along `x` and `y` and the part
+/// along `z`.
+///
+/// At least one of the two is always set, and both are for steps that go diagonally between the
+/// layers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Heading3d {
+    pub horizontal: Option<Heading>,
+    pub vertical: Option<Vertical>,
+}
+
+impl Heading3d {
+    /// The change in position from taking a step in this direction.
+    #[inline]
+    pub fn offset<T: SignedNum>(self) -> Voxel<T> {
+        let (x, y) = self.horizontal.map_or((T::zero(), T::zero()), Heading::offset);
+        let z = match self.vertical {
+            Some(Vertical::Up) => T::one(),
+            Some(Vertical::Down) => -T::one(),
+            None => T::zero(),
+        };
+
+        (x, y, z)
+    }
+
+    /// The direction pointing the other way.
+    #[inline]
+    pub fn opposite(self) -> Self {
+        Self {
+            horizontal: self.horizontal.map(Heading::opposite),
+            vertical: self.vertical.map(|vertical| match vertical {
+                Vertical::Up => Vertical::Down,
+                Vertical::Down => Vertical::Up,
+            }),
+        }
+    }
+
+    /// The direction of a step from one voxel to another, going by which way it moves along each
+    /// axis. Returns `None` if it doesn't move at all.
+    #[inline]
+    pub fn between<T: SignedNum>(start: Voxel<T>, end: Voxel<T>) -> Option<Self> {
+        let horizontal = Heading::between((start.0, start.1), (end.0, end.1));
+        let vertical = if end.2 > start.2 {
+            Some(Vertical::Up)
+        } else if end.2 < start.2 {
+            Some(Vertical::Down)
+        } else {
+            None
+        };
+
+        if horizontal.is_none() && vertical.is_none() {
+            None
+        } else {
+            Some(Self {
+                horizontal,
+                vertical,
+            })
+        }
+    }
+}
+
+/// The points that a [`Headings`] iterator can find the direction between.
+///
+/// [`Headings`]: struct.Headings.html
+pub trait StepHeading: Copy {
+    /// The kind of direction that steps between these points are taken in.
+    type Heading;
+
+    /// The direction of the step from `start` to `end`, or `None` if they're the same.
+    fn heading(start: Self, end: Self) -> Option<Self::Heading>;
+}
+
+impl<T: SignedNum> StepHeading for Point<T> {
+    type Heading = Heading;
+
+    #[inline]
+    fn heading(start: Self, end: Self) -> Option<Heading> {
+        Heading::between(start, end)
+    }
+}
+
+impl<T: SignedNum> StepHeading for Voxel<T> {
+    type Heading = Heading3d;
+
+    #[inline]
+    fn heading(start: Self, end: Self) -> Option<Heading3d> {
+        Heading3d::between(start, end)
+    }
+}
+
+/// An iterator that returns the direction of each step of a walk, instead of the points at either
+/// end of it.
+///
+/// Each step is turned into a [`Heading`] for 2D walks, or a [`Heading3d`] for 3D ones, which is
+/// what things like pathfinding and replays tend to want. Steps that go further than one cell give
+/// the direction they head in, and ones that don't move at all are `None`, which never happens
+/// with the lines in this crate.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::Bresenham;
+///
+/// fn main() {
+///     for heading in Bresenham::new((0, 0), (3, -2)).steps().headings() {
+///         print!("{:?}, ", heading.unwrap());
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// East, NorthEast, NorthEast,
+/// ```
+///
+/// [`Heading`]: enum.Heading.html
+/// [`Heading3d`]: struct.Heading3d.html
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Headings<T, I> {
+    steps: Steps<T, I>,
+}
+
+impl<T: StepHeading, I: Iterator<Item = T>> Iterator for Headings<T, I> {
+    type Item = Option<T::Heading>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.steps
+            .next()
+            .map(|(start, end)| T::heading(start, end))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.steps.size_hint()
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.steps.count()
+    }
+}
+
+impl<T: StepHeading, I: DoubleEndedIterator<Item = T>> DoubleEndedIterator for Headings<T, I> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.steps
+            .next_back()
+            .map(|(start, end)| T::heading(start, end))
+    }
+}
+
+impl<T: StepHeading, I: ExactSizeIterator<Item = T>> ExactSizeIterator for Headings<T, I> {}
+
+/// The points that a [`Moves`] iterator can find the change in position between.
+///
+/// [`Moves`]: struct.Moves.html
+pub trait StepMove: Copy {
+    /// The change in position from `start` to `end`.
+    fn delta(start: Self, end: Self) -> Self;
+}
+
+impl<T: SignedNum> StepMove for Point<T> {
+    #[inline]
+    fn delta(start: Self, end: Self) -> Self {
+        (end.0 - start.0, end.1 - start.1)
+    }
+}
+
+impl<T: SignedNum> StepMove for Voxel<T> {
+    #[inline]
+    fn delta(start: Self, end: Self) -> Self {
+        (end.0 - start.0, end.1 - start.1, end.2 - start.2)
+    }
 }
 
+/// An iterator that returns the change in position of each step of a walk, instead of the points
+/// at either end of it.
+///
+/// This is `(dx, dy)` for 2D walks and `(dx, dy, dz)` for 3D ones, for anything that's moved
+/// relative to where it is, like agents, turtles and plotters. Adding them all up to the first
+/// point gives the last one.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::WalkGrid;
+///
+/// fn main() {
+///     for (dx, dy) in WalkGrid::new((0, 0), (2, -1)).steps().moves() {
+///         print!("({}, {}), ", dx, dy);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// (1, 0), (0, -1), (1, 0),
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Moves<T, I> {
+    steps: Steps<T, I>,
+}
+
+impl<T: StepMove, I: Iterator<Item = T>> Iterator for Moves<T, I> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.steps.next().map(|(start, end)| T::delta(start, end))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.steps.size_hint()
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.steps.count()
+    }
+}
+
+impl<T: StepMove, I: DoubleEndedIterator<Item = T>> DoubleEndedIterator for Moves<T, I> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.steps
+            .next_back()
+            .map(|(start, end)| T::delta(start, end))
+    }
+}
+
+impl<T: StepMove, I: ExactSizeIterator<Item = T>> ExactSizeIterator for Moves<T, I> {}
+
 #[test]
 fn steps() {
     use Midpoint;
@@ -76,3 +491,133 @@ fn steps() {
         ]
     );
 }
+
+#[test]
+fn headings() {
+    use {Bresenham3d, Supercover, WalkGrid};
+
+    let headings = |start, end| {
+        Supercover::new(start, end)
+            .steps()
+            .headings()
+            .map(Option::unwrap)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        headings((0, 0), (2, -2)),
+        [Heading::NorthEast, Heading::NorthEast]
+    );
+    assert_eq!(
+        headings((0, 0), (-2, 1)),
+        [Heading::West, Heading::South, Heading::West]
+    );
+    assert_eq!(Supercover::new((3, 3), (3, 3)).steps().headings().count(), 0);
+
+    // The headings follow the walk, and match the directions it reports
+    let walk = WalkGrid::new((4, -2), (-3, 5));
+    let mut point = (4, -2);
+    for (heading, (_, direction)) in walk.clone().steps().headings().zip(walk.directions().skip(1)) {
+        let heading = heading.unwrap();
+        assert_eq!(heading, Heading::from(direction.unwrap()));
+        assert!(!heading.is_diagonal());
+
+        let (x, y): Point<i32> = heading.offset();
+        point = (point.0 + x, point.1 + y);
+    }
+    assert_eq!(point, (-3, 5));
+
+    for heading in headings((0, 0), (-5, -5)) {
+        assert_eq!(heading.opposite().opposite(), heading);
+        assert_eq!(heading.opposite().offset::<i32>(), (1, 1));
+    }
+
+    let mut voxel = (1, 2, 3);
+    for heading in Bresenham3d::new((1, 2, 3), (-2, 4, 9)).steps().headings() {
+        let heading = heading.unwrap();
+        assert_eq!(heading.vertical, Some(Vertical::Up));
+        assert_eq!(heading.opposite().vertical, Some(Vertical::Down));
+
+        let (x, y, z): Voxel<i32> = heading.offset();
+        voxel = (voxel.0 + x, voxel.1 + y, voxel.2 + z);
+    }
+    assert_eq!(voxel, (-2, 4, 9));
+
+    assert_eq!(Heading::between((1, 1), (1, 1)), None);
+    assert_eq!(Heading3d::between((1, 1, 1), (1, 1, 1)), None);
+    assert_eq!(
+        Heading3d::between((0, 0, 0), (0, 0, -4)),
+        Some(Heading3d {
+            horizontal: None,
+            vertical: Some(Vertical::Down),
+        })
+    );
+}
+
+#[test]
+fn moves() {
+    use {Bresenham, Bresenham3d};
+
+    assert_eq!(
+        Bresenham::new((0, 0), (3, -2))
+            .steps()
+            .moves()
+            .collect::<Vec<_>>(),
+        [(1, 0), (1, -1), (1, -1)]
+    );
+    assert_eq!(Bresenham::new((5, 5), (5, 5)).steps().moves().count(), 0);
+
+    // Adding up the moves gets to the end
+    let (x, y, z) = Bresenham3d::new((1, 2, 3), (-4, 7, 0))
+        .steps()
+        .moves()
+        .fold((1, 2, 3), |voxel, (x, y, z)| {
+            assert!(x * x + y * y + z * z <= 3);
+            (voxel.0 + x, voxel.1 + y, voxel.2 + z)
+        });
+    assert_eq!((x, y, z), (-4, 7, 0));
+}
+
+#[test]
+fn double_ended() {
+    use Bresenham;
+
+    // None of the lines can be walked backwards, but the points of one can
+    let points = Bresenham::new((0, 0), (5, 2)).collect::<Vec<_>>();
+    let steps = || Steps::new(points.iter().cloned());
+    let forwards = steps().collect::<Vec<_>>();
+
+    let mut backwards = steps().rev().collect::<Vec<_>>();
+    backwards.reverse();
+    assert_eq!(forwards, backwards);
+
+    // Taking from both ends meets in the middle, and the length stays right the whole time
+    for split in 0..forwards.len() + 1 {
+        let mut steps = steps();
+        let mut front = Vec::new();
+        let mut back = Vec::new();
+
+        for _ in 0..split {
+            front.extend(steps.next());
+            assert_eq!(steps.len(), steps.clone().fold(0, |count, _| count + 1));
+        }
+
+        while let Some(step) = steps.next_back() {
+            back.push(step);
+            assert_eq!(steps.len(), steps.clone().fold(0, |count, _| count + 1));
+        }
+
+        assert_eq!(steps.next(), None);
+        back.reverse();
+        front.extend(back);
+        assert_eq!(front, forwards);
+    }
+
+    let mut steps = steps();
+    steps.next_back();
+    assert_eq!(steps.next(), Some(((0, 0), (1, 0))));
+    assert_eq!(steps.len(), 3);
+
+    assert_eq!(Steps::new(Some((1, 1)).into_iter()).next_back(), None);
+    assert_eq!(Steps::new(Some((1, 1)).into_iter()).len(), 0);
+}
@@ -0,0 +1,151 @@
+/// An iterator that only returns the points of a line where a repeating bit pattern is set, like
+/// the line stipple from OpenGL.
+///
+/// The bits are read from the lowest one up, each one covering `factor` points, so a pattern of
+/// `0b0000_1111_0000_1111` with a factor of `1` draws four points and then skips four. It works
+/// with any kind of point, so it can be used with the 3D algorithms too.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{Bresenham, Stipple};
+///
+/// fn main() {
+///     for (x, y) in Stipple::new(Bresenham::new((0, 0), (9, 0)), 0b0011_0011_0011_0011, 1) {
+///         print!("({}, {}), ", x, y);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// (0, 0), (1, 0), (4, 0), (5, 0), (8, 0), (9, 0),
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Stipple<I> {
+    iterator: I,
+    pattern: u64,
+    length: u32,
+    factor: usize,
+    index: usize,
+}
+
+impl<I: Iterator> Stipple<I> {
+    /// Use a 16-bit pattern, where each bit covers `factor` points.
+    #[inline]
+    pub fn new(iterator: I, pattern: u16, factor: usize) -> Self {
+        Self::with_length(iterator, u64::from(pattern), 16, factor)
+    }
+
+    /// Use the lowest `length` bits of a pattern, where each bit covers `factor` points.
+    ///
+    /// The length is kept between `1` and `64`, and a factor of `0` is treated as `1`.
+    #[inline]
+    pub fn with_length(iterator: I, pattern: u64, length: u32, factor: usize) -> Self {
+        Self {
+            iterator,
+            pattern,
+            length: length.clamp(1, 64),
+            factor: factor.max(1),
+            index: 0,
+        }
+    }
+
+    /// Count how many points would be drawn if there were `count` more to come.
+    #[inline]
+    fn drawn(&self, count: usize) -> usize {
+        let period = self.length as usize * self.factor;
+        let mask = if self.length == 64 {
+            !0
+        } else {
+            (1 << self.length) - 1
+        };
+
+        // Whole repeats of the pattern, then whatever is left over one bit at a time
+        let mut drawn = count / period * (self.pattern & mask).count_ones() as usize * self.factor;
+        let mut left = count - count / period * period;
+        let mut index = self.index;
+
+        while left > 0 {
+            let run = (self.factor - index % self.factor).min(left);
+
+            if self.pattern >> (index / self.factor) & 1 == 1 {
+                drawn += run;
+            }
+
+            left -= run;
+            index = (index + run) % period;
+        }
+
+        drawn
+    }
+}
+
+impl<I: Iterator> Iterator for Stipple<I> {
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let period = self.length as usize * self.factor;
+
+        loop {
+            let point = self.iterator.next()?;
+            let bit = self.index / self.factor;
+
+            self.index += 1;
+
+            if self.index == period {
+                self.index = 0;
+            }
+
+            if self.pattern >> bit & 1 == 1 {
+                return Some(point);
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (low, high) = self.iterator.size_hint();
+        (self.drawn(low), high.map(|high| self.drawn(high)))
+    }
+}
+
+#[test]
+fn tests() {
+    use {Bresenham, Bresenham3d};
+
+    let xs = |stipple: Stipple<Bresenham<i32>>| stipple.map(|(x, _)| x).collect::<Vec<_>>();
+    let line = || Bresenham::new((0, 0), (19, 0));
+
+    assert_eq!(xs(Stipple::new(line(), 0xFFFF, 1)).len(), 20);
+    assert_eq!(xs(Stipple::new(line(), 0, 1)), []);
+    assert_eq!(xs(Stipple::new(line(), 0b1, 1)), [0, 16]);
+    assert_eq!(xs(Stipple::new(line(), 0b0101, 2)), [0, 1, 4, 5]);
+    assert_eq!(
+        xs(Stipple::with_length(line(), 0b100, 3, 1)),
+        [2, 5, 8, 11, 14, 17]
+    );
+    assert_eq!(xs(Stipple::with_length(line(), 0b1, 0, 0)).len(), 20);
+    assert_eq!(xs(Stipple::with_length(line(), 1 << 63 | 1, 64, 1)), [0]);
+
+    for &(pattern, length, factor) in &[(0b1011, 4, 1), (0b0110_0001, 8, 3), (!0, 64, 1), (0, 5, 2)]
+    {
+        let mut stipple = Stipple::with_length(0..20, pattern, length, factor);
+
+        loop {
+            let count = stipple.clone().count();
+            assert_eq!(stipple.size_hint(), (count, Some(count)));
+
+            if stipple.next().is_none() {
+                break;
+            }
+        }
+    }
+
+    assert_eq!(
+        Stipple::new(Bresenham3d::new((0, 0, 0), (3, 3, 3)), 0b1010, 1).collect::<Vec<_>>(),
+        [(1, 1, 1), (3, 3, 3)]
+    );
+}
@@ -0,0 +1,136 @@
+use {Point, RealNum, SignedNum};
+use alloc::collections::{btree_map, BTreeMap, BTreeSet};
+
+/// Anti-alias any shape by drawing it at a higher resolution and counting how much of each cell
+/// it covers.
+///
+/// The points are drawn `factor` times bigger than the cells they're binned into, so the cell
+/// `(x, y)` is made out of the points from `(x * factor, y * factor)` up to but not including
+/// `((x + 1) * factor, (y + 1) * factor)`. The coverage of a cell is how many of those points were
+/// drawn, out of `factor * factor`. Repeated points are only counted once.
+///
+/// This works for anything, including filled shapes that don't have an anti-aliased version of
+/// their own, but it's much slower than the analytic algorithms like [`XiaolinWu`]. The whole
+/// shape has to be drawn before anything is returned, so the cells come out sorted by `x` and then
+/// `y`.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{Supersampled, Triangle};
+///
+/// fn main() {
+///     let triangle = Triangle::new((0, 0), (7, 0), (0, 7));
+///
+///     for (cell, coverage) in Supersampled::<i32, f32>::new(4, triangle.fill()) {
+///         print!("({:?}, {}), ", cell, coverage);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// ((0, 0), 1), ((0, 1), 0.625), ((1, 0), 0.625),
+/// ```
+///
+/// [`XiaolinWu`]: struct.XiaolinWu.html
+#[derive(Debug)]
+pub struct Supersampled<T, I> {
+    cells: btree_map::IntoIter<Point<T>, I>,
+}
+
+impl<T: SignedNum, I: RealNum> Supersampled<T, I> {
+    /// Bin points drawn at `factor` times the resolution down to cells.
+    ///
+    /// Panics if `factor` isn't positive.
+    #[inline]
+    pub fn new(factor: T, points: impl IntoIterator<Item = Point<T>>) -> Self {
+        assert!(factor > T::zero(), "the supersampling factor must be positive");
+
+        let points = points.into_iter().collect::<BTreeSet<_>>();
+        let mut counts = BTreeMap::new();
+
+        for (x, y) in points {
+            *counts
+                .entry((div_floor(x, factor), div_floor(y, factor)))
+                .or_insert(0u64) += 1;
+        }
+
+        let samples = I::cast(factor.to_u64().unwrap().pow(2));
+        let cells = counts
+            .into_iter()
+            .map(|(cell, count)| (cell, I::cast(count) / samples))
+            .collect::<BTreeMap<_, _>>();
+
+        Self {
+            cells: cells.into_iter(),
+        }
+    }
+}
+
+#[inline]
+fn div_floor<T: SignedNum>(value: T, divisor: T) -> T {
+    let quotient = value / divisor;
+
+    if value % divisor < T::zero() {
+        quotient - T::one()
+    } else {
+        quotient
+    }
+}
+
+impl<T, I> Iterator for Supersampled<T, I> {
+    type Item = (Point<T>, I);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.cells.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.cells.size_hint()
+    }
+}
+
+impl<T, I> ExactSizeIterator for Supersampled<T, I> {}
+
+#[test]
+fn tests() {
+    use {Bresenham, Circle, Rect};
+
+    // A square that lines up with the cells covers them all fully
+    let square = Supersampled::<i32, f64>::new(3, Rect::new((-3, -3), (2, 2)).fill());
+    assert_eq!(
+        square.collect::<Vec<_>>(),
+        [((-1, -1), 1.0), ((-1, 0), 1.0), ((0, -1), 1.0), ((0, 0), 1.0)]
+    );
+
+    // Moving it over by one point leaves a third of the cells on either side covered
+    let square = Supersampled::<i32, f64>::new(3, Rect::new((-2, -3), (3, 2)).fill());
+    let coverage = square.collect::<Vec<_>>();
+    assert_eq!(coverage.len(), 6);
+    assert_eq!(coverage[0], ((-1, -1), 2.0 / 3.0));
+    assert_eq!(coverage[5], ((1, 0), 1.0 / 3.0));
+
+    // Points drawn more than once only count once
+    let twice = Bresenham::new((0, 0), (3, 0)).chain(Bresenham::new((0, 0), (3, 0)));
+    assert_eq!(
+        Supersampled::<i32, f32>::new(2, twice).collect::<Vec<_>>(),
+        [((0, 0), 0.5), ((1, 0), 0.5)]
+    );
+
+    // The coverage of a circle adds up to roughly its area
+    let circle = Supersampled::<i32, f64>::new(16, Circle::new((80, 80), 64).fill());
+    assert_eq!(circle.len(), 64);
+    let area = circle.map(|(_, coverage)| coverage).sum::<f64>();
+    assert!((area - 16.0 * core::f64::consts::PI).abs() < 1.0, "{}", area);
+}
+
+#[test]
+#[should_panic]
+fn factor() {
+    use Bresenham;
+
+    Supersampled::<i32, f32>::new(0, Bresenham::new((0, 0), (1, 1)));
+}
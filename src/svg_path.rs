@@ -0,0 +1,396 @@
+use {FloatNum, Path, Point};
+use core::fmt;
+use core::str::{self, FromStr};
+
+/// Why SVG path data couldn't be parsed, with where in the string it went wrong.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ParsePathError {
+    /// The byte offset of the problem in the path data.
+    pub position: usize,
+}
+
+impl fmt::Display for ParsePathError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "invalid path data at byte {}", self.position)
+    }
+}
+
+impl core::error::Error for ParsePathError {}
+
+impl<I: FloatNum> Path<I> {
+    /// Parse the path data from the `d` attribute of an SVG `<path>` element.
+    ///
+    /// All of the commands are supported, in both their absolute and relative forms. This needs
+    /// the `svg-path` feature. Numbers that don't fit in `I` are an error, but ones that fit can
+    /// still be too big for the points, so draw paths that can't be trusted with [`try_points`].
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// extern crate line_drawing;
+    /// use line_drawing::Path;
+    ///
+    /// fn main() {
+    ///     let path = Path::<f32>::from_svg("M 0,0 h 4 v 2 H 0 z").unwrap();
+    ///     println!("{:?}", path.points::<i32>().collect::<Vec<_>>());
+    /// }
+    /// ```
+    ///
+    /// ```text
+    /// [(0, 0), (1, 0), (2, 0), (3, 0), (4, 0), (4, 1), (4, 2), (3, 2), (2, 2), (1, 2), (0, 2),
+    ///  (0, 1)]
+    /// ```
+    ///
+    /// [`try_points`]: struct.Path.html#method.try_points
+    pub fn from_svg(data: &str) -> Result<Self, ParsePathError> {
+        let mut parser = Parser {
+            data: data.as_bytes(),
+            position: 0,
+        };
+        let mut path = Self::new();
+
+        let (mut pen, mut start) = ((0.0, 0.0), (0.0, 0.0));
+        // The last control point of a curve, for the smooth curve commands to reflect
+        let mut control: Option<(u8, Point<f64>)> = None;
+        let mut command = None;
+
+        // Numbers that are too big for `I`, or that add up to too much, can't be drawn
+        let cast = |(x, y): Point<f64>, position: usize| {
+            let point = (I::cast(x), I::cast(y));
+
+            if point.0.is_finite() && point.1.is_finite() {
+                Ok(point)
+            } else {
+                Err(ParsePathError { position })
+            }
+        };
+
+        loop {
+            parser.skip_separators();
+
+            let letter = match parser.peek() {
+                None => break,
+                Some(byte) if byte.is_ascii_alphabetic() => {
+                    parser.position += 1;
+                    byte
+                }
+                // Without a new command, the last one is repeated with the next arguments
+                Some(_) => match command {
+                    Some(letter) if letter != b'Z' && letter != b'z' => letter,
+                    _ => return Err(parser.error()),
+                },
+            };
+
+            if command.is_none() && letter != b'M' && letter != b'm' {
+                return Err(parser.error_at(parser.position - 1));
+            }
+
+            let origin = if letter.is_ascii_lowercase() { pen } else { (0.0, 0.0) };
+            let offset = |(x, y): Point<f64>| (origin.0 + x, origin.1 + y);
+            let reflected = |kind: u8| match control {
+                Some((last, (x, y))) if last == kind => (pen.0 * 2.0 - x, pen.1 * 2.0 - y),
+                _ => pen,
+            };
+
+            command = Some(letter);
+            let mut curve = None;
+
+            parser.skip_separators();
+            let at = parser.position;
+
+            pen = match letter.to_ascii_uppercase() {
+                b'M' => {
+                    let point = offset(parser.point()?);
+                    path = path.move_to(cast(point, at)?);
+                    start = point;
+
+                    // Any more points after a move are lines
+                    command = Some(if letter == b'M' { b'L' } else { b'l' });
+                    point
+                }
+                b'L' => {
+                    let point = offset(parser.point()?);
+                    path = path.line_to(cast(point, at)?);
+                    point
+                }
+                b'H' => {
+                    let point = (origin.0 + parser.number()?, pen.1);
+                    path = path.line_to(cast(point, at)?);
+                    point
+                }
+                b'V' => {
+                    let point = (pen.0, origin.1 + parser.number()?);
+                    path = path.line_to(cast(point, at)?);
+                    point
+                }
+                b'C' | b'S' => {
+                    let first = if letter.eq_ignore_ascii_case(&b'C') {
+                        offset(parser.point()?)
+                    } else {
+                        reflected(b'C')
+                    };
+                    let (second, end) = (offset(parser.point()?), offset(parser.point()?));
+
+                    path = path.cubic_to(cast(first, at)?, cast(second, at)?, cast(end, at)?);
+                    curve = Some((b'C', second));
+                    end
+                }
+                b'Q' | b'T' => {
+                    let middle = if letter.eq_ignore_ascii_case(&b'Q') {
+                        offset(parser.point()?)
+                    } else {
+                        reflected(b'Q')
+                    };
+                    let end = offset(parser.point()?);
+
+                    path = path.quad_to(cast(middle, at)?, cast(end, at)?);
+                    curve = Some((b'Q', middle));
+                    end
+                }
+                b'A' => {
+                    let radius = parser.point()?;
+                    let rotation = parser.number()?.to_radians();
+                    let (large_arc, sweep) = (parser.flag()?, parser.flag()?);
+                    let end = offset(parser.point()?);
+
+                    let rotation = I::cast(rotation);
+
+                    if !rotation.is_finite() {
+                        return Err(parser.error_at(at));
+                    }
+
+                    path = path.arc_to(
+                        cast(radius, at)?,
+                        rotation,
+                        large_arc,
+                        sweep,
+                        cast(end, at)?,
+                    );
+                    end
+                }
+                b'Z' => {
+                    path = path.close();
+                    start
+                }
+                _ => return Err(parser.error_at(parser.position - 1)),
+            };
+
+            control = curve;
+        }
+
+        Ok(path)
+    }
+}
+
+impl<I: FloatNum> FromStr for Path<I> {
+    type Err = ParsePathError;
+
+    #[inline]
+    fn from_str(data: &str) -> Result<Self, Self::Err> {
+        Self::from_svg(data)
+    }
+}
+
+struct Parser<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    #[inline]
+    fn peek(&self) -> Option<u8> {
+        self.data.get(self.position).copied()
+    }
+
+    #[inline]
+    fn error(&self) -> ParsePathError {
+        self.error_at(self.position)
+    }
+
+    #[inline]
+    fn error_at(&self, position: usize) -> ParsePathError {
+        ParsePathError { position }
+    }
+
+    #[inline]
+    fn skip_separators(&mut self) {
+        while let Some(b' ') | Some(b'\t') | Some(b'\n') | Some(b'\r') | Some(b',') = self.peek() {
+            self.position += 1;
+        }
+    }
+
+    #[inline]
+    fn skip_digits(&mut self) -> usize {
+        let start = self.position;
+
+        while self.peek().is_some_and(|byte| byte.is_ascii_digit()) {
+            self.position += 1;
+        }
+
+        self.position - start
+    }
+
+    /// Read a number, which can run straight on from the one before, like the two in `1-2` or
+    /// `0.5.5`.
+    fn number(&mut self) -> Result<f64, ParsePathError> {
+        self.skip_separators();
+        let start = self.position;
+
+        if let Some(b'+') | Some(b'-') = self.peek() {
+            self.position += 1;
+        }
+
+        let mut digits = self.skip_digits();
+
+        if self.peek() == Some(b'.') {
+            self.position += 1;
+            digits += self.skip_digits();
+        }
+
+        if digits == 0 {
+            return Err(self.error_at(start));
+        }
+
+        if let Some(b'e') | Some(b'E') = self.peek() {
+            self.position += 1;
+
+            if let Some(b'+') | Some(b'-') = self.peek() {
+                self.position += 1;
+            }
+
+            if self.skip_digits() == 0 {
+                return Err(self.error());
+            }
+        }
+
+        // Only ASCII has been skipped over, so this is always valid
+        str::from_utf8(&self.data[start..self.position])
+            .ok()
+            .and_then(|number| number.parse().ok())
+            .ok_or_else(|| self.error_at(start))
+    }
+
+    #[inline]
+    fn point(&mut self) -> Result<Point<f64>, ParsePathError> {
+        Ok((self.number()?, self.number()?))
+    }
+
+    /// Read one of the flags of an arc, which are a single `0` or `1` that don't need anything
+    /// between them and the next argument.
+    #[inline]
+    fn flag(&mut self) -> Result<bool, ParsePathError> {
+        self.skip_separators();
+
+        let flag = match self.peek() {
+            Some(b'0') => false,
+            Some(b'1') => true,
+            _ => return Err(self.error()),
+        };
+
+        self.position += 1;
+        Ok(flag)
+    }
+}
+
+#[test]
+fn tests() {
+    use PathSegment;
+
+    let parse = |data: &str| Path::<f64>::from_svg(data).map(|path| path.segments().to_vec());
+
+    assert_eq!(
+        Path::from_svg("M0 0 L4 0 Q4 4 0 4 Z"),
+        Ok(Path::<f32>::new()
+            .move_to((0.0, 0.0))
+            .line_to((4.0, 0.0))
+            .quad_to((4.0, 4.0), (0.0, 4.0))
+            .close())
+    );
+    assert_eq!("M 1 2".parse::<Path<f64>>(), Ok(Path::new().move_to((1.0, 2.0))));
+    assert_eq!(parse(""), Ok(Vec::new()));
+
+    // Relative commands, and points after a move being lines
+    assert_eq!(
+        parse("m1,1 2,0 0,2 h-2 v-1 z l3 3"),
+        Ok(vec![
+            PathSegment::MoveTo((1.0, 1.0)),
+            PathSegment::LineTo((3.0, 1.0)),
+            PathSegment::LineTo((3.0, 3.0)),
+            PathSegment::LineTo((1.0, 3.0)),
+            PathSegment::LineTo((1.0, 2.0)),
+            PathSegment::Close,
+            PathSegment::LineTo((4.0, 4.0)),
+        ])
+    );
+
+    // Numbers without anything between them
+    assert_eq!(
+        parse("M1-2.5.5-0L-.5e1+1E-1"),
+        Ok(vec![
+            PathSegment::MoveTo((1.0, -2.5)),
+            PathSegment::LineTo((0.5, 0.0)),
+            PathSegment::LineTo((-5.0, 0.1)),
+        ])
+    );
+
+    // Smooth curves reflect the last control point
+    assert_eq!(
+        parse("M0 0 C0 1 2 1 2 0 S4 -1 4 0 s1 1 2 0 Q1 1 2 2 T4 4 t2 2"),
+        Ok(vec![
+            PathSegment::MoveTo((0.0, 0.0)),
+            PathSegment::CubicTo((0.0, 1.0), (2.0, 1.0), (2.0, 0.0)),
+            PathSegment::CubicTo((2.0, -1.0), (4.0, -1.0), (4.0, 0.0)),
+            PathSegment::CubicTo((4.0, 1.0), (5.0, 1.0), (6.0, 0.0)),
+            PathSegment::QuadTo((1.0, 1.0), (2.0, 2.0)),
+            PathSegment::QuadTo((3.0, 3.0), (4.0, 4.0)),
+            PathSegment::QuadTo((5.0, 5.0), (6.0, 6.0)),
+        ])
+    );
+    assert_eq!(
+        parse("M1 1 T3 1").unwrap()[1],
+        PathSegment::QuadTo((1.0, 1.0), (3.0, 1.0))
+    );
+
+    // The arc flags can be squashed together with the next argument
+    assert_eq!(
+        parse("M0 0 a5 4 90 1010 0"),
+        Ok(vec![
+            PathSegment::MoveTo((0.0, 0.0)),
+            PathSegment::ArcTo {
+                radius: (5.0, 4.0),
+                rotation: core::f64::consts::FRAC_PI_2,
+                large_arc: true,
+                sweep: false,
+                end: (10.0, 0.0),
+            },
+        ])
+    );
+
+    assert_eq!(parse("L1 1"), Err(ParsePathError { position: 0 }));
+    assert_eq!(parse("M1 1 X"), Err(ParsePathError { position: 5 }));
+    assert_eq!(parse("M1 1 L2"), Err(ParsePathError { position: 7 }));
+    assert_eq!(parse("M1 1 Z 3 3"), Err(ParsePathError { position: 7 }));
+    assert_eq!(parse("M1 1 A1 1 0 2 0 3 3"), Err(ParsePathError { position: 12 }));
+    assert_eq!(parse("M1e 2"), Err(ParsePathError { position: 3 }));
+    assert_eq!(parse("M- 2"), Err(ParsePathError { position: 1 }));
+
+    // Numbers that don't fit, even once they're added up, are errors instead of infinite
+    assert_eq!(parse("M 0 0 L 1e400 0"), Err(ParsePathError { position: 8 }));
+    assert_eq!(
+        Path::<f32>::from_svg("M 0 0 L 1e250 0"),
+        Err(ParsePathError { position: 8 })
+    );
+    assert_eq!(
+        Path::<f32>::from_svg("M 0 0 h 3e38 h 3e38"),
+        Err(ParsePathError { position: 15 })
+    );
+    assert_eq!(
+        Path::<f32>::from_svg("M 0 0 A 1 1 1e300 0 0 2 2"),
+        Err(ParsePathError { position: 8 })
+    );
+    assert!(Path::<f32>::from_svg("M 0 0 L 3e9 0").is_ok());
+    assert_eq!(
+        ParsePathError { position: 7 }.to_string(),
+        "invalid path data at byte 7"
+    );
+}
@@ -0,0 +1,287 @@
+use {FloatNum, IntoPoint, Point, SignedNum};
+use steps::Steps;
+
+/// How far along the segment it leaves the current tile on one axis.
+#[inline]
+fn exit<I: FloatNum, O: SignedNum>(tile: O, sign: O, start: I, delta: I, size: I) -> I {
+    if sign == O::zero() {
+        return I::infinity();
+    }
+
+    let edge = if sign > O::zero() {
+        tile + O::one()
+    } else {
+        tile
+    };
+
+    (I::cast(edge) * size - start) / delta
+}
+
+/// Count how many more edges the segment crosses on one axis.
+#[inline]
+fn crossings<I: FloatNum, O: SignedNum>(tile: O, sign: O, start: I, delta: I, size: I) -> usize {
+    if sign == O::zero() {
+        return 0;
+    }
+
+    // Estimate from the tile the end is in, then correct it with the same test the walk uses
+    let end = ((start + delta) / size).floor();
+    let estimate = (I::cast(tile) - end).abs().to_usize().unwrap_or(0);
+    let edge = |count: usize| tile + sign * O::cast(count);
+
+    let mut count = estimate.saturating_sub(1);
+
+    while exit(edge(count), sign, start, delta, size) < I::one() {
+        count += 1;
+    }
+
+    while count > 0 && exit(edge(count - 1), sign, start, delta, size) >= I::one() {
+        count -= 1;
+    }
+
+    count
+}
+
+/// Find the tiles that a segment passes through, for tiles of any size.
+///
+/// Tile `(x, y)` covers the area from `(x, y) * tile_size` up to but not including
+/// `(x + 1, y + 1) * tile_size`. The tiles are returned in order from `start` to `end`. Where the
+/// segment passes exactly through a corner, it steps diagonally past the tiles that it only
+/// touches, and tiles that it only touches at `end` aren't included.
+///
+/// This is the coarse walk that tile-based renderers and broad-phase collision detection do before
+/// looking at the cells inside each tile. Use [`spans`] to also find the part of the segment in
+/// each tile.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::WalkTiles;
+///
+/// fn main() {
+///     for (x, y) in WalkTiles::<f32, i8>::new((4.0, 4.0), (40.0, 20.0), (16.0, 16.0)) {
+///         print!("({}, {}), ", x, y);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// (0, 0), (1, 0), (1, 1), (2, 1),
+/// ```
+///
+/// [`spans`]: #method.spans
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WalkTiles<I, O> {
+    tile: Point<O>,
+    sign: Point<O>,
+    start: Point<I>,
+    delta: Point<I>,
+    tile_size: Point<I>,
+    t: I,
+    done: bool,
+}
+
+impl<I: FloatNum, O: SignedNum> WalkTiles<I, O> {
+    #[inline]
+    pub fn new(start: impl IntoPoint<I>, end: impl IntoPoint<I>, tile_size: Point<I>) -> Self {
+        let (start, end) = (start.into_point(), end.into_point());
+
+        let tile = (
+            O::cast((start.0 / tile_size.0).floor()),
+            O::cast((start.1 / tile_size.1).floor()),
+        );
+        let delta = (end.0 - start.0, end.1 - start.1);
+
+        let sign = |delta: I| {
+            if delta > I::zero() {
+                O::one()
+            } else if delta < I::zero() {
+                -O::one()
+            } else {
+                O::zero()
+            }
+        };
+
+        Self {
+            tile,
+            sign: (sign(delta.0), sign(delta.1)),
+            start,
+            delta,
+            tile_size,
+            t: I::zero(),
+            done: false,
+        }
+    }
+
+    #[inline]
+    pub fn steps(self) -> Steps<Point<O>, Self> {
+        Steps::new(self)
+    }
+
+    /// Also return the part of the segment inside each tile.
+    ///
+    /// See [`TileSpans`] for details.
+    ///
+    /// [`TileSpans`]: struct.TileSpans.html
+    #[inline]
+    pub fn spans(self) -> TileSpans<I, O> {
+        TileSpans { walk: self }
+    }
+
+    /// Return the next tile along with the part of the segment inside it.
+    #[inline]
+    fn advance(&mut self) -> Option<(Point<O>, (I, I))> {
+        if self.done {
+            return None;
+        }
+
+        let tile = self.tile;
+        let (start, delta, size) = (self.start, self.delta, self.tile_size);
+        let next_x = exit(tile.0, self.sign.0, start.0, delta.0, size.0);
+        let next_y = exit(tile.1, self.sign.1, start.1, delta.1, size.1);
+
+        let enter = self.t;
+        let leave = next_x.min(next_y).min(I::one());
+
+        if leave >= I::one() {
+            self.done = true;
+        } else {
+            // If both edges are crossed at once then step diagonally
+            if next_x == leave {
+                self.tile.0 += self.sign.0;
+            }
+
+            if next_y == leave {
+                self.tile.1 += self.sign.1;
+            }
+
+            self.t = leave;
+        }
+
+        Some((tile, (enter, leave)))
+    }
+
+    /// Each step crosses an edge on one axis or both at once.
+    #[inline]
+    fn remaining(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+
+        let (start, delta, size) = (self.start, self.delta, self.tile_size);
+        let x = crossings(self.tile.0, self.sign.0, start.0, delta.0, size.0);
+        let y = crossings(self.tile.1, self.sign.1, start.1, delta.1, size.1);
+
+        (
+            x.max(y) + 1,
+            x.checked_add(y).and_then(|steps| steps.checked_add(1)),
+        )
+    }
+}
+
+impl<I: FloatNum, O: SignedNum> Iterator for WalkTiles<I, O> {
+    type Item = Point<O>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.advance().map(|(tile, _)| tile)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.remaining()
+    }
+}
+
+/// A [`WalkTiles`] that also returns the part of the segment inside each tile.
+///
+/// Each tile comes with a `(enter, exit)` pair saying where the segment enters and leaves it, from
+/// `0` at `start` to `1` at `end`. The spans of consecutive tiles join up, so together they cover
+/// the whole segment.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::WalkTiles;
+///
+/// fn main() {
+///     for (tile, span) in WalkTiles::<f32, i8>::new((8.0, 0.0), (40.0, 0.0), (16.0, 16.0)).spans() {
+///         print!("({:?}, {:?}), ", tile, span);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// ((0, 0), (0.0, 0.25)), ((1, 0), (0.25, 0.75)), ((2, 0), (0.75, 1.0)),
+/// ```
+///
+/// [`WalkTiles`]: struct.WalkTiles.html
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TileSpans<I, O> {
+    walk: WalkTiles<I, O>,
+}
+
+impl<I: FloatNum, O: SignedNum> Iterator for TileSpans<I, O> {
+    type Item = (Point<O>, (I, I));
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.walk.advance()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.walk.remaining()
+    }
+}
+
+#[test]
+fn tests() {
+    let walk_tiles = |a, b, size| WalkTiles::<f64, i32>::new(a, b, size).collect::<Vec<_>>();
+
+    assert_eq!(
+        walk_tiles((4.0, 4.0), (40.0, 20.0), (16.0, 16.0)),
+        [(0, 0), (1, 0), (1, 1), (2, 1)]
+    );
+    assert_eq!(walk_tiles((3.0, 3.0), (5.0, 5.0), (8.0, 8.0)), [(0, 0)]);
+    assert_eq!(walk_tiles((3.0, 3.0), (3.0, 3.0), (8.0, 8.0)), [(0, 0)]);
+
+    // Negative coordinates and going backwards
+    assert_eq!(
+        walk_tiles((1.0, 1.0), (-9.0, -1.5), (4.0, 4.0)),
+        [(0, 0), (-1, 0), (-1, -1), (-2, -1), (-3, -1)]
+    );
+
+    // Passing through a corner, and ending on an edge
+    assert_eq!(
+        walk_tiles((2.0, 2.0), (8.0, 8.0), (4.0, 4.0)),
+        [(0, 0), (1, 1)]
+    );
+    assert_eq!(
+        walk_tiles((1.0, 1.0), (-9.0, -1.0), (4.0, 4.0)),
+        [(0, 0), (-1, 0), (-2, -1), (-3, -1)]
+    );
+
+    // The spans join up and cover the whole segment
+    let spans = WalkTiles::<f64, i32>::new((-13.0, 7.5), (21.0, -30.0), (5.0, 3.0))
+        .spans()
+        .collect::<Vec<_>>();
+
+    let (_, (first, _)) = spans[0];
+    let (_, (_, last)) = spans[spans.len() - 1];
+    assert_eq!((first, last), (0.0, 1.0));
+
+    for pair in spans.windows(2) {
+        let ((_, (_, exit)), (_, (enter, _))) = (pair[0], pair[1]);
+        assert_eq!(exit, enter);
+    }
+
+    assert_eq!(
+        spans.iter().map(|&(tile, _)| tile).collect::<Vec<_>>(),
+        walk_tiles((-13.0, 7.5), (21.0, -30.0), (5.0, 3.0))
+    );
+}
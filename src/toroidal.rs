@@ -0,0 +1,153 @@
+use {Point, SignedNum};
+
+/// The remainder of a division that's always within `0..divisor`, for a positive divisor.
+#[inline]
+fn wrap<T: SignedNum>(value: T, divisor: T) -> T {
+    let remainder = value % divisor;
+
+    if remainder < T::zero() {
+        remainder + divisor
+    } else {
+        remainder
+    }
+}
+
+/// Find the copy of `end` on a wrapping grid of the given size that's closest to `start`.
+///
+/// On a grid where the edges wrap around, like the surface of a torus, there are several ways to
+/// get from one point to another. Drawing a line to the returned point instead of `end` and then
+/// wrapping it with [`Wrapped`] takes the shortest of them. When both ways around are the same
+/// length, the one in the positive direction is used.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::shortest_wrap;
+///
+/// fn main() {
+///     assert_eq!(shortest_wrap((1, 5), (9, 6), (10, 10)), (-1, 6));
+///     assert_eq!(shortest_wrap((1, 5), (3, 6), (10, 10)), (3, 6));
+/// }
+/// ```
+///
+/// [`Wrapped`]: struct.Wrapped.html
+#[inline]
+pub fn shortest_wrap<T: SignedNum>(start: Point<T>, end: Point<T>, size: Point<T>) -> Point<T> {
+    let axis = |start: T, end: T, size: T| {
+        let delta = wrap(end - start, size);
+
+        if delta * T::cast(2) > size {
+            start + delta - size
+        } else {
+            start + delta
+        }
+    };
+
+    (axis(start.0, end.0, size.0), axis(start.1, end.1, size.1))
+}
+
+/// An iterator that wraps points around the edges of a grid of the given size.
+///
+/// Every point is moved into the range from `(0, 0)` up to but not including `size`, so lines can
+/// go off one edge of the grid and come back on the other. Combine it with [`shortest_wrap`] to
+/// take the shortest way around.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{shortest_wrap, Bresenham};
+///
+/// fn main() {
+///     let size = (10, 10);
+///     let end = shortest_wrap((1, 5), (8, 5), size);
+///
+///     for (x, y) in Bresenham::new((1, 5), end).wrapped(size) {
+///         print!("({}, {}), ", x, y);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// (1, 5), (0, 5), (9, 5), (8, 5),
+/// ```
+///
+/// [`shortest_wrap`]: fn.shortest_wrap.html
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Wrapped<T, I> {
+    iterator: I,
+    size: Point<T>,
+}
+
+impl<T: SignedNum, I: Iterator<Item = Point<T>>> Wrapped<T, I> {
+    #[inline]
+    pub fn new(iterator: I, size: Point<T>) -> Self {
+        Self { iterator, size }
+    }
+}
+
+impl<T: SignedNum, I: Iterator<Item = Point<T>>> Iterator for Wrapped<T, I> {
+    type Item = Point<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (x, y) = self.iterator.next()?;
+
+        Some((wrap(x, self.size.0), wrap(y, self.size.1)))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iterator.size_hint()
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.iterator.count()
+    }
+
+    #[inline]
+    fn last(self) -> Option<Self::Item> {
+        let (x, y) = self.iterator.last()?;
+
+        Some((wrap(x, self.size.0), wrap(y, self.size.1)))
+    }
+}
+
+impl<T: SignedNum, I: ExactSizeIterator<Item = Point<T>>> ExactSizeIterator for Wrapped<T, I> {}
+
+#[test]
+fn tests() {
+    use {Bresenham, Supercover, WalkGrid};
+
+    let size = (8, 6);
+
+    // Shortest way around on each axis
+    assert_eq!(shortest_wrap((0, 0), (7, 5), size), (-1, -1));
+    assert_eq!(shortest_wrap((7, 5), (0, 0), size), (8, 6));
+    assert_eq!(shortest_wrap((2, 1), (4, 3), size), (4, 3));
+    assert_eq!(shortest_wrap((0, 0), (4, 3), size), (4, 3));
+    assert_eq!(shortest_wrap((3, 3), (3, 3), size), (3, 3));
+    assert_eq!(shortest_wrap((3, 2), (-7, 20), size), (1, 2));
+
+    assert_eq!(
+        Bresenham::new((0, 0), shortest_wrap((0, 0), (6, 4), size))
+            .wrapped(size)
+            .collect::<Vec<_>>(),
+        [(0, 0), (7, 5), (6, 4)]
+    );
+
+    // Points far outside the grid are wrapped too
+    for (x, y) in WalkGrid::new((-20, 13), (17, -9)).wrapped(size) {
+        assert!(x >= 0 && x < size.0 && y >= 0 && y < size.1);
+    }
+
+    assert_eq!(
+        Supercover::new((6, 0), (9, 3))
+            .wrapped(size)
+            .collect::<Vec<_>>(),
+        [(6, 0), (7, 1), (0, 2), (1, 3)]
+    );
+}
@@ -0,0 +1,259 @@
+use {FloatNum, IntoPoint, SignedNum};
+use steps::Steps;
+
+/// Which way a triangle in a triangular grid is pointing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TriangleOrientation {
+    Up,
+    Down,
+}
+
+/// Walk along a triangular grid, visiting every triangle that the segment passes through.
+///
+/// Points are given in the skewed coordinates of the grid, where the corners of the triangles are
+/// at integer positions and the two axes are 60 degrees apart. A point `(x, y)` in world space is
+/// at `(x - y / √3, 2 * y / √3)` in grid coordinates, for triangles with sides of length `1`.
+///
+/// Each parallelogram `(x, y)` between four corners is split into an [`Up`] triangle with corners
+/// at `(x, y)`, `(x + 1, y)` and `(x, y + 1)`, and a [`Down`] triangle with corners at
+/// `(x + 1, y)`, `(x, y + 1)` and `(x + 1, y + 1)`. The triangles are returned as
+/// `(x, y, orientation)`.
+///
+/// Consecutive triangles always share an edge, so when the segment passes exactly through a
+/// corner, the triangles on one side of it are included.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::WalkTriangles;
+///
+/// fn main() {
+///     for (x, y, orientation) in WalkTriangles::<f32, i8>::new((0.2, 0.2), (2.5, 0.3)) {
+///         print!("({}, {}, {:?}), ", x, y, orientation);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// (0, 0, Up), (0, 0, Down), (1, 0, Up), (1, 0, Down), (2, 0, Up),
+/// ```
+///
+/// [`Up`]: enum.TriangleOrientation.html#variant.Up
+/// [`Down`]: enum.TriangleOrientation.html#variant.Down
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WalkTriangles<I, O> {
+    // The strips between the lines of constant `x`, `y` and `x + y` that the walk is in
+    strips: (O, O, O),
+    signs: (O, O, O),
+    next_t: (I, I, I),
+    delta_t: (I, I, I),
+    done: bool,
+}
+
+/// Set up the walk across the strips between one set of parallel grid lines.
+#[inline]
+fn strip<I: FloatNum, O: SignedNum>(start: I, delta: I) -> (O, O, I, I) {
+    let floor = start.floor();
+
+    if delta > I::zero() {
+        (
+            O::cast(floor),
+            O::one(),
+            (floor + I::one() - start) / delta,
+            delta.recip(),
+        )
+    } else if delta < I::zero() {
+        // Starting exactly on a line going backwards is the same as starting in the strip behind it
+        let floor = if floor == start {
+            floor - I::one()
+        } else {
+            floor
+        };
+        (
+            O::cast(floor),
+            -O::one(),
+            (floor - start) / delta,
+            -delta.recip(),
+        )
+    } else {
+        (O::cast(floor), O::zero(), I::infinity(), I::zero())
+    }
+}
+
+impl<I: FloatNum, O: SignedNum> WalkTriangles<I, O> {
+    #[inline]
+    pub fn new(start: impl IntoPoint<I>, end: impl IntoPoint<I>) -> Self {
+        let (start, end) = (start.into_point(), end.into_point());
+
+        let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+
+        let x = strip(start.0, dx);
+        let y = strip(start.1, dy);
+        let diagonal = strip(start.0 + start.1, dx + dy);
+
+        Self {
+            strips: (x.0, y.0, diagonal.0),
+            signs: (x.1, y.1, diagonal.1),
+            next_t: (x.2, y.2, diagonal.2),
+            delta_t: (x.3, y.3, diagonal.3),
+            done: false,
+        }
+    }
+
+    #[inline]
+    pub fn steps(self) -> Steps<(O, O, TriangleOrientation), Self> {
+        Steps::new(self)
+    }
+}
+
+impl<I: FloatNum, O: SignedNum> Iterator for WalkTriangles<I, O> {
+    type Item = (O, O, TriangleOrientation);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let (x, y, diagonal) = self.strips;
+
+        let triangle = if diagonal == x + y {
+            (x, y, TriangleOrientation::Up)
+        } else {
+            (x, y, TriangleOrientation::Down)
+        };
+
+        // Cross the closest line, skipping any that would give an invalid triangle when several
+        // lines meet at a corner
+        let mut order = [(self.next_t.0, 0), (self.next_t.1, 1), (self.next_t.2, 2)];
+        order.sort_unstable_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(core::cmp::Ordering::Equal));
+
+        self.done = true;
+
+        for &(t, axis) in &order {
+            if t >= I::one() {
+                break;
+            }
+
+            let mut strips = self.strips;
+
+            match axis {
+                0 => strips.0 += self.signs.0,
+                1 => strips.1 += self.signs.1,
+                _ => strips.2 += self.signs.2,
+            }
+
+            let offset = strips.2 - strips.0 - strips.1;
+
+            if offset == O::zero() || offset == O::one() {
+                self.strips = strips;
+
+                match axis {
+                    0 => self.next_t.0 += self.delta_t.0,
+                    1 => self.next_t.1 += self.delta_t.1,
+                    _ => self.next_t.2 += self.delta_t.2,
+                }
+
+                self.done = false;
+                break;
+            }
+        }
+
+        Some(triangle)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+
+        // How many more lines of each set are crossed, which can be out by one either way from
+        // adding up the steps
+        let lines = |next_t: I, delta_t: I| {
+            if next_t < I::one() {
+                ((I::one() - next_t) / delta_t).ceil().to_usize()
+            } else {
+                Some(0)
+            }
+        };
+
+        let (x, y, diagonal) = (
+            lines(self.next_t.0, self.delta_t.0),
+            lines(self.next_t.1, self.delta_t.1),
+            lines(self.next_t.2, self.delta_t.2),
+        );
+
+        // Every step crosses one line, and the lines of each set are crossed one at a time
+        let low = [x, y, diagonal]
+            .iter()
+            .map(|lines| lines.unwrap_or(0).saturating_sub(1))
+            .max()
+            .unwrap_or(0);
+        let high = match (x, y, diagonal) {
+            (Some(x), Some(y), Some(diagonal)) => x
+                .checked_add(y)
+                .and_then(|lines| lines.checked_add(diagonal))
+                .and_then(|lines| lines.checked_add(4)),
+            _ => None,
+        };
+
+        (low + 1, high)
+    }
+}
+
+#[test]
+fn tests() {
+    use self::TriangleOrientation::{Down, Up};
+
+    let walk = |a, b| WalkTriangles::<f64, i32>::new(a, b).collect::<Vec<_>>();
+
+    assert_eq!(walk((0.2, 0.2), (0.3, 0.3)), [(0, 0, Up)]);
+    assert_eq!(
+        walk((0.2, 0.2), (2.5, 0.3)),
+        [
+            (0, 0, Up),
+            (0, 0, Down),
+            (1, 0, Up),
+            (1, 0, Down),
+            (2, 0, Up)
+        ]
+    );
+    assert_eq!(
+        walk((0.5, 0.9), (0.5, -1.5)),
+        [
+            (0, 0, Down),
+            (0, 0, Up),
+            (0, -1, Down),
+            (0, -1, Up),
+            (0, -2, Down)
+        ]
+    );
+
+    // Passing through a corner goes around one side of it
+    assert_eq!(
+        walk((0.5, 0.5), (1.5, 1.5)),
+        [(0, 0, Down), (1, 0, Up), (1, 0, Down), (1, 1, Up)]
+    );
+
+    // Ending exactly on a corner or an edge doesn't include the triangles past it
+    assert_eq!(walk((0.0, 0.0), (1.0, 0.0)), [(0, 0, Up)]);
+    assert_eq!(walk((1.0, 0.0), (0.0, 0.0)), [(0, 0, Up)]);
+
+    // Every triangle shares an edge with the one before it
+    let triangles = walk((-3.7, 8.1), (12.4, -5.3));
+
+    for pair in triangles.windows(2) {
+        let (a, b) = (pair[0], pair[1]);
+
+        let neighbours = match a {
+            (x, y, Up) => [(x, y, Down), (x - 1, y, Down), (x, y - 1, Down)],
+            (x, y, Down) => [(x, y, Up), (x + 1, y, Up), (x, y + 1, Up)],
+        };
+
+        assert!(neighbours.contains(&b));
+    }
+}
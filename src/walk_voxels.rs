@@ -1,5 +1,10 @@
-use {FloatNum, SignedNum, Voxel};
+use {Coord, IntoVoxel, LineError, RealNum, SignedNum, Voxel, VoxelBounds};
 use steps::Steps;
+use chunked::Chunked;
+use bounds::clip_axis;
+use error::{check_cells, check_float};
+use core::ops::ControlFlow;
+use num_traits::{CheckedAdd, NumCast, One, Signed};
 
 #[inline]
 fn compare<T: SignedNum>(a: T, b: T) -> T {
@@ -23,12 +28,14 @@ fn compare<T: SignedNum>(a: T, b: T) -> T {
 /// let a = (-0.1, -0.1, -0.1);
 /// let b = (0.1, 0.1, 0.1);
 /// 
-/// let center_length = WalkVoxels::<f32, i8>::new(a, b, &VoxelOrigin::Center).count();
+/// let center_length = WalkVoxels::<f32, i8>::new(a, b, VoxelOrigin::Center).count();
 /// assert_eq!(center_length, 1);
 /// 
-/// let corner_length = WalkVoxels::<f32, i8>::new(a, b, &VoxelOrigin::Corner).count();
+/// let corner_length = WalkVoxels::<f32, i8>::new(a, b, VoxelOrigin::Corner).count();
 /// assert_eq!(corner_length, 4);
 /// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum VoxelOrigin {
     Corner,
     Center,
@@ -37,20 +44,30 @@ pub enum VoxelOrigin {
 impl VoxelOrigin {
     #[inline]
     /// Round a voxel's position based on the origin.
-    pub fn round<I: FloatNum, O: SignedNum>(&self, voxel: Voxel<I>) -> Voxel<O> {
+    pub fn round<I: RealNum, O: SignedNum>(&self, voxel: Voxel<I>) -> Voxel<O> {
         let (x, y, z) = match *self {
             VoxelOrigin::Corner => (voxel.0.floor(), voxel.1.floor(), voxel.2.floor()),
             VoxelOrigin::Center => (voxel.0.round(), voxel.1.round(), voxel.2.round()),
         };
 
-        (O::cast(x), O::cast(y), O::cast(z))
+        (x.to_int(), y.to_int(), z.to_int())
+    }
+}
+
+impl Default for VoxelOrigin {
+    #[inline]
+    fn default() -> Self {
+        VoxelOrigin::Center
     }
 }
 
 /// Walk between two voxels, taking orthogonal steps and visiting all voxels in between.
 ///
 /// Implemented from [this Stack Overflow answer].
-/// This algorithm takes floating-point numbers as input and should be symmetrical.
+/// This algorithm takes floating-point numbers as input and should be symmetrical. Any other
+/// [`RealNum`] works too, like the [`Q16`] fixed-point type, as long as the length of the walk
+/// along one axis times its length along another fits in it. For `Q16` that's around 180 voxels
+/// along each.
 ///
 /// Example:
 ///
@@ -62,7 +79,7 @@ impl VoxelOrigin {
 ///     let a = (0.0, 0.0, 0.0);
 ///     let b = (5.0, 6.0, 7.0);
 ///
-///     for (i, (x, y, z)) in WalkVoxels::<f32, i8>::new(a, b, &VoxelOrigin::Center).enumerate() {
+///     for (i, (x, y, z)) in WalkVoxels::<f32, i8>::new(a, b, VoxelOrigin::Center).enumerate() {
 ///         if i > 0 && i % 5 == 0 {
 ///             println!();
 ///         }
@@ -79,27 +96,163 @@ impl VoxelOrigin {
 /// ```
 ///
 /// [this Stack Overflow answer]: https://stackoverflow.com/a/16507714
+/// [`RealNum`]: trait.RealNum.html
+/// [`Q16`]: struct.Q16.html
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct WalkVoxels<I, O> {
     voxel: Voxel<O>,
     count: O,
     sign_x: O,
     sign_y: O,
     sign_z: O,
-    err_x: I,
-    err_y: I,
-    err_z: I,
-    d_err_x: I,
-    d_err_y: I,
-    d_err_z: I,
+    // The distance to the next plane on each axis, scaled by the length of the line along each of
+    // the other two, so that `|cross_x.0| < |cross_y.0|` when the next x plane comes before the
+    // next y plane
+    cross_x: (I, I),
+    cross_y: (I, I),
+    cross_z: (I, I),
+    length: Voxel<I>,
 }
 
-impl<I: FloatNum, O: SignedNum> WalkVoxels<I, O> {
+impl<I: RealNum, O: SignedNum> WalkVoxels<I, O> {
     #[inline]
     /// Create a new `WalkVoxels` iterator, with the origin of the voxels.
-    pub fn new(start: Voxel<I>, end: Voxel<I>, origin: &VoxelOrigin) -> Self {
-        let start_i: Voxel<O> = origin.round(start);
-        let end_i: Voxel<O> = origin.round(end);
+    pub fn new(
+        start: impl IntoVoxel<I>,
+        end: impl IntoVoxel<I>,
+        origin: VoxelOrigin,
+    ) -> Self {
+        let (start, end) = (start.into_voxel(), end.into_voxel());
+
+        Self::from_voxels(start, end, origin.round(start), origin.round(end))
+    }
+
+    /// Create a walk like [`new`], but return an error if the points are NaN or infinite, or if
+    /// the voxels around them or the number of steps between them don't fit in `O`.
+    ///
+    /// [`new`]: #method.new
+    #[inline]
+    pub fn try_new(
+        start: impl IntoVoxel<I>,
+        end: impl IntoVoxel<I>,
+        origin: VoxelOrigin,
+    ) -> Result<Self, LineError> {
+        let (start, end) = (start.into_voxel(), end.into_voxel());
+
+        for &value in &[start.0, start.1, start.2, end.0, end.1, end.2] {
+            check_float::<I, O>(value)?;
+        }
+
+        let (first, last): (Voxel<O>, Voxel<O>) = (origin.round(start), origin.round(end));
+        let axes = [(first.0, last.0), (first.1, last.1), (first.2, last.2)];
+
+        for &(first, last) in &axes {
+            check_cells(first, last)?;
+        }
+
+        // Every step moves along one axis, so the steps along all of them have to fit together,
+        // with one more for the voxel it starts in
+        let count = axes.iter().try_fold(O::Wide::one(), |count, &(first, last)| {
+            count.checked_add(&(last.widen() - first.widen()).abs())
+        });
+
+        match count.and_then(<O as NumCast>::from) {
+            Some(_) => Ok(Self::new(start, end, origin)),
+            None => Err(LineError::Overflow),
+        }
+    }
+
+    /// Create a walk between end points of any number type, converting integers to
+    /// floating-point.
+    #[inline]
+    pub fn from_coords<C: Coord>(
+        start: impl IntoVoxel<C>,
+        end: impl IntoVoxel<C>,
+        origin: VoxelOrigin,
+    ) -> Self {
+        let (start, end) = (start.into_voxel(), end.into_voxel());
+
+        Self::new(
+            (start.0.to_float(), start.1.to_float(), start.2.to_float()),
+            (end.0.to_float(), end.1.to_float(), end.2.to_float()),
+            origin,
+        )
+    }
+
+    #[inline]
+    /// Create a new `WalkVoxels` iterator that only walks through the voxels inside `bounds`.
+    ///
+    /// The segment is clipped to the edges of the bounding voxels before walking it, so a ray
+    /// starting far outside of the world doesn't have to step all the way to it first. The clipped
+    /// segment always steps between voxels at their edges, so with [`VoxelOrigin::Center`] the
+    /// voxels next to the start might differ slightly from those of [`WalkVoxels::new`].
+    ///
+    /// [`VoxelOrigin::Center`]: enum.VoxelOrigin.html
+    /// [`WalkVoxels::new`]: struct.WalkVoxels.html#method.new
+    pub fn clipped(
+        start: impl IntoVoxel<I>,
+        end: impl IntoVoxel<I>,
+        origin: VoxelOrigin,
+        bounds: VoxelBounds<O>,
+    ) -> Self {
+        let (start, end) = (start.into_voxel(), end.into_voxel());
+
+        // Move the points so that the voxel edges are on the integer grid
+        let offset = match origin {
+            VoxelOrigin::Corner => I::zero(),
+            VoxelOrigin::Center => I::cast(0.5),
+        };
+
+        let start = (start.0 + offset, start.1 + offset, start.2 + offset);
+        let end = (end.0 + offset, end.1 + offset, end.2 + offset);
+        let delta = (end.0 - start.0, end.1 - start.1, end.2 - start.2);
 
+        let mut range = (I::zero(), I::one());
+
+        let (min, max) = (bounds.min, bounds.max);
+
+        for &(start, delta, min, max) in &[
+            (start.0, delta.0, min.0, max.0),
+            (start.1, delta.1, min.1, max.1),
+            (start.2, delta.2, min.2, max.2),
+        ] {
+            clip_axis(
+                start,
+                delta,
+                I::cast(min),
+                I::cast(max) + I::one(),
+                &mut range,
+            );
+        }
+
+        if range.0 > range.1 {
+            let mut walk = Self::new(start, start, origin);
+            walk.count = -O::one();
+            return walk;
+        }
+
+        let lerp = |t: I| {
+            (
+                start.0 + delta.0 * t,
+                start.1 + delta.1 * t,
+                start.2 + delta.2 * t,
+            )
+        };
+
+        let (start, end) = (lerp(range.0), lerp(range.1));
+
+        // Points exactly on the far edges of the bounds would round to the voxel outside of it
+        Self::from_voxels(
+            start,
+            end,
+            bounds.clamp(VoxelOrigin::Corner.round(start)),
+            bounds.clamp(VoxelOrigin::Corner.round(end)),
+        )
+    }
+
+    #[inline]
+    fn from_voxels(start: Voxel<I>, end: Voxel<I>, start_i: Voxel<O>, end_i: Voxel<O>) -> Self {
         let count =
             (start_i.0 - end_i.0).abs() + (start_i.1 - end_i.1).abs() + (start_i.2 - end_i.2).abs();
 
@@ -124,27 +277,27 @@ impl<I: FloatNum, O: SignedNum> WalkVoxels<I, O> {
             O::zero()
         });
 
-        // Only used for multiplying up the error margins
-        let vx = if start.0 == end.0 {
-            I::one()
-        } else {
-            end.0 - start.0
-        };
-        let vy = if start.1 == end.1 {
-            I::one()
-        } else {
-            end.1 - start.1
-        };
-        let vz = if start.2 == end.2 {
-            I::one()
-        } else {
-            end.2 - start.2
+        // Only used for scaling up the distances to the planes
+        let length = |start: I, end: I| {
+            if start == end {
+                I::one()
+            } else {
+                (end - start).abs()
+            }
         };
+        let length = (
+            length(start.0, end.0),
+            length(start.1, end.1),
+            length(start.2, end.2),
+        );
 
-        // Error is normalized to vx * vy * vz so we only have to multiply up
-        let vxvy = vx * vy;
-        let vxvz = vx * vz;
-        let vyvz = vy * vz;
+        // The next plane on an axis is crossed at `distance / length` along the line. Comparing
+        // two of them only needs each distance multiplied by the other length, instead of
+        // scaling all three up by the product of the lengths, which keeps the numbers small
+        // enough for fixed-point types
+        let distance_x = (I::cast(x_plane) - start.0) * I::cast(sign_x);
+        let distance_y = (I::cast(y_plane) - start.1) * I::cast(sign_y);
+        let distance_z = (I::cast(z_plane) - start.2) * I::cast(sign_z);
 
         Self {
             sign_x,
@@ -152,16 +305,65 @@ impl<I: FloatNum, O: SignedNum> WalkVoxels<I, O> {
             sign_z,
             count,
             voxel: start_i,
-            // Error from the next plane accumulators, scaled up by vx * vy * vz
-            // gx0 + vx * rx === gxp
-            // vx * rx === gxp - gx0
-            // rx === (gxp - gx0) / vx
-            err_x: (I::cast(x_plane) - start.0) * vyvz,
-            err_y: (I::cast(y_plane) - start.1) * vxvz,
-            err_z: (I::cast(z_plane) - start.2) * vxvy,
-            d_err_x: I::cast(sign_x) * vyvz,
-            d_err_y: I::cast(sign_y) * vxvz,
-            d_err_z: I::cast(sign_z) * vxvy,
+            cross_x: (distance_x * length.1, distance_x * length.2),
+            cross_y: (distance_y * length.0, distance_y * length.2),
+            cross_z: (distance_z * length.0, distance_z * length.1),
+            length,
+        }
+    }
+
+    /// Call a function with each voxel of a walk from `start` to `end`, stopping early if it
+    /// returns [`ControlFlow::Break`], like [`LineRasterizer::walk`] does for the 2D lines.
+    ///
+    /// [`ControlFlow::Break`]: https://doc.rust-lang.org/core/ops/enum.ControlFlow.html
+    /// [`LineRasterizer::walk`]: trait.LineRasterizer.html#method.walk
+    #[inline]
+    pub fn walk<B, F>(
+        start: impl IntoVoxel<I>,
+        end: impl IntoVoxel<I>,
+        origin: VoxelOrigin,
+        mut function: F,
+    ) -> ControlFlow<B>
+    where
+        F: FnMut(Voxel<O>) -> ControlFlow<B>,
+    {
+        let mut walk = Self::new(start, end, origin);
+
+        loop {
+            function(walk.voxel)?;
+
+            if walk.count == O::zero() {
+                return ControlFlow::Continue(());
+            }
+
+            walk.count -= O::one();
+            walk.step();
+        }
+    }
+
+    /// Step across whichever plane comes next.
+    #[inline]
+    fn step(&mut self) {
+        let x_zero = self.sign_x == O::zero();
+        let y_zero = self.sign_y == O::zero();
+        let z_zero = self.sign_z == O::zero();
+
+        let before = |a: I, b: I| a.abs() < b.abs();
+        let x_first = (y_zero || before(self.cross_x.0, self.cross_y.0))
+            && (z_zero || before(self.cross_x.1, self.cross_z.0));
+
+        if !x_zero && x_first {
+            self.voxel.0 += self.sign_x;
+            self.cross_x.0 += self.length.1;
+            self.cross_x.1 += self.length.2;
+        } else if !y_zero && (z_zero || before(self.cross_y.1, self.cross_z.1)) {
+            self.voxel.1 += self.sign_y;
+            self.cross_y.0 += self.length.0;
+            self.cross_y.1 += self.length.2;
+        } else if !z_zero {
+            self.voxel.2 += self.sign_z;
+            self.cross_z.0 += self.length.0;
+            self.cross_z.1 += self.length.1;
         }
     }
 
@@ -169,9 +371,16 @@ impl<I: FloatNum, O: SignedNum> WalkVoxels<I, O> {
     pub fn steps(self) -> Steps<Voxel<O>, Self> {
         Steps::new(self)
     }
+
+    /// Split the voxels into `(chunk, local)` coordinates for chunks of the given size, which has
+    /// to be positive on every axis.
+    #[inline]
+    pub fn chunked(self, size: Voxel<O>) -> Chunked<O, Self> {
+        Chunked::new(self, size)
+    }
 }
 
-impl<I: FloatNum, O: SignedNum> Iterator for WalkVoxels<I, O> {
+impl<I: RealNum, O: SignedNum> Iterator for WalkVoxels<I, O> {
     type Item = Voxel<O>;
 
     #[inline]
@@ -179,43 +388,76 @@ impl<I: FloatNum, O: SignedNum> Iterator for WalkVoxels<I, O> {
         if self.count >= O::zero() {
             self.count -= O::one();
 
-            // Which plane do we cross first?
-            let xr = self.err_x.abs();
-            let yr = self.err_y.abs();
-            let zr = self.err_z.abs();
-
-            let x_zero = self.sign_x == O::zero();
-            let y_zero = self.sign_y == O::zero();
-            let z_zero = self.sign_z == O::zero();
-
             let voxel = self.voxel;
-
-            if !x_zero && (y_zero || xr < yr) && (z_zero || xr < zr) {
-                self.voxel.0 += self.sign_x;
-                self.err_x += self.d_err_x;
-            } else if !y_zero && (z_zero || yr < zr) {
-                self.voxel.1 += self.sign_y;
-                self.err_y += self.d_err_y;
-            } else if !z_zero {
-                self.voxel.2 += self.sign_z;
-                self.err_z += self.d_err_z;
-            }
+            self.step();
 
             Some(voxel)
         } else {
             None
         }
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.count + O::one()).to_usize().unwrap_or(0);
+        (remaining, Some(remaining))
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.len()
+    }
 }
 
+impl<I: RealNum, O: SignedNum> ExactSizeIterator for WalkVoxels<I, O> {}
+
 #[test]
 fn tests() {
     assert_eq!(
         WalkVoxels::new(
             (0.472, -1.100, 0.179),
             (1.114, -0.391, 0.927),
-            &VoxelOrigin::Center
+            VoxelOrigin::Center
         ).collect::<Vec<_>>(),
         [(0, -1, 0), (1, -1, 0), (1, -1, 1), (1, 0, 1)]
     );
+
+    // The origin defaults to the center
+    assert_eq!(VoxelOrigin::default(), VoxelOrigin::Center);
+
+    let bounds = VoxelBounds::new((0, 0, 0), (15, 15, 15));
+
+    // Clipping should give the same voxels as filtering the whole walk
+    for &(start, end) in &[
+        ((-50.2, 3.7, 1.1), (20.3, 12.2, 9.9)),
+        ((20.3, 12.2, 9.9), (-50.2, 3.7, 1.1)),
+        ((7.3, -40.6, 2.2), (8.1, 30.4, 14.8)),
+        ((3.3, 4.4, -9.1), (3.3, 4.4, 22.7)),
+    ] {
+        let clipped = |origin| {
+            WalkVoxels::<f64, i32>::clipped(start, end, origin, bounds).collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            clipped(VoxelOrigin::Corner),
+            WalkVoxels::new(start, end, VoxelOrigin::Corner)
+                .filter(|&voxel| bounds.contains(voxel))
+                .collect::<Vec<_>>()
+        );
+
+        assert!(clipped(VoxelOrigin::Center)
+            .iter()
+            .all(|&voxel| bounds.contains(voxel)));
+    }
+
+    assert_eq!(
+        WalkVoxels::<f32, i32>::clipped(
+            (-10.0, -10.0, -10.0),
+            (-5.0, 20.0, 3.0),
+            VoxelOrigin::Center,
+            bounds
+        )
+        .count(),
+        0
+    );
 }
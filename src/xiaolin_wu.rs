@@ -1,13 +1,18 @@
-use {FloatNum, Point, SignedNum};
+use {Coord, FloatNum, IntoPoint, LineError, Point, RealNum, SignedNum};
 use steps::Steps;
+use error::{check_cells, check_float};
+use core::marker::PhantomData;
 use core::mem::swap;
 
 /// An implementation of [Xiaolin Wu's line algorithm].
 ///
 /// This algorithm works based on floating-points and returns an extra variable for how much a
-/// a point is covered, which is useful for anti-aliasing.
+/// a point is covered, which is useful for anti-aliasing. It also works on the [`Q16`]
+/// fixed-point type, for chips without a floating-point unit.
 ///
-/// Note that due to the implementation, the returned line will always go from left to right.
+/// Note that due to the implementation, the returned line will always go from left to right, or
+/// with `y` going up for steep lines. [`is_reversed`] tells you if it was turned around, and
+/// [`directed`] creates a line that always goes from `start` to `end` instead.
 ///
 /// Example:
 ///
@@ -27,18 +32,89 @@ use core::mem::swap;
 /// ```
 ///
 /// [Xiaolin Wu's line algorithm]: https://en.wikipedia.org/wiki/Xiaolin_Wu%27s_line_algorithm
+/// [`Q16`]: struct.Q16.html
+/// [`is_reversed`]: #method.is_reversed
+/// [`directed`]: #method.directed
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct XiaolinWu<I, O> {
     steep: bool,
     gradient: I,
     x: O,
     y: I,
     end_x: O,
+    step: O,
     lower: bool,
+    reversed: bool,
 }
 
-impl<I: FloatNum, O: SignedNum> XiaolinWu<I, O> {
+impl<I: RealNum, O: SignedNum> XiaolinWu<I, O> {
+    #[inline]
+    pub fn new(start: impl IntoPoint<I>, end: impl IntoPoint<I>) -> Self {
+        let (start, end) = (start.into_point(), end.into_point());
+
+        Self::with_direction(start, end, false)
+    }
+
+    /// Create a line like [`new`], but return an error if the points are NaN or infinite, or if
+    /// the cells around them or the distance between them don't fit in `O`.
+    ///
+    /// [`new`]: #method.new
+    #[inline]
+    pub fn try_new(start: impl IntoPoint<I>, end: impl IntoPoint<I>) -> Result<Self, LineError> {
+        let (start, end) = (start.into_point(), end.into_point());
+
+        for &value in &[start.0, start.1, end.0, end.1] {
+            check_float::<I, O>(value)?;
+        }
+
+        let cell = |value: I| -> O { value.round().to_int() };
+        check_cells(cell(start.0), cell(end.0))?;
+        check_cells(cell(start.1), cell(end.1))?;
+
+        Ok(Self::new(start, end))
+    }
+
+    /// Create a line between end points of any number type, converting integers to
+    /// floating-point.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// extern crate line_drawing;
+    /// use line_drawing::XiaolinWu;
+    ///
+    /// fn main() {
+    ///     let line = XiaolinWu::<f32, i32>::from_coords((0, 0), (5, 2));
+    ///     assert_eq!(line, XiaolinWu::new((0.0, 0.0), (5.0, 2.0)));
+    /// }
+    /// ```
     #[inline]
-    pub fn new(mut start: Point<I>, mut end: Point<I>) -> Self {
+    pub fn from_coords<C: Coord>(start: impl IntoPoint<C>, end: impl IntoPoint<C>) -> Self {
+        let (start, end) = (start.into_point(), end.into_point());
+
+        Self::new(
+            (start.0.to_float(), start.1.to_float()),
+            (end.0.to_float(), end.1.to_float()),
+        )
+    }
+
+    /// Create a line that goes from `start` to `end`, even if that's right to left.
+    ///
+    /// This is for things like tracers, that need to be drawn outwards from where they were
+    /// fired. For lines with whole-number end points it covers the same points as [`new`], just
+    /// in the opposite order when that line would be reversed.
+    ///
+    /// [`new`]: #method.new
+    #[inline]
+    pub fn directed(start: impl IntoPoint<I>, end: impl IntoPoint<I>) -> Self {
+        let (start, end) = (start.into_point(), end.into_point());
+
+        Self::with_direction(start, end, true)
+    }
+
+    #[inline]
+    fn with_direction(mut start: Point<I>, mut end: Point<I>, directed: bool) -> Self {
         let steep = (end.1 - start.1).abs() > (end.0 - start.0).abs();
 
         if steep {
@@ -46,11 +122,19 @@ impl<I: FloatNum, O: SignedNum> XiaolinWu<I, O> {
             end = (end.1, end.0);
         }
 
-        if start.0 > end.0 {
+        let backwards = start.0 > end.0;
+
+        if backwards && !directed {
             swap(&mut start, &mut end);
         }
 
-        let dx = end.0 - start.0;
+        let step = if backwards && directed {
+            -O::one()
+        } else {
+            O::one()
+        };
+
+        let dx = (end.0 - start.0).abs();
         let gradient = if dx.is_zero() {
             I::one()
         } else {
@@ -60,30 +144,61 @@ impl<I: FloatNum, O: SignedNum> XiaolinWu<I, O> {
         Self {
             steep,
             gradient,
-            x: O::cast(start.0.round()),
+            x: start.0.round().to_int(),
             y: start.1,
-            end_x: O::cast(end.0.round()),
+            end_x: end.0.round().to_int(),
+            step,
             lower: false,
+            reversed: backwards && !directed,
         }
     }
 
+    /// Whether the points are returned from `end` to `start`, because the line was turned around
+    /// to go left to right. This is always `false` for [`directed`] lines.
+    ///
+    /// [`directed`]: #method.directed
+    #[inline]
+    pub fn is_reversed(&self) -> bool {
+        self.reversed
+    }
+
     #[inline]
     pub fn steps(self) -> Steps<(Point<O>, I), Self> {
         Steps::new(self)
     }
 }
 
-impl<I: FloatNum, O: SignedNum> Iterator for XiaolinWu<I, O> {
+impl<I: FloatNum, O: SignedNum> XiaolinWu<I, O> {
+    /// Return the coverage as an integer alpha value like `u8` instead. See [`XiaolinWuAlpha`].
+    ///
+    /// [`XiaolinWuAlpha`]: struct.XiaolinWuAlpha.html
+    #[inline]
+    pub fn alpha<A: Alpha>(self) -> XiaolinWuAlpha<O, A> {
+        XiaolinWuAlpha {
+            steep: self.steep,
+            gradient: fixed(self.gradient),
+            x: self.x,
+            y: fixed(self.y),
+            end_x: self.end_x,
+            step: self.step,
+            lower: self.lower,
+            reversed: self.reversed,
+            alpha: PhantomData,
+        }
+    }
+}
+
+impl<I: RealNum, O: SignedNum> Iterator for XiaolinWu<I, O> {
     type Item = (Point<O>, I);
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        if self.x <= self.end_x {
+        if (self.end_x - self.x) * self.step >= O::zero() {
             // get the fractional part of y
             let fpart = self.y - self.y.floor();
 
             // Calculate the integer value of y
-            let mut y = O::cast(self.y);
+            let mut y: O = self.y.to_int();
             if self.lower {
                 y += O::one();
             }
@@ -94,7 +209,7 @@ impl<I: FloatNum, O: SignedNum> Iterator for XiaolinWu<I, O> {
             if self.lower {
                 // Return the lower point
                 self.lower = false;
-                self.x += O::one();
+                self.x += self.step;
                 self.y += self.gradient;
                 Some((point, fpart))
             } else {
@@ -103,7 +218,7 @@ impl<I: FloatNum, O: SignedNum> Iterator for XiaolinWu<I, O> {
                     self.lower = true;
                 } else {
                     // Otherwise move on
-                    self.x += O::one();
+                    self.x += self.step;
                     self.y += self.gradient;
                 }
 
@@ -114,6 +229,167 @@ impl<I: FloatNum, O: SignedNum> Iterator for XiaolinWu<I, O> {
             None
         }
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Each column has one or two points, and the lower point of the current one may be left
+        let columns = ((self.end_x - self.x) * self.step + O::one())
+            .to_usize()
+            .unwrap_or(0);
+        let high = columns
+            .checked_mul(2)
+            .map(|high| high - self.lower as usize);
+        (columns, high)
+    }
+}
+
+/// An integer type that coverage can be returned as, where the largest value is fully covered.
+///
+/// It's implemented for `u8` and `u16`.
+pub trait Alpha: Copy {
+    /// Convert a fraction out of `2^32` to an alpha value, rounding down.
+    fn from_fraction(fraction: u32) -> Self;
+
+    /// Get the alpha value that adds up to the largest one with this one.
+    fn complement(self) -> Self;
+}
+
+impl Alpha for u8 {
+    #[inline]
+    fn from_fraction(fraction: u32) -> Self {
+        (fraction >> 24) as u8
+    }
+
+    #[inline]
+    fn complement(self) -> Self {
+        !self
+    }
+}
+
+impl Alpha for u16 {
+    #[inline]
+    fn from_fraction(fraction: u32) -> Self {
+        (fraction >> 16) as u16
+    }
+
+    #[inline]
+    fn complement(self) -> Self {
+        !self
+    }
+}
+
+/// The number of fractional bits in the fixed-point numbers of [`XiaolinWuAlpha`].
+///
+/// [`XiaolinWuAlpha`]: struct.XiaolinWuAlpha.html
+const FRACTION_BITS: u32 = 32;
+
+/// Convert a float to fixed-point.
+#[inline]
+fn fixed<I: FloatNum>(value: I) -> i64 {
+    (value * I::cast(1u64 << FRACTION_BITS))
+        .round()
+        .to_i64()
+        .expect("coordinate doesn't fit in 32.32 fixed-point")
+}
+
+/// [`XiaolinWu`], returning the coverage as an [`Alpha`] value like `u8` instead of a float.
+///
+/// It's made with [`XiaolinWu::alpha`], and steps along the line in fixed-point, so the alpha
+/// values come straight out of the bits without any floating-point math. The two points in a
+/// column add up to the largest value, like `255` for `u8`. The `y` coordinates (or `x` for steep
+/// lines) need to be between `-2^31` and `2^31`.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::XiaolinWu;
+///
+/// fn main() {
+///     for ((x, y), alpha) in XiaolinWu::<f32, i8>::new((0.0, 0.0), (3.0, 6.0)).alpha::<u8>() {
+///         print!("(({}, {}), {}), ", x, y, alpha);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// ((0, 0), 255), ((0, 1), 127), ((1, 1), 128), ((1, 2), 255), ((1, 3), 127), ((2, 3), 128), ((2, 4), 255), ((2, 5), 127), ((3, 5), 128), ((3, 6), 255),
+/// ```
+///
+/// [`XiaolinWu`]: struct.XiaolinWu.html
+/// [`Alpha`]: trait.Alpha.html
+/// [`XiaolinWu::alpha`]: struct.XiaolinWu.html#method.alpha
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct XiaolinWuAlpha<O, A> {
+    steep: bool,
+    gradient: i64,
+    x: O,
+    y: i64,
+    end_x: O,
+    step: O,
+    lower: bool,
+    reversed: bool,
+    alpha: PhantomData<A>,
+}
+
+impl<O: SignedNum, A: Alpha> XiaolinWuAlpha<O, A> {
+    /// Whether the points are returned from `end` to `start`. See [`XiaolinWu::is_reversed`].
+    ///
+    /// [`XiaolinWu::is_reversed`]: struct.XiaolinWu.html#method.is_reversed
+    #[inline]
+    pub fn is_reversed(&self) -> bool {
+        self.reversed
+    }
+}
+
+impl<O: SignedNum, A: Alpha> Iterator for XiaolinWuAlpha<O, A> {
+    type Item = (Point<O>, A);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if (self.end_x - self.x) * self.step >= O::zero() {
+            // The lower bits are the fractional part
+            let fraction = self.y as u32;
+
+            let mut y = O::cast(self.y >> FRACTION_BITS);
+            if self.lower {
+                y += O::one();
+            }
+
+            let point = if self.steep { (y, self.x) } else { (self.x, y) };
+            let alpha = A::from_fraction(fraction);
+
+            if self.lower {
+                self.lower = false;
+                self.x += self.step;
+                self.y += self.gradient;
+                Some((point, alpha))
+            } else {
+                if fraction > 0 {
+                    self.lower = true;
+                } else {
+                    self.x += self.step;
+                    self.y += self.gradient;
+                }
+
+                Some((point, alpha.complement()))
+            }
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let columns = ((self.end_x - self.x) * self.step + O::one())
+            .to_usize()
+            .unwrap_or(0);
+        let high = columns
+            .checked_mul(2)
+            .map(|high| high - self.lower as usize);
+        (columns, high)
+    }
 }
 
 #[test]
@@ -164,4 +440,102 @@ fn tests() {
         xiaolin_wu((340.5, 290.77), (110.0, 170.0)),
         xiaolin_wu((110.0, 170.0), (340.5, 290.77))
     );
+
+    // Directed lines keep the order they were given in, and cover the same points
+
+    assert!(XiaolinWu::<f32, i32>::new((6.0, 3.0), (0.0, 0.0)).is_reversed());
+    assert!(!XiaolinWu::<f32, i32>::new((0.0, 0.0), (6.0, 3.0)).is_reversed());
+    assert!(!XiaolinWu::<f32, i32>::directed((6.0, 3.0), (0.0, 0.0)).is_reversed());
+
+    let sorted = |mut points: Vec<((i32, i32), f64)>| {
+        points.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        points
+    };
+
+    for &(start, end) in &[
+        ((6.0, 3.0), (0.0, 0.0)),
+        ((0.0, 0.0), (6.0, 3.0)),
+        ((2.0, 9.0), (-1.0, 1.0)),
+        ((4.0, 6.0), (4.0, 2.0)),
+    ] {
+        let line = XiaolinWu::directed(start, end).collect::<Vec<_>>();
+
+        assert_eq!(line[0].0, (start.0 as i32, start.1 as i32));
+        assert_eq!(line[line.len() - 1].0, (end.0 as i32, end.1 as i32));
+        assert_eq!(sorted(line), sorted(xiaolin_wu(start, end)));
+    }
+
+    let mut line = XiaolinWu::<f32, i32>::directed((5.0, 0.0), (0.0, 2.0));
+    loop {
+        let count = line.clone().fold(0, |count, _| count + 1);
+        let (low, high) = line.size_hint();
+        assert!(low <= count && count <= high.unwrap());
+
+        if line.next().is_none() {
+            break;
+        }
+    }
+}
+
+#[test]
+fn alpha() {
+    let lines = [
+        ((0.0, 0.0), (6.0, 3.0)),
+        ((0.0, 0.0), (3.0, 6.0)),
+        ((2.0, 4.0), (6.0, 4.0)),
+        ((9.0, 3.0), (1.0, 1.0)),
+        ((0.0, 0.0), (8.0, 3.0)),
+        ((0.5, 1.25), (4.75, 3.0)),
+    ];
+
+    for &(start, end) in &lines {
+        let line = XiaolinWu::<f64, i32>::new(start, end);
+        let bytes = line.clone().alpha::<u8>().collect::<Vec<_>>();
+        let words = line.clone().alpha::<u16>().collect::<Vec<_>>();
+        let floats = line.collect::<Vec<_>>();
+
+        assert_eq!(bytes.len(), floats.len());
+        assert_eq!(words.len(), floats.len());
+
+        for ((&(point, byte), &(_, word)), &(float_point, float)) in
+            bytes.iter().zip(&words).zip(&floats)
+        {
+            assert_eq!(point, float_point);
+            assert!((f64::from(byte) - float * 255.0).abs() <= 1.0);
+            assert!((f64::from(word) - float * 65535.0).abs() <= 1.0);
+        }
+    }
+
+    // The two points in a column add up to full coverage
+    let line = XiaolinWu::<f32, i32>::new((0.0, 0.0), (7.0, 2.0)).alpha::<u8>();
+    let points = line.collect::<Vec<_>>();
+    let column = |x| {
+        points
+            .iter()
+            .filter(|point| (point.0).0 == x)
+            .map(|point| u32::from(point.1))
+            .sum::<u32>()
+    };
+    assert!((0..=7).all(|x| column(x) == 255));
+
+    // It can be switched to part way through a line
+    let mut line = XiaolinWu::<f32, i32>::directed((8.0, 0.0), (0.0, 2.0));
+    line.next();
+    let alpha = line.clone().alpha::<u8>();
+    assert!(!alpha.is_reversed());
+    assert_eq!(
+        alpha.map(|(point, _)| point).collect::<Vec<_>>(),
+        line.map(|(point, _)| point).collect::<Vec<_>>()
+    );
+
+    let mut line = XiaolinWu::<f32, i32>::new((0.0, 0.0), (9.0, 4.0)).alpha::<u16>();
+    loop {
+        let count = line.clone().count();
+        let (low, high) = line.size_hint();
+        assert!(low <= count && count <= high.unwrap());
+
+        if line.next().is_none() {
+            break;
+        }
+    }
 }
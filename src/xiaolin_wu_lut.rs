@@ -0,0 +1,395 @@
+use {IntoPoint, Point, SignedNum};
+use num_traits::{One, Signed, ToPrimitive, Zero};
+use core::mem::swap;
+
+/// A coverage table with 16 levels that go up evenly from `0` to `255`, for [`XiaolinWuLut`].
+///
+/// The two points in a column always add up to `255` with it.
+///
+/// [`XiaolinWuLut`]: struct.XiaolinWuLut.html
+pub const LINEAR_COVERAGE: [u8; 16] = [
+    0, 17, 34, 51, 68, 85, 102, 119, 136, 153, 170, 187, 204, 221, 238, 255,
+];
+
+/// Divide `numerator` by the larger `denominator`, and get the first 32 bits of the fraction along
+/// with what's left over.
+///
+/// This is long division with shifts and subtractions, since chips like the Cortex-M0 don't have
+/// a divide instruction.
+#[inline]
+fn divide_fraction<W: SignedNum>(mut numerator: W, denominator: W) -> (u32, W) {
+    let mut quotient = 0;
+
+    for _ in 0..32 {
+        quotient <<= 1;
+
+        // Doubling the remainder could overflow, so compare it with what's left to `denominator`
+        let gap = denominator - numerator;
+        if numerator >= gap {
+            numerator -= gap;
+            quotient |= 1;
+        } else {
+            numerator += numerator;
+        }
+    }
+
+    (quotient, numerator)
+}
+
+/// An anti-aliased line in the style of [Xiaolin Wu's line algorithm], done with nothing but
+/// integer math, for microcontrollers without a floating-point unit.
+///
+/// It takes integer end points, and steps along the line with a 32-bit fixed-point fraction and
+/// the remainder of it, so the points are exactly where they'd be with unlimited precision. The
+/// top bits of the fraction are looked up in a small table to get the coverage of each point,
+/// which can be in whatever type and on whatever curve suits the display. The table needs a
+/// length that's a power of two, from `2` to `2^32`. [`LINEAR_COVERAGE`] has 16 levels as `u8`,
+/// and a table from [`Transfer::table`] can be used for 256 levels that are gamma encoded.
+///
+/// When the line is a fraction `f` of the way from one point to the next, the top bits of `f` are
+/// the index of the entry for the next point, and the one above gets the entry the same distance
+/// from the end of the table, so the last entry is full coverage.
+///
+/// Like [`XiaolinWu`], it always goes from left to right, or with `y` going up for steep lines,
+/// unless it's made with [`directed`].
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{XiaolinWuLut, LINEAR_COVERAGE};
+///
+/// fn main() {
+///     for ((x, y), alpha) in XiaolinWuLut::new((0, 0), (4, 1), &LINEAR_COVERAGE) {
+///         print!("(({}, {}), {}), ", x, y, alpha);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// ((0, 0), 255), ((1, 0), 187), ((1, 1), 68), ((2, 0), 119), ((2, 1), 136), ((3, 0), 51), ((3, 1), 204), ((4, 1), 255),
+/// ```
+///
+/// [Xiaolin Wu's line algorithm]: https://en.wikipedia.org/wiki/Xiaolin_Wu%27s_line_algorithm
+/// [`LINEAR_COVERAGE`]: constant.LINEAR_COVERAGE.html
+/// [`Transfer::table`]: enum.Transfer.html#method.table
+/// [`XiaolinWu`]: struct.XiaolinWu.html
+/// [`directed`]: #method.directed
+#[derive(Clone, Debug, PartialEq)]
+pub struct XiaolinWuLut<'a, T: SignedNum, A> {
+    steep: bool,
+    x: T,
+    step: T,
+    // Columns left to go, including the current one
+    columns: T::Wide,
+    y: T,
+    // Which way `y` goes, which is `-1` or `1`
+    rise: T,
+    // How far `y` is past the point above it in the direction of `rise`, as a fraction of `2^32`,
+    // plus a remainder out of `delta`
+    fraction: u32,
+    remainder: T::Wide,
+    gradient: u32,
+    gradient_remainder: T::Wide,
+    delta: T::Wide,
+    // Set for lines at exactly 45°, which move along `y` a whole point each time
+    diagonal: bool,
+    lower: bool,
+    reversed: bool,
+    table: &'a [A],
+    shift: u32,
+}
+
+impl<'a, T: SignedNum, A: Copy> XiaolinWuLut<'a, T, A> {
+    /// Panics if the length of `table` isn't a power of two from `2` to `2^32`.
+    #[inline]
+    pub fn new(start: impl IntoPoint<T>, end: impl IntoPoint<T>, table: &'a [A]) -> Self {
+        let (start, end) = (start.into_point(), end.into_point());
+
+        Self::with_direction(start, end, table, false)
+    }
+
+    /// Create a line that goes from `start` to `end`, even if that's right to left. See
+    /// [`XiaolinWu::directed`].
+    ///
+    /// [`XiaolinWu::directed`]: struct.XiaolinWu.html#method.directed
+    #[inline]
+    pub fn directed(start: impl IntoPoint<T>, end: impl IntoPoint<T>, table: &'a [A]) -> Self {
+        let (start, end) = (start.into_point(), end.into_point());
+
+        Self::with_direction(start, end, table, true)
+    }
+
+    #[inline]
+    fn with_direction(
+        mut start: Point<T>,
+        mut end: Point<T>,
+        table: &'a [A],
+        directed: bool,
+    ) -> Self {
+        let bits = table.len().trailing_zeros();
+        assert!(
+            table.len().is_power_of_two() && (1..=32).contains(&bits),
+            "the coverage table needs a length that's a power of two from 2 to 2^32"
+        );
+
+        let (delta_x, delta_y) = (end.0.widen() - start.0.widen(), end.1.widen() - start.1.widen());
+        let steep = delta_y.abs() > delta_x.abs();
+
+        if steep {
+            start = (start.1, start.0);
+            end = (end.1, end.0);
+        }
+
+        let backwards = start.0 > end.0;
+
+        if backwards && !directed {
+            swap(&mut start, &mut end);
+        }
+
+        let step = if backwards && directed {
+            -T::one()
+        } else {
+            T::one()
+        };
+
+        let delta = (end.0.widen() - start.0.widen()).abs();
+        let rise = end.1.widen() - start.1.widen();
+
+        let (gradient, gradient_remainder) = if rise.abs() == delta {
+            (0, T::Wide::zero())
+        } else {
+            divide_fraction(rise.abs(), delta)
+        };
+
+        Self {
+            steep,
+            x: start.0,
+            step,
+            columns: delta + T::Wide::one(),
+            y: start.1,
+            rise: if rise < T::Wide::zero() {
+                -T::one()
+            } else {
+                T::one()
+            },
+            fraction: 0,
+            remainder: T::Wide::zero(),
+            gradient,
+            gradient_remainder,
+            delta,
+            diagonal: rise.abs() == delta,
+            lower: false,
+            reversed: backwards && !directed,
+            table,
+            shift: 32 - bits,
+        }
+    }
+
+    /// Whether the points are returned from `end` to `start`. See [`XiaolinWu::is_reversed`].
+    ///
+    /// [`XiaolinWu::is_reversed`]: struct.XiaolinWu.html#method.is_reversed
+    #[inline]
+    pub fn is_reversed(&self) -> bool {
+        self.reversed
+    }
+
+    /// Move on to the next column.
+    #[inline]
+    fn advance(&mut self) {
+        self.columns -= T::Wide::one();
+
+        if self.columns.is_zero() {
+            return;
+        }
+
+        self.x += self.step;
+
+        let (fraction, mut carry) = self.fraction.overflowing_add(self.gradient);
+        self.fraction = fraction;
+
+        // The remainder carries into the fraction once it adds up to a whole `delta`
+        let gap = self.delta - self.gradient_remainder;
+        if self.remainder >= gap {
+            self.remainder -= gap;
+            let (fraction, overflow) = self.fraction.overflowing_add(1);
+            self.fraction = fraction;
+            carry |= overflow;
+        } else {
+            self.remainder += self.gradient_remainder;
+        }
+
+        if carry || self.diagonal {
+            self.y += self.rise;
+        }
+    }
+}
+
+impl<'a, T: SignedNum, A: Copy> Iterator for XiaolinWuLut<'a, T, A> {
+    type Item = (Point<T>, A);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.columns.is_zero() {
+            return None;
+        }
+
+        // Get the point above the line and how far past it the line is, which is the other way
+        // around when `y` goes down
+        let (y, fraction) = if self.rise > T::zero() || self.fraction == 0 {
+            (self.y, self.fraction)
+        } else {
+            (self.y - T::one(), self.fraction.wrapping_neg())
+        };
+
+        let index = (fraction >> self.shift) as usize;
+
+        if self.lower {
+            let y = y + T::one();
+            let point = if self.steep { (y, self.x) } else { (self.x, y) };
+
+            self.lower = false;
+            self.advance();
+            Some((point, self.table[index]))
+        } else {
+            let point = if self.steep { (y, self.x) } else { (self.x, y) };
+
+            if fraction > 0 {
+                self.lower = true;
+            } else {
+                self.advance();
+            }
+
+            Some((point, self.table[self.table.len() - 1 - index]))
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Each column has one or two points, and the lower point of the current one may be left
+        let columns = self.columns.to_usize().unwrap_or(usize::MAX);
+        let high = columns
+            .checked_mul(2)
+            .map(|high| high - self.lower as usize);
+        (columns, high)
+    }
+}
+
+#[test]
+fn tests() {
+    use {Transfer, XiaolinWu};
+
+    let lut = |a, b| XiaolinWuLut::new(a, b, &LINEAR_COVERAGE).collect::<Vec<_>>();
+
+    assert_eq!(
+        lut((0, 0), (6, 3)),
+        [
+            ((0, 0), 255),
+            ((1, 0), 119),
+            ((1, 1), 136),
+            ((2, 1), 255),
+            ((3, 1), 119),
+            ((3, 2), 136),
+            ((4, 2), 255),
+            ((5, 2), 119),
+            ((5, 3), 136),
+            ((6, 3), 255)
+        ]
+    );
+
+    assert_eq!(
+        lut((4, 2), (4, 6)),
+        [((4, 2), 255), ((4, 3), 255), ((4, 4), 255), ((4, 5), 255), ((4, 6), 255)]
+    );
+    assert_eq!(lut((3, 3), (0, 0)), [((0, 0), 255), ((1, 1), 255), ((2, 2), 255), ((3, 3), 255)]);
+    assert_eq!(lut((5, -1), (5, -1)), [((5, -1), 255)]);
+
+    // Going down, the point above gets the coverage for how far the line is past it
+    assert_eq!(
+        lut((0, 0), (4, -1)),
+        [
+            ((0, 0), 255),
+            ((1, -1), 51),
+            ((1, 0), 204),
+            ((2, -1), 119),
+            ((2, 0), 136),
+            ((3, -1), 187),
+            ((3, 0), 68),
+            ((4, -1), 255)
+        ]
+    );
+
+    // With a table of every `u8`, it's the same as the fixed-point alpha of the float version,
+    // other than rounding, on lines where the float version's gradient is exact
+    let table = Transfer::<f32>::Linear.table();
+    for &(start, end) in &[
+        ((0, 0), (6, 3)),
+        ((0, 0), (3, 6)),
+        ((9, 3), (1, 1)),
+        ((0, 0), (8, 3)),
+        ((-4, 7), (4, -25)),
+        ((2, 4), (6, 4)),
+    ] {
+        let float = |(x, y)| (f64::from(x), f64::from(y));
+        let alpha = XiaolinWu::<f64, i32>::new(float(start), float(end)).alpha::<u8>();
+        let line = XiaolinWuLut::new(start, end, &table);
+
+        assert_eq!(line.clone().count(), alpha.clone().count());
+        for ((point, a), (alpha_point, b)) in line.clone().zip(alpha) {
+            assert_eq!(point, alpha_point);
+            assert!((i32::from(a) - i32::from(b)).abs() <= 1);
+        }
+
+        // The columns all add up to full coverage
+        let points = line.collect::<Vec<_>>();
+        for &((x, y), _) in &points {
+            let column = |&&((other_x, other_y), _): &&((i32, i32), u8)| {
+                if (end.1 - start.1).abs() > (end.0 - start.0).abs() {
+                    other_y == y
+                } else {
+                    other_x == x
+                }
+            };
+            let total = points.iter().filter(column).map(|&(_, a)| u32::from(a)).sum::<u32>();
+            assert_eq!(total, 255);
+        }
+    }
+
+    // Directed lines keep the order they were given in, and cover the same points
+    assert!(XiaolinWuLut::new((6, 3), (0, 0), &LINEAR_COVERAGE).is_reversed());
+    assert!(!XiaolinWuLut::directed((6, 3), (0, 0), &LINEAR_COVERAGE).is_reversed());
+
+    for &(start, end) in &[((6, 3), (0, 0)), ((2, 9), (-1, 1)), ((4, 6), (4, 2))] {
+        let mut line = XiaolinWuLut::directed(start, end, &LINEAR_COVERAGE).collect::<Vec<_>>();
+        assert_eq!((line[0].0, line[line.len() - 1].0), (start, end));
+
+        let mut other = lut(start, end);
+        line.sort();
+        other.sort();
+        assert_eq!(line, other);
+    }
+
+    // Lines across the whole range of the type don't overflow
+    let line = XiaolinWuLut::new((-128i8, -128), (127, 127), &LINEAR_COVERAGE);
+    assert_eq!(line.clone().count(), 256);
+    assert_eq!(line.last(), Some(((127, 127), 255)));
+    let line = XiaolinWuLut::new((-128i8, 127), (127, -128), &[false, true]);
+    assert_eq!(line.clone().filter(|&(_, covered)| covered).count(), 256);
+
+    let mut line = XiaolinWuLut::new((0i64, 0), (9, 4), &LINEAR_COVERAGE);
+    loop {
+        let count = line.clone().count();
+        let (low, high) = line.size_hint();
+        assert!(low <= count && count <= high.unwrap());
+
+        if line.next().is_none() {
+            break;
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn table_length() {
+    XiaolinWuLut::new((0, 0), (1, 1), &[0u8, 1, 2]);
+}